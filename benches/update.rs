@@ -0,0 +1,72 @@
+//! Medição do custo por passo de `Network::update`
+//!
+//! Sem dependências extra: cada caso é cronometrado com `Instant` e o tempo
+//! médio por passo é impresso. Executar com:
+//!
+//! ```text
+//! cargo bench --bench update
+//! ```
+//!
+//! Para cada tamanho de grade mede também o cálculo de novidade e memória
+//! de um neurónio na versão densa (todas as N posições) e na restrita às
+//! posições com input ou memória não nulos, usada pela rede, e no fim a
+//! divisão do tempo de um passo por fase (`Network::timing_report`).
+
+use nen_v_prototipo::{ConnectivityType, Network, NENV};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Tempo médio de `iterations` execuções de `f`
+fn time_per_iteration<F: FnMut()>(iterations: u32, mut f: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    start.elapsed() / iterations
+}
+
+fn main() {
+    println!("{:>8}  {:>14}  {:>14}  {:>14}", "N", "update", "novelty densa", "novelty esparsa");
+    for side in [10, 20, 40, 60] {
+        let n = side * side;
+        let mut network = Network::new_seeded(n, ConnectivityType::Grid2D, 0.2, 0.5, 1);
+        let mut inputs = vec![0.0; n];
+        inputs[n / 2] = 1.0;
+        for _ in 0..20 {
+            network.update(&inputs);
+        }
+        let update = time_per_iteration(50, || network.update(black_box(&inputs)));
+
+        // Neurónio central: 8 vizinhos ativos e o input externo
+        let center = n / 2 + side / 2;
+        let mut support = network.neighbors(center).to_vec();
+        support.extend([n / 2, center]);
+        support.sort_unstable();
+        support.dedup();
+        let mut neuron_inputs = vec![0.0; n];
+        for &j in &support {
+            neuron_inputs[j] = 1.0;
+        }
+        let mut neuron: NENV = network.neurons[center].clone();
+
+        let dense = time_per_iteration(2000, || {
+            black_box(neuron.try_compute_novelty(black_box(&neuron_inputs)).unwrap());
+            neuron.update_memory_with_alpha(&neuron_inputs, 0.1);
+        });
+        let sparse = time_per_iteration(2000, || {
+            black_box(neuron.compute_novelty_sparse(black_box(&neuron_inputs), &support));
+            neuron.update_memory_sparse(&neuron_inputs, &support, 0.1);
+        });
+
+        println!("{:>8}  {:>14.2?}  {:>14.2?}  {:>14.2?}", n, update, dense, sparse);
+    }
+
+    let mut network = Network::new_seeded(3600, ConnectivityType::Grid2D, 0.2, 0.5, 1);
+    let mut inputs = vec![0.0; 3600];
+    inputs[1800] = 1.0;
+    network.enable_timing();
+    for _ in 0..20 {
+        network.update(&inputs);
+    }
+    println!("\nFases de update (N = 3600):\n{}", network.timing_report().unwrap());
+}
@@ -0,0 +1,22 @@
+//! Gera o código do serviço gRPC a partir de `proto/nenv.proto`
+//!
+//! Só corre com a feature `grpc` (`CARGO_FEATURE_GRPC`); o `protoc` vem do
+//! `protoc-bin-vendored`, pelo que não é preciso instalá-lo.
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=proto/nenv.proto");
+
+    if std::env::var_os("CARGO_FEATURE_GRPC").is_some() {
+        #[cfg(feature = "grpc")]
+        compile_grpc();
+    }
+}
+
+#[cfg(feature = "grpc")]
+fn compile_grpc() {
+    let protoc = protoc_bin_vendored::protoc_bin_path().expect("protoc vendorizado indisponível");
+    // SAFETY: o build script é single-threaded neste ponto
+    unsafe { std::env::set_var("PROTOC", protoc) };
+    tonic_build::compile_protos("proto/nenv.proto").expect("falha ao compilar proto/nenv.proto");
+}
@@ -0,0 +1,68 @@
+/*
+ * nen_v.h - Interface C do motor NEN-V
+ *
+ * Corresponde às funções exportadas por src/ffi.rs (feature "ffi").
+ * Compilar a biblioteca:
+ *
+ *     cargo rustc --release --lib --crate-type cdylib --features ffi
+ *
+ * Os ponteiros de estado devolvidos pelas funções nenv_network_firing,
+ * _energy, _priority e _novelty apontam para nenv_network_num_neurons()
+ * valores e continuam válidos até ao próximo nenv_network_step ou
+ * nenv_network_destroy da mesma rede.
+ */
+
+#ifndef NEN_V_H
+#define NEN_V_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Códigos de retorno de nenv_network_step */
+#define NENV_OK 0
+#define NENV_ERR_NULL (-1)
+#define NENV_ERR_LENGTH (-2)
+
+/* Topologia da rede */
+typedef enum NenvConnectivity {
+    NENV_FULLY_CONNECTED = 0,
+    NENV_GRID_2D = 1,
+} NenvConnectivity;
+
+/* Rede NEN-V (opaca) */
+typedef struct NenvNetwork NenvNetwork;
+
+/* Cria uma rede; devolve NULL se num_neurons for zero */
+NenvNetwork *nenv_network_create(size_t num_neurons,
+                                 NenvConnectivity connectivity,
+                                 double inhibitory_ratio,
+                                 double initial_threshold,
+                                 uint64_t seed);
+
+/* Liberta a rede (NULL é ignorado) */
+void nenv_network_destroy(NenvNetwork *net);
+
+/* Executa um passo; len tem de ser igual ao número de neurónios */
+int32_t nenv_network_step(NenvNetwork *net, const double *inputs, size_t len);
+
+size_t nenv_network_num_neurons(const NenvNetwork *net);
+size_t nenv_network_grid_width(const NenvNetwork *net);
+int64_t nenv_network_time_step(const NenvNetwork *net);
+double nenv_network_alert_level(const NenvNetwork *net);
+double nenv_network_average_novelty(const NenvNetwork *net);
+
+/* Estado por neurónio após o último passo */
+const uint8_t *nenv_network_firing(const NenvNetwork *net);
+const double *nenv_network_energy(const NenvNetwork *net);
+const double *nenv_network_priority(const NenvNetwork *net);
+const double *nenv_network_novelty(const NenvNetwork *net);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* NEN_V_H */
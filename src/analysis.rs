@@ -0,0 +1,863 @@
+//! Módulo de análise da atividade da rede
+//!
+//! Ferramentas para estudar a conectividade funcional emergente a partir
+//! dos disparos registados: matrizes de correlação e correlogramas cruzados.
+//! As matrizes retornadas podem ser desenhadas diretamente como heatmaps.
+//!
+//! Inclui também estimadores de teoria da informação (informação mútua e
+//! transferência de entropia) sobre sequências discretas, para quantificar
+//! quanta informação sobre os inputs é preservada pelas respostas.
+//!
+//! Por fim, `pca` reduz trajetórias de estado (tempo × neurónios) às suas
+//! componentes principais para visualização em baixa dimensão.
+//!
+//! Para comparar a NEN-V com simuladores de referência (trens importados com
+//! `SpikeTrain::read_gdf` ou `read_csv`), há a diferença de taxas por
+//! neurónio e a distância de van Rossum entre trens.
+//!
+//! As curvas de sintonia (`tuning_curves`) dão a resposta média de cada
+//! neurónio a cada estímulo; `topographic_quality` mede se vizinhos na
+//! grade preferem estímulos semelhantes (formação de mapas topográficos).
+//!
+//! Para experimentos de memória há medidas padronizadas sobre padrões de
+//! atividade: a sobreposição entre padrões (`pattern_overlap`), quanto de um
+//! padrão guardado é recuperado a partir de uma pista parcial
+//! (`completion_index`) e quanto a rede afasta respostas a inputs
+//! semelhantes (`separation_index`).
+
+use crate::spikes::SpikeTrain;
+use std::collections::HashMap;
+
+/// Contagens por janela de cada neurónio selecionado
+///
+/// Usa as mesmas janelas de `metrics::binned_counts` (a última janela
+/// parcial é descartada).
+///
+/// # Retorna
+/// Matriz `neurons.len() × num_bins`
+pub fn binned_spike_matrix(
+    train: &SpikeTrain,
+    neurons: &[usize],
+    start: i64,
+    end: i64,
+    bin: i64,
+) -> Vec<Vec<f64>> {
+    if bin <= 0 || end <= start {
+        return vec![Vec::new(); neurons.len()];
+    }
+
+    let num_bins = ((end - start) / bin) as usize;
+    let mut matrix = vec![vec![0.0; num_bins]; neurons.len()];
+
+    // Mapeia índice do neurónio → linha da matriz (uma única passagem pelos eventos)
+    let mut row_of = vec![None; train.num_neurons()];
+    for (row, &n) in neurons.iter().enumerate() {
+        if n < row_of.len() {
+            row_of[n] = Some(row);
+        }
+    }
+
+    for &(n, t) in train.events() {
+        if t < start || t >= end {
+            continue;
+        }
+        let bin_idx = ((t - start) / bin) as usize;
+        if let Some(Some(row)) = row_of.get(n)
+            && bin_idx < num_bins
+        {
+            matrix[*row][bin_idx] += 1.0;
+        }
+    }
+
+    matrix
+}
+
+/// Matriz de correlação de Pearson entre os trens de disparo (contagens por janela)
+///
+/// Para redes grandes, passe apenas um subconjunto de `neurons` (p.ex.
+/// `(0..n).step_by(k)`) para subamostrar a matriz.
+///
+/// Neurónios silenciosos (variância zero) têm correlação 0 com todos os
+/// outros, inclusive consigo mesmos.
+///
+/// # Argumentos
+/// * `train` - Disparos registados
+/// * `neurons` - Neurónios a incluir, na ordem das linhas/colunas da matriz
+/// * `start`, `end` - Janela de análise `[start, end)`
+/// * `bin` - Largura das janelas de contagem em passos
+pub fn correlation_matrix(
+    train: &SpikeTrain,
+    neurons: &[usize],
+    start: i64,
+    end: i64,
+    bin: i64,
+) -> Vec<Vec<f64>> {
+    let counts = binned_spike_matrix(train, neurons, start, end, bin);
+
+    // Centraliza cada série e guarda a sua norma
+    let centered: Vec<(Vec<f64>, f64)> = counts
+        .iter()
+        .map(|series| {
+            let mean = series.iter().sum::<f64>() / series.len().max(1) as f64;
+            let centered: Vec<f64> = series.iter().map(|c| c - mean).collect();
+            let norm = centered.iter().map(|c| c * c).sum::<f64>().sqrt();
+            (centered, norm)
+        })
+        .collect();
+
+    let n = neurons.len();
+    let mut matrix = vec![vec![0.0; n]; n];
+
+    for i in 0..n {
+        for j in i..n {
+            let (a, norm_a) = &centered[i];
+            let (b, norm_b) = &centered[j];
+
+            let value = if *norm_a > 0.0 && *norm_b > 0.0 {
+                let dot: f64 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
+                dot / (norm_a * norm_b)
+            } else {
+                0.0
+            };
+
+            matrix[i][j] = value;
+            matrix[j][i] = value;
+        }
+    }
+
+    matrix
+}
+
+/// Correlograma cruzado entre dois neurónios
+///
+/// Conta, para cada atraso τ em `[-max_lag, max_lag]`, quantos pares de
+/// disparos (a em t, b em t + τ) existem. Um pico em τ > 0 indica que `b`
+/// tende a disparar depois de `a`.
+///
+/// # Retorna
+/// Vetor de `2 * max_lag + 1` contagens; o índice `max_lag` corresponde a τ = 0
+pub fn cross_correlogram(train: &SpikeTrain, a: usize, b: usize, max_lag: i64) -> Vec<usize> {
+    let max_lag = max_lag.max(0);
+    let mut histogram = vec![0; (2 * max_lag + 1) as usize];
+
+    let times_a = train.spike_times(a);
+    let times_b = train.spike_times(b);
+
+    for &ta in &times_a {
+        for &tb in &times_b {
+            let lag = tb - ta;
+            if lag.abs() <= max_lag {
+                histogram[(lag + max_lag) as usize] += 1;
+            }
+        }
+    }
+
+    histogram
+}
+
+/// Sequência binária (0/1) de disparos de um neurónio, um símbolo por passo em `[start, end)`
+pub fn spike_sequence(train: &SpikeTrain, neuron: usize, start: i64, end: i64) -> Vec<usize> {
+    let mut sequence = vec![0; (end - start).max(0) as usize];
+    for t in train.spike_times(neuron) {
+        if t >= start && t < end {
+            sequence[(t - start) as usize] = 1;
+        }
+    }
+    sequence
+}
+
+/// Discretiza uma série contínua em `bins` símbolos de largura igual
+///
+/// Útil para converter canais de estímulo (amplitudes) em símbolos antes
+/// de estimar informação mútua. Séries constantes resultam no símbolo 0.
+pub fn discretize(values: &[f64], bins: usize) -> Vec<usize> {
+    let bins = bins.max(1);
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    values
+        .iter()
+        .map(|&v| {
+            if range > 0.0 {
+                (((v - min) / range) * bins as f64).floor().min((bins - 1) as f64) as usize
+            } else {
+                0
+            }
+        })
+        .collect()
+}
+
+/// Entropia (em bits) de uma distribuição dada por contagens
+fn entropy_from_counts<'a>(counts: impl Iterator<Item = &'a usize>, total: usize) -> f64 {
+    let total = total as f64;
+    counts
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let p = c as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Informação mútua I(X; Y) em bits entre duas sequências discretas
+///
+/// Estimador plug-in (frequências empíricas), sem correção de viés: para
+/// sequências curtas ou muitos símbolos, o valor tende a ser sobrestimado.
+/// Sequências de tamanhos diferentes são truncadas ao menor tamanho.
+pub fn mutual_information(x: &[usize], y: &[usize]) -> f64 {
+    let n = x.len().min(y.len());
+    if n == 0 {
+        return 0.0;
+    }
+
+    let mut joint: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut marginal_x: HashMap<usize, usize> = HashMap::new();
+    let mut marginal_y: HashMap<usize, usize> = HashMap::new();
+
+    for (&a, &b) in x.iter().zip(y.iter()).take(n) {
+        *joint.entry((a, b)).or_insert(0) += 1;
+        *marginal_x.entry(a).or_insert(0) += 1;
+        *marginal_y.entry(b).or_insert(0) += 1;
+    }
+
+    let h_x = entropy_from_counts(marginal_x.values(), n);
+    let h_y = entropy_from_counts(marginal_y.values(), n);
+    let h_xy = entropy_from_counts(joint.values(), n);
+
+    (h_x + h_y - h_xy).max(0.0)
+}
+
+/// Transferência de entropia T(X → Y) em bits, com histórico de um passo
+///
+/// Mede quanto o estado atual de `source` reduz a incerteza sobre o próximo
+/// estado de `target`, além do que o próprio passado de `target` já explica:
+///
+/// T = H(Yₜ₊₁ | Yₜ) − H(Yₜ₊₁ | Yₜ, Xₜ)
+///
+/// Usa o mesmo estimador plug-in de `mutual_information`.
+pub fn transfer_entropy(source: &[usize], target: &[usize]) -> f64 {
+    let n = source.len().min(target.len());
+    if n < 2 {
+        return 0.0;
+    }
+
+    let mut yyx: HashMap<(usize, usize, usize), usize> = HashMap::new();
+    let mut yx: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut yy: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut y: HashMap<usize, usize> = HashMap::new();
+
+    for t in 0..n - 1 {
+        let (y_next, y_now, x_now) = (target[t + 1], target[t], source[t]);
+        *yyx.entry((y_next, y_now, x_now)).or_insert(0) += 1;
+        *yx.entry((y_now, x_now)).or_insert(0) += 1;
+        *yy.entry((y_next, y_now)).or_insert(0) += 1;
+        *y.entry(y_now).or_insert(0) += 1;
+    }
+
+    let samples = n - 1;
+
+    // H(Yₜ₊₁ | Yₜ) = H(Yₜ₊₁, Yₜ) − H(Yₜ)
+    let h_next_given_past = entropy_from_counts(yy.values(), samples)
+        - entropy_from_counts(y.values(), samples);
+
+    // H(Yₜ₊₁ | Yₜ, Xₜ) = H(Yₜ₊₁, Yₜ, Xₜ) − H(Yₜ, Xₜ)
+    let h_next_given_past_and_source = entropy_from_counts(yyx.values(), samples)
+        - entropy_from_counts(yx.values(), samples);
+
+    (h_next_given_past - h_next_given_past_and_source).max(0.0)
+}
+
+/// Resultado de uma análise de componentes principais
+#[derive(Debug, Clone)]
+pub struct PcaResult {
+    /// Média de cada variável (coluna) removida antes da análise
+    pub mean: Vec<f64>,
+
+    /// Componentes principais (vetores unitários), da maior para a menor variância
+    pub components: Vec<Vec<f64>>,
+
+    /// Variância explicada por cada componente
+    pub explained_variance: Vec<f64>,
+
+    /// Fração da variância total explicada por cada componente
+    pub explained_variance_ratio: Vec<f64>,
+
+    /// Trajetória projetada: uma linha por amostra, uma coluna por componente
+    pub projections: Vec<Vec<f64>>,
+}
+
+/// Análise de componentes principais de uma matriz de estados
+///
+/// Cada linha de `states` é uma amostra (p.ex. um passo de tempo) e cada
+/// coluna uma variável (p.ex. a energia ou o disparo de um neurónio). Os
+/// autovetores da matriz de covariância são obtidos por iteração de
+/// potência com deflação, adequada para as poucas componentes usadas em
+/// visualização.
+///
+/// # Argumentos
+/// * `states` - Matriz amostras × variáveis (todas as linhas com o mesmo tamanho)
+/// * `num_components` - Número de componentes a extrair
+pub fn pca(states: &[Vec<f64>], num_components: usize) -> PcaResult {
+    let num_samples = states.len();
+    let dim = states.first().map(|row| row.len()).unwrap_or(0);
+    let num_components = num_components.min(dim);
+
+    // Centraliza os dados
+    let mut mean = vec![0.0; dim];
+    for row in states {
+        for (m, &v) in mean.iter_mut().zip(row.iter()) {
+            *m += v / num_samples as f64;
+        }
+    }
+    let centered: Vec<Vec<f64>> = states
+        .iter()
+        .map(|row| row.iter().zip(mean.iter()).map(|(v, m)| v - m).collect())
+        .collect();
+
+    // Matriz de covariância (dim × dim)
+    let denominator = (num_samples.max(2) - 1) as f64;
+    let mut covariance = vec![vec![0.0; dim]; dim];
+    for row in &centered {
+        for i in 0..dim {
+            if row[i] == 0.0 {
+                continue;
+            }
+            for j in i..dim {
+                covariance[i][j] += row[i] * row[j] / denominator;
+            }
+        }
+    }
+    for i in 1..dim {
+        let (upper, lower) = covariance.split_at_mut(i);
+        for (j, upper_row) in upper.iter().enumerate() {
+            lower[0][j] = upper_row[i];
+        }
+    }
+
+    let total_variance: f64 = (0..dim).map(|i| covariance[i][i]).sum();
+
+    let mut components = Vec::with_capacity(num_components);
+    let mut explained_variance = Vec::with_capacity(num_components);
+
+    for k in 0..num_components {
+        let (eigenvalue, eigenvector) = power_iteration(&covariance, k);
+        if eigenvalue <= 1e-12 {
+            break;
+        }
+
+        // Deflação: remove a componente encontrada da covariância
+        for i in 0..dim {
+            for j in 0..dim {
+                covariance[i][j] -= eigenvalue * eigenvector[i] * eigenvector[j];
+            }
+        }
+
+        components.push(eigenvector);
+        explained_variance.push(eigenvalue);
+    }
+
+    let explained_variance_ratio = explained_variance
+        .iter()
+        .map(|&v| if total_variance > 0.0 { v / total_variance } else { 0.0 })
+        .collect();
+
+    let projections = centered
+        .iter()
+        .map(|row| {
+            components
+                .iter()
+                .map(|c| c.iter().zip(row.iter()).map(|(a, b)| a * b).sum())
+                .collect()
+        })
+        .collect();
+
+    PcaResult {
+        mean,
+        components,
+        explained_variance,
+        explained_variance_ratio,
+        projections,
+    }
+}
+
+/// Autovalor dominante e autovetor unitário de uma matriz simétrica
+///
+/// O vetor inicial é determinístico (depende apenas de `seed`) para que a
+/// análise seja reprodutível.
+fn power_iteration(matrix: &[Vec<f64>], seed: usize) -> (f64, Vec<f64>) {
+    const MAX_ITERATIONS: usize = 1000;
+    const TOLERANCE: f64 = 1e-12;
+
+    let dim = matrix.len();
+    let mut vector: Vec<f64> = (0..dim)
+        .map(|i| 1.0 + ((i + seed) % 7) as f64 * 0.1)
+        .collect();
+    normalize(&mut vector);
+
+    let mut eigenvalue = 0.0;
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut next: Vec<f64> = matrix
+            .iter()
+            .map(|row| row.iter().zip(vector.iter()).map(|(a, b)| a * b).sum())
+            .collect();
+
+        let norm = normalize(&mut next);
+        if norm == 0.0 {
+            return (0.0, vector);
+        }
+
+        let delta: f64 = next
+            .iter()
+            .zip(vector.iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum();
+
+        vector = next;
+        eigenvalue = norm;
+
+        if delta < TOLERANCE {
+            break;
+        }
+    }
+
+    // Convenção de sinal: maior coordenada em módulo é positiva
+    let max_idx = (0..dim)
+        .max_by(|&a, &b| vector[a].abs().total_cmp(&vector[b].abs()))
+        .unwrap_or(0);
+    if dim > 0 && vector[max_idx] < 0.0 {
+        for v in &mut vector {
+            *v = -*v;
+        }
+    }
+
+    (eigenvalue, vector)
+}
+
+/// Normaliza um vetor para norma unitária e retorna a norma original
+fn normalize(vector: &mut [f64]) -> f64 {
+    let norm = vector.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        for v in vector.iter_mut() {
+            *v /= norm;
+        }
+    }
+    norm
+}
+
+/// Diferença de taxa de disparo por neurónio (`a - b`, disparos por passo)
+///
+/// Neurónios presentes num só dos trens contam com taxa zero no outro.
+///
+/// # Argumentos
+/// * `duration` - Duração comum da observação em passos
+pub fn rate_difference(a: &SpikeTrain, b: &SpikeTrain, duration: i64) -> Vec<f64> {
+    let num_neurons = a.num_neurons().max(b.num_neurons());
+    let (rates_a, rates_b) = (a.rates(duration), b.rates(duration));
+    (0..num_neurons)
+        .map(|i| rates_a.get(i).unwrap_or(&0.0) - rates_b.get(i).unwrap_or(&0.0))
+        .collect()
+}
+
+/// Distância de van Rossum entre dois trens de um neurónio
+///
+/// Cada trem é convolvido com uma exponencial causal de constante `tau` e
+/// a distância é `sqrt((1/tau) ∫ (f - g)² dt)`, calculada de forma fechada.
+/// Um único disparo sem correspondência contribui `sqrt(1/2)`.
+///
+/// # Argumentos
+/// * `a`, `b` - Tempos de disparo
+/// * `tau` - Constante de tempo do filtro em passos (maior que zero)
+pub fn van_rossum_distance(a: &[i64], b: &[i64], tau: f64) -> f64 {
+    let kernel_sum = |x: &[i64], y: &[i64]| -> f64 {
+        x.iter()
+            .flat_map(|&tx| y.iter().map(move |&ty| (-((tx - ty).abs() as f64) / tau).exp()))
+            .sum()
+    };
+    let squared = 0.5 * (kernel_sum(a, a) + kernel_sum(b, b) - 2.0 * kernel_sum(a, b));
+    squared.max(0.0).sqrt()
+}
+
+/// Distância de van Rossum média sobre todos os neurónios de dois trens
+pub fn population_van_rossum(a: &SpikeTrain, b: &SpikeTrain, tau: f64) -> f64 {
+    let num_neurons = a.num_neurons().max(b.num_neurons());
+    if num_neurons == 0 {
+        return 0.0;
+    }
+    (0..num_neurons)
+        .map(|i| van_rossum_distance(&a.spike_times(i), &b.spike_times(i), tau))
+        .sum::<f64>()
+        / num_neurons as f64
+}
+
+/// Curvas de sintonia: resposta média de cada neurónio a cada estímulo
+///
+/// # Argumentos
+/// * `labels` - Estímulo apresentado em cada ensaio (índice em `0..num_stimuli`)
+/// * `responses` - Resposta de cada neurónio em cada ensaio (p.ex. disparos)
+/// * `num_stimuli` - Número de estímulos distintos
+///
+/// # Retorna
+/// Matriz `neurónios × num_stimuli`; estímulos nunca apresentados ficam a 0
+pub fn tuning_curves(
+    labels: &[usize],
+    responses: &[Vec<f64>],
+    num_stimuli: usize,
+) -> Vec<Vec<f64>> {
+    let num_neurons = responses.first().map_or(0, Vec::len);
+    let mut curves = vec![vec![0.0; num_stimuli]; num_neurons];
+    let mut counts = vec![0usize; num_stimuli];
+
+    for (&label, trial) in labels.iter().zip(responses) {
+        if label >= num_stimuli {
+            continue;
+        }
+        counts[label] += 1;
+        for (curve, &response) in curves.iter_mut().zip(trial) {
+            curve[label] += response;
+        }
+    }
+    for curve in &mut curves {
+        for (value, &count) in curve.iter_mut().zip(&counts) {
+            if count > 0 {
+                *value /= count as f64;
+            }
+        }
+    }
+    curves
+}
+
+/// Estímulo preferido de cada neurónio (máximo da curva de sintonia)
+///
+/// `None` para neurónios que não respondem a nenhum estímulo.
+pub fn preferred_stimuli(curves: &[Vec<f64>]) -> Vec<Option<usize>> {
+    curves
+        .iter()
+        .map(|curve| {
+            curve
+                .iter()
+                .enumerate()
+                .filter(|&(_, &r)| r > 0.0)
+                .max_by(|a, b| a.1.total_cmp(b.1))
+                .map(|(k, _)| k)
+        })
+        .collect()
+}
+
+/// Qualidade do mapa topográfico numa grade
+///
+/// Compara a diferença média de preferência entre vizinhos (4-vizinhança)
+/// com a diferença média entre todos os pares:
+///
+/// ```text
+/// qualidade = 1 - média_vizinhos / média_todos_os_pares
+/// ```
+///
+/// Um mapa ordenado aproxima-se de 1; preferências aleatórias dão ≈ 0.
+/// Neurónios sem preferência são ignorados.
+///
+/// # Retorna
+/// 0.0 se não houver pares suficientes para comparar
+pub fn topographic_quality(preferred: &[Option<usize>], grid_width: usize) -> f64 {
+    if grid_width == 0 {
+        return 0.0;
+    }
+    let diff = |a: usize, b: usize| (a as f64 - b as f64).abs();
+    let tuned: Vec<(usize, usize)> =
+        preferred.iter().enumerate().filter_map(|(i, p)| p.map(|p| (i, p))).collect();
+
+    let (mut neighbor_sum, mut neighbor_pairs) = (0.0, 0usize);
+    let (mut all_sum, mut all_pairs) = (0.0, 0usize);
+    for (a, &(i, pi)) in tuned.iter().enumerate() {
+        for &(j, pj) in &tuned[a + 1..] {
+            all_sum += diff(pi, pj);
+            all_pairs += 1;
+
+            let (ri, ci) = (i / grid_width, i % grid_width);
+            let (rj, cj) = (j / grid_width, j % grid_width);
+            if ri.abs_diff(rj) + ci.abs_diff(cj) == 1 {
+                neighbor_sum += diff(pi, pj);
+                neighbor_pairs += 1;
+            }
+        }
+    }
+
+    if neighbor_pairs == 0 || all_pairs == 0 || all_sum == 0.0 {
+        return 0.0;
+    }
+    1.0 - (neighbor_sum / neighbor_pairs as f64) / (all_sum / all_pairs as f64)
+}
+
+/// Sobreposição entre dois padrões de atividade (similaridade do cosseno)
+///
+/// Para padrões binários de disparo é o número de neurónios ativos em comum
+/// normalizado pela média geométrica dos ativos de cada um. Padrões de
+/// comprimentos diferentes são comparados no prefixo comum.
+///
+/// # Retorna
+/// Valor em [-1, 1] (em [0, 1] para atividade não negativa); 0.0 se um dos
+/// padrões for nulo
+pub fn pattern_overlap(a: &[f64], b: &[f64]) -> f64 {
+    let (mut dot, mut norm_a, mut norm_b) = (0.0, 0.0, 0.0);
+    for (&x, &y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+/// Índice de completação de padrões
+///
+/// Fração da sobreposição em falta na pista que a recordação recupera:
+///
+/// ```text
+/// completação = (overlap(recordado, guardado) - overlap(pista, guardado))
+///             / (1 - overlap(pista, guardado))
+/// ```
+///
+/// 1.0 = padrão guardado recuperado por completo, 0.0 = a recordação não
+/// acrescenta nada à pista, negativo = a recordação afasta-se do padrão.
+///
+/// # Argumentos
+/// * `stored` - Padrão guardado (atividade durante a aprendizagem)
+/// * `cue` - Pista apresentada (p.ex. o padrão com parte dos neurónios em falta)
+/// * `recalled` - Atividade da rede em resposta à pista
+///
+/// # Retorna
+/// 0.0 se a pista já coincidir com o padrão guardado
+pub fn completion_index(stored: &[f64], cue: &[f64], recalled: &[f64]) -> f64 {
+    let cue_overlap = pattern_overlap(cue, stored);
+    let missing = 1.0 - cue_overlap;
+    if missing <= f64::EPSILON {
+        return 0.0;
+    }
+    (pattern_overlap(recalled, stored) - cue_overlap) / missing
+}
+
+/// Índice de separação de padrões
+///
+/// Diferença entre a sobreposição dos inputs e a sobreposição das respostas:
+///
+/// ```text
+/// separação = overlap(input_a, input_b) - overlap(resposta_a, resposta_b)
+/// ```
+///
+/// Positivo = a rede torna inputs semelhantes mais distintos (separação),
+/// negativo = aproxima-os (generalização ou completação), 0.0 = preserva a
+/// semelhança.
+pub fn separation_index(
+    input_a: &[f64],
+    input_b: &[f64],
+    response_a: &[f64],
+    response_b: &[f64],
+) -> f64 {
+    pattern_overlap(input_a, input_b) - pattern_overlap(response_a, response_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_van_rossum_distance() {
+        assert_relative_eq!(van_rossum_distance(&[5, 20], &[5, 20], 4.0), 0.0);
+        assert_relative_eq!(van_rossum_distance(&[5], &[], 4.0), 0.5f64.sqrt());
+
+        // Disparos mais próximos dão distâncias menores
+        let near = van_rossum_distance(&[10], &[11], 4.0);
+        let far = van_rossum_distance(&[10], &[30], 4.0);
+        assert!(near < far);
+        assert_relative_eq!(far, 1.0, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn test_rate_difference_pads_missing_neurons() {
+        let a = SpikeTrain::from_events(2, vec![(0, 1), (0, 3), (1, 2)]);
+        let b = SpikeTrain::from_events(3, vec![(0, 1), (2, 5)]);
+
+        let diff = rate_difference(&a, &b, 10);
+        assert_eq!(diff.len(), 3);
+        assert_relative_eq!(diff[0], 0.1);
+        assert_relative_eq!(diff[2], -0.1);
+        assert_relative_eq!(population_van_rossum(&a, &a, 2.0), 0.0);
+    }
+
+    #[test]
+    fn test_correlation_of_identical_and_opposite_trains() {
+        let mut train = SpikeTrain::new(3);
+        // Neurónios 0 e 1 disparam juntos nos passos pares; 2 nos ímpares
+        for t in 0..20 {
+            if t % 2 == 0 {
+                train.push(0, t);
+                train.push(1, t);
+            } else {
+                train.push(2, t);
+            }
+        }
+
+        let matrix = correlation_matrix(&train, &[0, 1, 2], 0, 20, 1);
+        assert_relative_eq!(matrix[0][0], 1.0, epsilon = 1e-12);
+        assert_relative_eq!(matrix[0][1], 1.0, epsilon = 1e-12);
+        assert_relative_eq!(matrix[0][2], -1.0, epsilon = 1e-12);
+        assert_relative_eq!(matrix[2][0], matrix[0][2]);
+    }
+
+    #[test]
+    fn test_silent_neuron_has_zero_correlation() {
+        let mut train = SpikeTrain::new(2);
+        train.push(0, 1);
+        train.push(0, 3);
+
+        let matrix = correlation_matrix(&train, &[0, 1], 0, 4, 1);
+        assert_eq!(matrix[1], vec![0.0, 0.0]);
+    }
+
+    /// Sequência binária pseudoaleatória determinística (LCG)
+    fn pseudo_random_bits(len: usize, seed: u64) -> Vec<usize> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                ((state >> 33) & 1) as usize
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_mutual_information_known_cases() {
+        // Sequência binária equiprovável copiada: I = H(X) = 1 bit
+        let x = vec![0, 1, 0, 1, 1, 0, 1, 0];
+        assert_relative_eq!(mutual_information(&x, &x), 1.0, epsilon = 1e-12);
+
+        // Independência exata: todas as combinações com a mesma frequência
+        let a = vec![0, 0, 1, 1];
+        let b = vec![0, 1, 0, 1];
+        assert_relative_eq!(mutual_information(&a, &b), 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_transfer_entropy_detects_direction() {
+        let source = pseudo_random_bits(5000, 42);
+
+        // Alvo copia a fonte com um passo de atraso
+        let mut target = vec![0];
+        target.extend_from_slice(&source[..source.len() - 1]);
+
+        let forward = transfer_entropy(&source, &target);
+        let backward = transfer_entropy(&target, &source);
+
+        assert!(forward > 0.95, "T(X→Y) = {}", forward);
+        assert!(backward < 0.01, "T(Y→X) = {}", backward);
+    }
+
+    #[test]
+    fn test_discretize_and_spike_sequence() {
+        assert_eq!(discretize(&[0.0, 0.5, 1.0, 2.0], 2), vec![0, 0, 1, 1]);
+        assert_eq!(discretize(&[3.0, 3.0], 4), vec![0, 0]);
+
+        let mut train = SpikeTrain::new(1);
+        train.push(0, 2);
+        train.push(0, 4);
+        assert_eq!(spike_sequence(&train, 0, 1, 5), vec![0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn test_pca_recovers_line_direction() {
+        // Pontos ao longo da direção (1, 2) com pequena perturbação ortogonal
+        let states: Vec<Vec<f64>> = (0..50)
+            .map(|t| {
+                let t = t as f64;
+                let noise = if (t as i64) % 2 == 0 { 0.01 } else { -0.01 };
+                vec![t + 2.0 * noise, 2.0 * t - noise]
+            })
+            .collect();
+
+        let result = pca(&states, 2);
+        let expected = [1.0 / 5f64.sqrt(), 2.0 / 5f64.sqrt()];
+
+        assert_relative_eq!(result.components[0][0], expected[0], epsilon = 1e-4);
+        assert_relative_eq!(result.components[0][1], expected[1], epsilon = 1e-4);
+        assert!(result.explained_variance_ratio[0] > 0.999);
+        assert_eq!(result.projections.len(), 50);
+        assert_eq!(result.projections[0].len(), result.components.len());
+    }
+
+    #[test]
+    fn test_pca_of_constant_states_has_no_components() {
+        let states = vec![vec![1.0, 1.0]; 10];
+        let result = pca(&states, 2);
+        assert!(result.components.is_empty());
+    }
+
+    #[test]
+    fn test_cross_correlogram_peak_at_lag() {
+        let mut train = SpikeTrain::new(2);
+        // b dispara sempre 2 passos depois de a
+        for t in (0..50).step_by(10) {
+            train.push(0, t);
+            train.push(1, t + 2);
+        }
+
+        let histogram = cross_correlogram(&train, 0, 1, 3);
+        assert_eq!(histogram.len(), 7);
+        assert_eq!(histogram[3 + 2], 5);
+        assert_eq!(histogram.iter().sum::<usize>(), 5);
+    }
+
+    #[test]
+    fn test_tuning_curves_and_preference() {
+        let labels = [0, 1, 0, 2];
+        let responses = vec![
+            vec![2.0, 0.0],
+            vec![0.0, 1.0],
+            vec![4.0, 0.0],
+            vec![1.0, 0.0],
+        ];
+        let curves = tuning_curves(&labels, &responses, 3);
+        assert_eq!(curves, vec![vec![3.0, 0.0, 1.0], vec![0.0, 1.0, 0.0]]);
+        assert_eq!(preferred_stimuli(&curves), vec![Some(0), Some(1)]);
+        assert_eq!(preferred_stimuli(&[vec![0.0, 0.0]]), vec![None]);
+    }
+
+    #[test]
+    fn test_topographic_quality_ordered_vs_shuffled() {
+        // Grade 4×4: preferência igual à coluna → mapa ordenado
+        let ordered: Vec<Option<usize>> = (0..16).map(|i| Some(i % 4)).collect();
+        let shuffled: Vec<Option<usize>> =
+            [0, 3, 1, 2, 2, 0, 3, 1, 1, 2, 0, 3, 3, 1, 2, 0].iter().map(|&p| Some(p)).collect();
+
+        let q_ordered = topographic_quality(&ordered, 4);
+        let q_shuffled = topographic_quality(&shuffled, 4);
+        assert!(q_ordered > 0.5, "{}", q_ordered);
+        assert!(q_shuffled < q_ordered);
+        assert_eq!(topographic_quality(&[Some(1), None], 2), 0.0);
+    }
+
+    #[test]
+    fn test_pattern_overlap_completion_and_separation() {
+        let stored = [1.0, 1.0, 1.0, 1.0, 0.0, 0.0];
+        let cue = [1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+        assert_relative_eq!(pattern_overlap(&stored, &stored), 1.0);
+        assert_relative_eq!(pattern_overlap(&stored, &[0.0, 0.0, 0.0, 0.0, 1.0, 1.0]), 0.0);
+        assert_relative_eq!(pattern_overlap(&stored, &[0.0; 6]), 0.0);
+        assert_relative_eq!(pattern_overlap(&cue, &stored), 0.5f64.sqrt());
+
+        assert_relative_eq!(completion_index(&stored, &cue, &stored), 1.0);
+        assert_relative_eq!(completion_index(&stored, &cue, &cue), 0.0, epsilon = 1e-12);
+        assert!(completion_index(&stored, &cue, &[0.0, 0.0, 0.0, 0.0, 1.0, 1.0]) < 0.0);
+        assert_eq!(completion_index(&stored, &stored, &cue), 0.0);
+
+        // Inputs semelhantes com respostas disjuntas: separação máxima
+        let (a, b) = ([1.0, 1.0, 1.0, 0.0], [1.0, 1.0, 0.0, 1.0]);
+        let overlap = pattern_overlap(&a, &b);
+        assert_relative_eq!(separation_index(&a, &b, &[1.0, 0.0], &[0.0, 1.0]), overlap);
+        assert_relative_eq!(separation_index(&a, &b, &a, &b), 0.0);
+        assert!(separation_index(&a, &b, &[1.0, 1.0], &[1.0, 1.0]) < 0.0);
+    }
+}
@@ -0,0 +1,40 @@
+//! Servidor REST de simulação remota do NEN-V
+//!
+//! Uso: `cargo run --features server --bin nenv-server -- [endereço]`
+//! (por omissão 127.0.0.1:8080). Ver `nen_v_prototipo::server` para os endpoints.
+//!
+//! Com a feature `grpc`, `--grpc <endereço>` serve também o gRPC de
+//! `proto/nenv.proto` sobre as mesmas redes (ver `nen_v_prototipo::grpc`).
+
+use nen_v_prototipo::server::SimulationServer;
+
+fn main() -> std::io::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let grpc_pos = args.iter().position(|arg| arg == "--grpc");
+    let addr = args
+        .iter()
+        .enumerate()
+        .find(|&(i, _)| grpc_pos.is_none_or(|pos| i != pos && i != pos + 1))
+        .map_or("127.0.0.1:8080", |(_, arg)| arg.as_str());
+
+    let server = SimulationServer::bind(addr)?;
+    println!("🌐 NEN-V REST em http://{}", server.local_addr());
+
+    #[cfg(feature = "grpc")]
+    let _grpc = match grpc_pos {
+        Some(pos) => {
+            let grpc_addr = args.get(pos + 1).map_or("127.0.0.1:50051", String::as_str);
+            let grpc = nen_v_prototipo::grpc::GrpcServer::bind(grpc_addr, server.service())?;
+            println!("🌐 NEN-V gRPC em {}", grpc.local_addr());
+            Some(grpc)
+        }
+        None => None,
+    };
+    #[cfg(not(feature = "grpc"))]
+    if grpc_pos.is_some() {
+        eprintln!("⚠️  --grpc requer a feature `grpc`");
+    }
+
+    server.wait();
+    Ok(())
+}
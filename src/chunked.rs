@@ -0,0 +1,369 @@
+//! Módulo de registo binário em blocos para simulações muito longas
+//!
+//! Com milhões de passos, um CSV fica lento de escrever e só pode ser lido
+//! de uma ponta à outra. `ChunkedSink` é um destino do `Recorder` que grava
+//! cada linha como um registo binário de tamanho fixo (`i64` do tempo e um
+//! `f64` por coluna, little-endian), em blocos de `chunk_len` registos, e
+//! mantém um índice com o intervalo de tempos de cada bloco:
+//!
+//! ```text
+//! registo.bin      "NENVREC1" · colunas (u32) · [nome (u32 + UTF-8)]... · registos...
+//! registo.bin.idx  "NENVIDX1" · registos por bloco (u64) · [primeiro, último tempo]...
+//! ```
+//!
+//! `ChunkedRecording` abre o par de ficheiros e lê linhas, intervalos ou uma
+//! coluna diretamente do disco (o registo `i` está numa posição fixa e o
+//! índice localiza um tempo sem percorrer o ficheiro), sem carregar a
+//! gravação inteira para a memória.
+//!
+//! Só blocos completos vão para o disco antes de `finish`; se o processo
+//! for interrompido, a leitura recupera todos os blocos já escritos.
+
+use crate::recorder::{RecordSink, RecordedColumns};
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+const DATA_MAGIC: &[u8; 8] = b"NENVREC1";
+const INDEX_MAGIC: &[u8; 8] = b"NENVIDX1";
+
+/// Registos por bloco usados por `ChunkedSink::create`
+pub const DEFAULT_CHUNK_LEN: usize = 4096;
+
+/// Caminho do índice de uma gravação (`<caminho>.idx`)
+pub fn index_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    let mut index = path.as_ref().as_os_str().to_owned();
+    index.push(".idx");
+    PathBuf::from(index)
+}
+
+/// Destino binário em blocos com índice de tempos
+pub struct ChunkedSink {
+    data: File,
+    index: File,
+    chunk_len: usize,
+    columns: Option<usize>,
+    /// Registos do bloco atual, ainda em memória
+    buffer: Vec<u8>,
+    buffered: usize,
+    first_time: i64,
+    last_time: i64,
+}
+
+impl ChunkedSink {
+    /// Cria (ou substitui) uma gravação com blocos de `DEFAULT_CHUNK_LEN`
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::with_chunk_len(path, DEFAULT_CHUNK_LEN)
+    }
+
+    /// Cria uma gravação com `chunk_len` registos por bloco (mínimo 1)
+    ///
+    /// Blocos maiores reduzem o índice e o número de escritas; cada bloco
+    /// ocupa `chunk_len · (8 + 8 · colunas)` bytes de memória enquanto enche.
+    pub fn with_chunk_len<P: AsRef<Path>>(path: P, chunk_len: usize) -> io::Result<Self> {
+        let data = File::create(&path)?;
+        let mut index = File::create(index_path(&path))?;
+        let chunk_len = chunk_len.max(1);
+        index.write_all(INDEX_MAGIC)?;
+        index.write_all(&(chunk_len as u64).to_le_bytes())?;
+        Ok(Self {
+            data,
+            index,
+            chunk_len,
+            columns: None,
+            buffer: Vec::new(),
+            buffered: 0,
+            first_time: 0,
+            last_time: 0,
+        })
+    }
+
+    /// Escreve o bloco atual e a respetiva entrada no índice
+    fn flush_chunk(&mut self) -> io::Result<()> {
+        if self.buffered == 0 {
+            return Ok(());
+        }
+        self.data.write_all(&self.buffer)?;
+        let mut entry = [0u8; 16];
+        entry[..8].copy_from_slice(&self.first_time.to_le_bytes());
+        entry[8..].copy_from_slice(&self.last_time.to_le_bytes());
+        self.index.write_all(&entry)?;
+        self.buffer.clear();
+        self.buffered = 0;
+        Ok(())
+    }
+}
+
+impl RecordSink for ChunkedSink {
+    fn begin(&mut self, columns: &[String]) -> io::Result<()> {
+        let mut header = DATA_MAGIC.to_vec();
+        header.extend_from_slice(&(columns.len() as u32).to_le_bytes());
+        for name in columns {
+            header.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            header.extend_from_slice(name.as_bytes());
+        }
+        self.data.write_all(&header)?;
+        self.columns = Some(columns.len());
+        self.buffer.reserve(self.chunk_len * record_size(columns.len()));
+        Ok(())
+    }
+
+    fn record(&mut self, time: i64, values: &[f64]) -> io::Result<()> {
+        if self.columns != Some(values.len()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("linha com {} valores para {:?} colunas", values.len(), self.columns),
+            ));
+        }
+        if self.buffered == 0 {
+            self.first_time = time;
+        }
+        self.last_time = time;
+        self.buffer.extend_from_slice(&time.to_le_bytes());
+        for value in values {
+            self.buffer.extend_from_slice(&value.to_le_bytes());
+        }
+        self.buffered += 1;
+        if self.buffered == self.chunk_len {
+            self.flush_chunk()?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.flush_chunk()?;
+        self.data.flush()?;
+        self.index.flush()
+    }
+}
+
+/// Bytes de um registo com `columns` colunas
+fn record_size(columns: usize) -> usize {
+    8 * (1 + columns)
+}
+
+/// Gravação de um `ChunkedSink` aberta para leitura aleatória
+#[derive(Debug)]
+pub struct ChunkedRecording {
+    file: File,
+    columns: Vec<String>,
+    /// Posição do primeiro registo no ficheiro
+    data_offset: u64,
+    len: usize,
+    chunk_len: usize,
+    /// Primeiro e último tempo de cada bloco
+    chunks: Vec<(i64, i64)>,
+}
+
+impl ChunkedRecording {
+    /// Abre uma gravação e o respetivo índice
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut file = File::open(&path)?;
+        let mut reader = BufReader::new(&mut file);
+
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != DATA_MAGIC {
+            return Err(invalid_data("não é uma gravação NEN-V".to_string()));
+        }
+        let count = read_u32(&mut reader)? as usize;
+        let mut columns = Vec::with_capacity(count);
+        let mut data_offset = 12;
+        for _ in 0..count {
+            let len = read_u32(&mut reader)? as usize;
+            let mut name = vec![0u8; len];
+            reader.read_exact(&mut name)?;
+            columns.push(String::from_utf8(name).map_err(|e| invalid_data(e.to_string()))?);
+            data_offset += 4 + len as u64;
+        }
+
+        let mut index = Vec::new();
+        File::open(index_path(&path))?.read_to_end(&mut index)?;
+        if index.len() < 16 || &index[..8] != INDEX_MAGIC {
+            return Err(invalid_data("índice inválido".to_string()));
+        }
+        let chunk_len = u64::from_le_bytes(index[8..16].try_into().unwrap()) as usize;
+        let chunks: Vec<(i64, i64)> = index[16..]
+            .chunks_exact(16)
+            .map(|entry| {
+                let first = i64::from_le_bytes(entry[..8].try_into().unwrap());
+                let last = i64::from_le_bytes(entry[8..].try_into().unwrap());
+                (first, last)
+            })
+            .collect();
+
+        // Só o bloco de `finish` pode estar incompleto; registos para além
+        // dos blocos indexados são de uma escrita interrompida
+        let size = record_size(columns.len()) as u64;
+        let stored = (file.metadata()?.len().saturating_sub(data_offset) / size) as usize;
+        let len = stored.min(chunks.len() * chunk_len);
+
+        Ok(Self {
+            file,
+            columns,
+            data_offset,
+            len,
+            chunk_len,
+            chunks,
+        })
+    }
+
+    /// Nomes das colunas
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Número de registos
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Verifica se a gravação não tem registos
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Número de blocos indexados
+    pub fn num_chunks(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Lê o registo `index`: tempo e valores
+    pub fn row(&mut self, index: usize) -> io::Result<(i64, Vec<f64>)> {
+        let rows = self.read(index..index + 1)?;
+        let values = rows.columns.iter().map(|(_, values)| values[0]).collect();
+        Ok((rows.times[0], values))
+    }
+
+    /// Lê os registos `range` em layout colunar
+    pub fn read(&mut self, range: Range<usize>) -> io::Result<RecordedColumns> {
+        if range.start > range.end || range.end > self.len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("intervalo {:?} fora de 0..{}", range, self.len),
+            ));
+        }
+        let size = record_size(self.columns.len());
+        let mut bytes = vec![0u8; range.len() * size];
+        self.file.seek(SeekFrom::Start(self.data_offset + (range.start * size) as u64))?;
+        self.file.read_exact(&mut bytes)?;
+
+        let mut columns: Vec<(String, Vec<f64>)> = self
+            .columns
+            .iter()
+            .map(|name| (name.clone(), Vec::with_capacity(range.len())))
+            .collect();
+        let mut times = Vec::with_capacity(range.len());
+        for record in bytes.chunks_exact(size) {
+            let mut fields = record.chunks_exact(8).map(|b| b.try_into().unwrap());
+            times.push(i64::from_le_bytes(fields.next().unwrap()));
+            for ((_, values), field) in columns.iter_mut().zip(fields) {
+                values.push(f64::from_le_bytes(field));
+            }
+        }
+        Ok(RecordedColumns { times, columns })
+    }
+
+    /// Lê uma coluna nos registos `range` (`None` se a coluna não existir)
+    pub fn column(&mut self, name: &str, range: Range<usize>) -> io::Result<Option<Vec<f64>>> {
+        let Some(idx) = self.columns.iter().position(|c| c == name) else {
+            return Ok(None);
+        };
+        let columns = self.read(range)?;
+        Ok(columns.columns.into_iter().nth(idx).map(|(_, values)| values))
+    }
+
+    /// Índice do primeiro registo com tempo ≥ `time` (`len()` se nenhum)
+    ///
+    /// O índice escolhe o bloco e só esse bloco é lido; assume tempos
+    /// crescentes, como os do `Recorder`.
+    pub fn find_time(&mut self, time: i64) -> io::Result<usize> {
+        let chunk = self.chunks.partition_point(|&(_, last)| last < time);
+        if chunk == self.chunks.len() {
+            return Ok(self.len);
+        }
+        let start = chunk * self.chunk_len;
+        let end = (start + self.chunk_len).min(self.len);
+        let times = self.read(start..end)?.times;
+        Ok(start + times.partition_point(|&t| t < time))
+    }
+}
+
+pub(crate) fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+pub(crate) fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{ConnectivityType, Network};
+    use crate::recorder::{NetworkScalar, Probe, Recorder};
+
+    #[test]
+    fn test_recording_round_trip_and_random_access() {
+        let dir = std::env::temp_dir().join(format!("nenv_chunked_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("registo.bin");
+
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 3);
+        let sink = ChunkedSink::with_chunk_len(&path, 16).unwrap();
+        let mut recorder = Recorder::new(2).with_memory().with_sink(Box::new(sink));
+        recorder.add_probe(Probe::Network(NetworkScalar::AverageEnergy));
+        recorder.add_probe(Probe::Network(NetworkScalar::NumFiring));
+        for _ in 0..200 {
+            network.update(&[1.0; 9]);
+            recorder.record(&network).unwrap();
+        }
+        recorder.finish().unwrap();
+        let expected = recorder.to_columns().unwrap();
+
+        let mut recording = ChunkedRecording::open(&path).unwrap();
+        assert_eq!(recording.len(), 100);
+        assert_eq!(recording.num_chunks(), 7);
+        assert_eq!(recording.columns(), ["avg_energy", "total_firing"]);
+        assert_eq!(recording.read(0..100).unwrap(), expected);
+
+        let (time, values) = recording.row(57).unwrap();
+        assert_eq!(time, expected.times[57]);
+        assert_eq!(values[0], expected.columns[0].1[57]);
+        let energy = recording.column("avg_energy", 40..60).unwrap().unwrap();
+        assert_eq!(energy, expected.columns[0].1[40..60]);
+        assert!(recording.column("nenhuma", 0..1).unwrap().is_none());
+        assert!(recording.read(90..101).is_err());
+
+        assert_eq!(recording.find_time(2).unwrap(), 0);
+        assert_eq!(recording.find_time(115).unwrap(), 57);
+        assert_eq!(recording.find_time(116).unwrap(), 57);
+        assert_eq!(recording.find_time(1000).unwrap(), 100);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_interrupted_recording_keeps_complete_chunks() {
+        let dir = std::env::temp_dir().join(format!("nenv_chunked_cut_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("registo.bin");
+
+        let mut sink = ChunkedSink::with_chunk_len(&path, 4).unwrap();
+        sink.begin(&["x".to_string()]).unwrap();
+        for t in 0..10 {
+            sink.record(t, &[t as f64 * 0.5]).unwrap();
+        }
+        assert!(sink.record(10, &[1.0, 2.0]).is_err());
+        // Sem finish: os 2 registos do último bloco ficam por escrever
+        drop(sink);
+
+        let mut recording = ChunkedRecording::open(&path).unwrap();
+        assert_eq!(recording.len(), 8);
+        assert_eq!(recording.row(7).unwrap(), (7, vec![3.5]));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
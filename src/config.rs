@@ -0,0 +1,280 @@
+//! Módulo de configuração da rede
+//!
+//! `NetworkConfig` reúne as constantes de dinâmica que antes estavam
+//! espalhadas pelos construtores (decaimento do alerta, integração
+//! novelty-alert, Glia, aprendizado, refratário e memória). Os valores por
+//! omissão reproduzem o comportamento de `Network::new`; a configuração é
+//! serializável para que cada experimento possa guardar os parâmetros com
+//! que foi executado.
+//!
+//! `threshold_distribution` permite limiares heterogéneos: em vez de um
+//! valor partilhado, cada neurónio recebe um limiar sorteado (uniforme ou
+//! normal truncada), o que muda muito a dinâmica da rede.
+
+use crate::error::{NenvError, Result};
+use crate::glia::GliaParams;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::F64Ext;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io::{self, BufReader, BufWriter};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+/// Distribuição dos limiares iniciais dos neurónios
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ThresholdDistribution {
+    /// Todos os neurónios usam o limiar inicial passado ao construtor
+    Fixed,
+    /// Uniforme em `[min, max)`
+    Uniform { min: f64, max: f64 },
+    /// Normal com média e desvio padrão, limitada a `[min, max]`
+    Normal {
+        mean: f64,
+        std_dev: f64,
+        min: f64,
+        max: f64,
+    },
+}
+
+impl ThresholdDistribution {
+    /// Sorteia um limiar (`base` é devolvido em `Fixed`)
+    pub fn sample<R: Rng + ?Sized>(&self, base: f64, rng: &mut R) -> f64 {
+        match *self {
+            ThresholdDistribution::Fixed => base,
+            ThresholdDistribution::Uniform { min, max } => {
+                if max > min {
+                    rng.gen_range(min..max)
+                } else {
+                    min
+                }
+            }
+            ThresholdDistribution::Normal {
+                mean,
+                std_dev,
+                min,
+                max,
+            } => {
+                // Box-Muller; 1 - u evita ln(0)
+                let u1: f64 = 1.0 - rng.gen_range(0.0..1.0);
+                let u2: f64 = rng.gen_range(0.0..1.0);
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * core::f64::consts::PI * u2).cos();
+                (mean + std_dev * z).clamp(min, max)
+            }
+        }
+    }
+}
+
+/// Parâmetros ajustáveis de uma rede NEN-V
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Duração de um passo de simulação em ms (positiva)
+    ///
+    /// Os parâmetros temporais abaixo são por ms e convertidos para o passo
+    /// (ver `timestep`); com 1.0 um passo é um milissegundo.
+    pub dt: f64,
+
+    /// Fração do alert_level perdida por ms
+    pub alert_decay_rate: f64,
+
+    /// Novidade média acima da qual o alert_level é reforçado
+    pub novelty_alert_threshold: f64,
+
+    /// Multiplicador do reforço de alerta (boost = novelty * sensitivity)
+    pub alert_sensitivity: f64,
+
+    /// Subida da taxa de disparo (fração da população acima da linha de base)
+    /// que reforça o alert_level
+    pub rate_surge_threshold: f64,
+
+    /// Multiplicador do reforço por surto de atividade (0 desliga o mecanismo)
+    pub rate_surge_sensitivity: f64,
+
+    /// Taxa (por ms) da média móvel que define a linha de base da taxa de
+    /// disparo
+    pub rate_baseline_rate: f64,
+
+    /// Fator de sensibilidade da priority à novidade
+    pub priority_sensitivity: f64,
+
+    /// Fração do excesso de priority perdida por ms (1.0 = priority
+    /// instantânea; valores menores mantêm a atenção após a novidade)
+    pub priority_decay: f64,
+
+    /// Fração da novidade de um neurónio que reforça a priority dos vizinhos
+    /// na grade (0 desliga o halo de atenção)
+    pub priority_spread: f64,
+
+    /// Raio do halo em células da grade (distância de Chebyshev)
+    pub priority_spread_radius: usize,
+
+    /// Ganho da inibição global: potencial retirado a todos os neurónios por
+    /// unidade de atividade do pool inibitório (0 desliga o mecanismo)
+    pub global_inhibition_gain: f64,
+
+    /// Constante de tempo (ms) com que o pool segue a fração de
+    /// neurónios excitatórios a disparar
+    pub global_inhibition_tau: f64,
+
+    /// Constantes metabólicas da Glia de cada neurónio (manutenção e
+    /// recuperação por ms, custo por disparo)
+    pub glia: GliaParams,
+
+    /// Taxa de aprendizado Hebbiano do Dendritoma
+    pub learning_rate: f64,
+
+    /// Período refratário em ms
+    pub refractory_period: i64,
+
+    /// Taxa de atualização da memória contextual por ms [0, 1]
+    pub memory_alpha: f64,
+
+    /// Aumento relativo de memory_alpha por unidade de alert_level (0 desliga):
+    /// alpha efetivo = memory_alpha * (1 + gain * alert_level)
+    pub memory_alpha_alert_gain: f64,
+
+    /// Distribuição dos limiares iniciais
+    pub threshold_distribution: ThresholdDistribution,
+
+    /// Calcula as reduções do passo (novidade total, custo metabólico) em
+    /// blocos fixos combinados em árvore (ver `reduce`); desligado, somam-se
+    /// neurónio a neurónio
+    ///
+    /// O passo é sempre em série: o modo fixa a ordem que um caminho paralelo
+    /// teria de seguir, mas não torna `update` paralelo.
+    pub strict_determinism: bool,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            dt: 1.0,
+            alert_decay_rate: 0.05,       // Decai 5% por ms
+            novelty_alert_threshold: 0.5, // Ativa alert quando novelty > 0.5
+            alert_sensitivity: 0.3,       // Boost = novelty * 0.3
+            rate_surge_threshold: 0.2,
+            rate_surge_sensitivity: 0.0, // Desligado por omissão
+            rate_baseline_rate: 0.05,
+            priority_sensitivity: 1.0,
+            priority_decay: 1.0,
+            priority_spread: 0.0,
+            priority_spread_radius: 1,
+            global_inhibition_gain: 0.0,
+            global_inhibition_tau: 10.0,
+            glia: GliaParams::default(),
+            learning_rate: 0.01,
+            refractory_period: 5,
+            memory_alpha: 0.1,
+            memory_alpha_alert_gain: 0.0,
+            threshold_distribution: ThresholdDistribution::Fixed,
+            strict_determinism: false,
+        }
+    }
+}
+
+impl NetworkConfig {
+    /// Verifica os parâmetros sem valor utilizável
+    ///
+    /// # Retorna
+    /// Erro se `dt` não for positivo e finito
+    pub fn validate(&self) -> Result<()> {
+        if !(self.dt.is_finite() && self.dt > 0.0) {
+            return Err(NenvError::InvalidParameter {
+                what: "dt",
+                value: self.dt,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl NetworkConfig {
+    /// Guarda a configuração em JSON
+    pub fn save_json<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+
+    /// Lê uma configuração em JSON (campos em falta ficam com o valor por omissão)
+    ///
+    /// Recusa configurações que não passam em `validate`.
+    pub fn load_json<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let config: Self = serde_json::from_reader(reader)?;
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_threshold_distributions_respect_bounds() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(ThresholdDistribution::Fixed.sample(0.7, &mut rng), 0.7);
+
+        let uniform = ThresholdDistribution::Uniform { min: 0.2, max: 0.4 };
+        let normal = ThresholdDistribution::Normal {
+            mean: 0.5,
+            std_dev: 0.2,
+            min: 0.1,
+            max: 0.9,
+        };
+        let mut sum = 0.0;
+        for _ in 0..2000 {
+            let u = uniform.sample(0.0, &mut rng);
+            assert!((0.2..0.4).contains(&u));
+            let x = normal.sample(0.0, &mut rng);
+            assert!((0.1..=0.9).contains(&x));
+            sum += x;
+        }
+        assert!((sum / 2000.0 - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_validate_rejects_unusable_dt() {
+        assert!(NetworkConfig::default().validate().is_ok());
+        for dt in [0.0, -0.5, f64::NAN] {
+            let config = NetworkConfig {
+                dt,
+                ..NetworkConfig::default()
+            };
+            assert!(matches!(config.validate(), Err(NenvError::InvalidParameter { what: "dt", .. })));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_json_round_trip_and_partial_config() {
+        let config = NetworkConfig {
+            refractory_period: 3,
+            glia: GliaParams {
+                energy_cost_fire: 4.0,
+                ..GliaParams::default()
+            },
+            ..NetworkConfig::default()
+        };
+        let path = std::env::temp_dir().join(format!("nenv_config_{}.json", std::process::id()));
+        config.save_json(&path).unwrap();
+        assert_eq!(NetworkConfig::load_json(&path).unwrap(), config);
+
+        std::fs::write(&path, r#"{"dt": 0.0}"#).unwrap();
+        let error = NetworkConfig::load_json(&path).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        std::fs::remove_file(&path).unwrap();
+
+        let partial: NetworkConfig = serde_json::from_str(r#"{"memory_alpha": 0.2}"#).unwrap();
+        assert_eq!(partial.memory_alpha, 0.2);
+        assert_eq!(partial.learning_rate, 0.01);
+    }
+}
@@ -0,0 +1,93 @@
+//! Módulo de contexto externo
+//!
+//! Um vetor de contexto (`Network::set_context`) modula o processamento dos
+//! grupos nomeados da rede: o mesmo input sensorial pode ser tratado como
+//! novo ou esperado consoante um sinal fornecido de fora (tarefa, estado
+//! comportamental, pista). Cada `ContextBinding` liga um canal do vetor a um
+//! grupo:
+//!
+//! ```text
+//! Threshold  limiar efetivo += gain * context[channel]
+//! Priority   potencial modulado *= 1 + gain * context[channel]
+//! ```
+//!
+//! O limiar e a priority guardados nos neurónios não são alterados; o efeito
+//! aplica-se apenas à decisão de disparo de cada passo.
+
+use crate::prelude::*;
+
+/// Grandeza modulada por um canal de contexto
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextTarget {
+    /// Desloca o limiar de disparo
+    Threshold,
+    /// Escala o potencial modulado (ganho atencional)
+    Priority,
+}
+
+/// Ligação de um canal de contexto a um grupo de neurónios
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextBinding {
+    /// Grupo afetado (ver `Network::define_group`)
+    pub group: String,
+
+    /// Índice do canal no vetor de contexto
+    pub channel: usize,
+
+    /// Grandeza modulada
+    pub target: ContextTarget,
+
+    /// Ganho aplicado ao valor do canal
+    pub gain: f64,
+}
+
+/// Efeito do contexto na decisão de disparo de um neurónio
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ContextEffect {
+    pub(crate) scale: f64,
+    pub(crate) threshold_offset: f64,
+}
+
+impl Default for ContextEffect {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            threshold_offset: 0.0,
+        }
+    }
+}
+
+impl ContextEffect {
+    /// Potencial comparado com o limiar próprio do neurónio
+    pub(crate) fn apply(&self, modulated_potential: f64) -> f64 {
+        modulated_potential * self.scale - self.threshold_offset
+    }
+
+    /// Acumula o efeito de um canal (`value` já multiplicado pelo ganho)
+    pub(crate) fn add(&mut self, target: ContextTarget, value: f64) {
+        match target {
+            ContextTarget::Threshold => self.threshold_offset += value,
+            ContextTarget::Priority => self.scale *= (1.0 + value).max(0.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effects_combine() {
+        let mut effect = ContextEffect::default();
+        assert_eq!(effect.apply(2.0), 2.0);
+
+        effect.add(ContextTarget::Priority, 0.5);
+        effect.add(ContextTarget::Threshold, 0.25);
+        effect.add(ContextTarget::Threshold, 0.25);
+        assert_eq!(effect.apply(2.0), 2.5);
+
+        // Ganho negativo suficiente silencia o grupo em vez de inverter o sinal
+        effect.add(ContextTarget::Priority, -3.0);
+        assert_eq!(effect.scale, 0.0);
+    }
+}
@@ -0,0 +1,284 @@
+//! Módulo de aprendizagem contínua: esquecimento entre tarefas
+//!
+//! O protocolo clássico: treinar na tarefa A, depois na tarefa B, e medir
+//! quanto de A se mantém (esquecimento catastrófico). Cada tarefa é um
+//! conjunto de padrões de input; o desempenho numa tarefa é o alinhamento
+//! médio dos campos recetivos com os seus padrões:
+//!
+//! ```text
+//! desempenho(T) = média sobre padrões p ∈ T e neurónios i de  w_i · p / |p|
+//! ```
+//!
+//! Com normalização L2 dos pesos, treinar em B roda os pesos para longe de
+//! A. A condição `consolidation` intercala fases de "sono" durante o treino
+//! de B: episódios sorteados da memória episódica (guardados enquanto eram
+//! novos, incluindo os de A) são reproduzidos com `Network::replay_episode`.
+//! Este é o único mecanismo de consolidação da rede.
+//!
+//! `ForgettingReport` reúne as medidas padrão (esquecimento, retenção e
+//! transferência para a frente e para trás).
+
+use crate::network::Network;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+
+/// Parâmetros do protocolo
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContinualParams {
+    /// Apresentações de padrões no treino de cada tarefa
+    pub presentations: usize,
+
+    /// Passos de cada apresentação (seguidos do mesmo número de passos sem input)
+    pub steps_per_presentation: usize,
+
+    /// Intercalar fases de sono com reprodução episódica durante o treino de B
+    pub consolidation: bool,
+
+    /// Apresentações de B entre fases de sono
+    pub sleep_interval: usize,
+
+    /// Episódios reproduzidos em cada fase de sono
+    pub sleep_replays: usize,
+
+    /// Capacidade e limiar da memória episódica usada no sono
+    pub episodic_capacity: usize,
+    pub episodic_threshold: f64,
+
+    /// Semente da ordem das apresentações e dos episódios sorteados
+    pub seed: u64,
+}
+
+impl Default for ContinualParams {
+    fn default() -> Self {
+        Self {
+            presentations: 100,
+            steps_per_presentation: 5,
+            consolidation: false,
+            sleep_interval: 10,
+            sleep_replays: 5,
+            episodic_capacity: 64,
+            episodic_threshold: 0.1,
+            seed: 42,
+        }
+    }
+}
+
+/// Desempenho nas duas tarefas ao longo do protocolo
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ForgettingReport {
+    /// Se a condição usou consolidação
+    pub consolidation: bool,
+
+    /// Desempenho em A antes do treino, depois de A e depois de B
+    pub a_initial: f64,
+    pub a_after_a: f64,
+    pub a_after_b: f64,
+
+    /// Desempenho em B antes do treino, depois de A e depois de B
+    pub b_initial: f64,
+    pub b_after_a: f64,
+    pub b_after_b: f64,
+}
+
+impl ForgettingReport {
+    /// Desempenho em A perdido durante o treino de B
+    pub fn forgetting(&self) -> f64 {
+        self.a_after_a - self.a_after_b
+    }
+
+    /// Fração do ganho em A que sobrevive ao treino de B (1 = nada esquecido)
+    pub fn retention(&self) -> f64 {
+        let gain = self.a_after_a - self.a_initial;
+        if gain.abs() < f64::EPSILON {
+            return 1.0;
+        }
+        (self.a_after_b - self.a_initial) / gain
+    }
+
+    /// Efeito do treino em B sobre A, relativo ao fim de A (negativo = esquecimento)
+    pub fn backward_transfer(&self) -> f64 {
+        self.a_after_b - self.a_after_a
+    }
+
+    /// Efeito do treino em A sobre B, antes de treinar B
+    pub fn forward_transfer(&self) -> f64 {
+        self.b_after_a - self.b_initial
+    }
+}
+
+impl fmt::Display for ForgettingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = if self.consolidation { "com consolidação" } else { "sem consolidação" };
+        writeln!(f, "Aprendizagem contínua ({}):", label)?;
+        writeln!(f, "            inicial   após A   após B")?;
+        writeln!(
+            f,
+            "  tarefa A  {:7.4}  {:7.4}  {:7.4}",
+            self.a_initial, self.a_after_a, self.a_after_b
+        )?;
+        writeln!(
+            f,
+            "  tarefa B  {:7.4}  {:7.4}  {:7.4}",
+            self.b_initial, self.b_after_a, self.b_after_b
+        )?;
+        writeln!(
+            f,
+            "  esquecimento {:+.4} | retenção {:.3} | BWT {:+.4} | FWT {:+.4}",
+            self.forgetting(),
+            self.retention(),
+            self.backward_transfer(),
+            self.forward_transfer()
+        )
+    }
+}
+
+/// Alinhamento médio dos campos recetivos com os padrões de uma tarefa
+pub fn task_performance(network: &Network, patterns: &[Vec<f64>]) -> f64 {
+    let mut total = 0.0;
+    let mut count = 0usize;
+    for pattern in patterns {
+        let norm = pattern.iter().map(|x| x * x).sum::<f64>().sqrt();
+        if norm == 0.0 || pattern.len() != network.num_neurons() {
+            continue;
+        }
+        for neuron in &network.neurons {
+            total += neuron.dendritoma.weighted_sum(pattern) / norm;
+            count += 1;
+        }
+    }
+    if count == 0 { 0.0 } else { total / count as f64 }
+}
+
+/// Executa o protocolo A → B numa rede e mede o esquecimento
+///
+/// # Argumentos
+/// * `network` - Rede a treinar (é modificada)
+/// * `task_a`, `task_b` - Padrões de cada tarefa (um valor por neurónio)
+/// * `params` - Parâmetros do protocolo
+pub fn run_continual(
+    network: &mut Network,
+    task_a: &[Vec<f64>],
+    task_b: &[Vec<f64>],
+    params: &ContinualParams,
+) -> ForgettingReport {
+    let mut rng = StdRng::seed_from_u64(params.seed);
+    let silence = vec![0.0; network.num_neurons()];
+    if params.consolidation {
+        network.enable_episodic_memory(params.episodic_capacity, params.episodic_threshold);
+    }
+
+    let a_initial = task_performance(network, task_a);
+    let b_initial = task_performance(network, task_b);
+
+    let train = |network: &mut Network, task: &[Vec<f64>], sleep: bool, rng: &mut StdRng| {
+        if task.is_empty() {
+            return;
+        }
+        for presentation in 1..=params.presentations {
+            let pattern = &task[rng.gen_range(0..task.len())];
+            for _ in 0..params.steps_per_presentation {
+                network.update(pattern);
+            }
+            for _ in 0..params.steps_per_presentation {
+                network.update(&silence);
+            }
+
+            if sleep && params.sleep_interval > 0 && presentation % params.sleep_interval == 0 {
+                let episodes = network.episodic_memory().map(|m| m.episodes().to_vec());
+                for episodes in episodes.iter().filter(|e| !e.is_empty()) {
+                    for _ in 0..params.sleep_replays {
+                        let episode = &episodes[rng.gen_range(0..episodes.len())];
+                        network.replay_episode(episode, params.steps_per_presentation);
+                        for _ in 0..params.steps_per_presentation {
+                            network.update(&silence);
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    train(network, task_a, false, &mut rng);
+    let a_after_a = task_performance(network, task_a);
+    let b_after_a = task_performance(network, task_b);
+
+    train(network, task_b, params.consolidation, &mut rng);
+    let a_after_b = task_performance(network, task_a);
+    let b_after_b = task_performance(network, task_b);
+
+    ForgettingReport {
+        consolidation: params.consolidation,
+        a_initial,
+        a_after_a,
+        a_after_b,
+        b_initial,
+        b_after_a,
+        b_after_b,
+    }
+}
+
+/// Compara o protocolo sem e com consolidação em redes idênticas
+///
+/// # Argumentos
+/// * `make_network` - Cria a rede de cada condição (deve ser reprodutível)
+///
+/// # Retorna
+/// `(sem consolidação, com consolidação)`
+pub fn compare_consolidation<F>(
+    make_network: F,
+    task_a: &[Vec<f64>],
+    task_b: &[Vec<f64>],
+    params: &ContinualParams,
+) -> (ForgettingReport, ForgettingReport)
+where
+    F: Fn() -> Network,
+{
+    let baseline = ContinualParams {
+        consolidation: false,
+        ..*params
+    };
+    let consolidated = ContinualParams {
+        consolidation: true,
+        ..*params
+    };
+    (
+        run_continual(&mut make_network(), task_a, task_b, &baseline),
+        run_continual(&mut make_network(), task_a, task_b, &consolidated),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ConnectivityType;
+
+    /// Padrões de 25 entradas ativos só na faixa `[start, start + 10)`
+    fn task(start: usize) -> Vec<Vec<f64>> {
+        (0..3)
+            .map(|k| {
+                (0..25)
+                    .map(|i| (i >= start && i < start + 10 && i % 3 != k) as u8 as f64)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_forgetting_report_for_disjoint_tasks() {
+        let (task_a, task_b) = (task(0), task(15));
+        let params = ContinualParams {
+            presentations: 60,
+            ..ContinualParams::default()
+        };
+        let make = || Network::new_seeded(25, ConnectivityType::Grid2D, 0.0, 0.5, 9);
+        let (baseline, consolidated) = compare_consolidation(make, &task_a, &task_b, &params);
+
+        // Treinar A melhora A; treinar B faz esquecer parte de A
+        assert!(baseline.a_after_a > baseline.a_initial);
+        assert!(baseline.b_after_b > baseline.b_after_a);
+        assert!(baseline.forgetting() > 0.0);
+        assert!(consolidated.forgetting() < baseline.forgetting());
+        assert!(baseline.to_string().contains("esquecimento"));
+    }
+}
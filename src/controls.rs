@@ -0,0 +1,285 @@
+//! Módulo de condições de controlo para protocolos de estímulo
+//!
+//! Uma afirmação de deteção de novidade só vale contra linhas de base: a
+//! mesma rede sob estímulos com a mesma "quantidade" de input, mas sem a
+//! estrutura do protocolo. A partir de qualquer `StimulusSchedule` geram-se:
+//!
+//! - `TemporalShuffle`: os vetores de input de cada passo por ordem aleatória
+//! - `SpatialPermutation`: os alvos trocados por uma permutação fixa dos neurónios
+//! - `MatchedNoise`: em cada passo, as mesmas amplitudes em neurónios sorteados
+//!
+//! `run_with_controls` executa o protocolo e os controlos em redes idênticas
+//! e devolve a série de uma métrica por condição.
+
+use crate::network::Network;
+use crate::stimulus::{Stimulus, StimulusSchedule};
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::SeedableRng;
+use std::fmt;
+
+/// Condição de um protocolo: o original ou um dos seus controlos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Control {
+    /// Protocolo sem alterações
+    Original,
+    /// Passos do protocolo baralhados no tempo
+    TemporalShuffle,
+    /// Alvos permutados no espaço (a mesma permutação em todos os passos)
+    SpatialPermutation,
+    /// Amplitudes de cada passo em neurónios sorteados a cada passo
+    MatchedNoise,
+}
+
+impl Control {
+    /// Todas as condições, começando pelo original
+    pub const ALL: [Control; 4] = [
+        Control::Original,
+        Control::TemporalShuffle,
+        Control::SpatialPermutation,
+        Control::MatchedNoise,
+    ];
+
+    /// Nome curto usado em tabelas e CSV
+    pub fn name(&self) -> &'static str {
+        match self {
+            Control::Original => "original",
+            Control::TemporalShuffle => "temporal_shuffle",
+            Control::SpatialPermutation => "spatial_permutation",
+            Control::MatchedNoise => "matched_noise",
+        }
+    }
+
+    /// Gera o protocolo desta condição a partir de `schedule`
+    ///
+    /// Os controlos têm a duração do original e, em cada passo, o mesmo
+    /// input total (`TemporalShuffle` preserva-o só no conjunto dos passos).
+    ///
+    /// # Argumentos
+    /// * `schedule` - Protocolo original
+    /// * `num_neurons` - Tamanho da rede (alvos fora dela são ignorados)
+    /// * `seed` - Semente das permutações e sorteios
+    pub fn apply(
+        &self,
+        schedule: &StimulusSchedule,
+        num_neurons: usize,
+        seed: u64,
+    ) -> StimulusSchedule {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let duration = schedule.duration().max(0);
+        let frames = || (0..duration).map(|t| schedule.inputs_at(num_neurons, t));
+
+        match self {
+            Control::Original => schedule.clone(),
+            Control::TemporalShuffle => {
+                let mut frames: Vec<Vec<f64>> = frames().collect();
+                frames.shuffle(&mut rng);
+                from_frames(frames.iter().map(|frame| nonzero(frame)))
+            }
+            Control::SpatialPermutation => {
+                let mut permutation: Vec<usize> = (0..num_neurons).collect();
+                permutation.shuffle(&mut rng);
+                let stimuli = schedule.stimuli.iter().map(|s| {
+                    let targets = s
+                        .targets
+                        .iter()
+                        .filter_map(|&i| permutation.get(i).copied());
+                    Stimulus::new(targets.collect(), s.amplitude, s.start, s.end)
+                });
+                StimulusSchedule {
+                    stimuli: stimuli.collect(),
+                }
+            }
+            Control::MatchedNoise => {
+                let noisy = frames().map(|frame| {
+                    let values = nonzero(&frame);
+                    let positions = index::sample(&mut rng, num_neurons, values.len());
+                    positions
+                        .into_iter()
+                        .zip(values.into_iter().map(|(_, v)| v))
+                        .collect()
+                });
+                from_frames(noisy.collect::<Vec<Vec<(usize, f64)>>>())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Control {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Pares `(neurónio, valor)` não nulos de um vetor de inputs
+fn nonzero(frame: &[f64]) -> Vec<(usize, f64)> {
+    frame
+        .iter()
+        .enumerate()
+        .filter(|&(_, &v)| v != 0.0)
+        .map(|(i, &v)| (i, v))
+        .collect()
+}
+
+/// Protocolo com um estímulo de um passo por valor de cada frame
+fn from_frames<I>(frames: I) -> StimulusSchedule
+where
+    I: IntoIterator<Item = Vec<(usize, f64)>>,
+{
+    let mut schedule = StimulusSchedule::new();
+    for (t, frame) in frames.into_iter().enumerate() {
+        let t = t as i64;
+        for (i, value) in frame {
+            schedule.add(Stimulus::new(vec![i], value, t, t + 1));
+        }
+    }
+    schedule
+}
+
+/// Série de uma métrica por condição, produzida por `run_with_controls`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControlComparison {
+    /// Condição e valor da métrica em cada passo, pela ordem pedida
+    pub conditions: Vec<(Control, Vec<f64>)>,
+}
+
+impl ControlComparison {
+    /// Série de uma condição
+    pub fn series(&self, control: Control) -> Option<&[f64]> {
+        self.conditions
+            .iter()
+            .find(|(c, _)| *c == control)
+            .map(|(_, series)| series.as_slice())
+    }
+
+    /// Média da métrica numa condição (0.0 se a série estiver vazia)
+    pub fn mean(&self, control: Control) -> Option<f64> {
+        self.series(control)
+            .map(|series| series.iter().sum::<f64>() / series.len().max(1) as f64)
+    }
+
+    /// Média do original menos a média do controlo
+    pub fn effect(&self, control: Control) -> Option<f64> {
+        Some(self.mean(Control::Original)? - self.mean(control)?)
+    }
+}
+
+impl fmt::Display for ControlComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<20} {:>12} {:>12}", "condição", "média", "efeito")?;
+        for (control, _) in &self.conditions {
+            let mean = self.mean(*control).unwrap_or(0.0);
+            match self.effect(*control) {
+                Some(effect) if *control != Control::Original => {
+                    writeln!(f, "{:<20} {:>12.4} {:>12.4}", control, mean, effect)?
+                }
+                _ => writeln!(f, "{:<20} {:>12.4} {:>12}", control, mean, "-")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Executa o protocolo e os seus controlos em redes idênticas
+///
+/// Cada condição corre `schedule.duration()` passos numa rede nova de
+/// `make_network`; `measure` é chamado após cada update.
+///
+/// # Argumentos
+/// * `make_network` - Cria a rede de cada condição (deve ser reprodutível)
+/// * `schedule` - Protocolo original
+/// * `controls` - Condições a executar (p.ex. `Control::ALL`)
+/// * `seed` - Semente dos controlos
+/// * `measure` - Métrica de um passo (p.ex. a novidade média)
+pub fn run_with_controls<F, M>(
+    make_network: F,
+    schedule: &StimulusSchedule,
+    controls: &[Control],
+    seed: u64,
+    mut measure: M,
+) -> ControlComparison
+where
+    F: Fn() -> Network,
+    M: FnMut(&Network, i64) -> f64,
+{
+    let duration = schedule.duration();
+    let conditions = controls
+        .iter()
+        .map(|&control| {
+            let mut network = make_network();
+            let n = network.num_neurons();
+            let variant = control.apply(schedule, n, seed);
+            let mut series = Vec::with_capacity(duration.max(0) as usize);
+            network.run(
+                duration,
+                |t| variant.inputs_at(n, t),
+                |network, t| series.push(measure(network, t)),
+            );
+            (control, series)
+        })
+        .collect();
+    ControlComparison { conditions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ConnectivityType;
+
+    fn protocol() -> StimulusSchedule {
+        StimulusSchedule::new()
+            .with(Stimulus::new(vec![1, 2], 2.0, 0, 30))
+            .with(Stimulus::new(vec![7], 3.0, 30, 40))
+    }
+
+    fn total(schedule: &StimulusSchedule, t: i64) -> f64 {
+        schedule.inputs_at(9, t).iter().sum()
+    }
+
+    #[test]
+    fn test_controls_preserve_input_amount() {
+        let schedule = protocol();
+        let overall = |s: &StimulusSchedule| (0..40).map(|t| total(s, t)).sum::<f64>();
+
+        for control in Control::ALL {
+            let variant = control.apply(&schedule, 9, 5);
+            assert_eq!(variant.duration(), 40, "{}", control);
+            assert_eq!(overall(&variant), overall(&schedule), "{}", control);
+        }
+
+        let spatial = Control::SpatialPermutation.apply(&schedule, 9, 5);
+        let noise = Control::MatchedNoise.apply(&schedule, 9, 5);
+        for t in 0..40 {
+            assert_eq!(total(&spatial, t), total(&schedule, t));
+            assert_eq!(total(&noise, t), total(&schedule, t));
+        }
+        // A permutação espacial é a mesma em todos os passos
+        assert_eq!(spatial.inputs_at(9, 0), spatial.inputs_at(9, 29));
+        assert_ne!(noise.inputs_at(9, 0), noise.inputs_at(9, 1));
+        assert_eq!(
+            Control::TemporalShuffle.apply(&schedule, 9, 5),
+            Control::TemporalShuffle.apply(&schedule, 9, 5)
+        );
+    }
+
+    #[test]
+    fn test_run_with_controls_uses_identical_networks() {
+        let make = || Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 4);
+        let comparison = run_with_controls(make, &protocol(), &Control::ALL, 1, |network, _| {
+            network.num_firing() as f64
+        });
+
+        assert_eq!(comparison.conditions.len(), 4);
+        assert!(comparison.conditions.iter().all(|(_, s)| s.len() == 40));
+        assert_eq!(comparison.effect(Control::Original), Some(0.0));
+        assert!(comparison.to_string().contains("matched_noise"));
+
+        let again = run_with_controls(make, &protocol(), &[Control::Original], 1, |network, _| {
+            network.num_firing() as f64
+        });
+        assert_eq!(
+            again.series(Control::Original),
+            comparison.series(Control::Original)
+        );
+    }
+}
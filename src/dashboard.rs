@@ -0,0 +1,225 @@
+//! Módulo de painel ao vivo no terminal
+//!
+//! Desenha, a cada passo, a grade como mapa de calor em caracteres e séries
+//! móveis (sparklines) de disparos, energia, novidade e alert_level, usando
+//! apenas sequências ANSI. Permite afinar parâmetros sem esperar pelos PNGs
+//! gerados no fim do experimento.
+
+use crate::network::Network;
+use crate::recorder::NeuronField;
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+/// Rampa de caracteres do mapa de calor, do menor ao maior valor
+const HEAT_RAMP: &[char] = &[' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+/// Níveis das sparklines
+const SPARK_LEVELS: &[char] = &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Caractere da rampa do mapa de calor para um valor na escala `[low, high]`
+pub(crate) fn heat_char(value: f64, low: f64, high: f64) -> char {
+    let span = high - low;
+    let level = if span > 0.0 {
+        ((value - low) / span * (HEAT_RAMP.len() - 1) as f64).round()
+    } else {
+        0.0
+    };
+    HEAT_RAMP[(level.max(0.0) as usize).min(HEAT_RAMP.len() - 1)]
+}
+
+/// Série móvel com os últimos valores de uma grandeza escalar
+#[derive(Debug, Clone)]
+struct RollingSeries {
+    label: &'static str,
+    values: VecDeque<f64>,
+}
+
+impl RollingSeries {
+    fn new(label: &'static str, capacity: usize) -> Self {
+        Self {
+            label,
+            values: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    fn push(&mut self, value: f64, capacity: usize) {
+        if self.values.len() == capacity {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    /// Linha com rótulo, sparkline escalada ao intervalo visível e último valor
+    fn render(&self) -> String {
+        let min = self.values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = self.values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let span = max - min;
+
+        let spark: String = self
+            .values
+            .iter()
+            .map(|&v| {
+                let level = if span > 0.0 {
+                    ((v - min) / span * (SPARK_LEVELS.len() - 1) as f64).round() as usize
+                } else {
+                    0
+                };
+                SPARK_LEVELS[level.min(SPARK_LEVELS.len() - 1)]
+            })
+            .collect();
+
+        let last = self.values.back().copied().unwrap_or(0.0);
+        format!("{:<10} {} {:.3}", self.label, spark, last)
+    }
+}
+
+/// Painel ao vivo de uma simulação
+#[derive(Debug, Clone)]
+pub struct Dashboard {
+    /// Campo mostrado no mapa de calor
+    field: NeuronField,
+
+    /// Escala fixa do mapa de calor (por omissão, mínimo e máximo do passo)
+    range: Option<(f64, f64)>,
+
+    /// Número de passos mostrados nas séries móveis
+    window: usize,
+
+    series: [RollingSeries; 4],
+}
+
+impl Dashboard {
+    /// Cria um painel
+    ///
+    /// # Argumentos
+    /// * `field` - Campo dos neurónios mostrado no mapa de calor
+    /// * `window` - Número de passos das séries móveis (mínimo 1)
+    pub fn new(field: NeuronField, window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            field,
+            range: None,
+            window,
+            series: [
+                RollingSeries::new("disparos", window),
+                RollingSeries::new("energia", window),
+                RollingSeries::new("novidade", window),
+                RollingSeries::new("alerta", window),
+            ],
+        }
+    }
+
+    /// Fixa a escala do mapa de calor
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Acrescenta o estado atual da rede às séries móveis
+    pub fn update(&mut self, network: &Network) {
+        let values = [
+            network.num_firing() as f64,
+            network.average_energy(),
+            network.average_novelty(),
+            network.alert_level,
+        ];
+        for (series, value) in self.series.iter_mut().zip(values) {
+            series.push(value, self.window);
+        }
+    }
+
+    /// Valores do campo por neurónio, largura da grade e escala do mapa de calor
+    pub(crate) fn heat_grid(&self, network: &Network) -> (Vec<f64>, usize, (f64, f64)) {
+        let values: Vec<f64> = network.neurons.iter().map(|n| self.field.read(n)).collect();
+        let width = if network.grid_width > 0 {
+            network.grid_width
+        } else {
+            (values.len() as f64).sqrt().ceil().max(1.0) as usize
+        };
+
+        let range = self.range.unwrap_or_else(|| {
+            let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            if min.is_finite() { (min, max) } else { (0.0, 1.0) }
+        });
+
+        (values, width, range)
+    }
+
+    /// Campo mostrado no mapa de calor
+    pub fn field(&self) -> NeuronField {
+        self.field
+    }
+
+    /// Rótulo e valores visíveis de cada série móvel
+    pub fn series(&self) -> impl Iterator<Item = (&'static str, &VecDeque<f64>)> {
+        self.series.iter().map(|series| (series.label, &series.values))
+    }
+
+    /// Texto do painel para o estado atual da rede
+    pub fn render(&self, network: &Network) -> String {
+        let (values, width, (low, high)) = self.heat_grid(network);
+
+        let mut out = format!(
+            "NEN-V  t = {}  |  {} [{:.2}, {:.2}]\n\n",
+            network.current_time_step,
+            self.field.name(),
+            low,
+            high
+        );
+
+        for row in values.chunks(width) {
+            for &v in row {
+                let c = heat_char(v, low, high);
+                // Dois caracteres por célula para compensar a proporção do terminal
+                out.push(c);
+                out.push(c);
+            }
+            out.push('\n');
+        }
+        out.push('\n');
+
+        for series in &self.series {
+            out.push_str(&series.render());
+            out.push('\n');
+        }
+
+        out
+    }
+
+    /// Atualiza as séries e redesenha o painel no terminal
+    pub fn draw(&mut self, network: &Network) -> io::Result<()> {
+        self.update(network);
+        let frame = self.render(network);
+
+        let mut stdout = io::stdout().lock();
+        // Cursor para o início e limpa o ecrã antes de desenhar
+        write!(stdout, "\x1b[H\x1b[2J{}", frame)?;
+        stdout.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ConnectivityType;
+
+    #[test]
+    fn test_render_grid_and_series() {
+        let mut network = Network::new(9, ConnectivityType::Grid2D, 0.0, 0.5);
+        let mut dashboard = Dashboard::new(NeuronField::Energy, 5).with_range(0.0, 100.0);
+
+        for _ in 0..8 {
+            network.update(&[0.0; 9]);
+            dashboard.update(&network);
+        }
+        let text = dashboard.render(&network);
+        let lines: Vec<&str> = text.lines().collect();
+
+        // Cabeçalho, linha vazia, 3 linhas da grade com 2 caracteres por célula
+        assert!(lines[0].contains("t = 8"));
+        assert_eq!(lines[2].chars().count(), 6);
+        assert!(text.contains("alerta"));
+        assert_eq!(dashboard.series[0].values.len(), 5);
+    }
+}
@@ -0,0 +1,386 @@
+//! Módulo de depuração passo a passo
+//!
+//! `Debugger` avança a simulação um passo (ou N passos) de cada vez e mostra
+//! o estado interno de um neurónio escolhido: pesos do Dendritoma dos seus
+//! pré-sinápticos, potencial integrado e modulado, limiar, novidade e estado
+//! da Glia, com o motivo de ter (ou não) disparado. É o modo `debug` da CLI:
+//!
+//! ```text
+//! cargo run -- debug 33
+//! [t=12] > 5        avança 5 passos
+//! [t=17] > n 66     passa a inspecionar o neurónio 66
+//! [t=17] > q        termina
+//! ```
+//!
+//! O potencial é lido com um `SimulationObserver` no hook
+//! `after_integration`; o potencial modulado é o de antes da inibição
+//! global e do contexto, como em `after_integration`.
+
+use crate::nenv::NeuronType;
+use crate::network::Network;
+use crate::observer::SimulationObserver;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::sync::{Arc, Mutex};
+
+/// Potenciais de um neurónio num passo, capturados durante `update`
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Potentials {
+    external: f64,
+    integrated: f64,
+    modulated: f64,
+    previous_outputs: Vec<f64>,
+}
+
+/// Observador que guarda os potenciais do neurónio inspecionado
+struct PotentialProbe {
+    neuron: Arc<Mutex<usize>>,
+    last: Arc<Mutex<Option<Potentials>>>,
+    external: f64,
+    previous_outputs: Vec<f64>,
+}
+
+impl SimulationObserver for PotentialProbe {
+    fn before_step(&mut self, network: &mut Network, external_inputs: &[f64]) {
+        let neuron = *self.neuron.lock().unwrap();
+        self.external = external_inputs.get(neuron).copied().unwrap_or(0.0);
+        self.previous_outputs.clear();
+        self.previous_outputs
+            .extend(network.neurons.iter().map(|n| n.output_signal));
+    }
+
+    fn after_integration(&mut self, _network: &Network, integrated: &[f64], modulated: &[f64]) {
+        let neuron = *self.neuron.lock().unwrap();
+        *self.last.lock().unwrap() = integrated.get(neuron).map(|&integrated| Potentials {
+            external: self.external,
+            integrated,
+            modulated: modulated[neuron],
+            previous_outputs: self.previous_outputs.clone(),
+        });
+    }
+}
+
+/// Motivo da decisão de disparo de um neurónio no último passo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FireReason {
+    /// Disparou: potencial acima do limiar fora do período refratário
+    Fired,
+    /// Potencial modulado abaixo (ou igual) ao limiar
+    BelowThreshold,
+    /// Potencial acima do limiar, mas em período refratário
+    Refractory,
+    /// Potencial acima do limiar, mas inibição global, contexto, dropout ou
+    /// falha metabólica impediram o disparo
+    Suppressed,
+    /// Neurónio lesionado
+    Lesioned,
+    /// Sem potenciais capturados (nenhum passo desde `attach` ou `select`)
+    NotStepped,
+}
+
+impl fmt::Display for FireReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            FireReason::Fired => "disparou",
+            FireReason::BelowThreshold => "não disparou: potencial abaixo do limiar",
+            FireReason::Refractory => "não disparou: período refratário",
+            FireReason::Suppressed => {
+                "não disparou: inibição, contexto, dropout ou falha metabólica"
+            }
+            FireReason::Lesioned => "não disparou: lesionado",
+            FireReason::NotStepped => "potenciais disponíveis após o próximo passo",
+        };
+        f.write_str(text)
+    }
+}
+
+/// Estado de um neurónio após o último passo
+#[derive(Debug, Clone, PartialEq)]
+pub struct NeuronInspection {
+    /// Índice do neurónio
+    pub neuron: usize,
+    /// Passo de tempo da rede
+    pub time_step: i64,
+    /// Tipo do neurónio
+    pub neuron_type: NeuronType,
+    /// `(pré-sináptico, peso, saída recebida no último passo)`, com a auto-conexão
+    pub weights: Vec<(usize, f64, f64)>,
+    /// Input externo recebido no último passo
+    pub external_input: f64,
+    /// Potencial integrado pelo Dendritoma (`None` antes do primeiro passo)
+    pub integrated: Option<f64>,
+    /// Potencial após modulação glial
+    pub modulated: Option<f64>,
+    /// Limiar de disparo
+    pub threshold: f64,
+    /// Disparou no último passo
+    pub is_firing: bool,
+    /// Motivo da decisão de disparo
+    pub reason: FireReason,
+    /// Último passo em que disparou (-1 se nunca)
+    pub last_fire_time: i64,
+    /// Novidade calculada no último passo
+    pub novelty: f64,
+    /// Energia da Glia
+    pub energy: f64,
+    /// Fração da energia máxima
+    pub energy_fraction: f64,
+    /// Priority da Glia
+    pub priority: f64,
+    /// Nível de alerta global da rede
+    pub alert_level: f64,
+}
+
+impl NeuronInspection {
+    /// Lê o estado do neurónio `neuron` (potenciais vindos do último passo)
+    fn capture(network: &Network, neuron: usize, potentials: Option<&Potentials>) -> Self {
+        let n = &network.neurons[neuron];
+        let self_connected = network.is_connected(neuron, neuron);
+        let sources = network.neighbors(neuron).iter().copied();
+        let mut sources: Vec<usize> = sources.chain(self_connected.then_some(neuron)).collect();
+        sources.sort_unstable();
+        let weights = sources
+            .into_iter()
+            .map(|j| {
+                let output = potentials.map_or(0.0, |p| p.previous_outputs[j]);
+                (j, n.dendritoma.weights[j], output)
+            })
+            .collect();
+
+        let reason = match potentials {
+            None => FireReason::NotStepped,
+            Some(_) if n.is_firing => FireReason::Fired,
+            Some(_) if network.is_lesioned(neuron) => FireReason::Lesioned,
+            Some(p) if p.modulated <= n.threshold => FireReason::BelowThreshold,
+            Some(_)
+                if n.last_fire_time >= 0
+                    && network.current_time_step - n.last_fire_time < n.refractory_period() =>
+            {
+                FireReason::Refractory
+            }
+            Some(_) => FireReason::Suppressed,
+        };
+
+        Self {
+            neuron,
+            time_step: network.current_time_step,
+            neuron_type: n.neuron_type,
+            weights,
+            external_input: potentials.map_or(0.0, |p| p.external),
+            integrated: potentials.map(|p| p.integrated),
+            modulated: potentials.map(|p| p.modulated),
+            threshold: n.threshold,
+            is_firing: n.is_firing,
+            reason,
+            last_fire_time: n.last_fire_time,
+            novelty: n.novelty,
+            energy: n.glia.energy,
+            energy_fraction: n.glia.energy_fraction(),
+            priority: n.glia.priority,
+            alert_level: network.alert_level,
+        }
+    }
+}
+
+impl fmt::Display for NeuronInspection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let potential = |value: Option<f64>| value.map_or("-".to_string(), |v| format!("{v:.4}"));
+        writeln!(
+            f,
+            "Neurónio {} ({:?}) em t={}: {}",
+            self.neuron, self.neuron_type, self.time_step, self.reason
+        )?;
+        writeln!(
+            f,
+            "  Potencial: externo {:.4} | integrado {} | modulado {} | limiar {:.4}",
+            self.external_input,
+            potential(self.integrated),
+            potential(self.modulated),
+            self.threshold
+        )?;
+        writeln!(
+            f,
+            "  Novidade {:.4} | último disparo {}",
+            self.novelty, self.last_fire_time
+        )?;
+        writeln!(
+            f,
+            "  Glia: energia {:.2} ({:.0}%) | priority {:.4} | alert_level {:.4}",
+            self.energy,
+            self.energy_fraction * 100.0,
+            self.priority,
+            self.alert_level
+        )?;
+        write!(f, "  Pesos ({} pré-sinápticos):", self.weights.len())?;
+        for &(j, weight, output) in &self.weights {
+            let marker = if output != 0.0 { "*" } else { "" };
+            write!(f, " {j}{marker}={weight:.4}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Comando lido na sessão interativa
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Step(u64),
+    Select(usize),
+    Quit,
+    Help,
+}
+
+impl Command {
+    fn parse(line: &str) -> Option<Self> {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            None | Some("s") => Some(Command::Step(1)),
+            Some("q") => Some(Command::Quit),
+            Some("h" | "?") => Some(Command::Help),
+            Some("n") => words.next()?.parse().ok().map(Command::Select),
+            Some(count) => count.parse().ok().map(Command::Step),
+        }
+    }
+}
+
+const HELP: &str = "Enter/s: 1 passo | <N>: N passos | n <i>: inspeciona o neurónio i | q: sair";
+
+/// Depurador passo a passo de um neurónio
+pub struct Debugger {
+    neuron: Arc<Mutex<usize>>,
+    last: Arc<Mutex<Option<Potentials>>>,
+}
+
+impl Debugger {
+    /// Regista na rede o observador que captura os potenciais de `neuron`
+    pub fn attach(network: &mut Network, neuron: usize) -> Self {
+        let neuron = Arc::new(Mutex::new(
+            neuron.min(network.num_neurons().saturating_sub(1)),
+        ));
+        let last = Arc::new(Mutex::new(None));
+        network.add_observer(Box::new(PotentialProbe {
+            neuron: Arc::clone(&neuron),
+            last: Arc::clone(&last),
+            external: 0.0,
+            previous_outputs: Vec::new(),
+        }));
+        Self { neuron, last }
+    }
+
+    /// Neurónio inspecionado
+    pub fn neuron(&self) -> usize {
+        *self.neuron.lock().unwrap()
+    }
+
+    /// Passa a inspecionar `neuron` (os potenciais só existem a partir do passo seguinte)
+    pub fn select(&self, network: &Network, neuron: usize) -> bool {
+        if neuron >= network.num_neurons() {
+            return false;
+        }
+        *self.neuron.lock().unwrap() = neuron;
+        *self.last.lock().unwrap() = None;
+        true
+    }
+
+    /// Estado atual do neurónio inspecionado
+    pub fn inspect(&self, network: &Network) -> NeuronInspection {
+        NeuronInspection::capture(network, self.neuron(), self.last.lock().unwrap().as_ref())
+    }
+
+    /// Sessão interativa: lê comandos de `reader` e escreve em `writer`
+    ///
+    /// Termina com `q`, no fim de `reader` ou após `max_steps` passos.
+    ///
+    /// # Argumentos
+    /// * `network` - Rede onde o depurador foi registado com `attach`
+    /// * `max_steps` - Passos disponíveis no protocolo
+    /// * `input_fn` - Inputs externos do passo `t` (como em `Network::run`)
+    pub fn run_session<R, W, I, F>(
+        &self,
+        network: &mut Network,
+        max_steps: i64,
+        mut input_fn: F,
+        reader: R,
+        mut writer: W,
+    ) -> io::Result<()>
+    where
+        R: BufRead,
+        W: Write,
+        I: AsRef<[f64]>,
+        F: FnMut(i64) -> I,
+    {
+        writeln!(writer, "{HELP}")?;
+        let mut t = 0;
+        let mut lines = reader.lines();
+        while t < max_steps {
+            write!(writer, "[t={}] > ", network.current_time_step)?;
+            writer.flush()?;
+            let Some(line) = lines.next().transpose()? else {
+                break;
+            };
+            match Command::parse(&line) {
+                Some(Command::Step(count)) => {
+                    let count = (count as i64).min(max_steps - t);
+                    for _ in 0..count {
+                        network.update(input_fn(t).as_ref());
+                        t += 1;
+                    }
+                    writeln!(writer, "{}", self.inspect(network))?;
+                }
+                Some(Command::Select(neuron)) if self.select(network, neuron) => {
+                    writeln!(writer, "{}", self.inspect(network))?;
+                }
+                Some(Command::Select(neuron)) => {
+                    writeln!(writer, "Neurónio {neuron} fora da rede")?;
+                }
+                Some(Command::Quit) => break,
+                Some(Command::Help) | None => writeln!(writer, "{HELP}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ConnectivityType;
+
+    #[test]
+    fn test_session_steps_and_explains_firing() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.2, 3);
+        let debugger = Debugger::attach(&mut network, 4);
+        assert_eq!(debugger.inspect(&network).reason, FireReason::NotStepped);
+
+        let commands = "\n3\nn 99\nn 0\nq\n1\n";
+        let mut output = Vec::new();
+        debugger
+            .run_session(
+                &mut network,
+                100,
+                |_| vec![2.0; 9],
+                commands.as_bytes(),
+                &mut output,
+            )
+            .unwrap();
+
+        assert_eq!(network.current_time_step, 4);
+        assert_eq!(debugger.neuron(), 0);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Neurónio 4 (Excitatory) em t=1: disparou"));
+        assert!(output.contains("Neurónio 99 fora da rede"));
+
+        network.update(&[0.0; 9]);
+        let inspection = debugger.inspect(&network);
+        let modulated = inspection.modulated.unwrap();
+        assert!(!inspection.is_firing);
+        assert!(matches!(
+            inspection.reason,
+            FireReason::BelowThreshold | FireReason::Refractory
+        ));
+        assert_eq!(
+            modulated <= inspection.threshold,
+            inspection.reason == FireReason::BelowThreshold
+        );
+        assert!(inspection.weights.len() >= network.neighbors(0).len());
+    }
+}
@@ -1,8 +1,12 @@
-/// Módulo responsável pela integração de sinais de entrada e aprendizado sináptico
-///
-/// O Dendritoma recebe e pondera os sinais de entrada, aplicando aprendizado
-/// Hebbiano com normalização L2 para estabilidade.
-
+//! Módulo responsável pela integração de sinais de entrada e aprendizado sináptico
+//!
+//! O Dendritoma recebe e pondera os sinais de entrada, aplicando aprendizado
+//! Hebbiano com normalização L2 para estabilidade.
+
+use crate::error::{check_finite, check_len, Result};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::F64Ext;
+use crate::prelude::*;
 use rand::Rng;
 
 #[derive(Debug, Clone)]
@@ -22,9 +26,19 @@ impl Dendritoma {
     ///
     /// # Argumentos
     /// * `num_inputs` - Número de conexões de entrada
+    #[cfg(feature = "std")]
     pub fn new(num_inputs: usize) -> Self {
-        let mut rng = rand::thread_rng();
+        Self::with_rng(num_inputs, &mut rand::thread_rng())
+    }
 
+    /// Cria um Dendritoma com pesos iniciais sorteados do gerador fornecido
+    ///
+    /// Com um gerador semeado a inicialização é reprodutível.
+    ///
+    /// # Argumentos
+    /// * `num_inputs` - Número de conexões de entrada
+    /// * `rng` - Gerador de números aleatórios
+    pub fn with_rng<R: Rng + ?Sized>(num_inputs: usize, rng: &mut R) -> Self {
         // Inicializa pesos aleatórios entre 0.1 e 0.3 para evitar uniformidade
         let weights: Vec<f64> = (0..num_inputs)
             .map(|_| rng.gen_range(0.1..0.3))
@@ -40,13 +54,33 @@ impl Dendritoma {
         }
     }
 
+    /// Volta a sortear os pesos iniciais e repõe a plasticidade uniforme
+    ///
+    /// A taxa de aprendizado é mantida.
+    pub fn reinitialize<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let learning_rate = self.learning_rate;
+        *self = Self::with_rng(self.weights.len(), rng);
+        self.learning_rate = learning_rate;
+    }
+
     /// Cria um Dendritoma com parâmetros personalizados
+    #[cfg(feature = "std")]
     pub fn with_params(num_inputs: usize, learning_rate: f64) -> Self {
         let mut dendritoma = Self::new(num_inputs);
         dendritoma.learning_rate = learning_rate;
         dendritoma
     }
 
+    /// Taxa de aprendizado Hebbiano
+    pub fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    /// Define a taxa de aprendizado Hebbiano
+    pub fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.learning_rate = learning_rate;
+    }
+
     /// Integra os sinais de entrada através de uma soma ponderada
     ///
     /// # Argumentos
@@ -54,13 +88,32 @@ impl Dendritoma {
     ///
     /// # Retorna
     /// O potencial integrado (soma ponderada dos inputs)
+    ///
+    /// # Panics
+    /// Se `inputs.len()` for diferente do número de pesos
+    #[deprecated(note = "entra em pânico com comprimentos diferentes; usar `try_integrate`")]
     pub fn integrate(&self, inputs: &[f64]) -> f64 {
         assert_eq!(
             inputs.len(),
             self.weights.len(),
             "Número de inputs deve ser igual ao número de pesos"
         );
+        self.weighted_sum(inputs)
+    }
+
+    /// Integra os sinais de entrada, devolvendo erro se o comprimento não coincidir
+    ///
+    /// # Retorna
+    /// O potencial integrado, ou `LengthMismatch` se `inputs.len()` for
+    /// diferente do número de pesos
+    pub fn try_integrate(&self, inputs: &[f64]) -> Result<f64> {
+        check_len("inputs", self.weights.len(), inputs.len())?;
+        Ok(self.weighted_sum(inputs))
+    }
 
+    /// Soma ponderada sem validação, para chamadores que já garantem o comprimento
+    pub(crate) fn weighted_sum(&self, inputs: &[f64]) -> f64 {
+        debug_assert_eq!(inputs.len(), self.weights.len());
         inputs
             .iter()
             .zip(self.weights.iter())
@@ -76,6 +129,9 @@ impl Dendritoma {
     ///
     /// # Argumentos
     /// * `inputs` - Vetor de sinais de entrada que estavam presentes durante o disparo
+    ///
+    /// # Panics
+    /// Se `inputs.len()` for diferente do número de pesos
     pub fn apply_learning(&mut self, inputs: &[f64]) {
         assert_eq!(
             inputs.len(),
@@ -85,10 +141,15 @@ impl Dendritoma {
 
         // Fase 1: Atualização Hebbiana
         // Apenas fortalece conexões de inputs positivos (excitatórios)
-        for i in 0..self.weights.len() {
-            if inputs[i] > 0.0 {
-                let hebbian_update = self.learning_rate * self.plasticity[i] * inputs[i];
-                self.weights[i] += hebbian_update;
+        for ((weight, &plasticity), &input) in self
+            .weights
+            .iter_mut()
+            .zip(self.plasticity.iter())
+            .zip(inputs.iter())
+        {
+            if input > 0.0 {
+                let hebbian_update = self.learning_rate * plasticity * input;
+                *weight += hebbian_update;
             }
         }
 
@@ -104,6 +165,22 @@ impl Dendritoma {
         }
     }
 
+    /// Substitui os pesos por valores importados (rede pré-treinada ou desenhada à mão)
+    ///
+    /// A plasticidade e a taxa de aprendizado são mantidas.
+    ///
+    /// # Argumentos
+    /// * `weights` - Um peso por conexão de entrada
+    ///
+    /// # Retorna
+    /// Erro se o comprimento não coincidir ou houver valores não finitos
+    pub fn set_weights_from(&mut self, weights: &[f64]) -> Result<()> {
+        check_len("pesos", self.weights.len(), weights.len())?;
+        check_finite("pesos", weights)?;
+        self.weights.copy_from_slice(weights);
+        Ok(())
+    }
+
     /// Retorna o número de conexões de entrada
     pub fn num_inputs(&self) -> usize {
         self.weights.len()
@@ -124,10 +201,16 @@ impl Dendritoma {
 mod tests {
     use super::*;
     use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn rng() -> StdRng {
+        StdRng::seed_from_u64(0)
+    }
 
     #[test]
     fn test_dendritoma_initialization() {
-        let dendritoma = Dendritoma::new(10);
+        let dendritoma = Dendritoma::with_rng(10, &mut rng());
         assert_eq!(dendritoma.weights.len(), 10);
         assert_eq!(dendritoma.plasticity.len(), 10);
 
@@ -137,13 +220,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_set_weights_from_validates_length() {
+        let mut dendritoma = Dendritoma::with_rng(3, &mut rng());
+        dendritoma.set_weights_from(&[0.5, 0.0, -0.2]).unwrap();
+        assert_eq!(dendritoma.weights, vec![0.5, 0.0, -0.2]);
+
+        assert!(dendritoma.set_weights_from(&[0.1, 0.2]).is_err());
+        assert!(dendritoma.set_weights_from(&[0.1, f64::NAN, 0.2]).is_err());
+        assert_eq!(dendritoma.weights, vec![0.5, 0.0, -0.2]);
+    }
+
     #[test]
     fn test_integration() {
-        let mut dendritoma = Dendritoma::new(3);
+        let mut dendritoma = Dendritoma::with_rng(3, &mut rng());
         dendritoma.weights = vec![0.5, 0.3, 0.2];
 
         let inputs = vec![1.0, 2.0, 3.0];
-        let potential = dendritoma.integrate(&inputs);
+        let potential = dendritoma.try_integrate(&inputs).unwrap();
 
         // 0.5*1.0 + 0.3*2.0 + 0.2*3.0 = 0.5 + 0.6 + 0.6 = 1.7
         assert_relative_eq!(potential, 1.7, epsilon = 1e-10);
@@ -151,11 +245,11 @@ mod tests {
 
     #[test]
     fn test_integration_with_negative_inputs() {
-        let mut dendritoma = Dendritoma::new(3);
+        let mut dendritoma = Dendritoma::with_rng(3, &mut rng());
         dendritoma.weights = vec![0.5, 0.3, 0.2];
 
         let inputs = vec![1.0, -2.0, 3.0];
-        let potential = dendritoma.integrate(&inputs);
+        let potential = dendritoma.try_integrate(&inputs).unwrap();
 
         // 0.5*1.0 + 0.3*(-2.0) + 0.2*3.0 = 0.5 - 0.6 + 0.6 = 0.5
         assert_relative_eq!(potential, 0.5, epsilon = 1e-10);
@@ -163,7 +257,7 @@ mod tests {
 
     #[test]
     fn test_hebbian_learning_strengthens_weights() {
-        let mut dendritoma = Dendritoma::new(3);
+        let mut dendritoma = Dendritoma::with_rng(3, &mut rng());
         dendritoma.weights = vec![0.3, 0.3, 0.3];
         dendritoma.learning_rate = 0.1;
 
@@ -178,7 +272,7 @@ mod tests {
 
     #[test]
     fn test_l2_normalization_maintains_unit_norm() {
-        let mut dendritoma = Dendritoma::new(5);
+        let mut dendritoma = Dendritoma::with_rng(5, &mut rng());
         dendritoma.weights = vec![0.2, 0.2, 0.2, 0.2, 0.2];
 
         let inputs = vec![1.0, 1.0, 1.0, 1.0, 1.0];
@@ -195,7 +289,7 @@ mod tests {
 
     #[test]
     fn test_no_learning_from_negative_inputs() {
-        let mut dendritoma = Dendritoma::new(2);
+        let mut dendritoma = Dendritoma::with_rng(2, &mut rng());
         // Usa pesos já normalizados para evitar mudanças pela normalização
         dendritoma.weights = vec![0.7071067811865475, 0.7071067811865475]; // sqrt(2)/2 cada
         let norm_before = dendritoma.weight_norm();
@@ -214,8 +308,9 @@ mod tests {
 
     #[test]
     #[should_panic(expected = "Número de inputs deve ser igual ao número de pesos")]
+    #[allow(deprecated)]
     fn test_integrate_panics_on_size_mismatch() {
-        let dendritoma = Dendritoma::new(3);
+        let dendritoma = Dendritoma::with_rng(3, &mut rng());
         let inputs = vec![1.0, 2.0]; // Tamanho errado
         dendritoma.integrate(&inputs);
     }
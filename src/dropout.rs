@@ -0,0 +1,75 @@
+//! Módulo de dropout de neurónios
+//!
+//! O dropout silencia aleatoriamente uma fração dos neurónios para medir a
+//! robustez da deteção de novidade a unidades pouco fiáveis. Ao contrário
+//! de `Network::lesion`, o silenciamento é temporário: a máscara é sorteada
+//! de novo a cada passo (`PerStep`) ou a cada ensaio (`PerTrial`, ou seja,
+//! ao ativar o dropout e em cada `Network::reset_state`).
+
+use crate::footprint::vec_bytes;
+use crate::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Quando a máscara de dropout é sorteada
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropoutMode {
+    /// Nova máscara a cada passo de simulação
+    PerStep,
+    /// Máscara fixa durante um ensaio
+    PerTrial,
+}
+
+/// Estado do dropout de uma rede
+#[derive(Debug, Clone)]
+pub(crate) struct Dropout {
+    pub(crate) probability: f64,
+    pub(crate) mode: DropoutMode,
+    pub(crate) mask: Vec<bool>,
+    rng: StdRng,
+}
+
+impl Dropout {
+    /// Cria o dropout com a máscara inicial já sorteada
+    pub(crate) fn new(num_neurons: usize, probability: f64, mode: DropoutMode, seed: u64) -> Self {
+        let mut dropout = Self {
+            probability: probability.clamp(0.0, 1.0),
+            mode,
+            mask: vec![false; num_neurons],
+            rng: StdRng::seed_from_u64(seed),
+        };
+        dropout.resample();
+        dropout
+    }
+
+    /// Bytes reservados pelos vetores (ver `footprint`)
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.mask)
+    }
+
+    /// Sorteia uma nova máscara (cada neurónio cai com `probability`)
+    pub(crate) fn resample(&mut self) {
+        for dropped in &mut self.mask {
+            *dropped = self.rng.gen_bool(self.probability);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_fraction_and_extremes() {
+        let mut dropout = Dropout::new(1000, 0.3, DropoutMode::PerStep, 7);
+        let first = dropout.mask.clone();
+        let dropped = first.iter().filter(|&&d| d).count();
+        assert!((250..350).contains(&dropped));
+
+        dropout.resample();
+        assert_ne!(dropout.mask, first);
+
+        assert!(Dropout::new(50, 1.5, DropoutMode::PerTrial, 1).mask.iter().all(|&d| d));
+        assert!(Dropout::new(50, 0.0, DropoutMode::PerTrial, 1).mask.iter().all(|&d| !d));
+    }
+}
@@ -0,0 +1,152 @@
+//! Módulo de fusão e ensembles de redes
+//!
+//! `Network::merge` junta duas redes treinadas numa só: os neurónios da
+//! segunda ficam depois dos da primeira (índices `n1..n1 + n2`), cada metade
+//! mantém os seus pesos, limiares e conectividade, e as duas metades só
+//! comunicam pelas conexões de um `BridgeSpec`:
+//!
+//! ```text
+//! conectividade:  [ A     ponte ]
+//!                 [ ponte B     ]
+//! ```
+//!
+//! Para redes com a mesma topologia treinadas a partir de sementes
+//! diferentes, `average_weights` calcula a matriz de pesos média (para usar
+//! com `Network::set_weight_matrix`) e `weight_distance` mede quanto os
+//! membros do ensemble divergem entre si.
+
+use crate::error::{check_len, Result};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::F64Ext;
+use crate::network::Network;
+use crate::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Conexões entre as duas redes numa fusão
+#[derive(Debug, Clone, PartialEq)]
+pub struct BridgeSpec {
+    /// Pares `(a, b)`: neurónio `a` da primeira rede e `b` da segunda
+    /// (índices locais de cada rede), ligados nos dois sentidos
+    pub pairs: Vec<(usize, usize)>,
+
+    /// Peso inicial das conexões da ponte
+    pub weight: f64,
+}
+
+impl Default for BridgeSpec {
+    fn default() -> Self {
+        Self {
+            pairs: Vec::new(),
+            weight: 0.2,
+        }
+    }
+}
+
+impl BridgeSpec {
+    /// Ponte com os pares dados
+    pub fn new(pairs: Vec<(usize, usize)>) -> Self {
+        Self {
+            pairs,
+            ..Self::default()
+        }
+    }
+
+    /// Ponte aleatória: cada par `(a, b)` é ligado com probabilidade `probability`
+    pub fn random(first: usize, second: usize, probability: f64, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let probability = probability.clamp(0.0, 1.0);
+        let pairs = (0..first)
+            .flat_map(|a| (0..second).map(move |b| (a, b)))
+            .filter(|_| rng.gen_bool(probability))
+            .collect();
+        Self::new(pairs)
+    }
+
+    /// Define o peso inicial das conexões da ponte
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// Matriz de pesos média de várias redes com o mesmo número de neurónios
+///
+/// # Retorna
+/// Erro se as redes tiverem tamanhos diferentes; matriz vazia sem redes
+pub fn average_weights(networks: &[&Network]) -> Result<Vec<Vec<f64>>> {
+    let Some((first, rest)) = networks.split_first() else {
+        return Ok(Vec::new());
+    };
+    let mut sum = first.weight_matrix();
+    for network in rest {
+        check_len("neurónios da rede do ensemble", sum.len(), network.num_neurons())?;
+        for (row, neuron) in sum.iter_mut().zip(&network.neurons) {
+            let weights = &neuron.dendritoma.weights;
+            check_len("pesos da rede do ensemble", row.len(), weights.len())?;
+            for (total, w) in row.iter_mut().zip(weights) {
+                *total += w;
+            }
+        }
+    }
+
+    let count = networks.len() as f64;
+    for total in sum.iter_mut().flatten() {
+        *total /= count;
+    }
+    Ok(sum)
+}
+
+/// Distância RMS entre as matrizes de pesos de duas redes
+///
+/// # Retorna
+/// Erro se as redes tiverem tamanhos diferentes
+pub fn weight_distance(a: &Network, b: &Network) -> Result<f64> {
+    check_len("neurónios da rede comparada", a.num_neurons(), b.num_neurons())?;
+    let mut total = 0.0;
+    let mut count = 0usize;
+    for (na, nb) in a.neurons.iter().zip(&b.neurons) {
+        let (wa, wb) = (&na.dendritoma.weights, &nb.dendritoma.weights);
+        check_len("pesos da rede comparada", wa.len(), wb.len())?;
+        total += wa.iter().zip(wb).map(|(x, y)| (x - y).powi(2)).sum::<f64>();
+        count += wa.len();
+    }
+    Ok(if count == 0 { 0.0 } else { (total / count as f64).sqrt() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ConnectivityType;
+
+    #[test]
+    fn test_average_of_seeded_networks() {
+        let a = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 1);
+        let b = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 2);
+        let average = average_weights(&[&a, &b]).unwrap();
+
+        let (wa, wb) = (a.weight_matrix(), b.weight_matrix());
+        assert!((average[4][7] - (wa[4][7] + wb[4][7]) / 2.0).abs() < 1e-12);
+        assert_eq!(average_weights(&[&a]).unwrap(), wa);
+
+        // A média fica a meio caminho de cada membro
+        let mut merged = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 3);
+        merged.set_weight_matrix(&average).unwrap();
+        let distance = weight_distance(&a, &b).unwrap();
+        assert!(distance > 0.0);
+        assert!((weight_distance(&merged, &a).unwrap() - distance / 2.0).abs() < 1e-12);
+
+        let small = Network::new_seeded(4, ConnectivityType::Grid2D, 0.0, 0.5, 1);
+        assert!(average_weights(&[&a, &small]).is_err());
+        assert!(weight_distance(&a, &small).is_err());
+    }
+
+    #[test]
+    fn test_random_bridge_is_reproducible() {
+        let bridge = BridgeSpec::random(4, 5, 0.5, 7).with_weight(0.4);
+        assert_eq!(bridge, BridgeSpec::random(4, 5, 0.5, 7).with_weight(0.4));
+        assert!(bridge.pairs.iter().all(|&(a, b)| a < 4 && b < 5));
+        assert!(!bridge.pairs.is_empty() && bridge.pairs.len() < 20);
+        assert!(BridgeSpec::random(4, 5, 0.0, 7).pairs.is_empty());
+    }
+}
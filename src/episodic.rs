@@ -0,0 +1,182 @@
+//! Módulo de memória episódica
+//!
+//! O `EpisodicBuffer` guarda automaticamente os padrões de input dos passos
+//! em que a novidade média da rede excede um limiar, com o passo de tempo e
+//! a pontuação de novidade. Ao contrário da memória contextual dos
+//! neurónios (que apenas habitua), os episódios podem ser consultados e
+//! reproduzidos mais tarde com `Network::replay_episode`.
+//!
+//! Quando o buffer está cheio, um novo episódio substitui o menos novo
+//! guardado (se o novo for mais novo), pelo que o buffer retém sempre os
+//! eventos mais salientes.
+
+use crate::footprint::vec_bytes;
+use crate::prelude::*;
+
+/// Evento novo guardado na memória episódica
+#[derive(Debug, Clone, PartialEq)]
+pub struct Episode {
+    /// Passo de tempo em que o evento ocorreu
+    pub time: i64,
+
+    /// Novidade média da rede nesse passo
+    pub novelty: f64,
+
+    /// Input externo completo do passo (um valor por neurónio)
+    pub inputs: Vec<f64>,
+
+    /// Neurónios que dispararam em resposta
+    pub firing: Vec<usize>,
+}
+
+/// Buffer de episódios novos com capacidade fixa
+#[derive(Debug, Clone)]
+pub struct EpisodicBuffer {
+    capacity: usize,
+    threshold: f64,
+    episodes: Vec<Episode>,
+}
+
+impl EpisodicBuffer {
+    /// Cria um buffer que guarda até `capacity` episódios (mínimo 1)
+    ///
+    /// # Argumentos
+    /// * `capacity` - Número máximo de episódios
+    /// * `threshold` - Novidade média mínima para guardar um passo
+    pub fn new(capacity: usize, threshold: f64) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            threshold,
+            episodes: Vec::new(),
+        }
+    }
+
+    /// Bytes reservados pelos episódios guardados (ver `footprint`)
+    pub(crate) fn heap_bytes(&self) -> usize {
+        let vectors: usize = self
+            .episodes
+            .iter()
+            .map(|e| vec_bytes(&e.inputs) + vec_bytes(&e.firing))
+            .sum();
+        vec_bytes(&self.episodes) + vectors
+    }
+
+    /// Novidade mínima para guardar um episódio
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Capacidade máxima do buffer
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Oferece um episódio ao buffer
+    ///
+    /// # Retorna
+    /// `true` se o episódio foi guardado
+    pub fn offer(&mut self, episode: Episode) -> bool {
+        if episode.novelty <= self.threshold {
+            return false;
+        }
+        if self.episodes.len() < self.capacity {
+            self.episodes.push(episode);
+            return true;
+        }
+
+        let weakest = self
+            .episodes
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.novelty.total_cmp(&b.novelty))
+            .map(|(i, _)| i);
+        match weakest {
+            Some(i) if self.episodes[i].novelty < episode.novelty => {
+                // Mantém a ordem cronológica
+                self.episodes.remove(i);
+                self.episodes.push(episode);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Número de episódios guardados
+    pub fn len(&self) -> usize {
+        self.episodes.len()
+    }
+
+    /// Verifica se o buffer está vazio
+    pub fn is_empty(&self) -> bool {
+        self.episodes.is_empty()
+    }
+
+    /// Episódios por ordem cronológica
+    pub fn episodes(&self) -> &[Episode] {
+        &self.episodes
+    }
+
+    /// Os `k` episódios mais novos, do maior para o menor
+    pub fn most_novel(&self, k: usize) -> Vec<&Episode> {
+        let mut sorted: Vec<&Episode> = self.episodes.iter().collect();
+        sorted.sort_by(|a, b| b.novelty.total_cmp(&a.novelty));
+        sorted.truncate(k);
+        sorted
+    }
+
+    /// Episódios ocorridos em `[start, end)`
+    pub fn between(&self, start: i64, end: i64) -> impl Iterator<Item = &Episode> {
+        self.episodes
+            .iter()
+            .filter(move |e| e.time >= start && e.time < end)
+    }
+
+    /// Episódio com o input mais próximo (distância euclidiana) de `pattern`
+    pub fn nearest(&self, pattern: &[f64]) -> Option<&Episode> {
+        let distance = |e: &Episode| -> f64 {
+            e.inputs
+                .iter()
+                .zip(pattern)
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum()
+        };
+        self.episodes
+            .iter()
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+    }
+
+    /// Remove todos os episódios
+    pub fn clear(&mut self) {
+        self.episodes.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn episode(time: i64, novelty: f64, input: f64) -> Episode {
+        Episode {
+            time,
+            novelty,
+            inputs: vec![input, 0.0],
+            firing: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_keeps_most_novel_episodes() {
+        let mut buffer = EpisodicBuffer::new(2, 0.5);
+        assert!(!buffer.offer(episode(1, 0.4, 1.0)));
+        assert!(buffer.offer(episode(2, 0.9, 2.0)));
+        assert!(buffer.offer(episode(3, 0.6, 3.0)));
+        assert!(!buffer.offer(episode(4, 0.55, 4.0)));
+        assert!(buffer.offer(episode(5, 0.7, 5.0)));
+
+        let times: Vec<i64> = buffer.episodes().iter().map(|e| e.time).collect();
+        assert_eq!(times, vec![2, 5]);
+        assert_eq!(buffer.most_novel(1)[0].time, 2);
+        assert_eq!(buffer.between(3, 10).count(), 1);
+        assert_eq!(buffer.nearest(&[4.8, 0.0]).unwrap().time, 5);
+    }
+}
@@ -0,0 +1,113 @@
+//! Módulo de erros da biblioteca
+//!
+//! `NenvError` reúne as falhas de validação que as variantes `try_*` da API
+//! devolvem em vez de entrar em pânico (comprimentos incompatíveis, redes
+//! vazias, valores não finitos, parâmetros inválidos). `integrate`,
+//! `update_memory` e `compute_novelty` estão obsoletas em favor das
+//! variantes `try_*`; as restantes funções que entram em pânico
+//! documentam-no numa secção `# Panics`.
+//!
+//! O `Display` e o `Error` vêm do `thiserror` sem features por omissão, pelo
+//! que o tipo funciona igualmente no núcleo `no_std`.
+//!
+//! Com a feature `std`, converte-se em `io::Error` (`InvalidInput`), pelo
+//! que pode ser propagado com `?` em funções que já retornam `io::Result`.
+
+use crate::prelude::*;
+#[cfg(feature = "std")]
+use std::io;
+
+/// Erro de validação da biblioteca NEN-V
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum NenvError {
+    /// Um vetor não tem o comprimento esperado
+    #[error("{what}: esperado comprimento {expected}, recebido {actual}")]
+    LengthMismatch {
+        what: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+
+    /// Operação que exige pelo menos um neurónio
+    #[error("a rede precisa de pelo menos um neurónio")]
+    EmptyNetwork,
+
+    /// Dados de entrada vazios onde é preciso pelo menos um elemento
+    #[error("{what}: sem dados")]
+    EmptyInput { what: &'static str },
+
+    /// Valor NaN ou infinito na posição `index`
+    #[error("{what}: valor não finito na posição {index}")]
+    NonFinite { what: &'static str, index: usize },
+
+    /// Parâmetro fora do intervalo válido
+    #[error("{what}: valor inválido {value}")]
+    InvalidParameter { what: &'static str, value: f64 },
+
+    /// Índice de neurónio fora da rede
+    #[error("índice {index} fora da rede de {len} neurónios")]
+    IndexOutOfRange { index: usize, len: usize },
+
+    /// Porta de entrada ou saída não definida
+    #[error("porta '{name}' não definida")]
+    UnknownPort { name: String },
+
+    /// Grupo de neurónios não definido
+    #[error("grupo '{name}' não definido")]
+    UnknownGroup { name: String },
+}
+
+/// Resultado com `NenvError`
+pub type Result<T> = core::result::Result<T, NenvError>;
+
+#[cfg(feature = "std")]
+impl From<NenvError> for io::Error {
+    fn from(error: NenvError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidInput, error)
+    }
+}
+
+/// Verifica que um vetor tem o comprimento esperado
+pub(crate) fn check_len(what: &'static str, expected: usize, actual: usize) -> Result<()> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(NenvError::LengthMismatch {
+            what,
+            expected,
+            actual,
+        })
+    }
+}
+
+/// Verifica que todos os valores são finitos
+pub(crate) fn check_finite(what: &'static str, values: &[f64]) -> Result<()> {
+    match values.iter().position(|v| !v.is_finite()) {
+        Some(index) => Err(NenvError::NonFinite { what, index }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_and_io_conversion() {
+        let error = check_len("inputs", 9, 4).unwrap_err();
+        assert_eq!(error.to_string(), "inputs: esperado comprimento 9, recebido 4");
+
+        #[cfg(feature = "std")]
+        {
+            let io_error: io::Error = error.into();
+            assert_eq!(io_error.kind(), io::ErrorKind::InvalidInput);
+        }
+        assert_eq!(
+            check_finite("pesos", &[0.0, f64::NAN]),
+            Err(NenvError::NonFinite {
+                what: "pesos",
+                index: 1
+            })
+        );
+    }
+}
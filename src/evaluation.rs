@@ -0,0 +1,216 @@
+//! Módulo de avaliação multiobjetivo: desempenho vs custo metabólico
+//!
+//! A eficiência energética é uma das propriedades centrais da NEN-V, por
+//! isso cada execução de avaliação devolve dois números: o desempenho na
+//! tarefa (maior é melhor, p.ex. AUC de deteção) e o custo metabólico total
+//! (`Network::metabolic_cost`, energia gasta em disparos; menor é melhor).
+//!
+//! `evaluate_sweep` avalia uma lista de genomas em paralelo e identifica a
+//! frente de Pareto: as configurações que nenhuma outra supera em ambos os
+//! objetivos ao mesmo tempo.
+
+use crate::evolution::{map_parallel, Genome};
+use crate::metrics::roc_auc;
+use crate::network::{ConnectivityType, Network};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Resultado de uma execução
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunReport {
+    /// Métrica da tarefa (maior é melhor)
+    pub performance: f64,
+
+    /// Energia total gasta em disparos (menor é melhor)
+    pub metabolic_cost: f64,
+}
+
+impl RunReport {
+    /// Relatório de uma rede no fim de uma execução
+    pub fn from_network(performance: f64, network: &Network) -> Self {
+        Self {
+            performance,
+            metabolic_cost: network.metabolic_cost(),
+        }
+    }
+
+    /// Verdadeiro se `self` é pelo menos tão bom em ambos os objetivos e
+    /// estritamente melhor em algum
+    pub fn dominates(&self, other: &RunReport) -> bool {
+        self.performance >= other.performance
+            && self.metabolic_cost <= other.metabolic_cost
+            && (self.performance > other.performance || self.metabolic_cost < other.metabolic_cost)
+    }
+}
+
+/// Índices dos relatórios não dominados, por ordem crescente de custo
+///
+/// Relatórios com valores NaN nunca entram na frente.
+pub fn pareto_front(reports: &[RunReport]) -> Vec<usize> {
+    let valid = |r: &RunReport| !r.performance.is_nan() && !r.metabolic_cost.is_nan();
+    let mut front: Vec<usize> = (0..reports.len())
+        .filter(|&i| valid(&reports[i]))
+        .filter(|&i| !reports.iter().any(|other| valid(other) && other.dominates(&reports[i])))
+        .collect();
+    front.sort_by(|&a, &b| reports[a].metabolic_cost.total_cmp(&reports[b].metabolic_cost));
+    front
+}
+
+/// Resultados de um sweep multiobjetivo
+#[derive(Debug, Clone)]
+pub struct SweepSummary {
+    /// Genoma e relatório de cada execução, pela ordem de avaliação
+    pub entries: Vec<(Genome, RunReport)>,
+
+    /// Índices em `entries` da frente de Pareto (custo crescente)
+    pub front: Vec<usize>,
+}
+
+impl SweepSummary {
+    /// Calcula a frente de Pareto de um conjunto de execuções
+    pub fn new(entries: Vec<(Genome, RunReport)>) -> Self {
+        let reports: Vec<RunReport> = entries.iter().map(|&(_, report)| report).collect();
+        let front = pareto_front(&reports);
+        Self { entries, front }
+    }
+
+    /// Execuções da frente de Pareto
+    pub fn front_entries(&self) -> impl Iterator<Item = &(Genome, RunReport)> {
+        self.front.iter().map(|&i| &self.entries[i])
+    }
+
+    /// Escreve todas as execuções em CSV, com uma coluna a marcar a frente
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let names: Vec<&str> = Genome::GENES.iter().map(|&(name, _, _)| name).collect();
+        writeln!(writer, "run,{},performance,metabolic_cost,pareto", names.join(","))?;
+        for (i, (genome, report)) in self.entries.iter().enumerate() {
+            let genes: Vec<String> = genome.to_genes().iter().map(|g| g.to_string()).collect();
+            writeln!(
+                writer,
+                "{},{},{},{},{}",
+                i,
+                genes.join(","),
+                report.performance,
+                report.metabolic_cost,
+                self.front.contains(&i) as u8
+            )?;
+        }
+        writer.flush()
+    }
+
+    /// Guarda o CSV num ficheiro
+    pub fn save_csv<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_csv(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl fmt::Display for SweepSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Frente de Pareto: {} de {} execuções",
+            self.front.len(),
+            self.entries.len()
+        )?;
+        writeln!(f, "{:>5} {:>12} {:>14}", "run", "desempenho", "custo")?;
+        for &i in &self.front {
+            let report = &self.entries[i].1;
+            writeln!(f, "{:>5} {:>12.4} {:>14.1}", i, report.performance, report.metabolic_cost)?;
+        }
+        Ok(())
+    }
+}
+
+/// Avalia cada genoma em paralelo e resume a frente de Pareto
+pub fn evaluate_sweep<F>(genomes: &[Genome], run: F, threads: usize) -> SweepSummary
+where
+    F: Fn(&Genome) -> RunReport + Sync,
+{
+    let reports = map_parallel(genomes, run, threads);
+    SweepSummary::new(genomes.iter().copied().zip(reports).collect())
+}
+
+/// Benchmark de deteção de novidade com custo metabólico
+///
+/// Uma rede 5×5 habitua-se a um padrão fixo; depois são apresentados
+/// alternadamente o padrão familiar e padrões aleatórios novos. O score de
+/// cada apresentação é a novidade média mais o alert_level no primeiro
+/// passo, e o desempenho é a AUC desses scores (novos = positivos). O custo
+/// inclui a fase de habituação.
+pub fn novelty_detection_report(genome: &Genome, seed: u64) -> RunReport {
+    const SIZE: usize = 25;
+    let mut network = genome.build_network(SIZE, ConnectivityType::Grid2D, 0.2, seed);
+    let mut rng = StdRng::seed_from_u64(seed ^ 0x5eed);
+
+    let familiar: Vec<f64> = (0..SIZE).map(|i| if i % 3 == 0 { 1.0 } else { 0.0 }).collect();
+    let silence = vec![0.0; SIZE];
+    for _ in 0..60 {
+        network.update(&familiar);
+    }
+
+    let present = |network: &mut Network, pattern: &[f64]| {
+        network.update(pattern);
+        let score = network.average_novelty() + network.alert_level;
+        for _ in 0..4 {
+            network.update(pattern);
+        }
+        for _ in 0..5 {
+            network.update(&silence);
+        }
+        score
+    };
+
+    let mut positives = Vec::new();
+    let mut negatives = Vec::new();
+    for _ in 0..8 {
+        negatives.push(present(&mut network, &familiar));
+        let novel: Vec<f64> =
+            (0..SIZE).map(|_| if rng.gen_bool(0.33) { 1.0 } else { 0.0 }).collect();
+        positives.push(present(&mut network, &novel));
+    }
+    let auc = roc_auc(&positives, &negatives).unwrap_or(0.5);
+    RunReport::from_network(auc, &network)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(performance: f64, metabolic_cost: f64) -> RunReport {
+        RunReport {
+            performance,
+            metabolic_cost,
+        }
+    }
+
+    #[test]
+    fn test_pareto_front() {
+        let reports = [
+            report(0.9, 100.0),
+            report(0.8, 50.0),
+            report(0.7, 80.0), // dominado por 1
+            report(0.9, 120.0), // dominado por 0
+            report(0.5, 10.0),
+            report(f64::NAN, 0.0),
+        ];
+        assert_eq!(pareto_front(&reports), vec![4, 1, 0]);
+    }
+
+    #[test]
+    fn test_sweep_reports_energy_cost() {
+        let mut cheap = Genome::default();
+        cheap.config.glia.energy_cost_fire = 1.0;
+        let genomes = [Genome::default(), cheap];
+
+        let summary = evaluate_sweep(&genomes, |g| novelty_detection_report(g, 3), 2);
+        let (default, cheap) = (summary.entries[0].1, summary.entries[1].1);
+        assert!(default.metabolic_cost > 0.0);
+        assert!(cheap.metabolic_cost < default.metabolic_cost);
+        assert!(summary.front.contains(&1));
+        assert!(summary.to_string().starts_with("Frente de Pareto:"));
+    }
+}
@@ -0,0 +1,213 @@
+//! Módulo de eventos da simulação
+//!
+//! Deteta eventos notáveis (disparos, ativação de alerta, novidade acima do
+//! limiar, checkpoints) e emite-os como um fluxo JSON Lines, permitindo que
+//! dashboards externos acompanhem a simulação em tempo real (`tail -f`).
+
+use crate::network::Network;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+/// Evento notável ocorrido durante a simulação
+///
+/// Serializado como um objeto JSON com o campo `event` a indicar o tipo,
+/// p.ex. `{"event":"neuron_fired","time":12,"neuron":55}`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum SimulationEvent {
+    /// Um neurónio disparou
+    NeuronFired { time: i64, neuron: usize },
+
+    /// O alert_level da rede ultrapassou o limiar de ativação
+    AlertActivated { time: i64, alert_level: f64 },
+
+    /// A novidade média da rede ultrapassou o limiar
+    NoveltyAboveThreshold {
+        time: i64,
+        avg_novelty: f64,
+        threshold: f64,
+    },
+
+    /// Um checkpoint do estado foi gravado
+    CheckpointWritten { time: i64, path: String },
+}
+
+/// Destino de eventos da simulação
+pub trait EventSink {
+    /// Emite um evento
+    fn emit(&mut self, event: &SimulationEvent) -> io::Result<()>;
+
+    /// Garante que os eventos emitidos ficam visíveis para consumidores
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Destino JSON Lines: um objeto JSON por linha
+pub struct JsonlEventSink<W: Write = BufWriter<File>> {
+    writer: W,
+}
+
+impl JsonlEventSink<BufWriter<File>> {
+    /// Cria (ou substitui) um ficheiro `.jsonl`
+    pub fn create(path: &str) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> JsonlEventSink<W> {
+    /// Cria um destino JSON Lines sobre qualquer `Write`
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Emite todos os eventos de um passo e faz flush
+    ///
+    /// Flush por passo (e não por evento) mantém o fluxo legível em tempo
+    /// real sem uma chamada de sistema por disparo.
+    pub fn emit_all(&mut self, events: &[SimulationEvent]) -> io::Result<()> {
+        for event in events {
+            self.emit(event)?;
+        }
+        self.flush()
+    }
+
+    /// Consome o destino e devolve o writer subjacente
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> EventSink for JsonlEventSink<W> {
+    fn emit(&mut self, event: &SimulationEvent) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, event)?;
+        writeln!(self.writer)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Deteta eventos comparando o estado da rede entre passos
+///
+/// Eventos de alerta e novidade são emitidos apenas na transição (quando o
+/// valor cruza o limiar de baixo para cima), não em todos os passos acima dele.
+#[derive(Debug, Clone)]
+pub struct EventDetector {
+    /// Emite um evento por disparo de neurónio
+    pub emit_firing: bool,
+
+    /// Limiar de alert_level que caracteriza "alerta ativo"
+    pub alert_threshold: f64,
+
+    /// Limiar de novidade média
+    pub novelty_threshold: f64,
+
+    alert_active: bool,
+    novelty_active: bool,
+}
+
+impl EventDetector {
+    /// Cria um detetor com limiares explícitos
+    pub fn new(alert_threshold: f64, novelty_threshold: f64) -> Self {
+        Self {
+            emit_firing: true,
+            alert_threshold,
+            novelty_threshold,
+            alert_active: false,
+            novelty_active: false,
+        }
+    }
+
+    /// Cria um detetor que usa o limiar de novidade configurado na rede
+    pub fn for_network(network: &Network) -> Self {
+        let (novelty_threshold, _) = network.novelty_alert_params();
+        Self::new(0.1, novelty_threshold)
+    }
+
+    /// Inspeciona a rede após um `update` e retorna os eventos do passo
+    pub fn detect(&mut self, network: &Network) -> Vec<SimulationEvent> {
+        let time = network.current_time_step;
+        let mut events = Vec::new();
+
+        if self.emit_firing {
+            events.extend(
+                network
+                    .neurons
+                    .iter()
+                    .filter(|n| n.is_firing)
+                    .map(|n| SimulationEvent::NeuronFired {
+                        time,
+                        neuron: n.id,
+                    }),
+            );
+        }
+
+        let alert_active = network.alert_level > self.alert_threshold;
+        if alert_active && !self.alert_active {
+            events.push(SimulationEvent::AlertActivated {
+                time,
+                alert_level: network.alert_level,
+            });
+        }
+        self.alert_active = alert_active;
+
+        let avg_novelty = network.average_novelty();
+        let novelty_active = avg_novelty > self.novelty_threshold;
+        if novelty_active && !self.novelty_active {
+            events.push(SimulationEvent::NoveltyAboveThreshold {
+                time,
+                avg_novelty,
+                threshold: self.novelty_threshold,
+            });
+        }
+        self.novelty_active = novelty_active;
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ConnectivityType;
+
+    #[test]
+    fn test_jsonl_format() {
+        let mut sink = JsonlEventSink::new(Vec::new());
+        sink.emit_all(&[
+            SimulationEvent::NeuronFired { time: 3, neuron: 7 },
+            SimulationEvent::CheckpointWritten {
+                time: 4,
+                path: "ckpt.json".to_string(),
+            },
+        ])
+        .unwrap();
+
+        let output = String::from_utf8(sink.into_inner()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], r#"{"event":"neuron_fired","time":3,"neuron":7}"#);
+        assert_eq!(
+            lines[1],
+            r#"{"event":"checkpoint_written","time":4,"path":"ckpt.json"}"#
+        );
+    }
+
+    #[test]
+    fn test_alert_event_is_edge_triggered() {
+        let mut network = Network::new(9, ConnectivityType::Grid2D, 0.0, 0.5);
+        let mut detector = EventDetector::new(0.5, f64::INFINITY);
+        detector.emit_firing = false;
+
+        network.set_alert_level(1.0);
+        network.update(&[0.0; 9]);
+        let first = detector.detect(&network);
+        assert!(matches!(first[..], [SimulationEvent::AlertActivated { .. }]));
+
+        // Alerta continua alto: nenhum evento novo
+        network.update(&[0.0; 9]);
+        assert!(detector.detect(&network).is_empty());
+    }
+}
@@ -0,0 +1,414 @@
+//! Módulo de otimização evolutiva de hiperparâmetros
+//!
+//! Um algoritmo genético simples procura valores de `NetworkConfig` (e do
+//! limiar inicial) que maximizam uma função de fitness fornecida pelo
+//! utilizador. Cada `Genome` é representado como um vetor de genes reais
+//! com limites fixos (ver `Genome::GENES`):
+//!
+//! ```text
+//! gene                      limites
+//! initial_threshold         [0.05, 2.0]
+//! learning_rate             [0.0, 0.1]
+//! memory_alpha              [0.01, 0.5]
+//! novelty_alert_threshold   [0.0, 2.0]
+//! alert_sensitivity         [0.0, 1.0]
+//! alert_decay_rate          [0.0, 0.5]
+//! energy_cost_fire          [1.0, 30.0]
+//! energy_recovery_rate      [0.1, 10.0]
+//! ```
+//!
+//! Cada geração usa seleção por torneio, crossover uniforme, mutação
+//! gaussiana proporcional à amplitude de cada gene e elitismo. As avaliações
+//! de uma geração correm em paralelo (`std::thread::scope`); a fitness é
+//! qualquer `tuning::Objective` (incluindo closures `Sync`).
+//! `novelty_detection_fitness` é um benchmark pronto a usar (AUC da deteção
+//! de padrões novos).
+
+use crate::config::NetworkConfig;
+use crate::evaluation::novelty_detection_report;
+use crate::network::{ConnectivityType, Network};
+use crate::tuning::Objective;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+
+/// Conjunto de hiperparâmetros avaliado pelo algoritmo genético
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Genome {
+    /// Limiar inicial passado ao construtor da rede
+    pub initial_threshold: f64,
+
+    /// Restantes parâmetros da rede
+    pub config: NetworkConfig,
+}
+
+impl Default for Genome {
+    fn default() -> Self {
+        Self {
+            initial_threshold: 0.5,
+            config: NetworkConfig::default(),
+        }
+    }
+}
+
+impl Genome {
+    /// Nome e limites `(min, max)` de cada gene, na ordem de `to_genes`
+    pub const GENES: [(&'static str, f64, f64); 8] = [
+        ("initial_threshold", 0.05, 2.0),
+        ("learning_rate", 0.0, 0.1),
+        ("memory_alpha", 0.01, 0.5),
+        ("novelty_alert_threshold", 0.0, 2.0),
+        ("alert_sensitivity", 0.0, 1.0),
+        ("alert_decay_rate", 0.0, 0.5),
+        ("energy_cost_fire", 1.0, 30.0),
+        ("energy_recovery_rate", 0.1, 10.0),
+    ];
+
+    /// Vetor de genes (ver `GENES`)
+    pub fn to_genes(&self) -> Vec<f64> {
+        let c = &self.config;
+        vec![
+            self.initial_threshold,
+            c.learning_rate,
+            c.memory_alpha,
+            c.novelty_alert_threshold,
+            c.alert_sensitivity,
+            c.alert_decay_rate,
+            c.glia.energy_cost_fire,
+            c.glia.energy_recovery_rate,
+        ]
+    }
+
+    /// Substitui os genes de `base`, limitando cada valor aos limites de `GENES`
+    ///
+    /// Parâmetros fora dos genes mantêm o valor de `base`; genes em falta
+    /// também.
+    pub fn from_genes(base: &Genome, genes: &[f64]) -> Genome {
+        let mut values = base.to_genes();
+        for (i, (&gene, &(_, min, max))) in genes.iter().zip(Self::GENES.iter()).enumerate() {
+            values[i] = gene.clamp(min, max);
+        }
+
+        let mut genome = *base;
+        genome.initial_threshold = values[0];
+        let c = &mut genome.config;
+        c.learning_rate = values[1];
+        c.memory_alpha = values[2];
+        c.novelty_alert_threshold = values[3];
+        c.alert_sensitivity = values[4];
+        c.alert_decay_rate = values[5];
+        c.glia.energy_cost_fire = values[6];
+        c.glia.energy_recovery_rate = values[7];
+        genome
+    }
+
+    /// Genoma com genes sorteados uniformemente dentro dos limites
+    pub fn random<R: Rng + ?Sized>(base: &Genome, rng: &mut R) -> Genome {
+        let genes: Vec<f64> = Self::GENES
+            .iter()
+            .map(|&(_, min, max)| rng.gen_range(min..=max))
+            .collect();
+        Self::from_genes(base, &genes)
+    }
+
+    /// Constrói uma rede reprodutível com estes parâmetros
+    pub fn build_network(
+        &self,
+        num_neurons: usize,
+        connectivity_type: ConnectivityType,
+        inhibitory_ratio: f64,
+        seed: u64,
+    ) -> Network {
+        Network::with_config_seeded(
+            num_neurons,
+            connectivity_type,
+            inhibitory_ratio,
+            self.initial_threshold,
+            self.config,
+            seed,
+        )
+    }
+
+    /// Guarda o genoma em JSON
+    pub fn save_json<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+
+    /// Lê um genoma guardado com `save_json`
+    pub fn load_json<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+}
+
+/// Parâmetros do algoritmo genético
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvolutionParams {
+    /// Número de genomas por geração
+    pub population_size: usize,
+
+    /// Número de gerações
+    pub generations: usize,
+
+    /// Probabilidade de cada gene sofrer mutação
+    pub mutation_rate: f64,
+
+    /// Desvio padrão da mutação, em fração da amplitude do gene
+    pub mutation_scale: f64,
+
+    /// Genomas sorteados em cada torneio de seleção
+    pub tournament_size: usize,
+
+    /// Melhores genomas copiados sem alteração para a geração seguinte
+    pub elitism: usize,
+
+    /// Threads usados para avaliar cada geração
+    pub threads: usize,
+
+    /// Semente do gerador (a mesma semente e fitness dão o mesmo resultado)
+    pub seed: u64,
+}
+
+impl Default for EvolutionParams {
+    fn default() -> Self {
+        Self {
+            population_size: 20,
+            generations: 10,
+            mutation_rate: 0.2,
+            mutation_scale: 0.1,
+            tournament_size: 3,
+            elitism: 1,
+            threads: 4,
+            seed: 42,
+        }
+    }
+}
+
+/// Resumo de uma geração
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct GenerationStats {
+    pub generation: usize,
+    pub best_fitness: f64,
+    pub mean_fitness: f64,
+}
+
+/// Resultado de uma execução do algoritmo genético
+#[derive(Debug, Clone)]
+pub struct EvolutionResult {
+    /// Melhor genoma encontrado em todas as gerações
+    pub best: Genome,
+
+    /// Fitness de `best`
+    pub best_fitness: f64,
+
+    /// Estatísticas de cada geração
+    pub history: Vec<GenerationStats>,
+}
+
+/// Avalia uma população em paralelo
+///
+/// A população é dividida em blocos contíguos, um por thread; a ordem dos
+/// resultados é a da população.
+pub fn evaluate_parallel<O>(population: &[Genome], objective: &O, threads: usize) -> Vec<f64>
+where
+    O: Objective + ?Sized,
+{
+    map_parallel(population, |g| objective.evaluate(g), threads)
+}
+
+/// Aplica `f` a cada genoma em paralelo, preservando a ordem
+pub(crate) fn map_parallel<T, F>(population: &[Genome], f: F, threads: usize) -> Vec<T>
+where
+    T: Send,
+    F: Fn(&Genome) -> T + Sync,
+{
+    if population.is_empty() {
+        return Vec::new();
+    }
+    let chunk = population.len().div_ceil(threads.max(1));
+    let f = &f;
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = population
+            .chunks(chunk)
+            .map(|genomes| scope.spawn(move || genomes.iter().map(f).collect::<Vec<_>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("avaliação de fitness falhou"))
+            .collect()
+    })
+}
+
+/// Algoritmo genético sobre `Genome`
+pub struct Evolution {
+    params: EvolutionParams,
+    base: Genome,
+}
+
+impl Evolution {
+    /// Cria um otimizador que parte de `Genome::default()`
+    pub fn new(params: EvolutionParams) -> Self {
+        Self {
+            params,
+            base: Genome::default(),
+        }
+    }
+
+    /// Genoma base: fornece os parâmetros fora dos genes e entra na população inicial
+    pub fn with_base(mut self, base: Genome) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// Parâmetros em uso
+    pub fn params(&self) -> &EvolutionParams {
+        &self.params
+    }
+
+    /// Executa o algoritmo genético, maximizando `fitness`
+    ///
+    /// Fitness não finita (NaN) é tratada como a pior possível.
+    pub fn run<O: Objective>(&self, fitness: O) -> EvolutionResult {
+        let p = &self.params;
+        let size = p.population_size.max(2);
+        let mut rng = StdRng::seed_from_u64(p.seed);
+
+        let mut population = vec![self.base];
+        population.extend((1..size).map(|_| Genome::random(&self.base, &mut rng)));
+
+        let mut best = self.base;
+        let mut best_fitness = f64::NEG_INFINITY;
+        let mut history = Vec::with_capacity(p.generations);
+
+        for generation in 0..p.generations.max(1) {
+            let scores: Vec<f64> = evaluate_parallel(&population, &fitness, p.threads)
+                .into_iter()
+                .map(|s| if s.is_nan() { f64::NEG_INFINITY } else { s })
+                .collect();
+
+            let mut ranking: Vec<usize> = (0..size).collect();
+            ranking.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+
+            if scores[ranking[0]] > best_fitness {
+                best_fitness = scores[ranking[0]];
+                best = population[ranking[0]];
+            }
+            let finite: Vec<f64> = scores.iter().copied().filter(|s| s.is_finite()).collect();
+            history.push(GenerationStats {
+                generation,
+                best_fitness: scores[ranking[0]],
+                mean_fitness: finite.iter().sum::<f64>() / finite.len().max(1) as f64,
+            });
+
+            // Próxima geração: elite + filhos de pais escolhidos por torneio
+            let mut next: Vec<Genome> =
+                ranking.iter().take(p.elitism.min(size)).map(|&i| population[i]).collect();
+            while next.len() < size {
+                let a = self.tournament(&scores, &mut rng);
+                let b = self.tournament(&scores, &mut rng);
+                next.push(self.offspring(&population[a], &population[b], &mut rng));
+            }
+            population = next;
+        }
+
+        EvolutionResult {
+            best,
+            best_fitness,
+            history,
+        }
+    }
+
+    /// Índice do melhor de `tournament_size` genomas sorteados
+    fn tournament(&self, scores: &[f64], rng: &mut StdRng) -> usize {
+        let mut winner = rng.gen_range(0..scores.len());
+        for _ in 1..self.params.tournament_size.max(1) {
+            let candidate = rng.gen_range(0..scores.len());
+            if scores[candidate] > scores[winner] {
+                winner = candidate;
+            }
+        }
+        winner
+    }
+
+    /// Crossover uniforme seguido de mutação gaussiana
+    fn offspring(&self, a: &Genome, b: &Genome, rng: &mut StdRng) -> Genome {
+        let (a, b) = (a.to_genes(), b.to_genes());
+        let genes: Vec<f64> = Genome::GENES
+            .iter()
+            .enumerate()
+            .map(|(i, &(_, min, max))| {
+                let mut gene = if rng.gen_bool(0.5) { a[i] } else { b[i] };
+                if rng.gen_bool(self.params.mutation_rate.clamp(0.0, 1.0)) {
+                    // Box-Muller; 1 - u evita ln(0)
+                    let u1: f64 = 1.0 - rng.gen_range(0.0..1.0);
+                    let u2: f64 = rng.gen_range(0.0..1.0);
+                    let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                    gene += z * self.params.mutation_scale * (max - min);
+                }
+                gene
+            })
+            .collect();
+        Genome::from_genes(&self.base, &genes)
+    }
+}
+
+/// Benchmark de deteção de novidade: AUC entre padrões novos e familiares
+///
+/// Igual a `evaluation::novelty_detection_report`, sem o custo metabólico.
+pub fn novelty_detection_fitness(genome: &Genome, seed: u64) -> f64 {
+    novelty_detection_report(genome, seed).performance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_genes_roundtrip_and_clamp() {
+        let genome = Genome::default();
+        assert_eq!(Genome::from_genes(&genome, &genome.to_genes()), genome);
+
+        let clamped = Genome::from_genes(&genome, &[10.0, -1.0]);
+        assert_eq!(clamped.initial_threshold, 2.0);
+        assert_eq!(clamped.config.learning_rate, 0.0);
+        assert_eq!(clamped.config.memory_alpha, genome.config.memory_alpha);
+    }
+
+    #[test]
+    fn test_evolution_maximizes_fitness() {
+        // Ótimo em initial_threshold = 1.0
+        let fitness = |g: &Genome| -(g.initial_threshold - 1.0).abs();
+        let params = EvolutionParams {
+            population_size: 16,
+            generations: 15,
+            threads: 3,
+            ..EvolutionParams::default()
+        };
+        let result = Evolution::new(params).run(fitness);
+
+        assert_eq!(result.history.len(), 15);
+        assert!(result.best_fitness > -0.05, "{}", result.best_fitness);
+        assert!(result.history[14].best_fitness >= result.history[0].best_fitness);
+
+        // Determinístico apesar da avaliação paralela
+        let again = Evolution::new(params).run(fitness);
+        assert_eq!(again.best, result.best);
+    }
+
+    #[test]
+    fn test_best_genome_export() {
+        let path = std::env::temp_dir().join("nenv_best_genome_test.json");
+        let genome = Genome::from_genes(&Genome::default(), &[0.7, 0.02]);
+        genome.save_json(&path).unwrap();
+        assert_eq!(Genome::load_json(&path).unwrap(), genome);
+        std::fs::remove_file(&path).ok();
+
+        let auc = novelty_detection_fitness(&genome, 1);
+        assert!((0.0..=1.0).contains(&auc));
+    }
+}
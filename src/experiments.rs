@@ -1,14 +1,106 @@
-/// Módulo com experimentos para demonstrar funcionalidades do NEN-V
-///
-/// Cada experimento demonstra um aspecto específico da arquitetura
+//! Módulo com experimentos para demonstrar funcionalidades do NEN-V
+//!
+//! Cada experimento demonstra um aspecto específico da arquitetura
 
+use crate::analysis::{preferred_stimuli, topographic_quality, tuning_curves};
+use crate::dashboard::Dashboard;
+use crate::debugger::{Debugger, FireReason};
 use crate::network::{ConnectivityType, Network};
-use crate::visualization::{generate_all_plots, SimulationSnapshot};
+#[cfg(feature = "plots")]
+use crate::plot_worker::PlotWorker;
+use crate::protocol::Protocol;
+use crate::recorder::{AsyncSink, CsvSink, NetworkScalar, NeuronField, Probe, Recorder};
+use crate::snapshot::SimulationSnapshot;
+use crate::stimulus::{Stimulus, StimulusSchedule};
+#[cfg(feature = "plots")]
+use crate::visualization::{
+    generate_all_plots, plot_incoming_weights_comparison, GridAnimation, HeatmapDumper,
+    PlotConfig,
+};
 use std::fs::File;
 use std::io::Write as IoWrite;
+use std::time::Duration;
+
+/// Protocolo do Experimento 1 (ver `protocol`)
+const HABITUATION_PROTOCOL: &str = r#"
+# Estímulo forte (2.0) no centro da grade, para garantir disparo inicial
+phase "habituate" { t = 11..100, target = [55], amplitude = 2.0 }
+"#;
+
+/// Estímulo do Experimento 1: constante no neurónio 55 entre t=10 e t=100
+fn habituation_protocol() -> Protocol {
+    Protocol::parse(HABITUATION_PROTOCOL).expect("HABITUATION_PROTOCOL é válido")
+}
+
+/// Experimento 1: Habituação (Secção 6 do Guia v2)
+///
+/// Testa se um neurónio reduz sua resposta a um estímulo constante
+/// devido ao esgotamento de energia (modulação glial).
+pub fn habituation_experiment() -> std::io::Result<()> {
+    // Configuração da rede
+    const NUM_NEURONS: usize = 100; // Grade 10x10
+    const INHIBITORY_RATIO: f64 = 0.2; // 20% inibitórios
+    const INITIAL_THRESHOLD: f64 = 0.2; // Limiar mais baixo para permitir disparos
+    const MAX_TIME: i64 = 200;
+
+    // Cria a rede
+    let mut network = Network::new(
+        NUM_NEURONS,
+        ConnectivityType::Grid2D,
+        INHIBITORY_RATIO,
+        INITIAL_THRESHOLD,
+    );
+
+    println!("{}\n", network);
+
+    // Neurónio alvo: centro da grade (índice 55 em grade 10x10)
+    const TARGET_NEURON: usize = 55;
+
+    println!("Neurónio alvo: {} (centro da grade)", TARGET_NEURON);
+    println!("Estímulo aplicado: t=10 até t=100\n");
+
+    let schedule = habituation_protocol().schedule();
+
+    // Cria arquivo de log
+    let mut log_file = File::create("habituation_log.csv")?;
+    writeln!(
+        log_file,
+        "time,target_firing,target_energy,total_firing,avg_energy"
+    )?;
+
+    // Loop de simulação
+    for t in 0..MAX_TIME {
+        // Gera inputs externos
+        let external_inputs = schedule.inputs_at(NUM_NEURONS, t);
+
+        // Atualiza a rede
+        network.update(&external_inputs);
+
+        // Coleta dados para análise
+        let target_neuron = &network.neurons[TARGET_NEURON];
+        let target_firing = if target_neuron.is_firing { 1 } else { 0 };
+        let target_energy = target_neuron.glia.energy;
+        let total_firing = network.num_firing();
+        let avg_energy = network.average_energy();
+
+        // Salva no log
+        writeln!(
+            log_file,
+            "{},{},{:.2},{},{}",
+            t, target_firing, target_energy, total_firing, avg_energy
+        )?;
 
-/// Experimento 1: Habituação (implementado em main.rs)
-/// Demonstra redução de resposta a estímulo constante
+        // Imprime progresso a cada 20 passos
+        if t % 20 == 0 {
+            println!(
+                "t={:3} | Alvo: firing={} energia={:5.1} | Rede: firing={:2} energia={:5.1}",
+                t, target_firing, target_energy, total_firing, avg_energy
+            );
+        }
+    }
+
+    Ok(())
+}
 
 /// Experimento 2: Detecção de Novidade com Priority
 ///
@@ -37,6 +129,8 @@ pub fn novelty_detection_experiment() -> std::io::Result<()> {
         INITIAL_THRESHOLD,
     );
 
+    network.enable_spike_recording();
+
     println!("Configuração:");
     println!("  - Neurónios: {}", NUM_NEURONS);
     println!("  - Fase 1 (t=0-100): Padrão A repetido → familiarização");
@@ -70,25 +164,9 @@ pub fn novelty_detection_experiment() -> std::io::Result<()> {
         let neuron_b = &network.neurons[NEURON_B];
 
         // Salva snapshots
-        snapshots_a.push(SimulationSnapshot {
-            time: t,
-            target_firing: neuron_a.is_firing,
-            target_energy: neuron_a.glia.energy,
-            target_priority: neuron_a.glia.priority,
-            total_firing: network.num_firing(),
-            avg_energy: network.average_energy(),
-            alert_level: network.alert_level,
-        });
+        snapshots_a.push(SimulationSnapshot::capture(&network, NEURON_A, t));
 
-        snapshots_b.push(SimulationSnapshot {
-            time: t,
-            target_firing: neuron_b.is_firing,
-            target_energy: neuron_b.glia.energy,
-            target_priority: neuron_b.glia.priority,
-            total_firing: network.num_firing(),
-            avg_energy: network.average_energy(),
-            alert_level: network.alert_level,
-        });
+        snapshots_b.push(SimulationSnapshot::capture(&network, NEURON_B, t));
 
         writeln!(
             log_file,
@@ -120,30 +198,55 @@ pub fn novelty_detection_experiment() -> std::io::Result<()> {
 
     println!("\n✅ Simulação concluída! Dados salvos em 'novelty_detection_log.csv'");
 
-    // Gera visualizações para neurônio A (familiar)
-    println!("📊 Gerando visualizações...");
-    if let Err(e) = generate_all_plots(&snapshots_a, "exp2_neuron_a_familiar") {
-        eprintln!("⚠️  Erro ao gerar gráficos do neurônio A: {}", e);
-    }
+    #[cfg(feature = "plots")]
+    {
+        // Gera visualizações para neurônio A (familiar)
+        println!("📊 Gerando visualizações...");
+        let spikes = network.spike_train();
+        if let Err(e) = generate_all_plots(
+            &snapshots_a,
+            spikes,
+            "exp2_neuron_a_familiar",
+            &PlotConfig::default(),
+        ) {
+            eprintln!("⚠️  Erro ao gerar gráficos do neurônio A: {}", e);
+        }
 
-    // Gera visualizações para neurônio B (novo)
-    if let Err(e) = generate_all_plots(&snapshots_b, "exp2_neuron_b_novel") {
-        eprintln!("⚠️  Erro ao gerar gráficos do neurônio B: {}", e);
-    } else {
-        println!("✅ Gráficos gerados:");
-        println!("   Neurônio A (familiar):");
-        println!("     - exp2_neuron_a_familiar_priority_alert.png");
-        println!("     - exp2_neuron_a_familiar_energy.png");
-        println!("     - exp2_neuron_a_familiar_activity.png");
-        println!("   Neurônio B (novo):");
-        println!("     - exp2_neuron_b_novel_priority_alert.png");
-        println!("     - exp2_neuron_b_novel_energy.png");
-        println!("     - exp2_neuron_b_novel_activity.png");
+        // Gera visualizações para neurônio B (novo)
+        if let Err(e) = generate_all_plots(
+            &snapshots_b,
+            None,
+            "exp2_neuron_b_novel",
+            &PlotConfig::default(),
+        ) {
+            eprintln!("⚠️  Erro ao gerar gráficos do neurônio B: {}", e);
+        } else {
+            println!("✅ Gráficos gerados:");
+            println!("   Neurônio A (familiar):");
+            println!("     - exp2_neuron_a_familiar_priority_alert.png");
+            println!("     - exp2_neuron_a_familiar_energy.png");
+            println!("     - exp2_neuron_a_familiar_activity.png");
+            println!("     - exp2_neuron_a_familiar_novelty.png");
+            println!("     - exp2_neuron_a_familiar_raster.png");
+            println!("   Neurônio B (novo):");
+            println!("     - exp2_neuron_b_novel_priority_alert.png");
+            println!("     - exp2_neuron_b_novel_energy.png");
+            println!("     - exp2_neuron_b_novel_activity.png");
+            println!("     - exp2_neuron_b_novel_novelty.png");
+        }
     }
+    #[cfg(not(feature = "plots"))]
+    report_plots_disabled();
 
     Ok(())
 }
 
+/// Aviso mostrado no lugar dos gráficos quando a feature `plots` está desligada
+#[cfg(not(feature = "plots"))]
+fn report_plots_disabled() {
+    println!("ℹ️  Gráficos omitidos (compilado sem a feature `plots`)");
+}
+
 /// Gera estímulos para experimento de detecção de novidade
 fn generate_novelty_stimulus(
     num_neurons: usize,
@@ -196,6 +299,8 @@ pub fn urgent_event_experiment() -> std::io::Result<()> {
         INITIAL_THRESHOLD,
     );
 
+    network.enable_spike_recording();
+
     println!("Configuração:");
     println!("  - Neurónios: {}", NUM_NEURONS);
     println!("  - t=0-50: Atividade normal");
@@ -232,15 +337,7 @@ pub fn urgent_event_experiment() -> std::io::Result<()> {
         let target = &network.neurons[TARGET];
 
         // Salva snapshot para visualização
-        snapshots.push(SimulationSnapshot {
-            time: t,
-            target_firing: target.is_firing,
-            target_energy: target.glia.energy,
-            target_priority: target.glia.priority,
-            total_firing: network.num_firing(),
-            avg_energy: network.average_energy(),
-            alert_level: network.alert_level,
-        });
+        snapshots.push(SimulationSnapshot::capture(&network, TARGET, t));
 
         writeln!(
             log_file,
@@ -266,20 +363,43 @@ pub fn urgent_event_experiment() -> std::io::Result<()> {
 
     println!("\n✅ Simulação concluída! Dados salvos em 'urgent_event_log.csv'");
 
-    // Gera visualizações
-    println!("📊 Gerando visualizações...");
-    if let Err(e) = generate_all_plots(&snapshots, "exp3_urgent_event") {
-        eprintln!("⚠️  Erro ao gerar gráficos: {}", e);
-    } else {
-        println!("✅ Gráficos gerados:");
-        println!("   - exp3_urgent_event_priority_alert.png");
-        println!("   - exp3_urgent_event_energy.png");
-        println!("   - exp3_urgent_event_activity.png");
+    #[cfg(feature = "plots")]
+    {
+        // Gera visualizações
+        println!("📊 Gerando visualizações...");
+        if let Err(e) = generate_all_plots(
+            &snapshots,
+            network.spike_train(),
+            "exp3_urgent_event",
+            &PlotConfig::default(),
+        ) {
+            eprintln!("⚠️  Erro ao gerar gráficos: {}", e);
+        } else {
+            println!("✅ Gráficos gerados:");
+            println!("   - exp3_urgent_event_priority_alert.png");
+            println!("   - exp3_urgent_event_energy.png");
+            println!("   - exp3_urgent_event_activity.png");
+            println!("   - exp3_urgent_event_novelty.png");
+            println!("   - exp3_urgent_event_raster.png");
+        }
     }
+    #[cfg(not(feature = "plots"))]
+    report_plots_disabled();
 
     Ok(())
 }
 
+/// Protocolo do Experimento 4 (ver `protocol`)
+const INTEGRATION_PROTOCOL: &str = r#"
+phase "familiarize" { t = 0..50, target = [33], amplitude = 2.0 }
+phase "novel" { t = 50..150, target = [66], amplitude = 2.0 }
+"#;
+
+/// Estímulos do Experimento 4: padrão A (neurónio 33) e depois o novo B (66)
+fn integration_protocol() -> Protocol {
+    Protocol::parse(INTEGRATION_PROTOCOL).expect("INTEGRATION_PROTOCOL é válido")
+}
+
 /// Experimento 4: Integração Novelty-Alert (v0.3.0)
 ///
 /// Demonstra o comportamento emergente da integração entre Priority e Alert Level:
@@ -309,6 +429,8 @@ pub fn novelty_alert_integration_experiment() -> std::io::Result<()> {
         INITIAL_THRESHOLD,
     );
 
+    network.enable_spike_recording();
+
     // Configuração da integração (ajustada para rede de 100 neurônios)
     // Threshold baixo para capturar novidade em uma rede pequena
     network.set_novelty_alert_params(0.04, 0.5);
@@ -332,32 +454,17 @@ pub fn novelty_alert_integration_experiment() -> std::io::Result<()> {
 
     let mut snapshots = Vec::new();
 
-    for t in 0..MAX_TIME {
-        let mut external_inputs = vec![0.0; NUM_NEURONS];
-
-        if t < 50 {
-            // Baseline: apenas padrão A
-            external_inputs[NEURON_A] = 2.0;
-        } else {
-            // Evento novo: padrão B (completamente diferente)
-            external_inputs[NEURON_B] = 2.0;
-        }
+    // Baseline com o padrão A; a partir de t=50, o padrão B (completamente diferente)
+    let schedule = integration_protocol().schedule();
 
-        network.update(&external_inputs);
+    for t in 0..MAX_TIME {
+        network.update(&schedule.inputs_at(NUM_NEURONS, t));
 
         let neuron_a = &network.neurons[NEURON_A];
         let neuron_b = &network.neurons[NEURON_B];
 
         // Snapshot para neurônio B (o novo)
-        snapshots.push(SimulationSnapshot {
-            time: t,
-            target_firing: neuron_b.is_firing,
-            target_energy: neuron_b.glia.energy,
-            target_priority: neuron_b.glia.priority,
-            total_firing: network.num_firing(),
-            avg_energy: network.average_energy(),
-            alert_level: network.alert_level,
-        });
+        snapshots.push(SimulationSnapshot::capture(&network, NEURON_B, t));
 
         writeln!(
             log_file,
@@ -391,16 +498,28 @@ pub fn novelty_alert_integration_experiment() -> std::io::Result<()> {
 
     println!("\n✅ Simulação concluída! Dados salvos em 'integration_experiment_log.csv'");
 
-    // Gera visualizações
-    println!("📊 Gerando visualizações...");
-    if let Err(e) = generate_all_plots(&snapshots, "exp4_integration") {
-        eprintln!("⚠️  Erro ao gerar gráficos: {}", e);
-    } else {
-        println!("✅ Gráficos gerados:");
-        println!("   - exp4_integration_priority_alert.png (KEY: mostra acoplamento)");
-        println!("   - exp4_integration_energy.png");
-        println!("   - exp4_integration_activity.png");
+    #[cfg(feature = "plots")]
+    {
+        // Gera visualizações
+        println!("📊 Gerando visualizações...");
+        if let Err(e) = generate_all_plots(
+            &snapshots,
+            network.spike_train(),
+            "exp4_integration",
+            &PlotConfig::default(),
+        ) {
+            eprintln!("⚠️  Erro ao gerar gráficos: {}", e);
+        } else {
+            println!("✅ Gráficos gerados:");
+            println!("   - exp4_integration_priority_alert.png (KEY: mostra acoplamento)");
+            println!("   - exp4_integration_energy.png");
+            println!("   - exp4_integration_activity.png");
+            println!("   - exp4_integration_novelty.png");
+            println!("   - exp4_integration_raster.png");
+        }
     }
+    #[cfg(not(feature = "plots"))]
+    report_plots_disabled();
 
     println!("\n📈 Análise Esperada:");
     println!("   1. t<50: priority≈1.0, novelty≈0.0, alert≈0.0 (baseline)");
@@ -411,3 +530,1545 @@ pub fn novelty_alert_integration_experiment() -> std::io::Result<()> {
 
     Ok(())
 }
+
+/// Resumo de um protocolo de habituação seguido de re-teste
+#[derive(Debug, Clone)]
+pub struct RetestSummary {
+    /// Taxa de disparo do alvo na janela final da habituação
+    pub habituated_rate: f64,
+
+    /// Taxa de disparo do alvo na janela de re-teste
+    pub retest_rate: f64,
+
+    /// Taxa de re-teste da execução de controlo (mesma semente, sem o estímulo interposto)
+    pub control_retest_rate: f64,
+}
+
+impl RetestSummary {
+    /// Diferença entre a resposta no re-teste e a resposta habituada
+    ///
+    /// Valores positivos indicam recuperação da resposta (desabituação
+    /// ou sensibilização).
+    pub fn recovery_index(&self) -> f64 {
+        self.retest_rate - self.habituated_rate
+    }
+
+    /// Diferença entre o re-teste e o re-teste do controlo
+    ///
+    /// Isola o efeito do estímulo interposto da recuperação espontânea
+    /// durante o intervalo sem estímulos.
+    pub fn control_difference(&self) -> f64 {
+        self.retest_rate - self.control_retest_rate
+    }
+}
+
+/// Tamanho da rede (grade 10x10) dos protocolos de re-teste
+const RETEST_NEURONS: usize = 100;
+
+/// Semente partilhada pela execução principal e pelo controlo
+const RETEST_SEED: u64 = 5;
+
+/// Rede dos protocolos de re-teste (idêntica em todas as execuções)
+fn retest_network() -> Network {
+    Network::new_seeded(RETEST_NEURONS, ConnectivityType::Grid2D, 0.2, 0.2, RETEST_SEED)
+}
+
+/// Aplica um protocolo e conta os disparos do alvo em duas janelas
+///
+/// # Retorna
+/// Taxas de disparo `(habituada, re-teste)`
+fn retest_rates(
+    network: &mut Network,
+    schedule: &StimulusSchedule,
+    target: usize,
+    habituated_window: (i64, i64),
+    retest_window: (i64, i64),
+    mut on_step: impl FnMut(&Network, i64) -> std::io::Result<()>,
+) -> std::io::Result<(f64, f64)> {
+    let mut habituated_spikes = 0;
+    let mut retest_spikes = 0;
+
+    for t in 0..schedule.duration() {
+        let external_inputs = schedule.inputs_at(network.num_neurons(), t);
+        network.update(&external_inputs);
+
+        if network.neurons[target].is_firing {
+            if t >= habituated_window.0 && t < habituated_window.1 {
+                habituated_spikes += 1;
+            }
+            if t >= retest_window.0 && t < retest_window.1 {
+                retest_spikes += 1;
+            }
+        }
+
+        on_step(network, t)?;
+    }
+
+    let window_len = |w: (i64, i64)| (w.1 - w.0).max(1) as f64;
+
+    Ok((
+        habituated_spikes as f64 / window_len(habituated_window),
+        retest_spikes as f64 / window_len(retest_window),
+    ))
+}
+
+/// Executa um protocolo de re-teste e mede a resposta do alvo em duas janelas
+///
+/// O controlo corre na mesma rede semeada, sem a fase interposta, e só
+/// contribui com a taxa de re-teste (não grava log nem gráficos).
+///
+/// # Argumentos
+/// * `protocol` - Protocolo de estímulos a aplicar
+/// * `interposed` - Fase retirada no controlo (o estímulo interposto)
+/// * `target` - Neurónio cuja resposta é medida
+/// * `habituated_window` - Janela `[início, fim)` do final da habituação
+/// * `retest_window` - Janela `[início, fim)` do re-teste
+/// * `prefix` - Prefixo para o log CSV e os gráficos
+fn run_retest_protocol(
+    protocol: &Protocol,
+    interposed: &str,
+    target: usize,
+    habituated_window: (i64, i64),
+    retest_window: (i64, i64),
+    prefix: &str,
+) -> std::io::Result<RetestSummary> {
+    let mut network = retest_network();
+
+    network.enable_spike_recording();
+
+    let log_path = format!("{}_log.csv", prefix);
+    let csv_sink = AsyncSink::new(CsvSink::create(&log_path)?);
+    let mut recorder = Recorder::new(1).with_sink(Box::new(csv_sink));
+    recorder.add_named_probe("target_firing", Probe::neuron(target, NeuronField::Firing));
+    recorder.add_named_probe("target_energy", Probe::neuron(target, NeuronField::Energy));
+    recorder.add_named_probe("target_priority", Probe::neuron(target, NeuronField::Priority));
+    recorder.add_probe(Probe::Network(NetworkScalar::NumFiring));
+    recorder.add_probe(Probe::Network(NetworkScalar::AverageEnergy));
+    recorder.add_probe(Probe::Network(NetworkScalar::AlertLevel));
+
+    #[cfg(feature = "plots")]
+    let initial_weights = network.neurons[target].dendritoma.weights.clone();
+
+    let mut snapshots = Vec::new();
+
+    let (habituated_rate, retest_rate) = retest_rates(
+        &mut network,
+        &protocol.schedule(),
+        target,
+        habituated_window,
+        retest_window,
+        |network, t| {
+            snapshots.push(SimulationSnapshot::capture(network, target, t));
+            recorder.record(network)
+        },
+    )?;
+
+    recorder.finish()?;
+    println!("✅ Dados salvos em '{}'", log_path);
+
+    #[cfg(feature = "plots")]
+    {
+        if let Err(e) = generate_all_plots(
+            &snapshots,
+            network.spike_train(),
+            prefix,
+            &PlotConfig::default(),
+        ) {
+            eprintln!("⚠️  Erro ao gerar gráficos: {}", e);
+        }
+        if let Err(e) = plot_incoming_weights_comparison(
+            &initial_weights,
+            &network.neurons[target].dendritoma.weights,
+            network.grid_width,
+            &format!("{}_target_weights.png", prefix),
+            &format!("{} - Pesos de entrada do neurónio {}", prefix, target),
+            &PlotConfig::default(),
+        ) {
+            eprintln!("⚠️  Erro ao gerar gráfico de pesos: {}", e);
+        }
+    }
+    #[cfg(not(feature = "plots"))]
+    report_plots_disabled();
+
+    let (_, control_retest_rate) = retest_rates(
+        &mut retest_network(),
+        &protocol.without_phase(interposed).schedule(),
+        target,
+        habituated_window,
+        retest_window,
+        |_, _| Ok(()),
+    )?;
+
+    Ok(RetestSummary {
+        habituated_rate,
+        retest_rate,
+        control_retest_rate,
+    })
+}
+
+/// Protocolo do Experimento 5 (ver `protocol`)
+const DISHABITUATION_PROTOCOL: &str = r#"
+phase "habituate" { t = 10..100, target = [55], amplitude = 2.0 }
+phase "novel" { t = 100..110, target = [22], amplitude = 4.0 }
+phase "retest" { t = 120..170, target = [55], amplitude = 2.0 }
+"#;
+
+/// Estímulos do Experimento 5: A (neurónio 55), o novo B (22) e A outra vez
+fn dishabituation_protocol() -> Protocol {
+    Protocol::parse(DISHABITUATION_PROTOCOL).expect("DISHABITUATION_PROTOCOL é válido")
+}
+
+/// Experimento 5: Desabituação
+///
+/// Seguimento canónico do experimento de habituação: após a resposta ao
+/// estímulo A ter diminuído, um estímulo forte e novo (B) é interposto e
+/// A é re-testado.
+///
+/// Protocolo:
+/// 1. Habituação (t=10-100): Estímulo A constante no neurónio alvo
+/// 2. Estímulo novo (t=100-110): Estímulo forte B num neurónio distante
+/// 3. Re-teste (t=120-170): Estímulo A novamente
+///
+/// O controlo repete o protocolo com a mesma semente, sem B, para separar
+/// a desabituação da recuperação espontânea entre t=100 e t=120.
+///
+/// Comportamento esperado:
+/// - Resposta a A no re-teste superior à resposta no final da habituação
+/// - Re-teste acima do re-teste do controlo
+pub fn dishabituation_experiment() -> std::io::Result<RetestSummary> {
+    println!("=== Experimento 5: Desabituação ===\n");
+
+    const TARGET: usize = 55;
+    const NOVEL: usize = 22;
+
+    let protocol = dishabituation_protocol();
+
+    println!("Configuração:");
+    println!("  - t=10-100: Estímulo A (neurónio {}) → habituação", TARGET);
+    println!("  - t=100-110: Estímulo forte B (neurónio {}) → novidade", NOVEL);
+    println!("  - t=120-170: Re-teste do estímulo A");
+    println!("  - Controlo: mesma semente, sem o estímulo B\n");
+
+    let summary = run_retest_protocol(
+        &protocol,
+        "novel",
+        TARGET,
+        (70, 100),
+        (120, 150),
+        "exp5_dishabituation",
+    )?;
+
+    println!("\n📈 Resultados:");
+    println!("   Taxa habituada (t=70-100): {:.3}", summary.habituated_rate);
+    println!("   Taxa no re-teste (t=120-150): {:.3}", summary.retest_rate);
+    println!("   Índice de desabituação: {:+.3}", summary.recovery_index());
+    println!("   Re-teste do controlo (sem B): {:.3}", summary.control_retest_rate);
+    println!("   Diferença face ao controlo: {:+.3}", summary.control_difference());
+
+    Ok(summary)
+}
+
+/// Protocolo do Experimento 6 (ver `protocol`)
+const SENSITIZATION_PROTOCOL: &str = r#"
+phase "habituate" { t = 10..100, target = [55], amplitude = 2.0 }
+# Linha 2 da grade 10x10 (neurónios 20-29)
+phase "noxious" { t = 100..105, target = [20..30], amplitude = 8.0 }
+phase "retest" { t = 120..170, target = [55], amplitude = 2.0 }
+"#;
+
+/// Estímulos do Experimento 6: A (neurónio 55), o nocivo na linha 2 e A outra vez
+fn sensitization_protocol() -> Protocol {
+    Protocol::parse(SENSITIZATION_PROTOCOL).expect("SENSITIZATION_PROTOCOL é válido")
+}
+
+/// Experimento 6: Sensibilização
+///
+/// Variante do protocolo de desabituação em que o estímulo interposto tem
+/// intensidade nociva e atinge uma região inteira da grade.
+///
+/// Protocolo:
+/// 1. Habituação (t=10-100): Estímulo A constante no neurónio alvo
+/// 2. Estímulo nocivo (t=100-105): Input de alta intensidade na linha 2 da grade
+/// 3. Re-teste (t=120-170): Estímulo A novamente
+///
+/// Comportamento esperado:
+/// - Novidade alta em toda a região → alert_level ativado
+/// - Resposta a A no re-teste superior à resposta habituada e ao controlo
+///   (mesma semente, sem o estímulo nocivo)
+pub fn sensitization_experiment() -> std::io::Result<RetestSummary> {
+    println!("=== Experimento 6: Sensibilização ===\n");
+
+    const TARGET: usize = 55;
+
+    let protocol = sensitization_protocol();
+    let noxious = protocol.phase("noxious").map_or(0.0, |p| p.stimulus.amplitude);
+
+    println!("Configuração:");
+    println!("  - t=10-100: Estímulo A (neurónio {}) → habituação", TARGET);
+    println!("  - t=100-105: Estímulo nocivo (amplitude {}) na linha 2", noxious);
+    println!("  - t=120-170: Re-teste do estímulo A");
+    println!("  - Controlo: mesma semente, sem o estímulo nocivo\n");
+
+    let summary = run_retest_protocol(
+        &protocol,
+        "noxious",
+        TARGET,
+        (70, 100),
+        (120, 150),
+        "exp6_sensitization",
+    )?;
+
+    println!("\n📈 Resultados:");
+    println!("   Taxa habituada (t=70-100): {:.3}", summary.habituated_rate);
+    println!("   Taxa no re-teste (t=120-150): {:.3}", summary.retest_rate);
+    println!("   Índice de sensibilização: {:+.3}", summary.recovery_index());
+    println!("   Re-teste do controlo (sem estímulo nocivo): {:.3}", summary.control_retest_rate);
+    println!("   Diferença face ao controlo: {:+.3}", summary.control_difference());
+
+    Ok(summary)
+}
+
+/// Métricas de uma fase do experimento de lesão
+#[derive(Debug, Clone, Default)]
+pub struct PhaseMetrics {
+    /// Disparos por neurónio por passo na região a jusante da lesão
+    pub downstream_rate: f64,
+
+    /// Média de neurónios a disparar por passo em toda a rede
+    pub mean_total_firing: f64,
+
+    /// Energia média da rede ao longo da fase
+    pub mean_energy: f64,
+}
+
+/// Resumo do experimento de lesão (antes, logo após e tardiamente após a lesão)
+#[derive(Debug, Clone)]
+pub struct LesionSummary {
+    pub pre: PhaseMetrics,
+    pub post_early: PhaseMetrics,
+    pub post_late: PhaseMetrics,
+}
+
+/// Experimento 7: Lesão e Recuperação Funcional
+///
+/// Estuda como a atividade originada numa região da grade chega a uma
+/// região distante antes e depois de uma faixa intermédia ser lesionada.
+///
+/// Protocolo:
+/// 1. Fase pré-lesão (t=0-150): Pulsos periódicos na região de entrada (coluna 1)
+/// 2. Lesão (t=150): Faixa central (colunas 4-5, linhas 2-7) silenciada e desconectada
+/// 3. Fase pós-lesão (t=150-300): Mesmos pulsos; atividade só pode contornar a lesão
+///
+/// Comportamento esperado:
+/// - Queda da atividade a jusante logo após a lesão
+/// - Recuperação parcial tardia através das linhas intactas (rerouting)
+pub fn lesion_experiment() -> std::io::Result<LesionSummary> {
+    println!("=== Experimento 7: Lesão e Recuperação Funcional ===\n");
+
+    const NUM_NEURONS: usize = 100;
+    const INITIAL_THRESHOLD: f64 = 0.2;
+    const MAX_TIME: i64 = 300;
+    const LESION_TIME: i64 = 150;
+
+    let mut network = Network::new(
+        NUM_NEURONS,
+        ConnectivityType::Grid2D,
+        0.2,
+        INITIAL_THRESHOLD,
+    );
+
+    network.enable_spike_recording();
+
+    let region = |rows: std::ops::Range<usize>, cols: std::ops::Range<usize>| -> Vec<usize> {
+        rows.flat_map(|r| cols.clone().map(move |c| (r, c)))
+            .filter_map(|(r, c)| network.coords_to_index(r, c))
+            .collect()
+    };
+
+    let input_region = region(3..7, 1..2);
+    let lesion_region = region(2..8, 4..6);
+    let downstream_region = region(3..7, 7..9);
+
+    // Pulsos de 5 passos a cada 20 passos na região de entrada
+    let mut schedule = StimulusSchedule::new();
+    for start in (0..MAX_TIME).step_by(20) {
+        schedule.add(Stimulus::new(input_region.clone(), 2.0, start, start + 5));
+    }
+
+    println!("Configuração:");
+    println!("  - Entrada: {:?}", input_region);
+    println!("  - Lesão em t={}: {} neurónios", LESION_TIME, lesion_region.len());
+    println!("  - Região a jusante: {:?}\n", downstream_region);
+
+    let mut log_file = File::create("lesion_experiment_log.csv")?;
+    writeln!(
+        log_file,
+        "time,downstream_firing,total_firing,avg_energy,avg_novelty,alert_level"
+    )?;
+
+    // Os gráficos são desenhados num thread à parte enquanto a simulação corre
+    #[cfg(feature = "plots")]
+    let mut plots = PlotWorker::new();
+
+    // Mapas de energia da grade a cada 75 passos (escala fixa para comparação)
+    #[cfg(feature = "plots")]
+    let energy_maps =
+        HeatmapDumper::new(NeuronField::Energy, 75, "exp7_lesion").with_range(0.0, 100.0);
+
+    // Animação da atividade de disparo na grade (um quadro a cada 5 passos)
+    #[cfg(feature = "plots")]
+    let mut firing_animation = GridAnimation::new(NeuronField::Firing, 5).with_range(0.0, 1.0);
+
+    let mut snapshots = Vec::new();
+    let mut phases = [PhaseMetrics::default(), PhaseMetrics::default(), PhaseMetrics::default()];
+    let mut phase_steps = [0usize; 3];
+
+    for t in 0..MAX_TIME {
+        if t == LESION_TIME {
+            println!("🔪 t={}: Lesão aplicada", t);
+            network.lesion(&lesion_region, true);
+        }
+
+        let external_inputs = schedule.inputs_at(NUM_NEURONS, t);
+        network.update(&external_inputs);
+
+        #[cfg(feature = "plots")]
+        {
+            energy_maps.dump_in(&network, &mut plots);
+            firing_animation.capture(&network);
+        }
+
+        let downstream_firing = downstream_region
+            .iter()
+            .filter(|&&i| network.neurons[i].is_firing)
+            .count();
+
+        // Fases: pré (50-150), pós-imediata (150-225), pós-tardia (225-300)
+        let phase = match t {
+            50..=149 => Some(0),
+            150..=224 => Some(1),
+            225.. => Some(2),
+            _ => None,
+        };
+
+        if let Some(p) = phase {
+            phases[p].downstream_rate += downstream_firing as f64 / downstream_region.len() as f64;
+            phases[p].mean_total_firing += network.num_firing() as f64;
+            phases[p].mean_energy += network.average_energy();
+            phase_steps[p] += 1;
+        }
+
+        snapshots.push(SimulationSnapshot {
+            time: t,
+            target_firing: downstream_firing > 0,
+            target_energy: network.neurons[downstream_region[0]].glia.energy,
+            target_priority: network.neurons[downstream_region[0]].glia.priority,
+            total_firing: network.num_firing(),
+            avg_energy: network.average_energy(),
+            alert_level: network.alert_level,
+            avg_novelty: network.average_novelty(),
+        });
+
+        writeln!(
+            log_file,
+            "{},{},{},{:.2},{:.3},{:.3}",
+            t,
+            downstream_firing,
+            network.num_firing(),
+            network.average_energy(),
+            network.average_novelty(),
+            network.alert_level
+        )?;
+    }
+
+    for (metrics, &steps) in phases.iter_mut().zip(phase_steps.iter()) {
+        let steps = steps.max(1) as f64;
+        metrics.downstream_rate /= steps;
+        metrics.mean_total_firing /= steps;
+        metrics.mean_energy /= steps;
+    }
+
+    println!("\n✅ Simulação concluída! Dados salvos em 'lesion_experiment_log.csv'");
+
+    #[cfg(feature = "plots")]
+    {
+        plots.submit_all_plots(
+            snapshots,
+            network.spike_train().cloned(),
+            "exp7_lesion",
+            &PlotConfig::default(),
+        );
+        firing_animation.save_gif_in(&mut plots, "exp7_lesion_firing.gif", 200);
+        for failure in plots.finish().failures {
+            eprintln!("⚠️  Erro ao gerar {}: {}", failure.name, failure.message);
+        }
+    }
+    #[cfg(not(feature = "plots"))]
+    report_plots_disabled();
+
+    let [pre, post_early, post_late] = phases;
+
+    println!("\n📈 Métricas por fase (taxa a jusante | disparos totais | energia):");
+    for (label, metrics) in [
+        ("Pré-lesão    ", &pre),
+        ("Pós imediata ", &post_early),
+        ("Pós tardia   ", &post_late),
+    ] {
+        println!(
+            "   {} {:.4} | {:5.2} | {:5.1}",
+            label, metrics.downstream_rate, metrics.mean_total_firing, metrics.mean_energy
+        );
+    }
+
+    Ok(LesionSummary {
+        pre,
+        post_early,
+        post_late,
+    })
+}
+
+/// Resumo do experimento de mapa topográfico
+#[derive(Debug, Clone)]
+pub struct TopographicSummary {
+    /// Qualidade do mapa (`analysis::topographic_quality`) antes do treino
+    pub quality_before: f64,
+
+    /// Qualidade do mapa após o treino
+    pub quality_after: f64,
+
+    /// Estímulo preferido de cada neurónio após o treino
+    pub preferred: Vec<Option<usize>>,
+}
+
+/// Barra vertical gaussiana centrada na coluna `position` da grade
+///
+/// Cada linha da barra soma `amplitude`, para que as posições junto às
+/// bordas não recebam menos input total.
+fn bar_stimulus(position: usize, width: usize, num_neurons: usize, amplitude: f64) -> Vec<f64> {
+    let profile: Vec<f64> = (0..width)
+        .map(|c| {
+            let d = c as f64 - position as f64;
+            (-d * d / 2.0).exp()
+        })
+        .collect();
+    let total: f64 = profile.iter().sum();
+    (0..num_neurons).map(|i| amplitude * profile[i % width] / total).collect()
+}
+
+/// Curvas de sintonia dos campos recetivos
+///
+/// A resposta de cada neurónio a um estímulo é o potencial que o estímulo
+/// produz através dos pesos do Dendritoma (sem dinâmica nem aprendizagem),
+/// o que evita empates entre neurónios que disparam uma vez por período
+/// refratário.
+fn receptive_field_tuning(network: &Network, stimuli: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let labels: Vec<usize> = (0..stimuli.len()).collect();
+    let responses: Vec<Vec<f64>> = stimuli
+        .iter()
+        .map(|stimulus| {
+            network.neurons.iter().map(|n| n.dendritoma.weighted_sum(stimulus)).collect()
+        })
+        .collect();
+    tuning_curves(&labels, &responses, stimuli.len())
+}
+
+/// Experimento 8: Formação de Mapa Topográfico
+///
+/// Apresenta barras verticais em posições aleatórias da grade e verifica
+/// se a aprendizagem Hebbiana com conectividade lateral organiza as
+/// preferências dos neurónios num mapa (vizinhos preferem posições
+/// próximas), como num mapa auto-organizado.
+///
+/// Protocolo:
+/// 1. Curvas de sintonia iniciais dos campos recetivos
+/// 2. Treino: 300 apresentações de 5 passos, barra numa posição aleatória
+/// 3. Curvas de sintonia finais e qualidade do mapa antes/depois
+///
+/// Comportamento esperado:
+/// - `quality_after` maior que `quality_before`
+pub fn topographic_map_experiment() -> std::io::Result<TopographicSummary> {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    println!("=== Experimento 8: Formação de Mapa Topográfico ===\n");
+
+    const NUM_NEURONS: usize = 100;
+    const WIDTH: usize = 10;
+    const PRESENTATIONS: usize = 300;
+    const STEPS: usize = 5;
+
+    const INITIAL_THRESHOLD: f64 = 1.0;
+
+    let mut network =
+        Network::new_seeded(NUM_NEURONS, ConnectivityType::Grid2D, 0.2, INITIAL_THRESHOLD, 8);
+    let stimuli: Vec<Vec<f64>> =
+        (0..WIDTH).map(|p| bar_stimulus(p, WIDTH, NUM_NEURONS, 2.0)).collect();
+
+    println!("Configuração:");
+    println!("  - {} posições de barra (uma por coluna da grade)", WIDTH);
+    println!("  - {} apresentações de {} passos\n", PRESENTATIONS, STEPS);
+
+    let before = preferred_stimuli(&receptive_field_tuning(&network, &stimuli));
+    let quality_before = topographic_quality(&before, WIDTH);
+
+    let mut rng = StdRng::seed_from_u64(8);
+    let silence = vec![0.0; NUM_NEURONS];
+    for _ in 0..PRESENTATIONS {
+        let stimulus = &stimuli[rng.gen_range(0..WIDTH)];
+        for _ in 0..STEPS {
+            network.update(stimulus);
+        }
+        for _ in 0..STEPS {
+            network.update(&silence);
+        }
+    }
+
+    let preferred = preferred_stimuli(&receptive_field_tuning(&network, &stimuli));
+    let quality_after = topographic_quality(&preferred, WIDTH);
+
+    let mut map_file = File::create("topographic_map.csv")?;
+    writeln!(map_file, "neuron,row,col,preferred_before,preferred_after")?;
+    for (i, (b, a)) in before.iter().zip(&preferred).enumerate() {
+        let show = |p: &Option<usize>| p.map_or(String::new(), |p| p.to_string());
+        writeln!(map_file, "{},{},{},{},{}", i, i / WIDTH, i % WIDTH, show(b), show(a))?;
+    }
+
+    println!("🗺️  Posição preferida por neurónio após o treino:");
+    for row in preferred.chunks(WIDTH) {
+        let cells: Vec<String> =
+            row.iter().map(|p| p.map_or("·".to_string(), |p| p.to_string())).collect();
+        println!("   {}", cells.join(" "));
+    }
+
+    println!("\n📈 Resultados:");
+    println!("   Qualidade do mapa antes do treino: {:+.3}", quality_before);
+    println!("   Qualidade do mapa após o treino:   {:+.3}", quality_after);
+    println!("   Preferências salvas em 'topographic_map.csv'");
+
+    Ok(TopographicSummary {
+        quality_before,
+        quality_after,
+        preferred,
+    })
+}
+
+/// Novidade média de cada região num teste multimodal
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RegionNovelty {
+    pub sound: f64,
+    pub bridge: f64,
+    pub image: f64,
+}
+
+/// Resumo do experimento de fusão multimodal
+#[derive(Debug, Clone)]
+pub struct CrossModalSummary {
+    /// Par som-imagem treinado
+    pub familiar_pair: RegionNovelty,
+
+    /// Som familiar com uma imagem nunca vista
+    pub novel_image: RegionNovelty,
+
+    /// Som e imagem familiares, mas de pares diferentes
+    pub mismatched_pair: RegionNovelty,
+}
+
+/// Experimento 9: Novidade Cruzada entre Modalidades
+///
+/// Dois fluxos (som e imagem) ocupam as metades esquerda e direita da
+/// grade, ligadas apenas por uma ponte de 2 colunas. A rede é treinada com
+/// três pares som-imagem fixos e depois testada com o par familiar, com o
+/// som familiar acompanhado de uma imagem nova, e com som e imagem
+/// familiares trocados entre pares.
+///
+/// Protocolo:
+/// 1. Treino: 150 apresentações de 5 passos de pares aleatórios
+/// 2. Teste: cada condição é apresentada 1 passo após um par familiar
+///    (30 ensaios por condição), medindo a novidade de cada região
+///
+/// Comportamento esperado:
+/// - A imagem nova eleva a novidade acima do par familiar
+/// - A troca de pares (novidade puramente relacional, sem nenhum padrão
+///   novo) também eleva a novidade, em todas as regiões
+pub fn multimodal_fusion_experiment() -> std::io::Result<CrossModalSummary> {
+    use crate::multimodal::BRIDGE_GROUP;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    println!("=== Experimento 9: Novidade Cruzada entre Modalidades ===\n");
+
+    const NUM_NEURONS: usize = 100;
+    const INITIAL_THRESHOLD: f64 = 0.5;
+    const PAIRS: usize = 3;
+    const PRESENTATIONS: usize = 150;
+    const STEPS: usize = 5;
+    const TRIALS: usize = 30;
+
+    let mut network =
+        Network::new_seeded(NUM_NEURONS, ConnectivityType::Grid2D, 0.2, INITIAL_THRESHOLD, 9);
+    let layout = network.define_modalities("som", "imagem", 2);
+
+    let mut rng = StdRng::seed_from_u64(9);
+    let mut random_pattern = |len: usize| -> Vec<f64> {
+        (0..len).map(|_| if rng.gen_bool(0.3) { 1.0 } else { 0.0 }).collect()
+    };
+    let sounds: Vec<Vec<f64>> = (0..PAIRS).map(|_| random_pattern(layout.first.len())).collect();
+    let images: Vec<Vec<f64>> = (0..PAIRS).map(|_| random_pattern(layout.second.len())).collect();
+    let novel_images: Vec<Vec<f64>> =
+        (0..TRIALS).map(|_| random_pattern(layout.second.len())).collect();
+
+    println!("Configuração:");
+    println!(
+        "  - Som: {} neurónios | ponte: {} | imagem: {}",
+        layout.first.len(),
+        layout.bridge.len(),
+        layout.second.len()
+    );
+    println!("  - {} pares treinados, {} apresentações\n", PAIRS, PRESENTATIONS);
+
+    let present = |network: &mut Network, sound: &[f64], image: &[f64], steps: usize| {
+        for _ in 0..steps {
+            network.stimulate_pattern("som", sound).expect("porta definida");
+            network.stimulate_pattern("imagem", image).expect("porta definida");
+            network.update(&[]);
+        }
+    };
+    let regions = |network: &Network| RegionNovelty {
+        sound: network.group_novelty("som").unwrap_or(0.0),
+        bridge: network.group_novelty(BRIDGE_GROUP).unwrap_or(0.0),
+        image: network.group_novelty("imagem").unwrap_or(0.0),
+    };
+
+    let mut rng = StdRng::seed_from_u64(90);
+    for _ in 0..PRESENTATIONS {
+        let k = rng.gen_range(0..PAIRS);
+        present(&mut network, &sounds[k], &images[k], STEPS);
+    }
+
+    let mut totals = [RegionNovelty::default(); 3];
+    for novel_image in &novel_images {
+        let k = rng.gen_range(0..PAIRS);
+        let other = (k + 1 + rng.gen_range(0..PAIRS - 1)) % PAIRS;
+        let third = (0..PAIRS).find(|&p| p != k && p != other).unwrap_or(k);
+        let probes: [(&[f64], &[f64]); 3] = [
+            (&sounds[other], &images[other]),
+            (&sounds[other], novel_image),
+            (&sounds[other], &images[third]),
+        ];
+        for (total, (sound, image)) in totals.iter_mut().zip(probes) {
+            present(&mut network, &sounds[k], &images[k], STEPS);
+            present(&mut network, sound, image, 1);
+            let novelty = regions(&network);
+            total.sound += novelty.sound / TRIALS as f64;
+            total.bridge += novelty.bridge / TRIALS as f64;
+            total.image += novelty.image / TRIALS as f64;
+        }
+    }
+    let [familiar_pair, novel_image, mismatched_pair] = totals;
+
+    println!("📈 Novidade média por região (som | ponte | imagem):");
+    for (label, novelty) in [
+        ("Par familiar         ", &familiar_pair),
+        ("Imagem nova          ", &novel_image),
+        ("Pares trocados       ", &mismatched_pair),
+    ] {
+        println!(
+            "   {} {:.4} | {:.4} | {:.4}",
+            label, novelty.sound, novelty.bridge, novelty.image
+        );
+    }
+
+    Ok(CrossModalSummary {
+        familiar_pair,
+        novel_image,
+        mismatched_pair,
+    })
+}
+
+/// Métricas de deteção de novidade num nível de ruído
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DetectionMetrics {
+    /// Desvio padrão do ruído de fundo
+    pub noise_std: f64,
+
+    /// Razão sinal-ruído (amplitude dos padrões / desvio do ruído)
+    pub snr: f64,
+
+    /// Fração de padrões novos detetados
+    pub hit_rate: f64,
+
+    /// Fração de padrões familiares detetados (falsos alarmes)
+    pub false_alarm_rate: f64,
+
+    /// Sensibilidade `stats::d_prime`
+    pub d_prime: f64,
+}
+
+/// Resumo do experimento de robustez ao ruído
+#[derive(Debug, Clone)]
+pub struct NoiseRobustnessSummary {
+    /// Métricas por nível de ruído, do menor para o maior
+    pub levels: Vec<DetectionMetrics>,
+}
+
+/// Experimento 10: Robustez da Deteção de Novidade ao Ruído
+///
+/// Repete o teste de deteção de novidade com ruído gaussiano de fundo
+/// crescente em todos os neurónios. Em cada nível uma rede nova (mesma
+/// semente) é familiarizada com um padrão e depois testada com ensaios
+/// alternados do padrão familiar e de padrões novos. Um `EventDetector`
+/// decide se cada ensaio produziu novidade acima do limiar, fixado em cada
+/// ensaio na novidade de base mais uma margem (a novidade de base sobe com
+/// o ruído).
+///
+/// Protocolo (por nível de ruído):
+/// 1. Familiarização: 80 passos do padrão familiar com ruído
+/// 2. Teste: 30 ensaios por classe de 3 passos, cada um após 20 passos do
+///    padrão familiar
+///
+/// Comportamento esperado:
+/// - Sem ruído, d' alto (acertos frequentes, poucos falsos alarmes)
+/// - d' diminui à medida que a SNR baixa
+pub fn noise_robustness_experiment() -> std::io::Result<NoiseRobustnessSummary> {
+    use crate::events::{EventDetector, SimulationEvent};
+    use crate::stats::d_prime;
+    use crate::stimulus::add_gaussian_noise;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    println!("=== Experimento 10: Robustez da Deteção de Novidade ao Ruído ===\n");
+
+    const NUM_NEURONS: usize = 100;
+    const INITIAL_THRESHOLD: f64 = 0.2;
+    const AMPLITUDE: f64 = 2.0;
+    const FAMILIARIZATION: usize = 80;
+    const TRIALS: usize = 30;
+    const PRESENTATION: usize = 3;
+    const GAP: usize = 20;
+    const DETECTION_MARGIN: f64 = 0.15;
+    const NOISE_LEVELS: [f64; 6] = [0.0, 0.2, 0.4, 0.8, 1.6, 3.2];
+
+    let mut rng = StdRng::seed_from_u64(10);
+    let mut random_pattern = || -> Vec<f64> {
+        (0..NUM_NEURONS).map(|_| if rng.gen_bool(0.2) { AMPLITUDE } else { 0.0 }).collect()
+    };
+    let familiar = random_pattern();
+    let novel: Vec<Vec<f64>> = (0..TRIALS).map(|_| random_pattern()).collect();
+
+    println!("Configuração:");
+    println!("  - Padrões com 20% dos neurónios a {:.1}", AMPLITUDE);
+    println!("  - Níveis de ruído (desvio padrão): {:?}", NOISE_LEVELS);
+    println!("  - {} ensaios por classe\n", TRIALS);
+
+    let mut levels = Vec::new();
+    for noise_std in NOISE_LEVELS {
+        let mut network =
+            Network::new_seeded(NUM_NEURONS, ConnectivityType::Grid2D, 0.2, INITIAL_THRESHOLD, 10);
+        network.set_novelty_alert_params(0.04, 0.5);
+        let mut noise_rng = StdRng::seed_from_u64(100);
+        let mut step = |network: &mut Network, pattern: &[f64]| {
+            let mut inputs = pattern.to_vec();
+            add_gaussian_noise(&mut inputs, noise_std, &mut noise_rng);
+            network.update(&inputs);
+        };
+        for _ in 0..FAMILIARIZATION {
+            step(&mut network, &familiar);
+        }
+
+        // Um ensaio é detetado se o detetor emitir novidade durante a apresentação
+        let mut trial = |network: &mut Network, pattern: &[f64]| {
+            for _ in 0..GAP {
+                step(network, &familiar);
+            }
+            let baseline = network.average_novelty();
+            let mut detector = EventDetector::new(f64::INFINITY, baseline + DETECTION_MARGIN);
+            detector.emit_firing = false;
+            detector.detect(network);
+            let mut detected = false;
+            for _ in 0..PRESENTATION {
+                step(network, pattern);
+                detected |= detector
+                    .detect(network)
+                    .iter()
+                    .any(|e| matches!(e, SimulationEvent::NoveltyAboveThreshold { .. }));
+            }
+            detected
+        };
+
+        let (mut hits, mut false_alarms) = (0, 0);
+        for pattern in &novel {
+            false_alarms += trial(&mut network, &familiar) as usize;
+            hits += trial(&mut network, pattern) as usize;
+        }
+
+        let hit_rate = hits as f64 / TRIALS as f64;
+        let false_alarm_rate = false_alarms as f64 / TRIALS as f64;
+        levels.push(DetectionMetrics {
+            noise_std,
+            snr: if noise_std > 0.0 { AMPLITUDE / noise_std } else { f64::INFINITY },
+            hit_rate,
+            false_alarm_rate,
+            d_prime: d_prime(hit_rate, false_alarm_rate, TRIALS),
+        });
+    }
+
+    let mut log_file = File::create("noise_robustness.csv")?;
+    writeln!(log_file, "noise_std,snr,hit_rate,false_alarm_rate,d_prime")?;
+    println!("📈 Resultados:");
+    println!("   {:>8} {:>8} {:>9} {:>13} {:>7}", "ruído", "SNR", "acertos", "falsos alarmes", "d'");
+    for level in &levels {
+        writeln!(
+            log_file,
+            "{},{},{:.4},{:.4},{:.4}",
+            level.noise_std, level.snr, level.hit_rate, level.false_alarm_rate, level.d_prime
+        )?;
+        println!(
+            "   {:>8.2} {:>8.1} {:>9.2} {:>13.2} {:>7.2}",
+            level.noise_std, level.snr, level.hit_rate, level.false_alarm_rate, level.d_prime
+        );
+    }
+    println!("   Métricas salvas em 'noise_robustness.csv'");
+
+    Ok(NoiseRobustnessSummary { levels })
+}
+
+/// Resposta do neurónio alvo a um trem de pulsos
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrequencyResponse {
+    /// Intervalo entre pulsos em passos
+    pub period: i64,
+
+    /// Frequência do trem em Hz (com `dt` ms por passo)
+    pub frequency_hz: f64,
+
+    /// Pulsos apresentados
+    pub pulses: usize,
+
+    /// Disparos do alvo durante o trem
+    pub spikes: usize,
+
+    /// Disparos por pulso (1.0 = cada pulso evoca um disparo)
+    pub response_rate: f64,
+
+    /// Taxa de disparo do alvo em Hz
+    pub firing_rate_hz: f64,
+
+    /// Energia gasta pelo alvo em disparos
+    pub energy_cost: f64,
+
+    /// Energia gasta por pulso apresentado
+    pub energy_per_pulse: f64,
+
+    /// Fração média da energia máxima do alvo durante o trem
+    pub mean_energy_fraction: f64,
+}
+
+/// Resumo do experimento de sintonia em frequência
+#[derive(Debug, Clone)]
+pub struct FrequencyTuningSummary {
+    /// Resposta por frequência, da mais alta para a mais baixa
+    pub responses: Vec<FrequencyResponse>,
+}
+
+impl FrequencyTuningSummary {
+    /// Frequência preferida: a mais alta com a maior resposta por pulso
+    ///
+    /// A resposta é normalizada pelos pulsos apresentados, pelo que um trem
+    /// contínuo não ganha só por conter mais pulsos.
+    pub fn best_frequency(&self) -> Option<&FrequencyResponse> {
+        let best = self
+            .responses
+            .iter()
+            .map(|r| r.response_rate)
+            .max_by(f64::total_cmp)?;
+        self.responses
+            .iter()
+            .filter(|r| r.response_rate == best)
+            .max_by(|a, b| a.frequency_hz.total_cmp(&b.frequency_hz))
+    }
+}
+
+/// Alvo, amplitude e durações dos trens de pulsos
+const FREQUENCY_TARGET: usize = 55;
+const FREQUENCY_AMPLITUDE: f64 = 2.0;
+const FREQUENCY_REST: i64 = 20;
+const FREQUENCY_TRAIN: i64 = 300;
+
+/// Apresenta um trem de pulsos com `period` passos entre pulsos numa rede nova
+fn frequency_response(period: i64) -> FrequencyResponse {
+    let mut network = Network::new_seeded(100, ConnectivityType::Grid2D, 0.2, 0.2, 11);
+    let n = network.num_neurons();
+    let dt = network.dt();
+    let cost = network.neurons[FREQUENCY_TARGET].glia.params().energy_cost_fire;
+
+    let mut schedule = StimulusSchedule::new();
+    let train = FREQUENCY_REST..FREQUENCY_REST + FREQUENCY_TRAIN;
+    for start in train.step_by(period.max(1) as usize) {
+        schedule.add(Stimulus::new(
+            vec![FREQUENCY_TARGET],
+            FREQUENCY_AMPLITUDE,
+            start,
+            start + 1,
+        ));
+    }
+    let pulses = schedule.stimuli.len();
+
+    let mut spikes = 0;
+    let mut energy_fraction = 0.0;
+    network.run(
+        FREQUENCY_REST + FREQUENCY_TRAIN,
+        |t| schedule.inputs_at(n, t),
+        |network, t| {
+            if t >= FREQUENCY_REST {
+                let target = &network.neurons[FREQUENCY_TARGET];
+                spikes += target.is_firing as usize;
+                energy_fraction += target.glia.energy_fraction() / FREQUENCY_TRAIN as f64;
+            }
+        },
+    );
+
+    let seconds = FREQUENCY_TRAIN as f64 * dt / 1000.0;
+    let energy_cost = spikes as f64 * cost;
+    FrequencyResponse {
+        period,
+        frequency_hz: 1000.0 / (period as f64 * dt),
+        pulses,
+        spikes,
+        response_rate: spikes as f64 / pulses as f64,
+        firing_rate_hz: spikes as f64 / seconds,
+        energy_cost,
+        energy_per_pulse: energy_cost / pulses as f64,
+        mean_energy_fraction: energy_fraction,
+    }
+}
+
+/// Experimento 11: Sintonia em Frequência
+///
+/// Estimula um neurónio alvo com trens de pulsos de um passo a frequências
+/// diferentes e mede quantos pulsos evocam disparo e quanta energia isso
+/// custa por pulso. O período refratário impede respostas a pulsos
+/// demasiado próximos e a recuperação da Glia limita a taxa sustentável,
+/// pelo que a resposta por pulso cai nas frequências altas enquanto a taxa
+/// de disparo satura. A frequência preferida é a mais alta que ainda evoca
+/// a resposta máxima por pulso.
+///
+/// Protocolo (por frequência, numa rede nova com a mesma semente):
+/// 1. 20 passos sem input
+/// 2. Trem de pulsos de amplitude 2.0 no alvo durante 300 passos
+///
+/// Comportamento esperado:
+/// - Resposta por pulso = 1 quando a Glia recupera entre pulsos
+/// - Resposta por pulso a cair junto ao período refratário e abaixo dele
+pub fn frequency_tuning_experiment() -> std::io::Result<FrequencyTuningSummary> {
+    println!("=== Experimento 11: Sintonia em Frequência ===\n");
+
+    const PERIODS: [i64; 10] = [1, 2, 3, 4, 5, 6, 8, 10, 20, 40];
+
+    let responses: Vec<FrequencyResponse> =
+        PERIODS.into_iter().map(frequency_response).collect();
+
+    let mut log_file = File::create("frequency_tuning.csv")?;
+    writeln!(
+        log_file,
+        "period,frequency_hz,pulses,spikes,response_rate,firing_rate_hz,energy_cost,\
+         energy_per_pulse,mean_energy_fraction"
+    )?;
+    println!("📈 Resultados (alvo {}):", FREQUENCY_TARGET);
+    println!(
+        "   {:>8} {:>8} {:>10} {:>10} {:>12} {:>8}",
+        "freq Hz", "pulsos", "resp/pulso", "disparo Hz", "custo/pulso", "energia"
+    );
+    for r in &responses {
+        writeln!(
+            log_file,
+            "{},{:.2},{},{},{:.4},{:.2},{:.2},{:.4},{:.4}",
+            r.period,
+            r.frequency_hz,
+            r.pulses,
+            r.spikes,
+            r.response_rate,
+            r.firing_rate_hz,
+            r.energy_cost,
+            r.energy_per_pulse,
+            r.mean_energy_fraction
+        )?;
+        println!(
+            "   {:>8.1} {:>8} {:>10.2} {:>10.1} {:>12.2} {:>7.0}%",
+            r.frequency_hz,
+            r.pulses,
+            r.response_rate,
+            r.firing_rate_hz,
+            r.energy_per_pulse,
+            r.mean_energy_fraction * 100.0
+        );
+    }
+
+    let summary = FrequencyTuningSummary { responses };
+    if let Some(best) = summary.best_frequency() {
+        println!(
+            "\n   Frequência preferida: {:.1} Hz ({:.2} disparos por pulso)",
+            best.frequency_hz, best.response_rate
+        );
+    }
+    println!("   Curva salva em 'frequency_tuning.csv'");
+    Ok(summary)
+}
+
+/// Resposta do neurónio alvo a um par de pulsos
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PairedPulseResponse {
+    /// Passos entre o primeiro e o segundo pulso
+    pub interval: i64,
+
+    /// Potencial modulado do alvo no primeiro pulso
+    pub first: f64,
+
+    /// Potencial modulado do alvo no segundo pulso
+    pub second: f64,
+
+    /// Resposta ao segundo pulso relativa ao primeiro
+    ///
+    /// `second / first` fora do período refratário (< 1 depressão, > 1
+    /// facilitação); 0.0 dentro dele, onde o pulso não pode evocar disparo.
+    pub ratio: f64,
+
+    /// O segundo pulso chegou dentro do período refratário do último
+    /// disparo do alvo
+    pub refractory: bool,
+
+    /// O alvo disparou no primeiro pulso
+    pub first_fired: bool,
+
+    /// Motivo da decisão no segundo pulso
+    pub second_reason: FireReason,
+
+    /// Fração de energia do alvo após o passo `t_segundo - 1`, imediatamente
+    /// antes do segundo pulso
+    pub energy_before_second: f64,
+}
+
+/// Resumo do experimento de pares de pulsos
+#[derive(Debug, Clone)]
+pub struct PairedPulseSummary {
+    /// Resposta por intervalo, do mais curto para o mais longo
+    pub responses: Vec<PairedPulseResponse>,
+}
+
+impl PairedPulseSummary {
+    /// Intervalo mais curto a partir do qual o segundo pulso volta a disparar
+    pub fn recovery_interval(&self) -> Option<i64> {
+        self.responses
+            .iter()
+            .find(|r| r.second_reason == FireReason::Fired)
+            .map(|r| r.interval)
+    }
+}
+
+/// Alvo, amplitude e repouso inicial dos pares de pulsos
+const PAIRED_PULSE_TARGET: usize = 55;
+const PAIRED_PULSE_AMPLITUDE: f64 = 2.0;
+const PAIRED_PULSE_REST: i64 = 20;
+
+/// Rede do experimento de pares de pulsos, sem aprendizado
+fn paired_pulse_network() -> Network {
+    let mut network = Network::new_seeded(100, ConnectivityType::Grid2D, 0.2, 0.2, 11);
+    network.set_plasticity(false);
+    network
+}
+
+/// Apresenta um par de pulsos separados por `interval` passos numa rede nova
+fn paired_pulse_trial(interval: i64) -> PairedPulseResponse {
+    let mut network = paired_pulse_network();
+    let n = network.num_neurons();
+    let debugger = Debugger::attach(&mut network, PAIRED_PULSE_TARGET);
+
+    let second_pulse = PAIRED_PULSE_REST + interval;
+    let schedule = StimulusSchedule::new()
+        .with(Stimulus::new(
+            vec![PAIRED_PULSE_TARGET],
+            PAIRED_PULSE_AMPLITUDE,
+            PAIRED_PULSE_REST,
+            PAIRED_PULSE_REST + 1,
+        ))
+        .with(Stimulus::new(
+            vec![PAIRED_PULSE_TARGET],
+            PAIRED_PULSE_AMPLITUDE,
+            second_pulse,
+            second_pulse + 1,
+        ));
+
+    // Até ao passo `second_pulse - 1`, inclusive
+    let mut first = None;
+    network.run(
+        second_pulse,
+        |t| schedule.inputs_at(n, t),
+        |network, t| {
+            if t == PAIRED_PULSE_REST {
+                first = Some(debugger.inspect(network));
+            }
+        },
+    );
+    let first = first.unwrap_or_else(|| debugger.inspect(&network));
+
+    let target = &network.neurons[PAIRED_PULSE_TARGET];
+    let energy_before_second = target.glia.energy_fraction();
+    let fire_time = network.current_time_step + 1;
+    let refractory = target.last_fire_time >= 0
+        && fire_time - target.last_fire_time < target.refractory_period();
+
+    network.update(&schedule.inputs_at(n, second_pulse));
+    let second = debugger.inspect(&network);
+
+    let first_potential = first.modulated.unwrap_or(0.0);
+    let second_potential = second.modulated.unwrap_or(0.0);
+    PairedPulseResponse {
+        interval,
+        first: first_potential,
+        second: second_potential,
+        ratio: if refractory || first_potential == 0.0 {
+            0.0
+        } else {
+            second_potential / first_potential
+        },
+        refractory,
+        first_fired: first.is_firing,
+        second_reason: second.reason,
+        energy_before_second,
+    }
+}
+
+/// Experimento 12: Pares de Pulsos
+///
+/// Apresenta ao neurónio alvo dois pulsos separados por intervalos
+/// crescentes e compara a resposta ao segundo com a resposta ao primeiro.
+/// A resposta é o potencial modulado do alvo no passo do pulso, capturado
+/// pelo `Debugger`: a energia gasta no primeiro disparo deprime o segundo
+/// até a Glia recuperar. Dentro do período refratário o segundo pulso não
+/// pode evocar disparo e a razão é 0.0 (o potencial pode até ser maior, com
+/// a priority ainda elevada pelo primeiro pulso, mas não é facilitação). O
+/// aprendizado fica desligado para que o primeiro pulso não altere os pesos
+/// e a razão reflita só a dinâmica de curto prazo.
+///
+/// Protocolo (por intervalo, numa rede nova com a mesma semente):
+/// 1. 20 passos sem input
+/// 2. Pulso de amplitude 2.0 no alvo (1 passo)
+/// 3. Segundo pulso igual `interval` passos depois
+///
+/// Comportamento esperado:
+/// - Segundo pulso refratário para intervalos menores que o período refratário
+/// - Razão < 1 logo a seguir, a tender para 1 com a recuperação da energia
+pub fn paired_pulse_experiment() -> std::io::Result<PairedPulseSummary> {
+    println!("=== Experimento 12: Pares de Pulsos ===\n");
+
+    const INTERVALS: [i64; 12] = [1, 2, 3, 4, 5, 6, 8, 10, 15, 20, 30, 50];
+
+    let responses: Vec<PairedPulseResponse> =
+        INTERVALS.into_iter().map(paired_pulse_trial).collect();
+
+    let mut log_file = File::create("paired_pulse.csv")?;
+    writeln!(
+        log_file,
+        "interval,first,second,ratio,refractory,first_fired,second_fired,energy_before_second"
+    )?;
+    println!("📈 Resultados (alvo {}):", PAIRED_PULSE_TARGET);
+    println!(
+        "   {:>9} {:>9} {:>9} {:>7} {:>8}  decisão no 2º pulso",
+        "intervalo", "1º pulso", "2º pulso", "razão", "energia"
+    );
+    for r in &responses {
+        writeln!(
+            log_file,
+            "{},{:.4},{:.4},{:.4},{},{},{},{:.4}",
+            r.interval,
+            r.first,
+            r.second,
+            r.ratio,
+            r.refractory as u8,
+            r.first_fired as u8,
+            (r.second_reason == FireReason::Fired) as u8,
+            r.energy_before_second
+        )?;
+        println!(
+            "   {:>9} {:>9.3} {:>9.3} {:>7.3} {:>7.0}%  {}{}",
+            r.interval,
+            r.first,
+            r.second,
+            r.ratio,
+            r.energy_before_second * 100.0,
+            r.second_reason,
+            if r.refractory && r.second_reason != FireReason::Refractory {
+                " (em período refratário)"
+            } else {
+                ""
+            }
+        );
+    }
+
+    let summary = PairedPulseSummary { responses };
+    match summary.recovery_interval() {
+        Some(interval) => {
+            println!("\n   Segundo pulso volta a disparar a partir de {} passos", interval)
+        }
+        None => println!("\n   Segundo pulso nunca disparou"),
+    }
+    println!("   Curva salva em 'paired_pulse.csv'");
+    Ok(summary)
+}
+
+/// Versão ao vivo do Experimento 4 no terminal
+///
+/// Mesmo protocolo (padrão A até t=50, depois padrão B novo), desenhando a
+/// cada passo o painel com o mapa de disparos da grade e as séries de
+/// disparos, energia, novidade e alert_level.
+///
+/// # Argumentos
+/// * `frame_delay` - Pausa entre passos para tornar a evolução visível
+pub fn live_integration_experiment(frame_delay: Duration) -> std::io::Result<()> {
+    const NUM_NEURONS: usize = 100;
+    const INITIAL_THRESHOLD: f64 = 0.2;
+    const MAX_TIME: i64 = 150;
+
+    let mut network = Network::new(
+        NUM_NEURONS,
+        ConnectivityType::Grid2D,
+        0.2,
+        INITIAL_THRESHOLD,
+    );
+    network.set_novelty_alert_params(0.04, 0.5);
+
+    let schedule = integration_protocol().schedule();
+
+    let mut dashboard = Dashboard::new(NeuronField::Firing, 60).with_range(0.0, 1.0);
+
+    network.try_run(
+        MAX_TIME,
+        |t| schedule.inputs_at(NUM_NEURONS, t),
+        |network, _| {
+            dashboard.draw(network)?;
+            std::thread::sleep(frame_delay);
+            Ok::<(), std::io::Error>(())
+        },
+    )?;
+
+    println!("\n✅ Simulação ao vivo concluída");
+    Ok(())
+}
+
+/// Versão do Experimento 4 com painel ratatui (ver `tui`)
+///
+/// Mesmo protocolo de `live_integration_experiment`, desenhado em ecrã
+/// alternativo; `q` ou `Esc` terminam antes do fim.
+///
+/// # Argumentos
+/// * `frame_delay` - Pausa entre passos
+#[cfg(feature = "tui")]
+pub fn tui_integration_experiment(frame_delay: Duration) -> std::io::Result<()> {
+    use crate::tui::{quit_requested, TuiDashboard};
+
+    const NUM_NEURONS: usize = 100;
+    const INITIAL_THRESHOLD: f64 = 0.2;
+    const MAX_TIME: i64 = 150;
+
+    let mut network = Network::new(
+        NUM_NEURONS,
+        ConnectivityType::Grid2D,
+        0.2,
+        INITIAL_THRESHOLD,
+    );
+    network.set_novelty_alert_params(0.04, 0.5);
+
+    let schedule = integration_protocol().schedule();
+
+    let dashboard = Dashboard::new(NeuronField::Firing, 60).with_range(0.0, 1.0);
+    let mut tui = TuiDashboard::new(ratatui::try_init()?, dashboard);
+
+    let mut result = Ok(());
+    for t in 0..MAX_TIME {
+        network.update(&schedule.inputs_at(NUM_NEURONS, t));
+        match tui.draw(&network).and_then(|_| quit_requested(frame_delay)) {
+            Ok(false) => {}
+            Ok(true) => break,
+            Err(e) => {
+                result = Err(e);
+                break;
+            }
+        }
+    }
+
+    // Repõe o terminal mesmo que o desenho tenha falhado
+    ratatui::try_restore()?;
+    result?;
+
+    println!("✅ Simulação ao vivo concluída em t={}", network.current_time_step);
+    Ok(())
+}
+
+/// Versão do Experimento 4 depurada passo a passo no terminal
+///
+/// Mesmo protocolo de `live_integration_experiment`; cada comando lido do
+/// stdin avança um ou mais passos e mostra o estado de `neuron` (ver
+/// `debugger::Debugger`).
+///
+/// # Argumentos
+/// * `neuron` - Neurónio inspecionado no início (muda com `n <i>`)
+pub fn debug_integration_experiment(neuron: usize) -> std::io::Result<()> {
+    const NUM_NEURONS: usize = 100;
+    const INITIAL_THRESHOLD: f64 = 0.2;
+    const MAX_TIME: i64 = 150;
+
+    let mut network = Network::new(
+        NUM_NEURONS,
+        ConnectivityType::Grid2D,
+        0.2,
+        INITIAL_THRESHOLD,
+    );
+    network.set_novelty_alert_params(0.04, 0.5);
+
+    let schedule = integration_protocol().schedule();
+
+    let debugger = Debugger::attach(&mut network, neuron);
+    debugger.run_session(
+        &mut network,
+        MAX_TIME,
+        |t| schedule.inputs_at(NUM_NEURONS, t),
+        std::io::stdin().lock(),
+        std::io::stdout(),
+    )?;
+
+    println!("\n✅ Depuração concluída em t={}", network.current_time_step);
+    Ok(())
+}
+
+/// Versão do Experimento 4 transmitida por WebSocket
+///
+/// Mesmo protocolo de `live_integration_experiment`, enviando o estado de
+/// cada passo aos clientes ligados ao servidor (ver `websocket::StepMessage`).
+///
+/// # Argumentos
+/// * `server` - Servidor já ligado ao endereço de escuta
+/// * `frame_delay` - Pausa entre passos
+#[cfg(feature = "websocket")]
+pub fn streamed_integration_experiment(
+    server: &crate::websocket::WebSocketServer,
+    frame_delay: Duration,
+) -> std::io::Result<()> {
+    const NUM_NEURONS: usize = 100;
+    const INITIAL_THRESHOLD: f64 = 0.2;
+    const MAX_TIME: i64 = 150;
+
+    let mut network = Network::new(
+        NUM_NEURONS,
+        ConnectivityType::Grid2D,
+        0.2,
+        INITIAL_THRESHOLD,
+    );
+    network.set_novelty_alert_params(0.04, 0.5);
+
+    let schedule = integration_protocol().schedule();
+
+    network.try_run(
+        MAX_TIME,
+        |t| schedule.inputs_at(NUM_NEURONS, t),
+        |network, _| {
+            server.broadcast(network)?;
+            std::thread::sleep(frame_delay);
+            Ok::<(), std::io::Error>(())
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_retest_control_differs_only_after_interposed_stimulus() {
+        const TARGET: usize = 55;
+        let protocol = dishabituation_protocol();
+        let schedule = protocol.schedule();
+        let control = protocol.without_phase("novel").schedule();
+        assert_eq!(control.stimuli.len(), 2);
+        assert_eq!(control.duration(), schedule.duration());
+        let rates = |schedule: &StimulusSchedule| {
+            retest_rates(&mut retest_network(), schedule, TARGET, (70, 100), (120, 150), |_, _| {
+                Ok(())
+            })
+            .unwrap()
+        };
+
+        // Mesma semente: até t=100 as execuções coincidem
+        let (habituated, _) = rates(&schedule);
+        let (control_habituated, _) = rates(&control);
+        assert_eq!(habituated, control_habituated);
+        assert_eq!(rates(&control), rates(&control));
+    }
+
+    #[test]
+    fn test_experiment_protocols_parse() {
+        assert_eq!(habituation_protocol().duration(), 100);
+        let sensitization = sensitization_protocol();
+        let noxious = &sensitization.phase("noxious").unwrap().stimulus;
+        assert_eq!(noxious.targets, (20..30).collect::<Vec<_>>());
+        for protocol in [dishabituation_protocol(), sensitization] {
+            assert_eq!(protocol.phases.len(), 3);
+            assert_eq!(protocol.duration(), 170);
+        }
+    }
+
+    #[test]
+    fn test_frequency_tuning_normalizes_per_pulse() {
+        let refractory_period = Network::new_seeded(100, ConnectivityType::Grid2D, 0.2, 0.2, 11)
+            .neurons[FREQUENCY_TARGET]
+            .refractory_period();
+        let summary = FrequencyTuningSummary {
+            responses: [1, 2, refractory_period, 10, 20].map(frequency_response).to_vec(),
+        };
+
+        for r in &summary.responses {
+            assert!(r.response_rate <= 1.0, "{:?}", r);
+            assert_relative_eq!(r.energy_per_pulse * r.pulses as f64, r.energy_cost);
+            if r.period < refractory_period {
+                assert!(r.response_rate < 1.0, "{:?}", r);
+            }
+        }
+        for r in &summary.responses[3..] {
+            assert_eq!(r.response_rate, 1.0, "{:?}", r);
+        }
+        assert_eq!(summary.best_frequency().unwrap().period, 10);
+    }
+
+    #[test]
+    fn test_paired_pulse_ratio_and_energy_sampling() {
+        let refractory_period =
+            paired_pulse_network().neurons[PAIRED_PULSE_TARGET].refractory_period();
+        for interval in 1..=12 {
+            let response = paired_pulse_trial(interval);
+            assert!(response.first_fired);
+            assert_eq!(response.refractory, interval < refractory_period, "{}", interval);
+            if response.refractory {
+                assert_eq!(response.ratio, 0.0);
+                assert_ne!(response.second_reason, FireReason::Fired);
+            } else {
+                assert!(response.ratio > 0.0 && response.ratio <= 1.0, "{:?}", response);
+            }
+
+            // Energia após o passo `t_segundo - 1`, só com o primeiro pulso
+            let mut network = paired_pulse_network();
+            let n = network.num_neurons();
+            network.run(
+                PAIRED_PULSE_REST + interval,
+                |t| {
+                    let mut inputs = vec![0.0; n];
+                    if t == PAIRED_PULSE_REST {
+                        inputs[PAIRED_PULSE_TARGET] = PAIRED_PULSE_AMPLITUDE;
+                    }
+                    inputs
+                },
+                |_, _| {},
+            );
+            let expected = network.neurons[PAIRED_PULSE_TARGET].glia.energy_fraction();
+            assert_eq!(response.energy_before_second, expected);
+        }
+    }
+}
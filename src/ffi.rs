@@ -0,0 +1,273 @@
+//! Módulo de interface C (FFI)
+//!
+//! Permite embutir o motor NEN-V em aplicações C/C++ (por exemplo, pilhas de
+//! robótica que querem o detetor novelty-alert a bordo). A rede é um
+//! ponteiro opaco `NenvNetwork`; após cada passo, o estado de cada neurónio
+//! fica disponível em buffers contíguos lidos diretamente pelo chamador.
+//!
+//! O cabeçalho correspondente está em `include/nen_v.h` e deve acompanhar
+//! qualquer mudança nas funções exportadas (um teste verifica que todas as
+//! funções estão declaradas). Para obter uma biblioteca partilhada:
+//!
+//! ```text
+//! cargo rustc --release --lib --crate-type cdylib --features ffi
+//! ```
+//!
+//! Os ponteiros de estado continuam válidos até ao próximo
+//! `nenv_network_step` ou `nenv_network_destroy` da mesma rede. Uma rede
+//! não deve ser usada por dois threads ao mesmo tempo.
+
+use crate::network::{ConnectivityType, Network};
+use std::ptr;
+
+/// Passo executado com sucesso
+pub const NENV_OK: i32 = 0;
+/// Ponteiro nulo recebido
+pub const NENV_ERR_NULL: i32 = -1;
+/// Número de entradas diferente do número de neurónios
+pub const NENV_ERR_LENGTH: i32 = -2;
+
+/// Topologia da rede, como em `ConnectivityType`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NenvConnectivity {
+    FullyConnected = 0,
+    Grid2D = 1,
+}
+
+/// Rede e buffers de estado expostos ao código C
+pub struct NenvNetwork {
+    network: Network,
+    firing: Vec<u8>,
+    energy: Vec<f64>,
+    priority: Vec<f64>,
+    novelty: Vec<f64>,
+}
+
+impl NenvNetwork {
+    fn new(network: Network) -> Self {
+        let n = network.num_neurons();
+        let mut handle = Self {
+            network,
+            firing: vec![0; n],
+            energy: vec![0.0; n],
+            priority: vec![0.0; n],
+            novelty: vec![0.0; n],
+        };
+        handle.refresh();
+        handle
+    }
+
+    /// Copia o estado atual dos neurónios para os buffers
+    fn refresh(&mut self) {
+        for (i, neuron) in self.network.neurons.iter().enumerate() {
+            self.firing[i] = neuron.is_firing as u8;
+            self.energy[i] = neuron.glia.energy;
+            self.priority[i] = neuron.glia.priority;
+            self.novelty[i] = neuron.novelty;
+        }
+    }
+}
+
+/// Cria uma rede com semente explícita
+///
+/// # Argumentos
+/// * `num_neurons` - Número de neurónios (maior que zero)
+/// * `connectivity` - Topologia da rede
+/// * `inhibitory_ratio` - Fração de neurónios inibitórios
+/// * `initial_threshold` - Limiar inicial de disparo
+/// * `seed` - Semente do gerador (a mesma semente gera a mesma rede)
+///
+/// # Retorna
+/// A rede, ou nulo se `num_neurons` for zero
+#[unsafe(no_mangle)]
+pub extern "C" fn nenv_network_create(
+    num_neurons: usize,
+    connectivity: NenvConnectivity,
+    inhibitory_ratio: f64,
+    initial_threshold: f64,
+    seed: u64,
+) -> *mut NenvNetwork {
+    if num_neurons == 0 {
+        return ptr::null_mut();
+    }
+    let connectivity = match connectivity {
+        NenvConnectivity::FullyConnected => ConnectivityType::FullyConnected,
+        NenvConnectivity::Grid2D => ConnectivityType::Grid2D,
+    };
+    let network =
+        Network::new_seeded(num_neurons, connectivity, inhibitory_ratio, initial_threshold, seed);
+    Box::into_raw(Box::new(NenvNetwork::new(network)))
+}
+
+/// Liberta uma rede (nulo é ignorado)
+///
+/// # Safety
+/// `net` tem de vir de `nenv_network_create` e não pode ser usado depois.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nenv_network_destroy(net: *mut NenvNetwork) {
+    if !net.is_null() {
+        drop(unsafe { Box::from_raw(net) });
+    }
+}
+
+/// Executa um passo de simulação
+///
+/// # Argumentos
+/// * `inputs` - Uma entrada externa por neurónio
+/// * `len` - Comprimento de `inputs` (tem de ser igual ao número de neurónios)
+///
+/// # Retorna
+/// `NENV_OK`, `NENV_ERR_NULL` ou `NENV_ERR_LENGTH`
+///
+/// # Safety
+/// `net` tem de ser uma rede válida e `inputs` apontar para `len` valores.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nenv_network_step(
+    net: *mut NenvNetwork,
+    inputs: *const f64,
+    len: usize,
+) -> i32 {
+    let Some(handle) = (unsafe { net.as_mut() }) else {
+        return NENV_ERR_NULL;
+    };
+    if len != handle.network.num_neurons() {
+        return NENV_ERR_LENGTH;
+    }
+    if inputs.is_null() {
+        return NENV_ERR_NULL;
+    }
+
+    let inputs = unsafe { std::slice::from_raw_parts(inputs, len) };
+    handle.network.update(inputs);
+    handle.refresh();
+    NENV_OK
+}
+
+/// Número de neurónios (comprimento de todos os buffers de estado)
+///
+/// # Safety
+/// `net` tem de ser uma rede válida.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nenv_network_num_neurons(net: *const NenvNetwork) -> usize {
+    unsafe { net.as_ref() }.map_or(0, |h| h.network.num_neurons())
+}
+
+/// Largura da grade (0 em redes sem grade)
+///
+/// # Safety
+/// `net` tem de ser uma rede válida.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nenv_network_grid_width(net: *const NenvNetwork) -> usize {
+    unsafe { net.as_ref() }.map_or(0, |h| h.network.grid_width)
+}
+
+/// Passo de tempo atual
+///
+/// # Safety
+/// `net` tem de ser uma rede válida.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nenv_network_time_step(net: *const NenvNetwork) -> i64 {
+    unsafe { net.as_ref() }.map_or(0, |h| h.network.current_time_step)
+}
+
+/// Alert level global da rede
+///
+/// # Safety
+/// `net` tem de ser uma rede válida.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nenv_network_alert_level(net: *const NenvNetwork) -> f64 {
+    unsafe { net.as_ref() }.map_or(0.0, |h| h.network.alert_level)
+}
+
+/// Novidade média da rede
+///
+/// # Safety
+/// `net` tem de ser uma rede válida.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nenv_network_average_novelty(net: *const NenvNetwork) -> f64 {
+    unsafe { net.as_ref() }.map_or(0.0, |h| h.network.average_novelty())
+}
+
+/// Disparos do último passo (1 = disparou, 0 = não)
+///
+/// # Safety
+/// `net` tem de ser uma rede válida.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nenv_network_firing(net: *const NenvNetwork) -> *const u8 {
+    unsafe { net.as_ref() }.map_or(ptr::null(), |h| h.firing.as_ptr())
+}
+
+/// Energia de cada neurónio
+///
+/// # Safety
+/// `net` tem de ser uma rede válida.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nenv_network_energy(net: *const NenvNetwork) -> *const f64 {
+    unsafe { net.as_ref() }.map_or(ptr::null(), |h| h.energy.as_ptr())
+}
+
+/// Priority de cada neurónio
+///
+/// # Safety
+/// `net` tem de ser uma rede válida.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nenv_network_priority(net: *const NenvNetwork) -> *const f64 {
+    unsafe { net.as_ref() }.map_or(ptr::null(), |h| h.priority.as_ptr())
+}
+
+/// Novidade de cada neurónio
+///
+/// # Safety
+/// `net` tem de ser uma rede válida.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nenv_network_novelty(net: *const NenvNetwork) -> *const f64 {
+    unsafe { net.as_ref() }.map_or(ptr::null(), |h| h.novelty.as_ptr())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_step_and_read_state() {
+        let net = nenv_network_create(9, NenvConnectivity::Grid2D, 0.0, 0.2, 3);
+        assert!(!net.is_null());
+
+        unsafe {
+            let n = nenv_network_num_neurons(net);
+            assert_eq!(n, 9);
+
+            let mut inputs = vec![0.0; n];
+            inputs[4] = 5.0;
+            assert_eq!(nenv_network_step(net, inputs.as_ptr(), n), NENV_OK);
+            assert_eq!(nenv_network_step(net, inputs.as_ptr(), 3), NENV_ERR_LENGTH);
+            assert_eq!(nenv_network_time_step(net), 1);
+
+            let firing = std::slice::from_raw_parts(nenv_network_firing(net), n);
+            assert_eq!(firing[4], 1);
+
+            nenv_network_destroy(net);
+            assert_eq!(nenv_network_step(ptr::null_mut(), inputs.as_ptr(), n), NENV_ERR_NULL);
+        }
+        assert!(nenv_network_create(0, NenvConnectivity::Grid2D, 0.0, 0.2, 3).is_null());
+    }
+
+    #[test]
+    fn test_header_declares_every_export() {
+        let source = include_str!("ffi.rs");
+        let header = include_str!("../include/nen_v.h");
+
+        let exports: Vec<&str> = source
+            .split("extern \"C\" fn ")
+            .skip(1)
+            .filter_map(|rest| rest.split('(').next())
+            .filter(|name| name.starts_with("nenv_"))
+            .collect();
+
+        assert!(exports.len() >= 10);
+        for name in exports {
+            assert!(header.contains(&format!("{}(", name)), "{} não está em nen_v.h", name);
+        }
+    }
+}
@@ -0,0 +1,281 @@
+//! Módulo de aritmética de ponto fixo Q16.16
+//!
+//! Para executar o detetor de anomalias em microcontroladores sem FPU, as
+//! três operações do caminho crítico (integração, modulação e novidade) têm
+//! aqui uma versão em inteiros: cada valor é um `i32` com 16 bits
+//! fracionários (`Fixed`), representando [-32768, 32768) com resolução
+//! `2^-16`. As operações saturam em vez de transbordar e as multiplicações
+//! usam um intermediário `i64`.
+//!
+//! Limites de precisão face à referência em `f64`, com `ε = 2^-17` (erro
+//! máximo de arredondamento de cada conversão e de cada produto) e os
+//! valores dentro do intervalo representável:
+//!
+//! ```text
+//! integrate(w, x)        |Δ| ≤ n · (W + X + 1.01) · ε     W, X = max |w_j|, max |x_j|
+//! modulate(p, e, π)      |Δ| ≤ (4·|p| + 9) · ε            e ∈ [0, 1], π ∈ [1, 3]
+//! novelty(x, m)          |Δ| ≤ 4 · ε
+//! update_memory (α)      |Δ| ≤ (D + 2) · ε / α, em regime   D = max |x_j - m_j|
+//! ```
+//!
+//! O erro da memória não cresce sem limite: cada passo encolhe o erro
+//! anterior por `(1 - α)` e acrescenta no máximo `(D + 2) · ε`.
+//!
+//! `FixedNeuron` quantiza o estado de um `NENV` (pesos, memória, limiar,
+//! energia e priority) para avaliar essas operações em ponto fixo.
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::F64Ext;
+use crate::nenv::NENV;
+use crate::prelude::*;
+use core::fmt;
+use core::ops::{Add, Mul, Sub};
+
+/// Número em ponto fixo Q16.16
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed(i32);
+
+impl Fixed {
+    /// Bits fracionários
+    pub const FRAC_BITS: u32 = 16;
+    pub const ZERO: Fixed = Fixed(0);
+    pub const ONE: Fixed = Fixed(1 << Self::FRAC_BITS);
+    pub const MAX: Fixed = Fixed(i32::MAX);
+    pub const MIN: Fixed = Fixed(i32::MIN);
+
+    /// Menor incremento representável (2^-16)
+    pub const RESOLUTION: f64 = 1.0 / (1u32 << Self::FRAC_BITS) as f64;
+
+    /// Valor a partir da representação interna
+    pub const fn from_raw(raw: i32) -> Self {
+        Self(raw)
+    }
+
+    /// Representação interna (valor × 2^16)
+    pub const fn raw(self) -> i32 {
+        self.0
+    }
+
+    /// Converte arredondando ao mais próximo (satura fora do intervalo; NaN → 0)
+    pub fn from_f64(value: f64) -> Self {
+        if value.is_nan() {
+            return Self::ZERO;
+        }
+        Self((value * Self::ONE.0 as f64).round().clamp(i32::MIN as f64, i32::MAX as f64) as i32)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 * Self::RESOLUTION
+    }
+
+    pub fn abs(self) -> Self {
+        Self(self.0.saturating_abs())
+    }
+
+    /// Satura um valor interno de 64 bits no intervalo de `i32`
+    fn saturate(raw: i64) -> Self {
+        Self(raw.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+    }
+
+    /// Produto interno arredondado ao mais próximo, ainda em 64 bits
+    fn mul_raw(self, other: Self) -> i64 {
+        let product = self.0 as i64 * other.0 as i64;
+        (product + (1 << (Self::FRAC_BITS - 1))) >> Self::FRAC_BITS
+    }
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+
+    /// Soma com saturação
+    fn add(self, other: Fixed) -> Fixed {
+        Fixed(self.0.saturating_add(other.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+
+    /// Subtração com saturação
+    fn sub(self, other: Fixed) -> Fixed {
+        Fixed(self.0.saturating_sub(other.0))
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+
+    /// Produto arredondado ao mais próximo, com saturação
+    fn mul(self, other: Fixed) -> Fixed {
+        Fixed::saturate(self.mul_raw(other))
+    }
+}
+
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_f64())
+    }
+}
+
+/// Quantiza um vetor de `f64`
+pub fn quantize(values: &[f64]) -> Vec<Fixed> {
+    values.iter().map(|&v| Fixed::from_f64(v)).collect()
+}
+
+/// Soma ponderada dos inputs (como `Dendritoma::integrate`)
+///
+/// Os produtos arredondados acumulam em 64 bits; só o resultado satura.
+pub fn integrate(weights: &[Fixed], inputs: &[Fixed]) -> Fixed {
+    assert_eq!(
+        inputs.len(),
+        weights.len(),
+        "Número de inputs deve ser igual ao número de pesos"
+    );
+    Fixed::saturate(weights.iter().zip(inputs).map(|(&w, &x)| w.mul_raw(x)).sum())
+}
+
+/// Potencial modulado pela energia e priority (como `Glia::modulate`)
+pub fn modulate(potential: Fixed, energy_fraction: Fixed, priority: Fixed) -> Fixed {
+    potential * energy_fraction.max(Fixed::ZERO) * priority
+}
+
+/// Diferença absoluta média entre input e memória (como `NENV::compute_novelty`)
+pub fn novelty(inputs: &[Fixed], memory: &[Fixed]) -> Fixed {
+    assert_eq!(
+        inputs.len(),
+        memory.len(),
+        "Número de inputs deve ser igual ao tamanho da memória"
+    );
+    if inputs.is_empty() {
+        return Fixed::ZERO;
+    }
+    let total: i64 = inputs
+        .iter()
+        .zip(memory)
+        .map(|(&x, &m)| (x.0 as i64 - m.0 as i64).abs())
+        .sum();
+    Fixed::saturate(total / inputs.len() as i64)
+}
+
+/// Estado de um neurónio quantizado para avaliação em ponto fixo
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedNeuron {
+    pub weights: Vec<Fixed>,
+    pub memory_trace: Vec<Fixed>,
+    pub threshold: Fixed,
+    pub energy_fraction: Fixed,
+    pub priority: Fixed,
+}
+
+impl FixedNeuron {
+    /// Quantiza o estado atual de um neurónio
+    pub fn from_neuron(neuron: &NENV) -> Self {
+        Self {
+            weights: quantize(&neuron.dendritoma.weights),
+            memory_trace: quantize(&neuron.memory_trace),
+            threshold: Fixed::from_f64(neuron.threshold),
+            energy_fraction: Fixed::from_f64(neuron.glia.energy_fraction()),
+            priority: Fixed::from_f64(neuron.glia.priority),
+        }
+    }
+
+    pub fn integrate(&self, inputs: &[Fixed]) -> Fixed {
+        integrate(&self.weights, inputs)
+    }
+
+    pub fn modulate(&self, potential: Fixed) -> Fixed {
+        modulate(potential, self.energy_fraction, self.priority)
+    }
+
+    pub fn novelty(&self, inputs: &[Fixed]) -> Fixed {
+        novelty(inputs, &self.memory_trace)
+    }
+
+    /// Atualiza a memória contextual: `m += α · (x - m)` (como `NENV::update_memory`)
+    pub fn update_memory(&mut self, inputs: &[Fixed], alpha: Fixed) {
+        assert_eq!(
+            inputs.len(),
+            self.memory_trace.len(),
+            "Número de inputs deve ser igual ao tamanho da memória"
+        );
+        for (memory, &input) in self.memory_trace.iter_mut().zip(inputs) {
+            *memory = *memory + alpha * (input - *memory);
+        }
+    }
+
+    /// Se o potencial modulado excede o limiar (sem contar o período refratário)
+    pub fn exceeds_threshold(&self, inputs: &[Fixed]) -> bool {
+        self.modulate(self.integrate(inputs)) > self.threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    const EPS: f64 = Fixed::RESOLUTION / 2.0;
+
+    #[test]
+    fn test_conversion_rounds_and_saturates() {
+        assert_eq!(Fixed::from_f64(1.0), Fixed::ONE);
+        assert_eq!(Fixed::from_f64(-0.5).raw(), -(1 << 15));
+        assert_eq!(Fixed::from_f64(1e9), Fixed::MAX);
+        assert_eq!(Fixed::from_f64(-1e9), Fixed::MIN);
+        assert_eq!(Fixed::from_f64(f64::NAN), Fixed::ZERO);
+        assert!((Fixed::from_f64(0.1).to_f64() - 0.1).abs() <= EPS);
+
+        assert_eq!(Fixed::MAX + Fixed::ONE, Fixed::MAX);
+        assert_eq!(Fixed::from_f64(1.5) * Fixed::from_f64(-2.0), Fixed::from_f64(-3.0));
+        assert_eq!(Fixed::from_f64(30000.0) * Fixed::from_f64(2.0), Fixed::MAX);
+    }
+
+    #[test]
+    fn test_operations_within_documented_bounds() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for trial in 0..50 {
+            let n = 64;
+            let mut neuron = NENV::builder(0, n).build_with_rng(&mut rng);
+            neuron.memory_trace = (0..n).map(|_| rng.gen_range(0.0..1.0)).collect();
+            neuron.glia.energy = rng.gen_range(0.0..100.0);
+            neuron.glia.priority = rng.gen_range(1.0..3.0);
+            let inputs: Vec<f64> = (0..n)
+                .map(|j| if (j + trial) % 3 == 0 { rng.gen_range(-1.0..1.0) } else { 0.0 })
+                .collect();
+
+            let fixed = FixedNeuron::from_neuron(&neuron);
+            let fixed_inputs = quantize(&inputs);
+
+            // Pesos iniciais em [0.1, 0.3) e inputs em [-1, 1)
+            let potential = neuron.dendritoma.try_integrate(&inputs).unwrap();
+            let error = (fixed.integrate(&fixed_inputs).to_f64() - potential).abs();
+            assert!(error <= n as f64 * (0.3 + 1.0 + 1.01) * EPS);
+
+            let modulated = neuron.glia.modulate(potential);
+            let error = (fixed.modulate(Fixed::from_f64(potential)).to_f64() - modulated).abs();
+            assert!(error <= (4.0 * potential.abs() + 9.0) * EPS);
+
+            let novelty = neuron.try_compute_novelty(&inputs).unwrap();
+            assert!((fixed.novelty(&fixed_inputs).to_f64() - novelty).abs() <= 4.0 * EPS);
+        }
+    }
+
+    #[test]
+    fn test_memory_error_stays_bounded() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let mut neuron = NENV::builder(0, 16).with_memory_alpha(0.1).build_with_rng(&mut rng);
+        let mut fixed = FixedNeuron::from_neuron(&neuron);
+        let alpha = Fixed::from_f64(0.1);
+
+        // Inputs e memória em [0, 1]: D ≤ 1
+        for _ in 0..500 {
+            let inputs: Vec<f64> = (0..16).map(|_| rng.gen_range(0.0..1.0)).collect();
+            neuron.try_update_memory(&inputs).unwrap();
+            fixed.update_memory(&quantize(&inputs), alpha);
+        }
+        for (m, f) in neuron.memory_trace.iter().zip(&fixed.memory_trace) {
+            assert!((f.to_f64() - m).abs() <= 3.0 * EPS / 0.1);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Módulo de estimativa da memória ocupada por uma rede
+//!
+//! Quase toda a memória de uma rede está em quatro matrizes N×N: pesos,
+//! plasticidade e traços de memória de cada neurónio (em `f64`) e a matriz
+//! de conectividade (`u8`); os buffers de input de `update` são lineares em
+//! N. Uma rede de 10 000 neurónios precisa por isso de cerca de 2.5 GB:
+//!
+//! ```text
+//! bytes ≈ N² · (3 · 8 + 1)
+//! ```
+//!
+//! `Network::memory_footprint` soma a capacidade dos vetores de uma rede
+//! existente; `MemoryFootprint::estimate` prevê o valor para uma rede nova
+//! sem a construir, para dimensionar simulações grandes antes de as lançar.
+//! Ambas contam só a memória dos vetores da rede (não a do alocador nem a
+//! dos observadores).
+
+use crate::context::ContextEffect;
+use crate::nenv::NENV;
+use crate::network::ConnectivityType;
+use crate::prelude::*;
+use core::fmt;
+use core::mem::size_of;
+
+/// Bytes ocupados por componente da rede
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryFootprint {
+    /// Estado de cada neurónio (sem os vetores) e máscara de lesões
+    pub neurons: usize,
+
+    /// Matriz de conectividade e listas de vizinhos
+    pub connectivity: usize,
+
+    /// Pesos dos Dendritomas e ganhos sinápticos
+    pub weights: usize,
+
+    /// Fatores de plasticidade dos Dendritomas
+    pub plasticity: usize,
+
+    /// Traços de memória contextual e respetivas posições não nulas
+    pub memory_traces: usize,
+
+    /// Buffers de trabalho de `update` (alocados no primeiro passo)
+    pub step_buffers: usize,
+
+    /// Subsistemas opcionais e registos (religação, crescimento, modelos
+    /// preditivos, blocos, dropout, disparos, histórico e episódios)
+    pub optional: usize,
+}
+
+impl MemoryFootprint {
+    /// Estimativa para uma rede nova de `num_neurons` neurónios, depois do
+    /// primeiro passo e sem subsistemas opcionais
+    ///
+    /// As listas de vizinhos contam com a reserva extra de um `Vec` que
+    /// cresce (até o dobro do comprimento); as posições não nulas da memória
+    /// são reservadas à medida. Assume o grau máximo da topologia e nenhum
+    /// input externo (cada posição de input não nula acrescenta uma por
+    /// neurónio).
+    pub fn estimate(num_neurons: usize, connectivity_type: ConnectivityType) -> Self {
+        let n = num_neurons;
+        let degree = match connectivity_type {
+            ConnectivityType::FullyConnected => n.saturating_sub(1),
+            ConnectivityType::Grid2D => n.saturating_sub(1).min(8),
+        };
+        let row = size_of::<Vec<f64>>();
+        let square = n * n * size_of::<f64>();
+        let index = size_of::<usize>();
+        // Vizinhos acrescentados um a um; posições com os vizinhos e o próprio
+        let neighbors = if degree == 0 { 0 } else { degree.next_power_of_two().max(4) };
+        let support = degree + 1;
+
+        Self {
+            neurons: n * (size_of::<NENV>() + size_of::<bool>()),
+            connectivity: n * (row + n) + n * (row + neighbors * index) + n * index,
+            weights: square,
+            plasticity: square,
+            memory_traces: square + n * (row + support * index + size_of::<bool>()),
+            step_buffers: n * (4 * size_of::<f64>() + size_of::<ContextEffect>()),
+            optional: 0,
+        }
+    }
+
+    /// Total em bytes
+    pub fn total(&self) -> usize {
+        self.neurons
+            + self.connectivity
+            + self.weights
+            + self.plasticity
+            + self.memory_traces
+            + self.step_buffers
+            + self.optional
+    }
+}
+
+impl fmt::Display for MemoryFootprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = [
+            ("neurónios", self.neurons),
+            ("conectividade", self.connectivity),
+            ("pesos", self.weights),
+            ("plasticidade", self.plasticity),
+            ("memória", self.memory_traces),
+            ("buffers", self.step_buffers),
+            ("opcionais", self.optional),
+            ("total", self.total()),
+        ];
+        for (name, bytes) in rows {
+            writeln!(f, "{:<14} {:>12}", name, format_bytes(bytes))?;
+        }
+        Ok(())
+    }
+}
+
+/// Formata um número de bytes com a unidade binária adequada (B, KiB, ...)
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Bytes reservados por um vetor
+pub(crate) fn vec_bytes<T>(values: &Vec<T>) -> usize {
+    values.capacity() * size_of::<T>()
+}
+
+/// Bytes reservados por um vetor de vetores (incluindo o vetor exterior)
+pub(crate) fn matrix_bytes<T>(rows: &Vec<Vec<T>>) -> usize {
+    vec_bytes(rows) + rows.iter().map(vec_bytes).sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 << 30), "3.0 GiB");
+    }
+
+    #[test]
+    fn test_estimate_is_dominated_by_square_matrices() {
+        let n = 1000;
+        let estimate = MemoryFootprint::estimate(n, ConnectivityType::Grid2D);
+        assert_eq!(estimate.weights, n * n * 8);
+        assert_eq!(estimate.optional, 0);
+        let squares = 3 * n * n * 8 + n * n;
+        assert!(estimate.total() >= squares);
+        assert!((estimate.total() as f64) < 1.05 * squares as f64);
+
+        let full = MemoryFootprint::estimate(n, ConnectivityType::FullyConnected);
+        assert!(full.connectivity > estimate.connectivity);
+        assert_eq!(MemoryFootprint::estimate(0, ConnectivityType::Grid2D).total(), 0);
+    }
+}
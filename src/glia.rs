@@ -1,7 +1,79 @@
-/// Módulo responsável pela modulação metabólica do neurónio
+//! Módulo responsável pela modulação metabólica do neurónio
+//!
+//! A Glia modula a atividade do neurónio com base no seu estado metabólico,
+//! implementando dinâmicas homeostáticas através da gestão de energia.
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::F64Ext;
+use crate::timestep::amount_per_step;
+use serde::{Deserialize, Serialize};
+
+/// Constantes metabólicas de uma Glia
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GliaParams {
+    pub max_energy: f64,
+    pub energy_cost_fire: f64,
+    pub energy_cost_maintenance: f64,
+    pub energy_recovery_rate: f64,
+}
+
+impl Default for GliaParams {
+    fn default() -> Self {
+        Self {
+            max_energy: 100.0,
+            energy_cost_fire: 10.0,
+            energy_cost_maintenance: 0.1,
+            energy_recovery_rate: 2.0,
+        }
+    }
+}
+
+impl GliaParams {
+    /// Converte a manutenção e a recuperação (por ms) para passos de `dt` ms
+    pub(crate) fn per_step(self, dt: f64) -> Self {
+        Self {
+            energy_cost_maintenance: amount_per_step(self.energy_cost_maintenance, dt),
+            energy_recovery_rate: amount_per_step(self.energy_recovery_rate, dt),
+            ..self
+        }
+    }
+}
+
+/// Falha estocástica de disparo dependente da energia
 ///
-/// A Glia modula a atividade do neurónio com base no seu estado metabólico,
-/// implementando dinâmicas homeostáticas através da gestão de energia.
+/// Em vez de apenas escalar o potencial, a energia baixa torna a
+/// transmissão pouco fiável: um neurónio acima do limiar dispara com
+/// probabilidade `reliability(energy_fraction)`. Com energia acima de
+/// `saturation` o disparo é sempre transmitido.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpikeFailure {
+    /// Fração de energia a partir da qual a transmissão é garantida (0, 1]
+    pub saturation: f64,
+
+    /// Curvatura da queda de fiabilidade (1.0 = linear)
+    pub exponent: f64,
+}
+
+impl Default for SpikeFailure {
+    fn default() -> Self {
+        Self {
+            saturation: 0.5,
+            exponent: 1.0,
+        }
+    }
+}
+
+impl SpikeFailure {
+    /// Probabilidade de transmitir um disparo com a fração de energia dada
+    ///
+    /// Fórmula: reliability = clamp(energy_fraction / saturation, 0, 1) ^ exponent
+    pub fn reliability(&self, energy_fraction: f64) -> f64 {
+        if self.saturation <= 0.0 {
+            return 1.0;
+        }
+        (energy_fraction / self.saturation).clamp(0.0, 1.0).powf(self.exponent)
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Glia {
@@ -11,7 +83,10 @@ pub struct Glia {
     /// Prioridade do neurónio (para uso futuro em mecanismos de atenção)
     pub priority: f64,
 
-    /// Nível de alerta (para uso futuro em estados globais da rede)
+    /// Nível de alerta usado no último passo metabólico
+    ///
+    /// `update_state` usa este valor; `update_state_with_alert` (usado pela
+    /// `Network`) substitui-o pelo alert_level global recebido.
     pub alert_level: f64,
 
     // Constantes metabólicas
@@ -53,6 +128,36 @@ impl Glia {
         }
     }
 
+    /// Constantes metabólicas atuais
+    pub fn params(&self) -> GliaParams {
+        GliaParams {
+            max_energy: self.max_energy,
+            energy_cost_fire: self.energy_cost_fire,
+            energy_cost_maintenance: self.energy_cost_maintenance,
+            energy_recovery_rate: self.energy_recovery_rate,
+        }
+    }
+
+    /// Substitui as constantes metabólicas mantendo o estado
+    ///
+    /// A energia atual é limitada ao novo máximo.
+    pub fn set_params(&mut self, params: GliaParams) {
+        self.max_energy = params.max_energy;
+        self.energy_cost_fire = params.energy_cost_fire;
+        self.energy_cost_maintenance = params.energy_cost_maintenance;
+        self.energy_recovery_rate = params.energy_recovery_rate;
+        self.energy = self.energy.min(self.max_energy);
+    }
+
+    /// Repõe o estado inicial (energia máxima, priority 1.0, sem alerta)
+    ///
+    /// As constantes metabólicas são mantidas.
+    pub fn reset(&mut self) {
+        self.energy = self.max_energy;
+        self.priority = 1.0;
+        self.alert_level = 0.0;
+    }
+
     /// Modula o potencial integrado baseado na energia disponível e priority
     ///
     /// Fórmula v2: potencial_modulado = potencial_integrado * energy_factor * priority
@@ -83,6 +188,15 @@ impl Glia {
     /// O alert_level global aumenta a taxa de recuperação quando a rede
     /// está em estado de alerta, permitindo respostas mais rápidas.
     pub fn update_state(&mut self, did_fire: bool) {
+        self.update_state_with_alert(did_fire, self.alert_level);
+    }
+
+    /// Como `update_state`, com o nível de alerta dado em vez de `self.alert_level`
+    ///
+    /// Usado pela rede, que mantém um único alert_level partilhado; o valor
+    /// recebido fica em `self.alert_level`.
+    pub fn update_state_with_alert(&mut self, did_fire: bool, alert_level: f64) {
+        self.alert_level = alert_level;
         if did_fire {
             // Consome energia ao disparar
             self.energy -= self.energy_cost_fire;
@@ -91,7 +205,7 @@ impl Glia {
             let base_recovery = self.energy_recovery_rate * (1.0 - self.energy / self.max_energy);
 
             // Alert_level aumenta a recuperação (1.0 = +100% de recuperação)
-            let alert_boost = base_recovery * self.alert_level;
+            let alert_boost = base_recovery * alert_level;
 
             self.energy += base_recovery + alert_boost;
         }
@@ -128,6 +242,34 @@ mod tests {
         assert_eq!(glia.alert_level, 0.0);
     }
 
+    #[test]
+    fn test_set_params_clamps_energy() {
+        let mut glia = Glia::new();
+        let params = GliaParams {
+            max_energy: 50.0,
+            ..GliaParams::default()
+        };
+        glia.set_params(params);
+        assert_eq!(glia.energy, 50.0);
+        assert_eq!(glia.params(), params);
+        assert_eq!(Glia::new().params(), GliaParams::default());
+    }
+
+    #[test]
+    fn test_spike_failure_reliability() {
+        let failure = SpikeFailure::default();
+        assert_eq!(failure.reliability(1.0), 1.0);
+        assert_eq!(failure.reliability(0.5), 1.0);
+        assert_relative_eq!(failure.reliability(0.25), 0.5, epsilon = 1e-12);
+        assert_eq!(failure.reliability(0.0), 0.0);
+
+        let steep = SpikeFailure {
+            exponent: 2.0,
+            ..failure
+        };
+        assert_relative_eq!(steep.reliability(0.25), 0.25, epsilon = 1e-12);
+    }
+
     #[test]
     fn test_modulation_full_energy() {
         let glia = Glia::new();
@@ -237,6 +379,21 @@ mod tests {
         assert_relative_eq!(glia_zero.energy, glia_normal.energy, epsilon = 1e-10);
     }
 
+    #[test]
+    fn test_shared_alert_matches_own_alert_level() {
+        let mut own = Glia::new();
+        let mut shared = Glia::new();
+        own.energy = 50.0;
+        shared.energy = 50.0;
+
+        // O alerta passado substitui o campo da própria Glia
+        own.alert_level = 0.7;
+        own.update_state(false);
+        shared.update_state_with_alert(false, 0.7);
+        assert_eq!(own.energy, shared.energy);
+        assert_eq!(shared.alert_level, 0.7);
+    }
+
     #[test]
     fn test_priority_modulation() {
         let mut glia = Glia::new();
@@ -0,0 +1,206 @@
+//! Módulo de medidas de teoria dos grafos sobre o conectoma
+//!
+//! Opera diretamente sobre a matriz de conectividade da `Network`
+//! (`matrix[i][j] = 1` significa que o neurónio i recebe input de j), para
+//! validar as topologias geradas. Auto-conexões (diagonal) são ignoradas.
+
+use std::collections::{BTreeMap, VecDeque};
+
+/// Grau de entrada de cada neurónio (número de neurónios pré-sinápticos)
+pub fn in_degrees(matrix: &[Vec<u8>]) -> Vec<usize> {
+    matrix
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|&(j, &c)| c != 0 && i != j)
+                .count()
+        })
+        .collect()
+}
+
+/// Grau de saída de cada neurónio (número de neurónios pós-sinápticos)
+pub fn out_degrees(matrix: &[Vec<u8>]) -> Vec<usize> {
+    let mut degrees = vec![0; matrix.len()];
+    for (i, row) in matrix.iter().enumerate() {
+        for (j, &c) in row.iter().enumerate() {
+            if c != 0 && i != j && j < degrees.len() {
+                degrees[j] += 1;
+            }
+        }
+    }
+    degrees
+}
+
+/// Distribuição de graus de entrada: grau → número de neurónios com esse grau
+pub fn degree_distribution(matrix: &[Vec<u8>]) -> BTreeMap<usize, usize> {
+    let mut distribution = BTreeMap::new();
+    for degree in in_degrees(matrix) {
+        *distribution.entry(degree).or_insert(0) += 1;
+    }
+    distribution
+}
+
+/// Listas de adjacência do grafo não-direcionado subjacente
+fn undirected_neighbors(matrix: &[Vec<u8>]) -> Vec<Vec<usize>> {
+    let n = matrix.len();
+    let mut adjacent = vec![vec![false; n]; n];
+    for (i, row) in matrix.iter().enumerate() {
+        for (j, &c) in row.iter().enumerate().take(n) {
+            if c != 0 && i != j {
+                adjacent[i][j] = true;
+                adjacent[j][i] = true;
+            }
+        }
+    }
+
+    adjacent
+        .iter()
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .filter(|&(_, &a)| a)
+                .map(|(j, _)| j)
+                .collect()
+        })
+        .collect()
+}
+
+/// Coeficiente de agrupamento local de cada neurónio
+///
+/// Fração de pares de vizinhos que também estão conectados entre si, no
+/// grafo não-direcionado subjacente. Neurónios com menos de dois vizinhos
+/// têm coeficiente 0.
+pub fn local_clustering(matrix: &[Vec<u8>]) -> Vec<f64> {
+    let neighbors = undirected_neighbors(matrix);
+    let n = matrix.len();
+
+    let mut is_neighbor = vec![vec![false; n]; n];
+    for (i, list) in neighbors.iter().enumerate() {
+        for &j in list {
+            is_neighbor[i][j] = true;
+        }
+    }
+
+    neighbors
+        .iter()
+        .map(|list| {
+            let k = list.len();
+            if k < 2 {
+                return 0.0;
+            }
+
+            let mut links = 0;
+            for (a_idx, &a) in list.iter().enumerate() {
+                for &b in &list[a_idx + 1..] {
+                    if is_neighbor[a][b] {
+                        links += 1;
+                    }
+                }
+            }
+
+            2.0 * links as f64 / (k * (k - 1)) as f64
+        })
+        .collect()
+}
+
+/// Coeficiente de agrupamento médio da rede (média dos coeficientes locais)
+pub fn clustering_coefficient(matrix: &[Vec<u8>]) -> f64 {
+    let local = local_clustering(matrix);
+    if local.is_empty() {
+        return 0.0;
+    }
+    local.iter().sum::<f64>() / local.len() as f64
+}
+
+/// Comprimento médio do caminho mais curto entre pares alcançáveis
+///
+/// Segue a direção dos sinais (de j para i quando `matrix[i][j] = 1`),
+/// com busca em largura a partir de cada neurónio. Pares sem caminho são
+/// excluídos da média.
+///
+/// # Retorna
+/// `None` se nenhum par de neurónios distintos for alcançável
+pub fn average_shortest_path(matrix: &[Vec<u8>]) -> Option<f64> {
+    let n = matrix.len();
+
+    // Listas pós-sinápticas: para onde o sinal de j segue
+    let mut targets = vec![Vec::new(); n];
+    for (i, row) in matrix.iter().enumerate() {
+        for (j, &c) in row.iter().enumerate().take(n) {
+            if c != 0 && i != j {
+                targets[j].push(i);
+            }
+        }
+    }
+
+    let mut total_length = 0usize;
+    let mut pairs = 0usize;
+    let mut distance = vec![usize::MAX; n];
+    let mut queue = VecDeque::new();
+
+    for source in 0..n {
+        distance.fill(usize::MAX);
+        distance[source] = 0;
+        queue.push_back(source);
+
+        while let Some(current) = queue.pop_front() {
+            for &next in &targets[current] {
+                if distance[next] == usize::MAX {
+                    distance[next] = distance[current] + 1;
+                    total_length += distance[next];
+                    pairs += 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    if pairs == 0 {
+        None
+    } else {
+        Some(total_length as f64 / pairs as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{ConnectivityType, Network};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_fully_connected_measures() {
+        let network = Network::new(6, ConnectivityType::FullyConnected, 0.0, 0.5);
+        let matrix = &network.connectivity_matrix;
+
+        // Diagonal é ignorada: cada neurónio tem 5 vizinhos
+        assert_eq!(in_degrees(matrix), vec![5; 6]);
+        assert_eq!(out_degrees(matrix), vec![5; 6]);
+        assert_relative_eq!(clustering_coefficient(matrix), 1.0);
+        assert_relative_eq!(average_shortest_path(matrix).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_grid_degree_distribution() {
+        let network = Network::new(9, ConnectivityType::Grid2D, 0.0, 0.5);
+        let distribution = degree_distribution(&network.connectivity_matrix);
+
+        // Grade 3x3 (Moore): 4 cantos (3), 4 bordas (5), 1 centro (8)
+        assert_eq!(distribution.get(&3), Some(&4));
+        assert_eq!(distribution.get(&5), Some(&4));
+        assert_eq!(distribution.get(&8), Some(&1));
+    }
+
+    #[test]
+    fn test_path_graph() {
+        // Cadeia 0 → 1 → 2 (matrix[i][j] = 1 se i recebe de j)
+        let matrix = vec![vec![0, 0, 0], vec![1, 0, 0], vec![0, 1, 0]];
+
+        // Pares alcançáveis: (0,1)=1, (0,2)=2, (1,2)=1 → média 4/3
+        assert_relative_eq!(average_shortest_path(&matrix).unwrap(), 4.0 / 3.0);
+        assert_relative_eq!(clustering_coefficient(&matrix), 0.0);
+        assert!(average_shortest_path(&[vec![0]]).is_none());
+    }
+}
@@ -0,0 +1,230 @@
+//! Módulo de crescimento desenvolvimental sobre uma reserva fixa
+//!
+//! A rede não muda de tamanho: todos os neurónios são alocados à partida e
+//! `num_neurons()`, as entradas e os vetores de estado mantêm o comprimento
+//! N. O crescimento começa com poucos neurónios ativos (as sementes); os
+//! restantes formam a reserva, tratados como lesionados e sem conexões até
+//! serem ativados. A cada `interval` passos:
+//!
+//! - **Ativação** (análogo da neurogénese): um neurónio da reserva é ativado
+//!   se a novidade média dos seus vizinhos ativos (na topologia original)
+//!   exceder `activation_threshold`; no máximo `max_activations` por ronda,
+//!   os de regiões mais novas primeiro. Ao ser ativado recupera as conexões
+//!   originais com os vizinhos ativos.
+//! - **Sinaptogénese**: pares ativos não conectados cujo traço de coatividade
+//!   (como em `rewiring`) excede `synaptogenesis_threshold` ganham a
+//!   conexão, no máximo `max_synapses` por ronda.
+//!
+//! Cada ativação e sinapse nova fica registada como `GrowthEvent`, e o
+//! tamanho da rede após cada ronda como `GrowthSnapshot` (trajetória de
+//! crescimento, exportável em CSV).
+
+use crate::footprint::{matrix_bytes, vec_bytes};
+use crate::prelude::*;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+/// Parâmetros do crescimento
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrowthParams {
+    /// Passos entre rondas de crescimento
+    pub interval: i64,
+
+    /// Novidade regional acima da qual um neurónio da reserva é ativado
+    pub activation_threshold: f64,
+
+    /// Ativações por ronda
+    pub max_activations: usize,
+
+    /// Taxa da média móvel de coatividade (0, 1]
+    pub trace_rate: f64,
+
+    /// Coatividade acima da qual um par ativo ganha uma conexão
+    pub synaptogenesis_threshold: f64,
+
+    /// Sinapses novas por ronda
+    pub max_synapses: usize,
+}
+
+impl Default for GrowthParams {
+    fn default() -> Self {
+        Self {
+            interval: 10,
+            activation_threshold: 0.3,
+            max_activations: 2,
+            trace_rate: 0.05,
+            synaptogenesis_threshold: 0.2,
+            max_synapses: 4,
+        }
+    }
+}
+
+/// Alteração estrutural feita numa ronda de crescimento
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrowthEvent {
+    /// O neurónio `neuron` saiu da reserva
+    Activation { time: i64, neuron: usize },
+    /// `target` passou a receber input de `source`
+    Synaptogenesis {
+        time: i64,
+        target: usize,
+        source: usize,
+    },
+}
+
+/// Tamanho da rede após uma ronda de crescimento
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrowthSnapshot {
+    pub time: i64,
+    /// Neurónios ativos (fora da reserva)
+    pub active_neurons: usize,
+    /// Conexões entre neurónios (sem auto-conexões)
+    pub synapses: usize,
+}
+
+/// Escreve uma trajetória de crescimento em CSV (`time,active_neurons,synapses`)
+#[cfg(feature = "std")]
+pub fn write_trajectory_csv<W: Write>(
+    trajectory: &[GrowthSnapshot],
+    mut writer: W,
+) -> io::Result<()> {
+    writeln!(writer, "time,active_neurons,synapses")?;
+    for snapshot in trajectory {
+        writeln!(writer, "{},{},{}", snapshot.time, snapshot.active_neurons, snapshot.synapses)?;
+    }
+    writer.flush()
+}
+
+/// Estado do crescimento de uma rede
+#[derive(Debug, Clone)]
+pub(crate) struct Growth {
+    pub(crate) params: GrowthParams,
+    /// Conectividade original, usada para ligar os neurónios ativados
+    pub(crate) template: Vec<Vec<u8>>,
+    coactivity: Vec<Vec<f64>>,
+    previous_firing: Vec<bool>,
+    pub(crate) events: Vec<GrowthEvent>,
+    pub(crate) trajectory: Vec<GrowthSnapshot>,
+}
+
+impl Growth {
+    pub(crate) fn new(template: Vec<Vec<u8>>, params: GrowthParams) -> Self {
+        let n = template.len();
+        Self {
+            params,
+            template,
+            coactivity: vec![vec![0.0; n]; n],
+            previous_firing: vec![false; n],
+            events: Vec::new(),
+            trajectory: Vec::new(),
+        }
+    }
+
+    /// Bytes reservados pelos vetores (ver `footprint`)
+    pub(crate) fn heap_bytes(&self) -> usize {
+        matrix_bytes(&self.template)
+            + matrix_bytes(&self.coactivity)
+            + vec_bytes(&self.previous_firing)
+            + vec_bytes(&self.events)
+            + vec_bytes(&self.trajectory)
+    }
+
+    /// Atualiza os traços de coatividade com os disparos do passo atual
+    pub(crate) fn observe(&mut self, firing: &[bool]) {
+        let rate = self.params.trace_rate;
+        for (row, &post) in self.coactivity.iter_mut().zip(firing) {
+            let pres = firing.iter().zip(&self.previous_firing);
+            for (trace, (&pre_now, &pre_before)) in row.iter_mut().zip(pres) {
+                let event = if post && (pre_now || pre_before) { 1.0 } else { 0.0 };
+                *trace += rate * (event - *trace);
+            }
+        }
+        self.previous_firing.copy_from_slice(firing);
+    }
+
+    /// Neurónios da reserva a ativar, das regiões mais novas primeiro
+    pub(crate) fn plan_activations(&self, novelty: &[f64], reserve: &[bool]) -> Vec<usize> {
+        let mut candidates: Vec<(usize, f64)> = (0..reserve.len())
+            .filter(|&d| reserve[d])
+            .filter_map(|d| {
+                let region: Vec<f64> = (0..reserve.len())
+                    .filter(|&j| j != d && !reserve[j])
+                    .filter(|&j| self.template[d][j] != 0 || self.template[j][d] != 0)
+                    .map(|j| novelty[j])
+                    .collect();
+                if region.is_empty() {
+                    return None;
+                }
+                let regional = region.iter().sum::<f64>() / region.len() as f64;
+                (regional > self.params.activation_threshold).then_some((d, regional))
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+        candidates.into_iter().take(self.params.max_activations).map(|(d, _)| d).collect()
+    }
+
+    /// Pares ativos `(target, source)` não conectados que devem ganhar uma conexão
+    pub(crate) fn plan_synapses(
+        &self,
+        connectivity: &[Vec<u8>],
+        reserve: &[bool],
+    ) -> Vec<(usize, usize)> {
+        let threshold = self.params.synaptogenesis_threshold;
+        let mut candidates: Vec<(usize, usize, f64)> = Vec::new();
+        for (target, row) in connectivity.iter().enumerate() {
+            if reserve[target] {
+                continue;
+            }
+            for (source, &connected) in row.iter().enumerate() {
+                let trace = self.coactivity[target][source];
+                if source != target && connected == 0 && !reserve[source] && trace > threshold {
+                    candidates.push((target, source, trace));
+                }
+            }
+        }
+        candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+        candidates
+            .into_iter()
+            .take(self.params.max_synapses)
+            .map(|(target, source, _)| (target, source))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activations_need_novel_active_neighbours() {
+        // Cadeia 0 - 1 - 2 - 3
+        let template = vec![
+            vec![0, 1, 0, 0],
+            vec![1, 0, 1, 0],
+            vec![0, 1, 0, 1],
+            vec![0, 0, 1, 0],
+        ];
+        let growth = Growth::new(template, GrowthParams::default());
+        let reserve = [false, true, true, true];
+
+        // Só 1 tem um vizinho ativo; é ativado se a região for nova
+        assert_eq!(growth.plan_activations(&[0.8, 0.0, 0.0, 0.0], &reserve), vec![1]);
+        assert!(growth.plan_activations(&[0.1, 0.0, 0.0, 0.0], &reserve).is_empty());
+    }
+
+    #[test]
+    fn test_synapses_between_coactive_active_neurons() {
+        let params = GrowthParams {
+            trace_rate: 1.0,
+            ..GrowthParams::default()
+        };
+        let mut growth = Growth::new(vec![vec![0; 3]; 3], params);
+        growth.observe(&[true, true, true]);
+
+        let connectivity = vec![vec![0, 1, 0], vec![0, 0, 0], vec![0, 0, 0]];
+        let reserve = [false, false, true];
+        let mut plan = growth.plan_synapses(&connectivity, &reserve);
+        plan.sort();
+        assert_eq!(plan, vec![(1, 0)]);
+    }
+}
@@ -0,0 +1,281 @@
+//! Módulo do serviço gRPC de simulação remota
+//!
+//! Expõe o `server::SimulationService` por gRPC (tonic), com as operações
+//! definidas em `proto/nenv.proto`: criar e remover redes, substituir as
+//! entradas, avançar N passos e ler estado e métricas. Partilha o serviço
+//! com o REST, pelo que as redes criadas num transporte são visíveis no
+//! outro (`nenv-server --grpc <endereço>`).
+//!
+//! Disponível com a feature `grpc`.
+
+use crate::server::{CreateRequest, NetworkMetrics, NetworkState, ServiceError, SimulationService};
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::JoinHandle;
+use tokio::sync::oneshot;
+use tonic::transport::server::TcpIncoming;
+use tonic::{Request, Response, Status};
+
+/// Tipos e stubs gerados a partir de `proto/nenv.proto`
+pub mod proto {
+    tonic::include_proto!("nenv");
+}
+
+use proto::simulation_server::{Simulation, SimulationServer};
+
+impl From<ServiceError> for Status {
+    fn from(error: ServiceError) -> Self {
+        match error {
+            ServiceError::NotFound => Status::not_found(error.to_string()),
+            ServiceError::InvalidArgument(message) => Status::invalid_argument(message),
+        }
+    }
+}
+
+impl From<NetworkMetrics> for proto::Metrics {
+    fn from(metrics: NetworkMetrics) -> Self {
+        Self {
+            time: metrics.time,
+            num_firing: metrics.num_firing as u64,
+            avg_energy: metrics.avg_energy,
+            avg_novelty: metrics.avg_novelty,
+            alert_level: metrics.alert_level,
+        }
+    }
+}
+
+impl From<NetworkState> for proto::State {
+    fn from(state: NetworkState) -> Self {
+        Self {
+            time: state.time,
+            grid_width: state.grid_width as u64,
+            firing: state.firing,
+            energy: state.energy,
+            priority: state.priority,
+            novelty: state.novelty,
+        }
+    }
+}
+
+/// Implementação gRPC sobre um `SimulationService` partilhado
+#[derive(Debug, Clone)]
+pub struct GrpcSimulation {
+    service: Arc<Mutex<SimulationService>>,
+}
+
+impl GrpcSimulation {
+    /// Cria o serviço gRPC sobre um serviço existente (p.ex. o do REST)
+    pub fn new(service: Arc<Mutex<SimulationService>>) -> Self {
+        Self { service }
+    }
+
+    /// Executa uma operação com o serviço bloqueado
+    ///
+    /// Um pânico noutro pedido não invalida as redes, por isso um mutex
+    /// envenenado é recuperado em vez de recusar todos os pedidos seguintes.
+    fn with_service<T>(
+        &self,
+        f: impl FnOnce(&mut SimulationService) -> Result<T, ServiceError>,
+    ) -> Result<Response<T>, ServiceError> {
+        let mut service = self.service.lock().unwrap_or_else(PoisonError::into_inner);
+        f(&mut service).map(Response::new)
+    }
+}
+
+#[tonic::async_trait]
+impl Simulation for GrpcSimulation {
+    async fn create_network(
+        &self,
+        request: Request<proto::CreateNetworkRequest>,
+    ) -> Result<Response<proto::NetworkId>, Status> {
+        let request = request.into_inner();
+        let request = CreateRequest {
+            num_neurons: usize::try_from(request.num_neurons)
+                .map_err(|_| Status::invalid_argument("num_neurons demasiado grande"))?,
+            connectivity: request.connectivity,
+            inhibitory_ratio: request.inhibitory_ratio.unwrap_or(0.2),
+            threshold: request.threshold.unwrap_or(0.2),
+            seed: request.seed,
+        };
+        Ok(self.with_service(|s| s.create(request).map(|id| proto::NetworkId { id }))?)
+    }
+
+    async fn list_networks(
+        &self,
+        _request: Request<proto::Empty>,
+    ) -> Result<Response<proto::NetworkList>, Status> {
+        Ok(self.with_service(|s| Ok(proto::NetworkList { ids: s.ids() }))?)
+    }
+
+    async fn delete_network(
+        &self,
+        request: Request<proto::NetworkId>,
+    ) -> Result<Response<proto::Empty>, Status> {
+        let id = request.into_inner().id;
+        Ok(self.with_service(|s| s.delete(id).map(|_| proto::Empty {}))?)
+    }
+
+    async fn set_inputs(
+        &self,
+        request: Request<proto::SetInputsRequest>,
+    ) -> Result<Response<proto::Empty>, Status> {
+        let proto::SetInputsRequest { id, inputs } = request.into_inner();
+        Ok(self.with_service(|s| s.set_inputs(id, inputs).map(|_| proto::Empty {}))?)
+    }
+
+    async fn step(
+        &self,
+        request: Request<proto::StepRequest>,
+    ) -> Result<Response<proto::Metrics>, Status> {
+        let request = request.into_inner();
+        let steps = usize::try_from(request.steps.unwrap_or(1))
+            .map_err(|_| Status::invalid_argument("steps excede o máximo por pedido"))?;
+        let inputs = (!request.inputs.is_empty()).then_some(request.inputs);
+        Ok(self.with_service(|s| s.step(request.id, steps, inputs).map(Into::into))?)
+    }
+
+    async fn get_state(
+        &self,
+        request: Request<proto::NetworkId>,
+    ) -> Result<Response<proto::State>, Status> {
+        let id = request.into_inner().id;
+        Ok(self.with_service(|s| s.state(id).map(Into::into))?)
+    }
+
+    async fn get_metrics(
+        &self,
+        request: Request<proto::NetworkId>,
+    ) -> Result<Response<proto::Metrics>, Status> {
+        let id = request.into_inner().id;
+        Ok(self.with_service(|s| s.metrics(id).map(Into::into))?)
+    }
+}
+
+/// Servidor gRPC que atende um `SimulationService` num thread dedicado
+pub struct GrpcServer {
+    local_addr: SocketAddr,
+    shutdown: Option<oneshot::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl GrpcServer {
+    /// Abre o socket e inicia o runtime do servidor num thread dedicado
+    ///
+    /// # Argumentos
+    /// * `addr` - Endereço de escuta (p.ex. "0.0.0.0:50051"; porta 0 escolhe uma livre)
+    /// * `service` - Serviço partilhado (p.ex. `SimulationServer::service()`)
+    pub fn bind<A: ToSocketAddrs>(
+        addr: A,
+        service: Arc<Mutex<SimulationService>>,
+    ) -> io::Result<Self> {
+        let listener = std::net::TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        listener.set_nonblocking(true)?;
+
+        let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build()?;
+        let incoming = {
+            let _guard = runtime.enter();
+            TcpIncoming::from_listener(tokio::net::TcpListener::from_std(listener)?, true, None)
+                .map_err(io::Error::other)?
+        };
+
+        let (shutdown, stopped) = oneshot::channel::<()>();
+        let handle = std::thread::spawn(move || {
+            let server = tonic::transport::Server::builder()
+                .add_service(SimulationServer::new(GrpcSimulation::new(service)))
+                .serve_with_incoming_shutdown(incoming, async {
+                    let _ = stopped.await;
+                });
+            // Erros de transporte terminam apenas o servidor gRPC
+            let _ = runtime.block_on(server);
+        });
+
+        Ok(Self {
+            local_addr,
+            shutdown: Some(shutdown),
+            handle: Some(handle),
+        })
+    }
+
+    /// Endereço efetivo de escuta
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Bloqueia até o thread servidor terminar
+    pub fn wait(mut self) {
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for GrpcServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proto::simulation_client::SimulationClient;
+    use tonic::Code;
+
+    #[test]
+    fn test_grpc_round_trip_shares_service() {
+        let service = Arc::new(Mutex::new(SimulationService::new()));
+        let server = GrpcServer::bind("127.0.0.1:0", Arc::clone(&service)).unwrap();
+        let endpoint = format!("http://{}", server.local_addr());
+
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            let mut client = SimulationClient::connect(endpoint).await.unwrap();
+
+            let id = client
+                .create_network(proto::CreateNetworkRequest {
+                    num_neurons: 9,
+                    inhibitory_ratio: Some(0.0),
+                    seed: Some(1),
+                    ..Default::default()
+                })
+                .await
+                .unwrap()
+                .into_inner()
+                .id;
+
+            let mut inputs = vec![0.0; 9];
+            inputs[4] = 5.0;
+            client.set_inputs(proto::SetInputsRequest { id, inputs }).await.unwrap();
+            let error = client
+                .set_inputs(proto::SetInputsRequest { id, inputs: vec![1.0] })
+                .await
+                .unwrap_err();
+            assert_eq!(error.code(), Code::InvalidArgument);
+
+            let metrics = client
+                .step(proto::StepRequest { id, steps: Some(3), inputs: vec![] })
+                .await
+                .unwrap()
+                .into_inner();
+            assert_eq!(metrics.time, 3);
+
+            let state = client.get_state(proto::NetworkId { id }).await.unwrap().into_inner();
+            assert_eq!(state.energy.len(), 9);
+            assert_eq!(state.grid_width, 3);
+
+            client.delete_network(proto::NetworkId { id }).await.unwrap();
+            let error = client.get_metrics(proto::NetworkId { id }).await.unwrap_err();
+            assert_eq!(error.code(), Code::NotFound);
+        });
+
+        // As redes vivem no serviço partilhado (o mesmo que o REST usaria)
+        assert!(service.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,176 @@
+//! Módulo de histórico recente da rede
+//!
+//! Mantém, num buffer circular de capacidade fixa, os vetores de disparo,
+//! energia e novidade dos últimos N passos, para ferramentas interativas e
+//! detetores online que precisam de olhar para trás sem armazenamento próprio.
+
+use crate::footprint::vec_bytes;
+use crate::prelude::*;
+use alloc::collections::VecDeque;
+use core::mem::size_of;
+
+/// Estado por neurónio num passo de simulação
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryFrame {
+    /// Passo de tempo do registo
+    pub time: i64,
+
+    /// Estado de disparo de cada neurónio
+    pub firing: Vec<bool>,
+
+    /// Energia de cada neurónio
+    pub energy: Vec<f64>,
+
+    /// Novidade de cada neurónio
+    pub novelty: Vec<f64>,
+}
+
+/// Buffer circular com os últimos passos da simulação
+#[derive(Debug, Clone)]
+pub struct NetworkHistory {
+    capacity: usize,
+    frames: VecDeque<HistoryFrame>,
+}
+
+impl NetworkHistory {
+    /// Cria um histórico que guarda no máximo `capacity` passos (mínimo 1)
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            frames: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Bytes reservados pelos registos (ver `footprint`)
+    pub(crate) fn heap_bytes(&self) -> usize {
+        let frames = self.frames.capacity() * size_of::<HistoryFrame>();
+        let vectors: usize = self
+            .frames
+            .iter()
+            .map(|f| vec_bytes(&f.firing) + vec_bytes(&f.energy) + vec_bytes(&f.novelty))
+            .sum();
+        frames + vectors
+    }
+
+    /// Adiciona um passo, descartando o mais antigo se o buffer estiver cheio
+    pub fn push(&mut self, frame: HistoryFrame) {
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// Capacidade máxima do histórico
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Número de passos atualmente guardados
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Verifica se o histórico está vazio
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Passo mais recente
+    pub fn latest(&self) -> Option<&HistoryFrame> {
+        self.frames.back()
+    }
+
+    /// Passo registado `steps_back` passos antes do mais recente
+    ///
+    /// `steps_back = 0` é o mais recente.
+    pub fn steps_back(&self, steps_back: usize) -> Option<&HistoryFrame> {
+        let idx = self.frames.len().checked_sub(steps_back + 1)?;
+        self.frames.get(idx)
+    }
+
+    /// Itera do passo mais antigo para o mais recente
+    pub fn iter(&self) -> impl Iterator<Item = &HistoryFrame> {
+        self.frames.iter()
+    }
+
+    /// Número de disparos de cada neurónio ao longo do histórico
+    pub fn firing_counts(&self) -> Vec<usize> {
+        let num_neurons = self.latest().map(|f| f.firing.len()).unwrap_or(0);
+        let mut counts = vec![0; num_neurons];
+
+        for frame in &self.frames {
+            for (count, &fired) in counts.iter_mut().zip(frame.firing.iter()) {
+                if fired {
+                    *count += 1;
+                }
+            }
+        }
+
+        counts
+    }
+
+    /// Matriz tempo × neurónios com o estado de disparo (1.0/0.0)
+    ///
+    /// Pronta para `analysis::pca`.
+    pub fn firing_matrix(&self) -> Vec<Vec<f64>> {
+        self.frames
+            .iter()
+            .map(|f| f.firing.iter().map(|&b| if b { 1.0 } else { 0.0 }).collect())
+            .collect()
+    }
+
+    /// Matriz tempo × neurónios com a energia
+    pub fn energy_matrix(&self) -> Vec<Vec<f64>> {
+        self.frames.iter().map(|f| f.energy.clone()).collect()
+    }
+
+    /// Matriz tempo × neurónios com a novidade
+    pub fn novelty_matrix(&self) -> Vec<Vec<f64>> {
+        self.frames.iter().map(|f| f.novelty.clone()).collect()
+    }
+
+    /// Remove todos os passos guardados
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(time: i64, fired: bool) -> HistoryFrame {
+        HistoryFrame {
+            time,
+            firing: vec![fired, false],
+            energy: vec![100.0, 100.0],
+            novelty: vec![0.0, 0.0],
+        }
+    }
+
+    #[test]
+    fn test_ring_buffer_discards_oldest() {
+        let mut history = NetworkHistory::new(3);
+        for t in 0..5 {
+            history.push(frame(t, true));
+        }
+
+        assert_eq!(history.len(), 3);
+        let times: Vec<i64> = history.iter().map(|f| f.time).collect();
+        assert_eq!(times, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_steps_back() {
+        let mut history = NetworkHistory::new(4);
+        for t in 0..3 {
+            history.push(frame(t, t % 2 == 0));
+        }
+
+        assert_eq!(history.steps_back(0).unwrap().time, 2);
+        assert_eq!(history.steps_back(2).unwrap().time, 0);
+        assert!(history.steps_back(3).is_none());
+        assert_eq!(history.firing_counts(), vec![2, 0]);
+    }
+}
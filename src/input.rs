@@ -0,0 +1,181 @@
+//! Módulo de fontes de input externo para `Network::update_from`
+//!
+//! `Network::update` recebe um vetor denso com um valor por neurónio. Um
+//! sensor que só produz alguns valores não nulos por passo teria de
+//! construir esse vetor a cada passo; com `InputSource`, a rede aceita o
+//! input na forma em que ele chega:
+//!
+//! - `&[f64]` / `&Vec<f64>` / `&[f64; N]`: vetor denso, usado sem cópia
+//! - `&[(usize, f64)]` / `&Vec<(usize, f64)>`: pares `(neurónio, valor)`
+//! - `Dense(iter)`: iterador de valores pela ordem dos neurónios
+//! - `Sparse(iter)`: iterador de pares `(neurónio, valor)`
+//!
+//! ```text
+//! network.update_from(&frame);                          // denso, sem cópia
+//! network.update_from(&[(12, 1.0), (40, 0.5)]);         // esparso
+//! network.update_from(Sparse(sensor.events()));          // direto do sensor
+//! ```
+//!
+//! As fontes não densas são escritas num buffer da rede reutilizado entre
+//! passos, do qual só as posições escritas no passo anterior são limpas: o
+//! custo por passo é proporcional ao número de valores, não a N. Índices
+//! repetidos somam-se e índices fora da rede são ignorados, como os valores
+//! a mais em `update`.
+
+use crate::prelude::*;
+
+/// Input externo de um passo de simulação
+pub trait InputSource {
+    /// Vetor denso já materializado, passado a `update` sem cópia
+    fn as_dense(&self) -> Option<&[f64]> {
+        None
+    }
+
+    /// Entrega cada valor como `(neurónio, valor)`
+    fn for_each_value<F: FnMut(usize, f64)>(self, f: F);
+}
+
+impl InputSource for &[f64] {
+    fn as_dense(&self) -> Option<&[f64]> {
+        Some(self)
+    }
+
+    fn for_each_value<F: FnMut(usize, f64)>(self, mut f: F) {
+        for (i, &value) in self.iter().enumerate() {
+            f(i, value);
+        }
+    }
+}
+
+impl InputSource for &Vec<f64> {
+    fn as_dense(&self) -> Option<&[f64]> {
+        Some(self.as_slice())
+    }
+
+    fn for_each_value<F: FnMut(usize, f64)>(self, f: F) {
+        self.as_slice().for_each_value(f)
+    }
+}
+
+impl<const N: usize> InputSource for &[f64; N] {
+    fn as_dense(&self) -> Option<&[f64]> {
+        Some(self.as_slice())
+    }
+
+    fn for_each_value<F: FnMut(usize, f64)>(self, f: F) {
+        self.as_slice().for_each_value(f)
+    }
+}
+
+impl InputSource for &[(usize, f64)] {
+    fn for_each_value<F: FnMut(usize, f64)>(self, mut f: F) {
+        for &(i, value) in self {
+            f(i, value);
+        }
+    }
+}
+
+impl InputSource for &Vec<(usize, f64)> {
+    fn for_each_value<F: FnMut(usize, f64)>(self, f: F) {
+        self.as_slice().for_each_value(f)
+    }
+}
+
+impl<const N: usize> InputSource for &[(usize, f64); N] {
+    fn for_each_value<F: FnMut(usize, f64)>(self, f: F) {
+        self.as_slice().for_each_value(f)
+    }
+}
+
+/// Iterador de valores pela ordem dos neurónios
+#[derive(Debug, Clone)]
+pub struct Dense<I>(pub I);
+
+impl<I: IntoIterator<Item = f64>> InputSource for Dense<I> {
+    fn for_each_value<F: FnMut(usize, f64)>(self, mut f: F) {
+        for (i, value) in self.0.into_iter().enumerate() {
+            f(i, value);
+        }
+    }
+}
+
+/// Iterador de pares `(neurónio, valor)`
+#[derive(Debug, Clone)]
+pub struct Sparse<I>(pub I);
+
+impl<I: IntoIterator<Item = (usize, f64)>> InputSource for Sparse<I> {
+    fn for_each_value<F: FnMut(usize, f64)>(self, mut f: F) {
+        for (i, value) in self.0 {
+            f(i, value);
+        }
+    }
+}
+
+/// Buffer denso reutilizado para fontes não densas
+#[derive(Debug, Clone, Default)]
+pub(crate) struct InputBuffer {
+    values: Vec<f64>,
+    /// Posições escritas desde a última limpeza
+    touched: Vec<usize>,
+}
+
+impl InputBuffer {
+    /// Escreve `source` num vetor de `n` valores e devolve-o
+    pub(crate) fn load<S: InputSource>(&mut self, n: usize, source: S) -> &[f64] {
+        for &i in &self.touched {
+            if let Some(value) = self.values.get_mut(i) {
+                *value = 0.0;
+            }
+        }
+        self.touched.clear();
+        self.values.resize(n, 0.0);
+
+        let (values, touched) = (&mut self.values, &mut self.touched);
+        source.for_each_value(|i, value| {
+            if let Some(slot) = values.get_mut(i) {
+                touched.push(i);
+                *slot += value;
+            }
+        });
+        &self.values
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        crate::footprint::vec_bytes(&self.values) + crate::footprint::vec_bytes(&self.touched)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{ConnectivityType, Network};
+
+    #[test]
+    fn test_buffer_clears_only_previous_positions() {
+        let mut buffer = InputBuffer::default();
+        assert_eq!(buffer.load(4, &[(1, 0.5), (1, 0.25), (9, 1.0)]), &[0.0, 0.75, 0.0, 0.0]);
+        assert_eq!(buffer.load(4, Sparse([(3, 2.0)])), &[0.0, 0.0, 0.0, 2.0]);
+        assert_eq!(buffer.load(4, Dense([1.0, 2.0])), &[1.0, 2.0, 0.0, 0.0]);
+        assert_eq!(buffer.load(4, Sparse(core::iter::empty())), &[0.0; 4]);
+    }
+
+    #[test]
+    fn test_sources_match_dense_update() {
+        let stimulus = |t: usize| vec![(4, 1.0 + t as f64 * 0.1), (7, 0.5)];
+        let mut dense = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 2);
+        let mut sparse = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 2);
+        let mut iterated = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 2);
+
+        for t in 0..30 {
+            let mut inputs = vec![0.0; 9];
+            for (i, value) in stimulus(t) {
+                inputs[i] = value;
+            }
+            dense.update(&inputs);
+            sparse.update_from(&stimulus(t));
+            iterated.update_from(Dense(inputs.iter().copied()));
+        }
+        assert_eq!(sparse.first_difference(&dense, 0.0), None);
+        assert_eq!(iterated.first_difference(&dense, 0.0), None);
+    }
+}
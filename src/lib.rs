@@ -1,21 +1,165 @@
-/// Biblioteca NEN-V (Neurónio-Entrada-Núcleo-Vasos)
-///
-/// Uma arquitetura de rede neural bioinspirada que integra:
-/// - Dendritoma: processamento e aprendizado sináptico
-/// - Glia: modulação metabólica e homeostase
-/// - NENV: neurónios com memória contextual
-/// - Network: orquestração da simulação
+//! Biblioteca NEN-V (Neurónio-Entrada-Núcleo-Vasos)
+//!
+//! Uma arquitetura de rede neural bioinspirada que integra:
+//! - Dendritoma: processamento e aprendizado sináptico
+//! - Glia: modulação metabólica e homeostase
+//! - NENV: neurónios com memória contextual
+//! - Network: orquestração da simulação
+//! - Recorder: registo de sondas ao longo da simulação
+//!
+//! Sem a feature `std` (ativa por omissão) a biblioteca é `no_std` com
+//! `alloc`: ficam o núcleo de simulação (neurónios, rede e os seus
+//! subsistemas) e a aritmética de ponto fixo, sem I/O de ficheiros,
+//! experimentos, análise nem integrações. Os construtores que sorteiam com
+//! `thread_rng` (`Network::new`, `NENV::new`, ...) também exigem `std`; no
+//! núcleo usam-se as variantes `*_seeded` e `with_rng`. As funções de `f64`
+//! que `core` não tem (`sqrt`, `exp`, ...) são implementadas em software.
+//!
+//! ```text
+//! cargo build --lib --no-default-features
+//! ```
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod analysis;
+#[cfg(feature = "std")]
+pub mod chunked;
+pub mod config;
+pub mod context;
+#[cfg(feature = "std")]
+pub mod controls;
+#[cfg(feature = "std")]
+pub mod continual;
+#[cfg(feature = "std")]
+pub mod dashboard;
+#[cfg(feature = "std")]
+pub mod debugger;
 pub mod dendritoma;
+pub mod dropout;
+pub mod ensemble;
+pub mod episodic;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod evaluation;
+#[cfg(feature = "std")]
+pub mod events;
+#[cfg(feature = "std")]
+pub mod evolution;
+#[cfg(feature = "std")]
 pub mod experiments;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fixed;
+pub mod footprint;
 pub mod glia;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "std")]
+pub mod graph;
+pub mod growth;
+pub mod history;
+pub mod input;
+mod math;
+#[cfg(feature = "std")]
+pub mod metrics;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+pub mod multimodal;
 pub mod nenv;
 pub mod network;
+pub mod observer;
+#[cfg(feature = "osc")]
+pub mod osc;
+#[cfg(feature = "plots")]
+pub mod plot_worker;
+pub mod ports;
+pub mod predictive;
+mod prelude;
+pub mod profiling;
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+#[cfg(feature = "std")]
+pub mod protocol;
+#[cfg(feature = "std")]
+pub mod realtime;
+#[cfg(feature = "std")]
+pub mod recorder;
+pub mod reduce;
+pub mod rewiring;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod snapshot;
+pub mod spikes;
+#[cfg(feature = "std")]
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod stimulus;
+#[cfg(feature = "std")]
+pub mod stress;
+#[cfg(feature = "std")]
+pub mod sweep;
+#[cfg(feature = "std")]
+pub mod tensor;
+pub mod tiles;
+pub mod timestep;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(feature = "std")]
+pub mod tuning;
+#[cfg(feature = "plots")]
 pub mod visualization;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "websocket")]
+pub mod websocket;
+pub mod weights;
+#[cfg(feature = "zeromq")]
+pub mod zeromq;
 
 // Re-exporta tipos principais para facilitar uso
+#[cfg(feature = "std")]
+pub use chunked::{ChunkedRecording, ChunkedSink};
+pub use config::{NetworkConfig, ThresholdDistribution};
+pub use context::{ContextBinding, ContextTarget};
+#[cfg(feature = "std")]
+pub use controls::{Control, ControlComparison};
+#[cfg(feature = "std")]
+pub use continual::{ContinualParams, ForgettingReport};
 pub use dendritoma::Dendritoma;
-pub use glia::Glia;
-pub use nenv::{NeuronType, NENV};
-pub use network::{ConnectivityType, Network};
-pub use visualization::SimulationSnapshot;
+pub use dropout::DropoutMode;
+pub use ensemble::BridgeSpec;
+pub use episodic::{Episode, EpisodicBuffer};
+pub use error::NenvError;
+#[cfg(feature = "std")]
+pub use evaluation::{RunReport, SweepSummary};
+#[cfg(feature = "std")]
+pub use evolution::{Evolution, EvolutionParams, Genome};
+pub use footprint::MemoryFootprint;
+pub use glia::{Glia, GliaParams, SpikeFailure};
+pub use growth::{GrowthEvent, GrowthParams, GrowthSnapshot};
+pub use input::InputSource;
+pub use multimodal::ModalityLayout;
+pub use nenv::{NENVBuilder, NeuronType, NENV};
+pub use network::{ConnectivityType, Network, WarmUpParams};
+pub use ports::{InputPort, OutputMode, OutputPort};
+pub use predictive::ForwardModel;
+pub use profiling::{TimingReport, UpdatePhase};
+#[cfg(feature = "std")]
+pub use protocol::Protocol;
+#[cfg(feature = "std")]
+pub use recorder::{Probe, Recorder};
+pub use rewiring::RewiringParams;
+pub use spikes::SpikeTrain;
+#[cfg(feature = "std")]
+pub use sweep::{SweepJob, SweepResults, SweepRunner};
+#[cfg(feature = "std")]
+pub use stimulus::{Stimulus, StimulusSchedule};
+#[cfg(feature = "std")]
+pub use tensor::{StateTensor, StateTensorWriter};
+pub use tiles::TileParams;
+#[cfg(feature = "std")]
+pub use tuning::{Objective, RandomSearch, SweepLog};
+pub use snapshot::SimulationSnapshot;
@@ -1,102 +1,61 @@
-use nen_v_prototipo::{experiments, ConnectivityType, Network};
-use std::fs::File;
-use std::io::Write;
+use nen_v_prototipo::{experiments, stress};
+use std::time::Duration;
 
 fn main() -> std::io::Result<()> {
-    println!("=== NEN-V Protótipo v0.3.0 ===");
-    println!("Experimento 4: Integração Novelty-Alert\n");
-
-    // Executa experimento de integração
-    experiments::novelty_alert_integration_experiment()?;
-
-    Ok(())
-}
+    let args: Vec<String> = std::env::args().skip(1).collect();
 
-/// Experimento de Habituação (Secção 6 do Guia v2)
-///
-/// Testa se um neurónio reduz sua resposta a um estímulo constante
-/// devido ao esgotamento de energia (modulação glial).
-fn run_habituation_experiment() -> std::io::Result<()> {
-    // Configuração da rede
-    const NUM_NEURONS: usize = 100; // Grade 10x10
-    const INHIBITORY_RATIO: f64 = 0.2; // 20% inibitórios
-    const INITIAL_THRESHOLD: f64 = 0.2; // Limiar mais baixo para permitir disparos
-    const MAX_TIME: i64 = 200;
+    // --live: painel no terminal em vez dos arquivos de log e gráficos
+    let live = args.iter().any(|arg| arg == "--live");
 
-    // Cria a rede
-    let mut network = Network::new(
-        NUM_NEURONS,
-        ConnectivityType::Grid2D,
-        INHIBITORY_RATIO,
-        INITIAL_THRESHOLD,
-    );
-
-    println!("Rede criada:");
-    println!("  - {} neurónios (grade 10x10)", NUM_NEURONS);
-    println!("  - {}% inibitórios", (INHIBITORY_RATIO * 100.0) as usize);
-    println!("  - Limiar inicial: {}\n", INITIAL_THRESHOLD);
-
-    // Neurónio alvo: centro da grade (índice 55 em grade 10x10)
-    const TARGET_NEURON: usize = 55;
-
-    println!("Neurónio alvo: {} (centro da grade)", TARGET_NEURON);
-    println!("Estímulo aplicado: t=10 até t=100\n");
-
-    // Cria arquivo de log
-    let mut log_file = File::create("habituation_log.csv")?;
-    writeln!(
-        log_file,
-        "time,target_firing,target_energy,total_firing,avg_energy"
-    )?;
+    println!("=== NEN-V Protótipo v0.3.0 ===");
 
-    // Loop de simulação
-    for t in 0..MAX_TIME {
-        // Gera inputs externos
-        let external_inputs = generate_habituation_stimulus(NUM_NEURONS, t, TARGET_NEURON);
+    // --stress [MiB]: teste de escala (passos/s e memória por tamanho de rede)
+    if let Some(pos) = args.iter().position(|arg| arg == "--stress") {
+        let mut config = stress::StressConfig::default();
+        if let Some(mib) = args.get(pos + 1).and_then(|arg| arg.parse::<usize>().ok()) {
+            config.memory_budget = mib << 20;
+        }
+        println!("Teste de escala (limite {} MiB por rede)\n", config.memory_budget >> 20);
+        println!("\n{}", stress::run_stress(&config));
+        return Ok(());
+    }
 
-        // Atualiza a rede
-        network.update(&external_inputs);
+    // debug [neurónio]: avança passo a passo e inspeciona um neurónio
+    if args.first().map(String::as_str) == Some("debug") {
+        let neuron = args.get(1).and_then(|arg| arg.parse().ok()).unwrap_or(33);
+        return experiments::debug_integration_experiment(neuron);
+    }
 
-        // Coleta dados para análise
-        let target_neuron = &network.neurons[TARGET_NEURON];
-        let target_firing = if target_neuron.is_firing { 1 } else { 0 };
-        let target_energy = target_neuron.glia.energy;
-        let total_firing = network.num_firing();
-        let avg_energy = network.average_energy();
+    // habituation: experimento 1 (habituação a um estímulo constante)
+    if args.first().map(String::as_str) == Some("habituation") {
+        println!("Experimento 1: Habituação\n");
+        return experiments::habituation_experiment();
+    }
 
-        // Salva no log
-        writeln!(
-            log_file,
-            "{},{},{:.2},{},{}",
-            t, target_firing, target_energy, total_firing, avg_energy
-        )?;
+    println!("Experimento 4: Integração Novelty-Alert\n");
 
-        // Imprime progresso a cada 20 passos
-        if t % 20 == 0 {
-            println!(
-                "t={:3} | Alvo: firing={} energia={:5.1} | Rede: firing={:2} energia={:5.1}",
-                t, target_firing, target_energy, total_firing, avg_energy
-            );
-        }
+    // --ws <endereço>: transmite cada passo por WebSocket
+    #[cfg(feature = "websocket")]
+    if let Some(pos) = args.iter().position(|arg| arg == "--ws") {
+        let addr = args.get(pos + 1).map(String::as_str).unwrap_or("127.0.0.1:9001");
+        let server = nen_v_prototipo::websocket::WebSocketServer::bind(addr)?;
+        println!("🌐 WebSocket em ws://{} (Enter para iniciar)", server.local_addr());
+        std::io::stdin().read_line(&mut String::new())?;
+        return experiments::streamed_integration_experiment(&server, Duration::from_millis(50));
     }
 
-    Ok(())
-}
-
-/// Gera estímulo para experimento de habituação
-///
-/// Aplica um sinal constante forte ao neurónio alvo durante um período específico.
-fn generate_habituation_stimulus(
-    num_neurons: usize,
-    time: i64,
-    target_neuron: usize,
-) -> Vec<f64> {
-    let mut inputs = vec![0.0; num_neurons];
+    // --tui: painel ratatui (mapa de calor da grade e gráficos móveis)
+    #[cfg(feature = "tui")]
+    if args.iter().any(|arg| arg == "--tui") {
+        return experiments::tui_integration_experiment(Duration::from_millis(50));
+    }
 
-    // Aplica estímulo forte ao neurónio alvo entre t=10 e t=100
-    if time > 10 && time < 100 {
-        inputs[target_neuron] = 2.0; // Estímulo mais forte para garantir disparo inicial
+    if live {
+        experiments::live_integration_experiment(Duration::from_millis(50))?;
+    } else {
+        // Executa experimento de integração
+        experiments::novelty_alert_integration_experiment()?;
     }
 
-    inputs
+    Ok(())
 }
@@ -0,0 +1,262 @@
+//! Funções matemáticas de `f64` para o núcleo `no_std`
+//!
+//! `core` não inclui `sqrt`, `exp`, `ln`, ... (em `std` vêm da libm do
+//! sistema). Com a feature `std` os métodos inerentes de `f64` são usados
+//! diretamente e este módulo fica vazio; sem ela, os módulos do núcleo
+//! importam `F64Ext`, que implementa as mesmas operações em software:
+//!
+//! - `trunc`, `floor`, `ceil`, `round`: exatos
+//! - `sqrt`: Newton a partir de uma aproximação pelos bits, erro ≤ 1 ulp
+//! - `exp`, `ln`: redução de argumento e séries, erro relativo ~1e-15
+//! - `powi` e `powf` com expoente inteiro: quadrados sucessivos; restantes
+//!   `powf`: `exp(y · ln x)`
+//! - `cos`: redução a [-π, π] e série de Taylor
+
+#[cfg(any(not(feature = "std"), test))]
+mod soft {
+    use core::f64::consts::{LN_2, PI, SQRT_2};
+
+    /// Operações de `f64` que `core` não fornece
+    ///
+    /// Dentro das implementações as chamadas são qualificadas
+    /// (`F64Ext::trunc(x)`): nos testes `std` está disponível e `x.trunc()`
+    /// seria o método inerente.
+    pub(crate) trait F64Ext {
+        fn trunc(self) -> f64;
+        fn floor(self) -> f64;
+        fn ceil(self) -> f64;
+        fn round(self) -> f64;
+        fn sqrt(self) -> f64;
+        fn powi(self, n: i32) -> f64;
+        fn exp(self) -> f64;
+        fn ln(self) -> f64;
+        fn powf(self, y: f64) -> f64;
+        fn cos(self) -> f64;
+    }
+
+    /// 2^52: acima disto todos os `f64` são inteiros
+    const INTEGRAL: f64 = 4_503_599_627_370_496.0;
+
+    /// `x · 2^k` sem perder os resultados subnormais
+    fn scale(mut x: f64, mut k: i32) -> f64 {
+        while k > 1023 {
+            x *= f64::from_bits(0x7fe0_0000_0000_0000); // 2^1023
+            k -= 1023;
+        }
+        while k < -1022 {
+            x *= f64::from_bits(0x0010_0000_0000_0000); // 2^-1022
+            k += 1022;
+        }
+        x * f64::from_bits(((k + 1023) as u64) << 52)
+    }
+
+    impl F64Ext for f64 {
+        fn trunc(self) -> f64 {
+            if self.abs() < INTEGRAL { (self as i64) as f64 } else { self }
+        }
+
+        fn floor(self) -> f64 {
+            let t = F64Ext::trunc(self);
+            if t > self { t - 1.0 } else { t }
+        }
+
+        fn ceil(self) -> f64 {
+            let t = F64Ext::trunc(self);
+            if t < self { t + 1.0 } else { t }
+        }
+
+        /// Arredonda ao inteiro mais próximo, metades para longe de zero
+        fn round(self) -> f64 {
+            let t = F64Ext::trunc(self);
+            if (self - t).abs() >= 0.5 { t + self.signum() } else { t }
+        }
+
+        fn sqrt(self) -> f64 {
+            if self.is_nan() || self < 0.0 {
+                return f64::NAN;
+            }
+            if self == 0.0 || self.is_infinite() {
+                return self;
+            }
+            // Metade do expoente como aproximação inicial, depois Newton
+            let mut y = f64::from_bits((self.to_bits() >> 1) + 0x1ff8_0000_0000_0000);
+            for _ in 0..6 {
+                y = 0.5 * (y + self / y);
+            }
+            y
+        }
+
+        fn powi(self, n: i32) -> f64 {
+            let mut base = self;
+            let mut exponent = n.unsigned_abs();
+            let mut result = 1.0;
+            while exponent > 0 {
+                if exponent & 1 == 1 {
+                    result *= base;
+                }
+                base *= base;
+                exponent >>= 1;
+            }
+            if n < 0 { 1.0 / result } else { result }
+        }
+
+        fn exp(self) -> f64 {
+            if self.is_nan() {
+                return self;
+            }
+            if self > 709.8 {
+                return f64::INFINITY;
+            }
+            if self < -745.2 {
+                return 0.0;
+            }
+            // exp(x) = 2^k · exp(r), |r| ≤ ln2 / 2
+            // ln 2 = LN2_HI + LN2_LO; k · LN2_HI é exato para |k| < 2^11
+            const LN2_HI: f64 = f64::from_bits(0x3fe6_2e42_fee0_0000);
+            const LN2_LO: f64 = f64::from_bits(0x3dea_39ef_3579_3c76);
+            let k = F64Ext::round(self / LN_2);
+            let r = (self - k * LN2_HI) - k * LN2_LO;
+            let mut term = 1.0;
+            let mut sum = 1.0;
+            for i in 1..=16 {
+                term *= r / i as f64;
+                sum += term;
+            }
+            scale(sum, k as i32)
+        }
+
+        fn ln(self) -> f64 {
+            if self.is_nan() || self < 0.0 {
+                return f64::NAN;
+            }
+            if self == 0.0 {
+                return f64::NEG_INFINITY;
+            }
+            if self.is_infinite() {
+                return self;
+            }
+            // x = m · 2^e com m em [√2/2, √2)
+            let (mut x, mut e) = (self, 0i32);
+            if x < f64::MIN_POSITIVE {
+                x *= scale(1.0, 54);
+                e -= 54;
+            }
+            let bits = x.to_bits();
+            e += ((bits >> 52) & 0x7ff) as i32 - 1023;
+            let mut m = f64::from_bits((bits & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000);
+            if m > SQRT_2 {
+                m /= 2.0;
+                e += 1;
+            }
+            // ln m = 2 · atanh(s), s = (m - 1) / (m + 1)
+            let s = (m - 1.0) / (m + 1.0);
+            let s2 = s * s;
+            let mut power = s;
+            let mut sum = 0.0;
+            for k in 0..14 {
+                sum += power / (2 * k + 1) as f64;
+                power *= s2;
+            }
+            2.0 * sum + e as f64 * LN_2
+        }
+
+        fn powf(self, y: f64) -> f64 {
+            if y == 0.0 || self == 1.0 {
+                return 1.0;
+            }
+            if self == 0.0 {
+                return if y > 0.0 { 0.0 } else { f64::INFINITY };
+            }
+            // Expoentes inteiros são exatos por quadrados sucessivos
+            if F64Ext::trunc(y) == y && y.abs() <= i32::MAX as f64 {
+                return F64Ext::powi(self, y as i32);
+            }
+            // Base negativa só tem resultado real para expoentes inteiros
+            if self < 0.0 {
+                return f64::NAN;
+            }
+            F64Ext::exp(y * F64Ext::ln(self))
+        }
+
+        fn cos(self) -> f64 {
+            if !self.is_finite() {
+                return f64::NAN;
+            }
+            let r = self - F64Ext::round(self / (2.0 * PI)) * 2.0 * PI;
+            let r2 = r * r;
+            let mut term = 1.0;
+            let mut sum = 1.0;
+            for k in 1..=18 {
+                term *= -r2 / ((2 * k - 1) * (2 * k)) as f64;
+                sum += term;
+            }
+            sum
+        }
+    }
+}
+
+#[cfg(not(any(feature = "std", test)))]
+pub(crate) use soft::F64Ext;
+
+#[cfg(test)]
+mod tests {
+    use super::soft::F64Ext;
+
+    /// Valores de teste em várias ordens de grandeza, com sinal
+    fn samples() -> Vec<f64> {
+        let mut values = vec![0.0, 0.5, 1.0, 1.5, 2.5, 0.499_999_999_999_999_94, 1e-300, 1e300];
+        for i in 0..400 {
+            let x = (i as f64 * 0.737).sin() * 10f64.powi(i % 7 - 3);
+            values.push(x);
+        }
+        let negatives: Vec<f64> = values.iter().map(|v| -v).collect();
+        values.extend(negatives);
+        values
+    }
+
+    fn close(a: f64, b: f64, tolerance: f64) -> bool {
+        a == b || (a - b).abs() <= tolerance * b.abs().max(f64::MIN_POSITIVE)
+    }
+
+    #[test]
+    fn test_rounding_matches_std() {
+        for x in samples() {
+            assert_eq!(F64Ext::trunc(x), x.trunc(), "trunc({})", x);
+            assert_eq!(F64Ext::floor(x), x.floor(), "floor({})", x);
+            assert_eq!(F64Ext::ceil(x), x.ceil(), "ceil({})", x);
+            assert_eq!(F64Ext::round(x), x.round(), "round({})", x);
+        }
+        assert!(F64Ext::round(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_transcendentals_match_std() {
+        for x in samples() {
+            let a = x.abs();
+            assert!(close(F64Ext::sqrt(a), a.sqrt(), 2.3e-16), "sqrt({})", a);
+            if a > 0.0 {
+                assert!(close(F64Ext::ln(a), a.ln(), 1e-15), "ln({})", a);
+            }
+            if x.abs() < 700.0 {
+                assert!(close(F64Ext::exp(x), x.exp(), 1e-14), "exp({})", x);
+            }
+            if x.abs() < 100.0 {
+                assert!((F64Ext::cos(x) - x.cos()).abs() < 1e-13, "cos({})", x);
+            }
+            for n in [-3, 0, 2, 7] {
+                assert!(close(F64Ext::powi(x, n), x.powi(n), 1e-15), "powi({}, {})", x, n);
+            }
+            if a > 1e-100 && a < 1e100 {
+                for y in [-2.0, 0.5, 1.3] {
+                    assert!(close(F64Ext::powf(a, y), a.powf(y), 1e-13), "powf({}, {})", a, y);
+                }
+            }
+        }
+        assert_eq!(F64Ext::exp(-800.0), 0.0);
+        assert_eq!(F64Ext::exp(800.0), f64::INFINITY);
+        assert_eq!(F64Ext::powf(-2.0, 3.0), -8.0);
+        assert!(F64Ext::powf(-2.0, 0.5).is_nan());
+        assert!(F64Ext::sqrt(-1.0).is_nan());
+        assert_eq!(F64Ext::ln(0.0), f64::NEG_INFINITY);
+    }
+}
@@ -0,0 +1,217 @@
+//! Módulo de métricas de atividade de disparo
+//!
+//! Estatísticas padrão calculadas sobre um `SpikeTrain` (ou sobre séries
+//! de contagens registadas por um `Recorder`), para que as análises sejam
+//! consistentes entre experimentos.
+//!
+//! `roc_auc` avalia detetores (p.ex. novidade em eventos novos vs familiares)
+//! a partir dos scores atribuídos a cada classe.
+
+use crate::spikes::SpikeTrain;
+
+/// Taxa média de disparo da população (disparos por neurónio por passo)
+///
+/// # Argumentos
+/// * `train` - Disparos registados
+/// * `duration` - Duração da observação em passos
+pub fn mean_rate(train: &SpikeTrain, duration: i64) -> f64 {
+    if train.num_neurons() == 0 || duration <= 0 {
+        return 0.0;
+    }
+    train.len() as f64 / (train.num_neurons() as f64 * duration as f64)
+}
+
+/// Coeficiente de variação dos intervalos entre disparos (ISI) de um neurónio
+///
+/// CV = desvio padrão / média dos ISIs. Um trem perfeitamente regular tem
+/// CV = 0; um processo de Poisson tem CV ≈ 1.
+///
+/// # Retorna
+/// `None` se o neurónio tiver menos de dois ISIs
+pub fn isi_cv(train: &SpikeTrain, neuron: usize) -> Option<f64> {
+    let isis: Vec<f64> = train.isis(neuron).into_iter().map(|i| i as f64).collect();
+    if isis.len() < 2 {
+        return None;
+    }
+
+    let (mean, variance) = mean_and_variance(&isis);
+    if mean <= 0.0 {
+        return None;
+    }
+    Some(variance.sqrt() / mean)
+}
+
+/// Fator de Fano de uma série de contagens (variância / média)
+///
+/// Aplica-se diretamente a colunas de contagem registadas, como
+/// `total_firing` de um `MemorySink`.
+///
+/// # Retorna
+/// `None` se a série estiver vazia ou tiver média zero
+pub fn fano_factor_of(counts: &[f64]) -> Option<f64> {
+    if counts.is_empty() {
+        return None;
+    }
+
+    let (mean, variance) = mean_and_variance(counts);
+    if mean <= 0.0 {
+        return None;
+    }
+    Some(variance / mean)
+}
+
+/// Fator de Fano das contagens de disparo da população em janelas fixas
+///
+/// # Argumentos
+/// * `train` - Disparos registados
+/// * `start` - Início da observação (inclusivo)
+/// * `end` - Fim da observação (exclusivo)
+/// * `bin` - Largura de cada janela em passos
+pub fn fano_factor(train: &SpikeTrain, start: i64, end: i64, bin: i64) -> Option<f64> {
+    fano_factor_of(&binned_counts(train, start, end, bin))
+}
+
+/// Fração de neurónios que dispararam pelo menos uma vez na janela `[start, end)`
+pub fn active_fraction(train: &SpikeTrain, start: i64, end: i64) -> f64 {
+    if train.num_neurons() == 0 {
+        return 0.0;
+    }
+
+    let active = train
+        .time_window(start, end)
+        .spike_counts()
+        .iter()
+        .filter(|&&count| count > 0)
+        .count();
+
+    active as f64 / train.num_neurons() as f64
+}
+
+/// Contagens de disparo da população em janelas de largura `bin`
+///
+/// A última janela pode ser parcial e é descartada para não enviesar a variância.
+pub fn binned_counts(train: &SpikeTrain, start: i64, end: i64, bin: i64) -> Vec<f64> {
+    if bin <= 0 || end <= start {
+        return Vec::new();
+    }
+
+    let num_bins = ((end - start) / bin) as usize;
+    let mut counts = vec![0.0; num_bins];
+
+    for &(_, t) in train.events() {
+        if t >= start && t < end {
+            let idx = ((t - start) / bin) as usize;
+            if idx < num_bins {
+                counts[idx] += 1.0;
+            }
+        }
+    }
+
+    counts
+}
+
+/// Área sob a curva ROC de um detetor
+///
+/// Probabilidade de um exemplo positivo receber um score maior que um
+/// negativo (empates contam metade). 0.5 corresponde ao acaso.
+///
+/// # Argumentos
+/// * `positives` - Scores dos exemplos que deviam ser detetados
+/// * `negatives` - Scores dos restantes exemplos
+///
+/// # Retorna
+/// `None` se alguma das classes estiver vazia
+pub fn roc_auc(positives: &[f64], negatives: &[f64]) -> Option<f64> {
+    if positives.is_empty() || negatives.is_empty() {
+        return None;
+    }
+    let mut wins = 0.0;
+    for p in positives {
+        for n in negatives {
+            if p > n {
+                wins += 1.0;
+            } else if p == n {
+                wins += 0.5;
+            }
+        }
+    }
+    Some(wins / (positives.len() * negatives.len()) as f64)
+}
+
+/// Média e variância populacional de uma série
+fn mean_and_variance(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    /// Neurónio 0 dispara a cada `period` passos em [0, duration)
+    fn regular_train(num_neurons: usize, period: i64, duration: i64) -> SpikeTrain {
+        let mut train = SpikeTrain::new(num_neurons);
+        for t in (0..duration).step_by(period as usize) {
+            train.push(0, t);
+        }
+        train
+    }
+
+    #[test]
+    fn test_mean_rate_regular_train() {
+        // 10 disparos em 100 passos, 2 neurónios → 10 / 200 = 0.05
+        let train = regular_train(2, 10, 100);
+        assert_relative_eq!(mean_rate(&train, 100), 0.05, epsilon = 1e-12);
+        assert_eq!(mean_rate(&train, 0), 0.0);
+    }
+
+    #[test]
+    fn test_isi_cv_regular_is_zero() {
+        let train = regular_train(1, 7, 70);
+        assert_relative_eq!(isi_cv(&train, 0).unwrap(), 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_isi_cv_alternating_intervals() {
+        // ISIs alternados 1, 3, 1, 3 → média 2, desvio 1 → CV = 0.5
+        let mut train = SpikeTrain::new(1);
+        for t in [0, 1, 4, 5, 8] {
+            train.push(0, t);
+        }
+        assert_relative_eq!(isi_cv(&train, 0).unwrap(), 0.5, epsilon = 1e-12);
+        assert!(isi_cv(&SpikeTrain::new(1), 0).is_none());
+    }
+
+    #[test]
+    fn test_fano_factor_known_cases() {
+        // Contagens constantes → variância zero
+        let train = regular_train(1, 5, 100);
+        assert_relative_eq!(fano_factor(&train, 0, 100, 10).unwrap(), 0.0, epsilon = 1e-12);
+
+        // Contagens 0, 2, 0, 2 → média 1, variância 1 → F = 1
+        assert_relative_eq!(fano_factor_of(&[0.0, 2.0, 0.0, 2.0]).unwrap(), 1.0);
+        assert!(fano_factor_of(&[0.0, 0.0]).is_none());
+    }
+
+    #[test]
+    fn test_active_fraction() {
+        let mut train = SpikeTrain::new(4);
+        train.push(0, 1);
+        train.push(1, 5);
+        train.push(1, 6);
+
+        assert_relative_eq!(active_fraction(&train, 0, 10), 0.5);
+        assert_relative_eq!(active_fraction(&train, 2, 10), 0.25);
+    }
+
+    #[test]
+    fn test_roc_auc() {
+        assert_relative_eq!(roc_auc(&[0.9, 0.8], &[0.1, 0.2]).unwrap(), 1.0);
+        assert_relative_eq!(roc_auc(&[0.1], &[0.9]).unwrap(), 0.0);
+        assert_relative_eq!(roc_auc(&[0.5, 0.9], &[0.5, 0.1]).unwrap(), 0.875);
+        assert!(roc_auc(&[], &[0.1]).is_none());
+    }
+}
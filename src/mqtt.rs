@@ -0,0 +1,359 @@
+//! Módulo de publicação de alertas por MQTT
+//!
+//! `MqttEventSink` é um cliente MQTT 3.1.1 mínimo (apenas publicação) escrito
+//! sobre `TcpStream`, pensado para usar a NEN-V como monitor de anomalias de
+//! sensores num dispositivo de borda: os eventos de alerta e de novidade do
+//! `EventDetector` são publicados num broker (Mosquitto, EMQX, ...) como JSON,
+//! no mesmo formato do `JsonlEventSink`.
+//!
+//! ```text
+//! tópico              conteúdo
+//! <prefixo>/alert     {"event":"alert_activated","time":..,"alert_level":..}
+//! <prefixo>/novelty   {"event":"novelty_above_threshold","time":..,...}
+//! <prefixo>/score     {"time":..,"avg_novelty":..,"alert_level":..}
+//! ```
+//!
+//! Os disparos individuais e os checkpoints não são publicados. O QoS 1 e 2
+//! são síncronos: `emit` só retorna depois da confirmação do broker.
+//!
+//! Disponível com a feature `mqtt`.
+
+use crate::events::{EventSink, SimulationEvent};
+use crate::network::Network;
+use serde::Serialize;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+/// Nível de garantia de entrega MQTT
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QoS {
+    /// Sem confirmação (QoS 0)
+    #[default]
+    AtMostOnce = 0,
+    /// Confirmado com PUBACK (QoS 1)
+    AtLeastOnce = 1,
+    /// Confirmado com PUBREC/PUBREL/PUBCOMP (QoS 2)
+    ExactlyOnce = 2,
+}
+
+/// Configuração da ligação e da filtragem das publicações
+#[derive(Debug, Clone)]
+pub struct MqttConfig {
+    /// Identificador do cliente no broker
+    pub client_id: String,
+    /// Prefixo dos tópicos (sem `/` final)
+    pub topic_prefix: String,
+    /// Nível de entrega das publicações
+    pub qos: QoS,
+    /// Pede ao broker para reter a última mensagem de cada tópico
+    pub retain: bool,
+    /// Intervalo de keep-alive em segundos (0 desliga)
+    pub keep_alive_secs: u16,
+    /// Credenciais opcionais
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Alert level mínimo para publicar um alerta
+    pub min_alert_level: f64,
+    /// Novidade média mínima para publicar novidade ou score
+    pub min_novelty: f64,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            client_id: "nenv".to_string(),
+            topic_prefix: "nenv".to_string(),
+            qos: QoS::AtMostOnce,
+            retain: false,
+            keep_alive_secs: 60,
+            username: None,
+            password: None,
+            min_alert_level: 0.0,
+            min_novelty: 0.0,
+        }
+    }
+}
+
+/// Score de anomalia contínuo publicado em `<prefixo>/score`
+#[derive(Debug, Clone, Serialize)]
+struct AnomalyScore {
+    time: i64,
+    avg_novelty: f64,
+    alert_level: f64,
+}
+
+/// Destino de eventos que publica alertas e novidade num broker MQTT
+pub struct MqttEventSink {
+    stream: TcpStream,
+    config: MqttConfig,
+    next_packet_id: u16,
+    last_sent: Instant,
+}
+
+impl MqttEventSink {
+    /// Liga-se ao broker e faz o CONNECT
+    ///
+    /// # Argumentos
+    /// * `addr` - Endereço do broker (p.ex. "localhost:1883")
+    /// * `config` - Configuração do cliente
+    pub fn connect<A: ToSocketAddrs>(addr: A, config: MqttConfig) -> io::Result<Self> {
+        let mut stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+        stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+
+        let mut flags = 0x02; // clean session
+        let mut body = Vec::new();
+        write_string(&mut body, "MQTT");
+        body.push(4); // protocolo 3.1.1
+        if config.username.is_some() {
+            flags |= 0x80;
+        }
+        if config.password.is_some() {
+            flags |= 0x40;
+        }
+        body.push(flags);
+        body.extend_from_slice(&config.keep_alive_secs.to_be_bytes());
+        write_string(&mut body, &config.client_id);
+        if let Some(username) = &config.username {
+            write_string(&mut body, username);
+        }
+        if let Some(password) = &config.password {
+            write_string(&mut body, password);
+        }
+        write_packet(&mut stream, 0x10, &body)?;
+
+        let (header, ack) = read_packet(&mut stream)?;
+        if header != 0x20 || ack.len() != 2 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "CONNACK inválido"));
+        }
+        if ack[1] != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::ConnectionRefused,
+                format!("broker recusou a ligação (código {})", ack[1]),
+            ));
+        }
+
+        Ok(Self {
+            stream,
+            config,
+            next_packet_id: 1,
+            last_sent: Instant::now(),
+        })
+    }
+
+    /// Configuração em uso
+    pub fn config(&self) -> &MqttConfig {
+        &self.config
+    }
+
+    /// Publica um payload em `<prefixo>/<subtópico>` com o QoS configurado
+    pub fn publish(&mut self, subtopic: &str, payload: &[u8]) -> io::Result<()> {
+        let qos = self.config.qos as u8;
+        let topic = format!("{}/{}", self.config.topic_prefix, subtopic);
+
+        let mut body = Vec::with_capacity(topic.len() + payload.len() + 4);
+        write_string(&mut body, &topic);
+        let packet_id = self.next_packet_id;
+        if qos > 0 {
+            body.extend_from_slice(&packet_id.to_be_bytes());
+            self.next_packet_id = self.next_packet_id.checked_add(1).unwrap_or(1);
+        }
+        body.extend_from_slice(payload);
+
+        let header = 0x30 | (qos << 1) | self.config.retain as u8;
+        write_packet(&mut self.stream, header, &body)?;
+        self.last_sent = Instant::now();
+
+        match self.config.qos {
+            QoS::AtMostOnce => Ok(()),
+            QoS::AtLeastOnce => self.wait_ack(0x40, packet_id),
+            QoS::ExactlyOnce => {
+                self.wait_ack(0x50, packet_id)?;
+                write_packet(&mut self.stream, 0x62, &packet_id.to_be_bytes())?;
+                self.wait_ack(0x70, packet_id)
+            }
+        }
+    }
+
+    /// Publica o score de anomalia do passo atual se a novidade o justificar
+    ///
+    /// Ao contrário dos eventos do detetor (emitidos na transição), o score é
+    /// publicado em todos os passos com novidade média acima de `min_novelty`.
+    pub fn publish_score(&mut self, network: &Network) -> io::Result<()> {
+        let avg_novelty = network.average_novelty();
+        if avg_novelty < self.config.min_novelty {
+            return Ok(());
+        }
+        let score = AnomalyScore {
+            time: network.current_time_step,
+            avg_novelty,
+            alert_level: network.alert_level,
+        };
+        self.publish("score", &serde_json::to_vec(&score)?)
+    }
+
+    /// Lê pacotes até à confirmação `header` do pacote `packet_id`
+    fn wait_ack(&mut self, header: u8, packet_id: u16) -> io::Result<()> {
+        loop {
+            let (received, body) = read_packet(&mut self.stream)?;
+            // PINGRESP e confirmações antigas são ignorados
+            if received == header && body == packet_id.to_be_bytes() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl EventSink for MqttEventSink {
+    fn emit(&mut self, event: &SimulationEvent) -> io::Result<()> {
+        let subtopic = match event {
+            SimulationEvent::AlertActivated { alert_level, .. }
+                if *alert_level >= self.config.min_alert_level =>
+            {
+                "alert"
+            }
+            SimulationEvent::NoveltyAboveThreshold { avg_novelty, .. }
+                if *avg_novelty >= self.config.min_novelty =>
+            {
+                "novelty"
+            }
+            _ => return Ok(()),
+        };
+        self.publish(subtopic, &serde_json::to_vec(event)?)
+    }
+
+    /// Envia um PINGREQ se a ligação esteve inativa metade do keep-alive
+    fn flush(&mut self) -> io::Result<()> {
+        let keep_alive = Duration::from_secs(self.config.keep_alive_secs as u64);
+        if !keep_alive.is_zero() && self.last_sent.elapsed() >= keep_alive / 2 {
+            write_packet(&mut self.stream, 0xC0, &[])?;
+            self.last_sent = Instant::now();
+        }
+        self.stream.flush()
+    }
+}
+
+impl Drop for MqttEventSink {
+    fn drop(&mut self) {
+        let _ = write_packet(&mut self.stream, 0xE0, &[]);
+    }
+}
+
+/// Escreve uma string UTF-8 com prefixo de comprimento (u16 big-endian)
+fn write_string(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(&(s.len() as u16).to_be_bytes());
+    buf.extend_from_slice(s.as_bytes());
+}
+
+/// Codifica o "remaining length" (7 bits por byte, bit 7 = continuação)
+fn encode_remaining_length(mut len: usize, buf: &mut Vec<u8>) {
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        buf.push(byte);
+        if len == 0 {
+            break;
+        }
+    }
+}
+
+/// Escreve um pacote de controlo completo
+fn write_packet<W: Write>(writer: &mut W, header: u8, body: &[u8]) -> io::Result<()> {
+    let mut packet = Vec::with_capacity(body.len() + 5);
+    packet.push(header);
+    encode_remaining_length(body.len(), &mut packet);
+    packet.extend_from_slice(body);
+    writer.write_all(&packet)
+}
+
+/// Lê um pacote de controlo
+///
+/// # Retorna
+/// Par `(byte de cabeçalho, corpo)`
+fn read_packet<R: Read>(reader: &mut R) -> io::Result<(u8, Vec<u8>)> {
+    let mut byte = [0u8; 1];
+    reader.read_exact(&mut byte)?;
+    let header = byte[0];
+
+    let mut len = 0usize;
+    for shift in 0..4 {
+        reader.read_exact(&mut byte)?;
+        len |= ((byte[0] & 0x7F) as usize) << (7 * shift);
+        if byte[0] & 0x80 == 0 {
+            let mut body = vec![0; len];
+            reader.read_exact(&mut body)?;
+            return Ok((header, body));
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::InvalidData, "remaining length inválido"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_remaining_length_encoding() {
+        let mut buf = Vec::new();
+        encode_remaining_length(321, &mut buf);
+        assert_eq!(buf, vec![0xC1, 0x02]);
+
+        let mut packet = Vec::new();
+        write_packet(&mut packet, 0x30, &[7; 321]).unwrap();
+        let (header, body) = read_packet(&mut packet.as_slice()).unwrap();
+        assert_eq!(header, 0x30);
+        assert_eq!(body.len(), 321);
+    }
+
+    #[test]
+    fn test_qos1_alert_publish_against_fake_broker() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // Broker mínimo: aceita o CONNECT, confirma e devolve o PUBLISH recebido
+        let broker = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let (header, _) = read_packet(&mut stream).unwrap();
+            assert_eq!(header, 0x10);
+            write_packet(&mut stream, 0x20, &[0, 0]).unwrap();
+
+            let (header, body) = read_packet(&mut stream).unwrap();
+            let topic_len = u16::from_be_bytes([body[0], body[1]]) as usize;
+            let packet_id = &body[2 + topic_len..4 + topic_len];
+            write_packet(&mut stream, 0x40, packet_id).unwrap();
+            (header, body)
+        });
+
+        let config = MqttConfig {
+            topic_prefix: "sensores/nenv".to_string(),
+            qos: QoS::AtLeastOnce,
+            min_alert_level: 0.5,
+            ..MqttConfig::default()
+        };
+        let mut sink = MqttEventSink::connect(addr, config).unwrap();
+
+        // Abaixo do limiar: não publicado
+        sink.emit(&SimulationEvent::AlertActivated {
+            time: 1,
+            alert_level: 0.2,
+        })
+        .unwrap();
+        sink.emit(&SimulationEvent::AlertActivated {
+            time: 2,
+            alert_level: 0.8,
+        })
+        .unwrap();
+
+        let (header, body) = broker.join().unwrap();
+        assert_eq!(header, 0x32);
+        let topic_len = u16::from_be_bytes([body[0], body[1]]) as usize;
+        assert_eq!(&body[2..2 + topic_len], b"sensores/nenv/alert");
+        let payload: serde_json::Value = serde_json::from_slice(&body[4 + topic_len..]).unwrap();
+        assert_eq!(payload["time"], 2);
+    }
+}
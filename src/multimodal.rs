@@ -0,0 +1,84 @@
+//! Módulo de fusão multimodal
+//!
+//! Duas modalidades independentes (p.ex. som e imagem) ocupam regiões
+//! disjuntas da grade, separadas por uma região ponte de `bridge_columns`
+//! colunas:
+//!
+//! ```text
+//! colunas:  [ primeira modalidade | ponte | segunda modalidade ]
+//! ```
+//!
+//! `Network::define_modalities` cria um grupo e uma porta de entrada por
+//! modalidade (com o nome da modalidade), o grupo `BRIDGE_GROUP` para a
+//! ponte, e remove as conexões diretas entre as duas modalidades, para que
+//! a interação cruzada passe apenas pela ponte. Cada fluxo é então enviado
+//! com `stimulate_pattern` (um valor por neurónio da região).
+//!
+//! Em redes sem grade, os índices são tratados como uma única linha.
+
+use crate::prelude::*;
+
+/// Nome do grupo da região ponte
+pub const BRIDGE_GROUP: &str = "bridge";
+
+/// Regiões de duas modalidades e da ponte entre elas
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModalityLayout {
+    /// Neurónios da primeira modalidade (colunas à esquerda)
+    pub first: Vec<usize>,
+
+    /// Neurónios da ponte (colunas centrais)
+    pub bridge: Vec<usize>,
+
+    /// Neurónios da segunda modalidade (colunas à direita)
+    pub second: Vec<usize>,
+}
+
+impl ModalityLayout {
+    /// Divide `num_neurons` neurónios por colunas de uma grade de largura `width`
+    ///
+    /// As colunas restantes após a ponte são repartidas ao meio (a coluna
+    /// extra, se houver, fica na segunda modalidade). Uma ponte mais larga
+    /// que a grade ocupa todas as colunas.
+    pub fn split_columns(num_neurons: usize, width: usize, bridge_columns: usize) -> Self {
+        let width = if width == 0 { num_neurons } else { width };
+        let bridge_columns = bridge_columns.min(width);
+        let first_columns = (width - bridge_columns) / 2;
+        let bridge_end = first_columns + bridge_columns;
+
+        let mut layout = Self {
+            first: Vec::new(),
+            bridge: Vec::new(),
+            second: Vec::new(),
+        };
+        for i in 0..num_neurons {
+            let col = i % width.max(1);
+            if col < first_columns {
+                layout.first.push(i);
+            } else if col < bridge_end {
+                layout.bridge.push(i);
+            } else {
+                layout.second.push(i);
+            }
+        }
+        layout
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_columns() {
+        // Grade 5×2: colunas 0-1 | 2 | 3-4
+        let layout = ModalityLayout::split_columns(10, 5, 1);
+        assert_eq!(layout.first, vec![0, 1, 5, 6]);
+        assert_eq!(layout.bridge, vec![2, 7]);
+        assert_eq!(layout.second, vec![3, 4, 8, 9]);
+
+        // Sem grade: uma só linha
+        let line = ModalityLayout::split_columns(5, 0, 2);
+        assert_eq!((line.first.len(), line.bridge.len(), line.second.len()), (1, 2, 2));
+    }
+}
@@ -1,10 +1,15 @@
-/// Módulo que implementa o neurónio NENV (Neurónio-Entrada-Núcleo-Vasos)
-///
-/// O NENV é a unidade central da arquitetura, integrando o Dendritoma (entrada),
-/// a Glia (modulação metabólica) e memória contextual.
+//! Módulo que implementa o neurónio NENV (Neurónio-Entrada-Núcleo-Vasos)
+//!
+//! O NENV é a unidade central da arquitetura, integrando o Dendritoma (entrada),
+//! a Glia (modulação metabólica) e memória contextual.
 
+use crate::config::NetworkConfig;
 use crate::dendritoma::Dendritoma;
-use crate::glia::Glia;
+use crate::error::{check_len, Result};
+use crate::glia::{Glia, GliaParams};
+use crate::prelude::*;
+use crate::timestep::{decay_per_step, duration_in_steps};
+use rand::Rng;
 
 /// Tipo de neurónio: Excitatório ou Inibitório
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -45,6 +50,9 @@ pub struct NENV {
     /// Sinal de saída (+1.0 para excitatório, -1.0 para inibitório, 0.0 se não disparou)
     pub output_signal: f64,
 
+    /// Novidade do input calculada no último passo da rede
+    pub novelty: f64,
+
     // Parâmetros de dinâmica
     refractory_period: i64,
     memory_alpha: f64,
@@ -58,37 +66,91 @@ impl NENV {
     /// * `num_inputs` - Número de conexões de entrada
     /// * `initial_threshold` - Limiar de disparo inicial
     /// * `neuron_type` - Tipo do neurónio (excitatório ou inibitório)
+    #[cfg(feature = "std")]
     pub fn new(
         id: usize,
         num_inputs: usize,
         initial_threshold: f64,
         neuron_type: NeuronType,
     ) -> Self {
+        Self::with_rng(id, num_inputs, initial_threshold, neuron_type, &mut rand::thread_rng())
+    }
+
+    /// Cria um neurónio NENV com pesos iniciais sorteados do gerador fornecido
+    ///
+    /// # Argumentos
+    /// * `id` - Identificador único
+    /// * `num_inputs` - Número de conexões de entrada
+    /// * `initial_threshold` - Limiar de disparo inicial
+    /// * `neuron_type` - Tipo do neurónio (excitatório ou inibitório)
+    /// * `rng` - Gerador usado na inicialização dos pesos
+    pub fn with_rng<R: Rng + ?Sized>(
+        id: usize,
+        num_inputs: usize,
+        initial_threshold: f64,
+        neuron_type: NeuronType,
+        rng: &mut R,
+    ) -> Self {
+        let defaults = NetworkConfig::default();
         Self {
             id,
             neuron_type,
-            dendritoma: Dendritoma::new(num_inputs),
+            dendritoma: Dendritoma::with_rng(num_inputs, rng),
             glia: Glia::new(),
             memory_trace: vec![0.0; num_inputs],
             last_fire_time: -1,
             threshold: initial_threshold,
             is_firing: false,
             output_signal: 0.0,
-            refractory_period: 5,
-            memory_alpha: 0.1,
+            novelty: 0.0,
+            refractory_period: defaults.refractory_period,
+            memory_alpha: defaults.memory_alpha,
+        }
+    }
+
+    /// Inicia um builder com todos os parâmetros de dinâmica configuráveis
+    ///
+    /// Os valores por omissão coincidem com `NENV::new` (excitatório,
+    /// limiar 0.5 e os parâmetros de `NetworkConfig::default()`).
+    pub fn builder(id: usize, num_inputs: usize) -> NENVBuilder {
+        let defaults = NetworkConfig::default();
+        NENVBuilder {
+            id,
+            num_inputs,
+            neuron_type: NeuronType::Excitatory,
+            threshold: 0.5,
+            refractory_period: defaults.refractory_period,
+            memory_alpha: defaults.memory_alpha,
+            learning_rate: defaults.learning_rate,
+            glia_params: defaults.glia,
         }
     }
 
     /// Cria um neurónio excitatório
+    #[cfg(feature = "std")]
     pub fn excitatory(id: usize, num_inputs: usize, initial_threshold: f64) -> Self {
         Self::new(id, num_inputs, initial_threshold, NeuronType::Excitatory)
     }
 
     /// Cria um neurónio inibitório
+    #[cfg(feature = "std")]
     pub fn inhibitory(id: usize, num_inputs: usize, initial_threshold: f64) -> Self {
         Self::new(id, num_inputs, initial_threshold, NeuronType::Inhibitory)
     }
 
+    /// Limpa o estado dinâmico mantendo os pesos aprendidos
+    ///
+    /// Repõe disparo, saída, novidade, último disparo, memória contextual e a
+    /// Glia (energia máxima). Limiar, tipo e parâmetros de dinâmica mantêm-se.
+    pub fn reset_state(&mut self) {
+        self.glia.reset();
+        self.memory_trace.fill(0.0);
+        self.last_fire_time = -1;
+        self.is_firing = false;
+        self.output_signal = 0.0;
+        self.novelty = 0.0;
+    }
+
     /// Decide se o neurónio deve disparar baseado no potencial modulado
     ///
     /// # Argumentos
@@ -127,19 +189,65 @@ impl NENV {
     ///
     /// # Argumentos
     /// * `inputs` - Vetor de sinais de entrada atual
+    ///
+    /// # Panics
+    /// Se `inputs.len()` for diferente do tamanho da memória
+    #[deprecated(note = "entra em pânico com comprimentos diferentes; usar `try_update_memory`")]
     pub fn update_memory(&mut self, inputs: &[f64]) {
+        self.update_memory_with_alpha(inputs, self.memory_alpha);
+    }
+
+    /// Como `update_memory`, com uma taxa de atualização explícita neste passo
+    ///
+    /// Permite modular a velocidade de habituação (p.ex. durante o alerta)
+    /// sem alterar o `memory_alpha` guardado.
+    ///
+    /// # Panics
+    /// Se `inputs.len()` for diferente do tamanho da memória
+    pub fn update_memory_with_alpha(&mut self, inputs: &[f64], alpha: f64) {
         assert_eq!(
             inputs.len(),
             self.memory_trace.len(),
             "Número de inputs deve ser igual ao tamanho da memória"
         );
 
-        for i in 0..self.memory_trace.len() {
-            self.memory_trace[i] =
-                (1.0 - self.memory_alpha) * self.memory_trace[i] + self.memory_alpha * inputs[i];
+        for (memory, &input) in self.memory_trace.iter_mut().zip(inputs.iter()) {
+            *memory = (1.0 - alpha) * *memory + alpha * input;
         }
     }
 
+    /// Como `update_memory_with_alpha`, atualizando apenas as posições `indices`
+    ///
+    /// Fora de `indices` o input tem de ser zero: aí a memória só decai, e
+    /// uma memória já a zero fica a zero, pelo que basta visitar as posições
+    /// com input ou memória diferentes de zero.
+    ///
+    /// # Panics
+    /// Se `inputs.len()` for diferente do tamanho da memória ou algum índice
+    /// estiver fora dela
+    pub fn update_memory_sparse(&mut self, inputs: &[f64], indices: &[usize], alpha: f64) {
+        assert_eq!(
+            inputs.len(),
+            self.memory_trace.len(),
+            "Número de inputs deve ser igual ao tamanho da memória"
+        );
+
+        for &j in indices {
+            let memory = &mut self.memory_trace[j];
+            *memory = (1.0 - alpha) * *memory + alpha * inputs[j];
+        }
+    }
+
+    /// Atualiza a memória contextual, devolvendo erro se o comprimento não coincidir
+    ///
+    /// # Retorna
+    /// `LengthMismatch` se `inputs.len()` for diferente do tamanho da memória
+    pub fn try_update_memory(&mut self, inputs: &[f64]) -> Result<()> {
+        check_len("inputs", self.memory_trace.len(), inputs.len())?;
+        self.update_memory_with_alpha(inputs, self.memory_alpha);
+        Ok(())
+    }
+
     /// Calcula a novidade do padrão de entrada atual
     ///
     /// Novidade é medida como a diferença absoluta média entre o input atual
@@ -151,12 +259,22 @@ impl NENV {
     ///
     /// # Retorna
     /// Valor de novidade [0.0, ∞), onde 0 = completamente familiar
+    ///
+    /// # Panics
+    /// Se `inputs.len()` for diferente do tamanho da memória
+    #[deprecated(note = "entra em pânico com comprimentos diferentes; usar `try_compute_novelty`")]
     pub fn compute_novelty(&self, inputs: &[f64]) -> f64 {
         assert_eq!(
             inputs.len(),
             self.memory_trace.len(),
             "Número de inputs deve ser igual ao tamanho da memória"
         );
+        self.dense_novelty(inputs)
+    }
+
+    /// Diferença absoluta média sem validação, para chamadores que já garantem o comprimento
+    pub(crate) fn dense_novelty(&self, inputs: &[f64]) -> f64 {
+        debug_assert_eq!(inputs.len(), self.memory_trace.len());
 
         // Calcula diferença absoluta média entre input e memória
         let total_diff: f64 = inputs
@@ -169,6 +287,40 @@ impl NENV {
         total_diff / inputs.len() as f64
     }
 
+    /// Como `compute_novelty`, somando apenas as posições `indices`
+    ///
+    /// O resultado é idêntico (bit a bit, com `indices` em ordem crescente)
+    /// quando input e memória são zero em todas as outras posições, e o custo
+    /// passa a ser proporcional a `indices.len()`. A normalização continua a
+    /// ser pelo número total de inputs.
+    ///
+    /// # Panics
+    /// Se `inputs.len()` for diferente do tamanho da memória ou algum índice
+    /// estiver fora dela
+    pub fn compute_novelty_sparse(&self, inputs: &[f64], indices: &[usize]) -> f64 {
+        assert_eq!(
+            inputs.len(),
+            self.memory_trace.len(),
+            "Número de inputs deve ser igual ao tamanho da memória"
+        );
+
+        let total_diff: f64 = indices
+            .iter()
+            .map(|&j| (inputs[j] - self.memory_trace[j]).abs())
+            .sum();
+        total_diff / inputs.len() as f64
+    }
+
+    /// Calcula a novidade, devolvendo erro se o comprimento não coincidir
+    ///
+    /// # Retorna
+    /// A novidade, ou `LengthMismatch` se `inputs.len()` for diferente do
+    /// tamanho da memória
+    pub fn try_compute_novelty(&self, inputs: &[f64]) -> Result<f64> {
+        check_len("inputs", self.memory_trace.len(), inputs.len())?;
+        Ok(self.dense_novelty(inputs))
+    }
+
     /// Atualiza o priority da Glia baseado na novidade do input
     ///
     /// Priority aumenta com novidade, tornando o neurónio mais sensível
@@ -187,6 +339,25 @@ impl NENV {
         self.glia.priority = self.glia.priority.min(3.0);
     }
 
+    /// Atualiza a priority como acumulador com fuga da novidade
+    ///
+    /// O excesso de priority sobre a base decai a cada passo e soma a
+    /// novidade atual, pelo que a atenção a um neurónio recentemente novo
+    /// persiste depois de o input se repetir.
+    ///
+    /// Fórmula: priority = 1.0 + (1 - decay) * (priority - 1.0) + novelty * sensitivity_factor
+    ///
+    /// Com `decay = 1.0` equivale a `update_priority`.
+    ///
+    /// # Argumentos
+    /// * `novelty` - Valor de novidade calculado
+    /// * `sensitivity_factor` - Multiplicador de sensibilidade
+    /// * `decay` - Fração do excesso perdida por passo [0.0, 1.0]
+    pub fn accumulate_priority(&mut self, novelty: f64, sensitivity_factor: f64, decay: f64) {
+        let retained = (1.0 - decay.clamp(0.0, 1.0)) * (self.glia.priority - 1.0);
+        self.glia.priority = (1.0 + retained + novelty * sensitivity_factor).min(3.0);
+    }
+
     /// Processa um passo completo de atualização do neurónio
     ///
     /// Esta função encapsula o fluxo completo:
@@ -202,9 +373,19 @@ impl NENV {
     ///
     /// # Retorna
     /// O sinal de saída do neurónio
+    ///
+    /// # Panics
+    /// Se `inputs.len()` for diferente do número de pesos ou do tamanho da
+    /// memória (ver `try_step`)
     pub fn step(&mut self, inputs: &[f64], current_time: i64) -> f64 {
+        assert_eq!(
+            inputs.len(),
+            self.dendritoma.num_inputs(),
+            "Número de inputs deve ser igual ao número de pesos"
+        );
+
         // Fase 1: Integração
-        let integrated_potential = self.dendritoma.integrate(inputs);
+        let integrated_potential = self.dendritoma.weighted_sum(inputs);
 
         // Fase 2: Modulação glial
         let modulated_potential = self.glia.modulate(integrated_potential);
@@ -219,16 +400,31 @@ impl NENV {
 
         // Fase 5: Atualização de estado
         self.glia.update_state(self.is_firing);
-        self.update_memory(inputs);
+        self.update_memory_with_alpha(inputs, self.memory_alpha);
 
         self.output_signal
     }
 
+    /// Versão de `step` que valida o comprimento dos inputs antes de alterar o estado
+    pub fn try_step(&mut self, inputs: &[f64], current_time: i64) -> Result<f64> {
+        check_len("inputs", self.dendritoma.num_inputs(), inputs.len())?;
+        check_len("inputs", self.memory_trace.len(), inputs.len())?;
+        Ok(self.step(inputs, current_time))
+    }
+
     /// Retorna o potencial modulado atual sem modificar o estado
     ///
     /// Útil para debugging e visualização
+    ///
+    /// # Panics
+    /// Se `inputs.len()` for diferente do número de pesos
     pub fn get_modulated_potential(&self, inputs: &[f64]) -> f64 {
-        let integrated = self.dendritoma.integrate(inputs);
+        assert_eq!(
+            inputs.len(),
+            self.dendritoma.num_inputs(),
+            "Número de inputs deve ser igual ao número de pesos"
+        );
+        let integrated = self.dendritoma.weighted_sum(inputs);
         self.glia.modulate(integrated)
     }
 
@@ -237,20 +433,123 @@ impl NENV {
         self.refractory_period = period;
     }
 
+    /// Período refratário atual
+    pub fn refractory_period(&self) -> i64 {
+        self.refractory_period
+    }
+
+    /// Taxa de atualização da memória atual
+    pub fn memory_alpha(&self) -> f64 {
+        self.memory_alpha
+    }
+
     /// Define a taxa de atualização da memória
     pub fn set_memory_alpha(&mut self, alpha: f64) {
         self.memory_alpha = alpha.clamp(0.0, 1.0);
     }
 }
 
+/// Builder de `NENV` (ver `NENV::builder`)
+#[derive(Debug, Clone)]
+pub struct NENVBuilder {
+    id: usize,
+    num_inputs: usize,
+    neuron_type: NeuronType,
+    threshold: f64,
+    refractory_period: i64,
+    memory_alpha: f64,
+    learning_rate: f64,
+    glia_params: GliaParams,
+}
+
+impl NENVBuilder {
+    /// Tipo do neurónio
+    pub fn with_type(mut self, neuron_type: NeuronType) -> Self {
+        self.neuron_type = neuron_type;
+        self
+    }
+
+    /// Limiar de disparo inicial
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Período refratário em passos
+    pub fn with_refractory_period(mut self, period: i64) -> Self {
+        self.refractory_period = period;
+        self
+    }
+
+    /// Taxa de atualização da memória contextual (limitada a [0, 1])
+    pub fn with_memory_alpha(mut self, alpha: f64) -> Self {
+        self.memory_alpha = alpha.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Taxa de aprendizado Hebbiano do Dendritoma
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    /// Aplica refratário, memory_alpha, learning_rate e Glia de uma configuração
+    ///
+    /// Os valores por ms da configuração são convertidos para o passo
+    /// `config.dt` (ver `timestep`).
+    pub fn with_config(self, config: &NetworkConfig) -> Self {
+        let dt = config.dt;
+        self.with_refractory_period(duration_in_steps(config.refractory_period, dt))
+            .with_memory_alpha(decay_per_step(config.memory_alpha, dt))
+            .with_learning_rate(config.learning_rate)
+            .with_glia_params(config.glia.per_step(dt))
+    }
+
+    /// Constantes metabólicas da Glia (a energia inicial é o máximo)
+    pub fn with_glia_params(mut self, params: GliaParams) -> Self {
+        self.glia_params = params;
+        self
+    }
+
+    /// Cria o neurónio com pesos iniciais aleatórios
+    #[cfg(feature = "std")]
+    pub fn build(self) -> NENV {
+        self.build_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Cria o neurónio com pesos iniciais sorteados do gerador fornecido
+    pub fn build_with_rng<R: Rng + ?Sized>(self, rng: &mut R) -> NENV {
+        let mut neuron =
+            NENV::with_rng(self.id, self.num_inputs, self.threshold, self.neuron_type, rng);
+        neuron.refractory_period = self.refractory_period;
+        neuron.memory_alpha = self.memory_alpha;
+        neuron.dendritoma.set_learning_rate(self.learning_rate);
+        let p = self.glia_params;
+        neuron.glia = Glia::with_params(
+            p.max_energy,
+            p.energy_cost_fire,
+            p.energy_cost_maintenance,
+            p.energy_recovery_rate,
+        );
+        neuron
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::NenvError;
     use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn rng() -> StdRng {
+        StdRng::seed_from_u64(0)
+    }
 
     #[test]
     fn test_nenv_initialization() {
-        let neuron = NENV::excitatory(0, 10, 0.5);
+        let neuron = NENV::with_rng(0, 10, 0.5, NeuronType::Excitatory, &mut rng());
 
         assert_eq!(neuron.id, 0);
         assert_eq!(neuron.neuron_type, NeuronType::Excitatory);
@@ -262,7 +561,7 @@ mod tests {
 
     #[test]
     fn test_excitatory_neuron_output() {
-        let mut neuron = NENV::excitatory(0, 2, 1.5); // Limiar ajustado
+        let mut neuron = NENV::with_rng(0, 2, 1.5, NeuronType::Excitatory, &mut rng()); // Limiar ajustado
 
         // Configura pesos não normalizados para garantir disparo
         // potencial = 1.0*1.0 + 1.0*1.0 = 2.0
@@ -280,7 +579,7 @@ mod tests {
 
     #[test]
     fn test_inhibitory_neuron_output() {
-        let mut neuron = NENV::inhibitory(0, 2, 1.5); // Limiar ajustado
+        let mut neuron = NENV::with_rng(0, 2, 1.5, NeuronType::Inhibitory, &mut rng()); // Limiar ajustado
 
         // Configura pesos não normalizados para garantir disparo
         neuron.dendritoma.weights = vec![1.0, 1.0];
@@ -296,7 +595,7 @@ mod tests {
 
     #[test]
     fn test_refractory_period() {
-        let mut neuron = NENV::excitatory(0, 2, 1.5); // Limiar ajustado
+        let mut neuron = NENV::with_rng(0, 2, 1.5, NeuronType::Excitatory, &mut rng()); // Limiar ajustado
         neuron.dendritoma.weights = vec![1.0, 1.0];
         neuron.glia.priority = 1.0;
         neuron.set_refractory_period(5);
@@ -321,18 +620,18 @@ mod tests {
 
     #[test]
     fn test_memory_update() {
-        let mut neuron = NENV::excitatory(0, 3, 0.5);
+        let mut neuron = NENV::with_rng(0, 3, 0.5, NeuronType::Excitatory, &mut rng());
         neuron.set_memory_alpha(0.5); // Alta taxa para teste rápido
 
         let inputs1 = vec![1.0, 0.0, 0.0];
-        neuron.update_memory(&inputs1);
+        neuron.try_update_memory(&inputs1).unwrap();
 
         // Após uma atualização, memória deve ser 0.5 * inputs1
         assert_relative_eq!(neuron.memory_trace[0], 0.5, epsilon = 1e-10);
         assert_relative_eq!(neuron.memory_trace[1], 0.0, epsilon = 1e-10);
 
         let inputs2 = vec![0.0, 1.0, 0.0];
-        neuron.update_memory(&inputs2);
+        neuron.try_update_memory(&inputs2).unwrap();
 
         // Memória do primeiro canal decai, segundo canal aumenta
         assert_relative_eq!(neuron.memory_trace[0], 0.25, epsilon = 1e-10);
@@ -341,7 +640,7 @@ mod tests {
 
     #[test]
     fn test_threshold_prevents_firing() {
-        let mut neuron = NENV::excitatory(0, 2, 10.0); // Limiar muito alto
+        let mut neuron = NENV::with_rng(0, 2, 10.0, NeuronType::Excitatory, &mut rng()); // Limiar muito alto
         neuron.dendritoma.weights = vec![0.5, 0.5];
 
         let inputs = vec![1.0, 1.0];
@@ -353,7 +652,7 @@ mod tests {
 
     #[test]
     fn test_energy_depletion_prevents_firing() {
-        let mut neuron = NENV::excitatory(0, 2, 0.1);
+        let mut neuron = NENV::with_rng(0, 2, 0.1, NeuronType::Excitatory, &mut rng());
         neuron.dendritoma.weights = vec![1.0, 1.0];
         neuron.glia.energy = 0.0; // Sem energia
 
@@ -371,14 +670,14 @@ mod tests {
 
     #[test]
     fn test_compute_novelty_zero_for_familiar() {
-        let mut neuron = NENV::excitatory(0, 3, 0.5);
+        let mut neuron = NENV::with_rng(0, 3, 0.5, NeuronType::Excitatory, &mut rng());
 
         // Define memória como um padrão específico
         neuron.memory_trace = vec![0.5, 0.3, 0.2];
 
         // Input idêntico à memória
         let inputs = vec![0.5, 0.3, 0.2];
-        let novelty = neuron.compute_novelty(&inputs);
+        let novelty = neuron.try_compute_novelty(&inputs).unwrap();
 
         // Novidade deve ser zero (completamente familiar)
         assert_relative_eq!(novelty, 0.0, epsilon = 1e-10);
@@ -386,36 +685,100 @@ mod tests {
 
     #[test]
     fn test_compute_novelty_high_for_novel() {
-        let mut neuron = NENV::excitatory(0, 3, 0.5);
+        let mut neuron = NENV::with_rng(0, 3, 0.5, NeuronType::Excitatory, &mut rng());
 
         // Memória com zeros (nenhum input recente)
         neuron.memory_trace = vec![0.0, 0.0, 0.0];
 
         // Input forte e completamente novo
         let inputs = vec![1.0, 1.0, 1.0];
-        let novelty = neuron.compute_novelty(&inputs);
+        let novelty = neuron.try_compute_novelty(&inputs).unwrap();
 
         // Novidade deve ser 1.0 (média de diferenças absolutas)
         assert_relative_eq!(novelty, 1.0, epsilon = 1e-10);
     }
 
+    #[test]
+    fn test_builder_sets_dynamic_parameters() {
+        let glia_params = GliaParams {
+            max_energy: 40.0,
+            ..GliaParams::default()
+        };
+        let neuron = NENV::builder(3, 4)
+            .with_type(NeuronType::Inhibitory)
+            .with_threshold(0.7)
+            .with_refractory_period(2)
+            .with_memory_alpha(0.3)
+            .with_learning_rate(0.05)
+            .with_glia_params(glia_params)
+            .build_with_rng(&mut rng());
+
+        assert_eq!(neuron.id, 3);
+        assert_eq!(neuron.neuron_type, NeuronType::Inhibitory);
+        assert_eq!(neuron.threshold, 0.7);
+        assert_eq!(neuron.refractory_period(), 2);
+        assert_eq!(neuron.memory_alpha(), 0.3);
+        assert_eq!(neuron.dendritoma.learning_rate(), 0.05);
+        assert_eq!(neuron.glia.params(), glia_params);
+        assert_eq!(neuron.glia.energy, 40.0);
+
+        let default = NENV::builder(0, 4).build_with_rng(&mut rng());
+        assert_eq!(default.refractory_period(), NENV::with_rng(0, 4, 0.5, NeuronType::Excitatory, &mut rng()).refractory_period());
+    }
+
+    #[test]
+    fn test_try_variants_report_length_mismatch() {
+        let mut neuron = NENV::with_rng(0, 3, 0.5, NeuronType::Excitatory, &mut rng());
+        let error = neuron.try_compute_novelty(&[1.0]).unwrap_err();
+        assert_eq!(
+            error,
+            NenvError::LengthMismatch {
+                what: "inputs",
+                expected: 3,
+                actual: 1
+            }
+        );
+        assert!(neuron.try_update_memory(&[1.0; 4]).is_err());
+        assert!(neuron.try_step(&[1.0; 2], 0).is_err());
+        assert!(neuron.try_step(&[1.0; 3], 0).is_ok());
+    }
+
     #[test]
     fn test_compute_novelty_partial() {
-        let mut neuron = NENV::excitatory(0, 4, 0.5);
+        let mut neuron = NENV::with_rng(0, 4, 0.5, NeuronType::Excitatory, &mut rng());
 
         neuron.memory_trace = vec![0.5, 0.5, 0.5, 0.5];
         let inputs = vec![1.0, 0.0, 1.0, 0.0];
 
-        let novelty = neuron.compute_novelty(&inputs);
+        let novelty = neuron.try_compute_novelty(&inputs).unwrap();
 
         // Diferenças: |1.0-0.5| + |0.0-0.5| + |1.0-0.5| + |0.0-0.5| = 2.0
         // Média: 2.0 / 4 = 0.5
         assert_relative_eq!(novelty, 0.5, epsilon = 1e-10);
     }
 
+    #[test]
+    fn test_sparse_novelty_and_memory_match_dense() {
+        let mut dense = NENV::with_rng(0, 6, 0.5, NeuronType::Excitatory, &mut rng());
+        dense.memory_trace = vec![0.0, 0.4, 0.0, 0.0, 0.2, 0.0];
+        let mut sparse = dense.clone();
+
+        // Fora de 1, 3 e 4 input e memória são zero
+        let inputs = vec![0.0, 1.0, 0.0, 0.7, 0.0, 0.0];
+        let indices = [1, 3, 4];
+        assert_eq!(
+            sparse.compute_novelty_sparse(&inputs, &indices),
+            dense.try_compute_novelty(&inputs).unwrap()
+        );
+
+        sparse.update_memory_sparse(&inputs, &indices, 0.3);
+        dense.update_memory_with_alpha(&inputs, 0.3);
+        assert_eq!(sparse.memory_trace, dense.memory_trace);
+    }
+
     #[test]
     fn test_update_priority_increases_with_novelty() {
-        let mut neuron = NENV::excitatory(0, 2, 0.5);
+        let mut neuron = NENV::with_rng(0, 2, 0.5, NeuronType::Excitatory, &mut rng());
 
         // Priority inicial deve ser 1.0
         assert_eq!(neuron.glia.priority, 1.0);
@@ -429,7 +792,7 @@ mod tests {
 
     #[test]
     fn test_update_priority_sensitivity_factor() {
-        let mut neuron = NENV::excitatory(0, 2, 0.5);
+        let mut neuron = NENV::with_rng(0, 2, 0.5, NeuronType::Excitatory, &mut rng());
 
         // Sensitivity factor = 2.0 (mais sensível)
         neuron.update_priority(0.5, 2.0);
@@ -438,9 +801,23 @@ mod tests {
         assert_relative_eq!(neuron.glia.priority, 2.0, epsilon = 1e-10);
     }
 
+    #[test]
+    fn test_accumulate_priority_decays_slowly() {
+        let mut neuron = NENV::with_rng(0, 2, 0.5, NeuronType::Excitatory, &mut rng());
+        neuron.accumulate_priority(0.5, 1.0, 0.1);
+        assert_relative_eq!(neuron.glia.priority, 1.5, epsilon = 1e-10);
+
+        // Sem novidade, o excesso cai 10% por passo em vez de desaparecer
+        neuron.accumulate_priority(0.0, 1.0, 0.1);
+        assert_relative_eq!(neuron.glia.priority, 1.45, epsilon = 1e-10);
+
+        neuron.accumulate_priority(0.0, 1.0, 1.0);
+        assert_relative_eq!(neuron.glia.priority, 1.0, epsilon = 1e-10);
+    }
+
     #[test]
     fn test_update_priority_clamps_at_max() {
-        let mut neuron = NENV::excitatory(0, 2, 0.5);
+        let mut neuron = NENV::with_rng(0, 2, 0.5, NeuronType::Excitatory, &mut rng());
 
         // Novelty muito alto com sensitivity alto
         neuron.update_priority(10.0, 1.0);
@@ -451,7 +828,7 @@ mod tests {
 
     #[test]
     fn test_priority_modulates_potential() {
-        let mut neuron = NENV::excitatory(0, 2, 0.1);
+        let mut neuron = NENV::with_rng(0, 2, 0.1, NeuronType::Excitatory, &mut rng());
         neuron.dendritoma.weights = vec![0.7071067811865475, 0.7071067811865475];
         neuron.glia.energy = 100.0; // Energia máxima
         neuron.glia.priority = 2.0; // Priority dobrado
@@ -467,7 +844,7 @@ mod tests {
 
     #[test]
     fn test_priority_enables_firing() {
-        let mut neuron = NENV::excitatory(0, 2, 1.5); // Limiar alto
+        let mut neuron = NENV::with_rng(0, 2, 1.5, NeuronType::Excitatory, &mut rng()); // Limiar alto
         neuron.dendritoma.weights = vec![0.7071067811865475, 0.7071067811865475];
         neuron.glia.priority = 1.0; // Priority normal
 
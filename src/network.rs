@@ -1,8 +1,45 @@
-/// Módulo que implementa a rede NEN-V
-///
-/// A Network orquestra a simulação, gerindo os neurónios e suas conexões.
+//! Módulo que implementa a rede NEN-V
+//!
+//! A Network orquestra a simulação, gerindo os neurónios e suas conexões.
 
+use crate::config::NetworkConfig;
+use crate::context::{ContextBinding, ContextEffect, ContextTarget};
+use crate::dropout::{Dropout, DropoutMode};
+use crate::ensemble::BridgeSpec;
+use crate::episodic::{Episode, EpisodicBuffer};
+use crate::error::{check_finite, check_len, NenvError, Result};
+use crate::footprint::{matrix_bytes, vec_bytes, MemoryFootprint};
+use crate::glia::{GliaParams, SpikeFailure};
+use crate::growth::{Growth, GrowthEvent, GrowthParams, GrowthSnapshot};
+use crate::history::{HistoryFrame, NetworkHistory};
+use crate::input::{InputBuffer, InputSource};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::F64Ext;
+use crate::multimodal::{ModalityLayout, BRIDGE_GROUP};
 use crate::nenv::{NeuronType, NENV};
+use crate::observer::{Observers, SimulationObserver};
+use crate::ports::{InputPort, OutputMode, OutputPort};
+use crate::predictive::ForwardModel;
+use crate::prelude::*;
+use crate::profiling::{StepTimer, TimingReport, UpdatePhase};
+use crate::reduce::{self, CHUNK_LEN};
+use crate::rewiring::{Rewire, Rewiring, RewiringParams};
+use crate::snapshot::NetworkStateSnapshot;
+use crate::spikes::SpikeTrain;
+use crate::timestep::{decay_per_step, duration_in_steps, tau_in_steps};
+use crate::tiles::{TileNovelty, TileParams};
+#[cfg(feature = "std")]
+use crate::weights::load_weight_matrix;
+use crate::weights::{WeightStatistics, WeightStats};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::path::Path;
 
 /// Tipo de topologia de rede
 #[derive(Debug, Clone, Copy)]
@@ -13,6 +50,62 @@ pub enum ConnectivityType {
     Grid2D,
 }
 
+/// Opções de `Network::warm_up_with`
+///
+/// Por omissão o aquecimento não aprende nem regista nada: só deixa
+/// assentar memórias contextuais, energias, priority e alerta.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WarmUpParams {
+    /// Aplica o aprendizado dos Dendritomas durante o aquecimento
+    pub learning: bool,
+    /// Mantém registo de disparos, histórico, memória episódica,
+    /// observadores e custo metabólico ativos durante o aquecimento
+    pub recording: bool,
+}
+
+/// Buffers de trabalho de `Network::update`, reutilizados entre passos
+///
+/// Alocados no primeiro passo (ou quando o número de neurónios muda); a
+/// partir daí um passo sem subsistemas opcionais não aloca memória.
+#[derive(Debug, Default)]
+struct StepBuffers {
+    /// Saídas do passo anterior (as atuais ficam nos neurónios)
+    previous_outputs: Vec<f64>,
+    integrated: Vec<f64>,
+    modulated: Vec<f64>,
+    /// Input combinado de um neurónio, recolhido de novo em cada fase
+    ///
+    /// Fica a zero entre usos (ver `clear_gathered`), pelo que ocupa N
+    /// valores em vez dos N×N de guardar o input de todos os neurónios.
+    row: Vec<f64>,
+    /// Posições de input externo não nulo
+    external_support: Vec<usize>,
+    context_effects: Vec<ContextEffect>,
+    /// Somas parciais por bloco das reduções determinísticas
+    partials: Vec<f64>,
+}
+
+impl StepBuffers {
+    /// Garante buffers para `n` neurónios
+    fn resize(&mut self, n: usize) {
+        self.previous_outputs.resize(n, 0.0);
+        self.integrated.resize(n, 0.0);
+        self.modulated.resize(n, 0.0);
+        self.context_effects.resize(n, ContextEffect::default());
+        self.row.resize(n, 0.0);
+    }
+
+    fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.previous_outputs)
+            + vec_bytes(&self.integrated)
+            + vec_bytes(&self.modulated)
+            + vec_bytes(&self.row)
+            + vec_bytes(&self.external_support)
+            + vec_bytes(&self.context_effects)
+            + vec_bytes(&self.partials)
+    }
+}
+
 /// Estrutura principal da rede NEN-V
 #[derive(Debug)]
 pub struct Network {
@@ -32,20 +125,110 @@ pub struct Network {
 
     /// Nível de alerta global da rede [0.0, 1.0]
     /// 0.0 = estado normal, 1.0 = alerta máximo
-    /// Afeta a recuperação de energia de todos os neurónios
+    /// Afeta a recuperação de energia de todos os neurónios (cada Glia lê
+    /// este valor no passo, sem cópia por neurónio)
     pub alert_level: f64,
 
-    /// Taxa de decaimento do alert_level (retorna gradualmente ao baseline)
-    alert_decay_rate: f64,
+    /// Parâmetros de dinâmica (decaimento do alerta, integração novelty-alert, ...)
+    config: NetworkConfig,
 
     /// Novidade média atual da rede (calculada no último update)
     current_avg_novelty: f64,
 
-    /// Threshold de novidade para ativar alert_level automaticamente
-    novelty_alert_threshold: f64,
+    /// Linha de base (média móvel) da fração de neurónios ativos a disparar
+    firing_rate_baseline: f64,
+
+    /// Atividade do pool inibitório global virtual [0.0, 1.0]
+    inhibition_pool: f64,
+
+    /// Marca os neurónios lesionados (silenciados permanentemente)
+    lesioned: Vec<bool>,
+
+    /// Dropout opcional (silenciamento aleatório temporário)
+    dropout: Option<Dropout>,
+
+    /// Falha estocástica de disparo opcional e o seu gerador
+    spike_failure: Option<(SpikeFailure, StdRng)>,
+
+    /// Disparos perdidos por falha metabólica no último passo
+    failed_spikes: usize,
+
+    /// Energia total gasta em disparos desde a criação ou `reset_state`
+    metabolic_cost: f64,
+
+    /// Religação dependente de atividade opcional (ver `enable_rewiring`)
+    rewiring: Option<Rewiring>,
+
+    /// Crescimento desenvolvimental opcional (ver `enable_reserve_growth`)
+    growth: Option<Growth>,
+
+    /// Grupos nomeados de neurónios (regiões, populações, etc.)
+    groups: BTreeMap<String, Vec<usize>>,
+
+    /// Vetor de contexto externo atual (ver `set_context`)
+    context: Vec<f64>,
+
+    /// Ligações entre canais de contexto e grupos
+    context_bindings: Vec<ContextBinding>,
+
+    /// Mudanças agendadas de memory_alpha `(passo, grupo, alpha)`, por ordem de passo
+    memory_alpha_schedule: Vec<(i64, Option<String>, f64)>,
+
+    /// Registo opcional de disparos (ativado com enable_spike_recording)
+    spike_train: Option<SpikeTrain>,
+
+    /// Histórico circular opcional dos últimos passos (ativado com enable_history)
+    history: Option<NetworkHistory>,
+
+    /// Tempo acumulado por fase de update (ativado com enable_timing)
+    timing: Option<TimingReport>,
+
+    /// Modelos diretos por neurónio no modo de novidade preditiva
+    forward_models: Option<Vec<ForwardModel>>,
+
+    /// Camada opcional de novidade por blocos da grade
+    tiles: Option<TileNovelty>,
+
+    /// Memória episódica opcional de eventos novos (ativada com enable_episodic_memory)
+    episodic: Option<EpisodicBuffer>,
+
+    /// Portas de entrada nomeadas (ver `stimulate`)
+    input_ports: BTreeMap<String, InputPort>,
 
-    /// Sensibilidade do boost de alert baseado em novidade
-    alert_sensitivity: f64,
+    /// Portas de saída nomeadas (ver `read_outputs`)
+    output_ports: BTreeMap<String, OutputPort>,
+
+    /// Input acumulado pelas portas para o próximo update (vazio se nenhum)
+    pending_inputs: Vec<f64>,
+
+    /// Buffer denso das fontes de input não densas de `update_from`
+    input_buffer: InputBuffer,
+
+    /// Observadores chamados nas fases de cada update
+    observers: Observers,
+
+    /// Ganho estático opcional de cada conexão (gains[i][j] escala o sinal j → i)
+    synaptic_gains: Option<Vec<Vec<f64>>>,
+
+    /// Neurónios pré-sinápticos de cada neurónio (sem auto-conexões)
+    neighbor_lists: Vec<Vec<usize>>,
+
+    /// Grau de saída de cada neurónio (sem auto-conexões)
+    out_degrees: Vec<usize>,
+
+    /// Posições onde a memória contextual de cada neurónio pode não ser zero
+    /// (ordem crescente); fora delas input e memória são zero e a novidade e
+    /// a memória só são calculadas aqui
+    memory_support: Vec<Vec<usize>>,
+
+    /// Posições de input externo já acrescentadas a `memory_support`
+    external_seen: Vec<bool>,
+
+    /// Buffers reutilizados por `update`
+    buffers: StepBuffers,
+
+    /// Aprendizado dos Dendritomas ativo (desligado em `warm_up`)
+    plasticity: bool,
 }
 
 impl Network {
@@ -56,12 +239,197 @@ impl Network {
     /// * `connectivity_type` - Tipo de topologia
     /// * `inhibitory_ratio` - Proporção de neurónios inibitórios (0.0 a 1.0)
     /// * `initial_threshold` - Limiar de disparo inicial para todos os neurónios
+    #[cfg(feature = "std")]
     pub fn new(
         num_neurons: usize,
         connectivity_type: ConnectivityType,
         inhibitory_ratio: f64,
         initial_threshold: f64,
     ) -> Self {
+        Self::with_rng(
+            num_neurons,
+            connectivity_type,
+            inhibitory_ratio,
+            initial_threshold,
+            NetworkConfig::default(),
+            &mut rand::thread_rng(),
+        )
+    }
+
+    /// Cria uma rede com inicialização reprodutível a partir de uma semente
+    ///
+    /// Duas redes criadas com a mesma semente e os mesmos parâmetros têm pesos
+    /// iniciais idênticos e, com os mesmos inputs, evoluem de forma idêntica.
+    ///
+    /// # Argumentos
+    /// * `num_neurons` - Número total de neurónios
+    /// * `connectivity_type` - Tipo de topologia
+    /// * `inhibitory_ratio` - Proporção de neurónios inibitórios (0.0 a 1.0)
+    /// * `initial_threshold` - Limiar de disparo inicial para todos os neurónios
+    /// * `seed` - Semente do gerador usado na inicialização dos pesos
+    pub fn new_seeded(
+        num_neurons: usize,
+        connectivity_type: ConnectivityType,
+        inhibitory_ratio: f64,
+        initial_threshold: f64,
+        seed: u64,
+    ) -> Self {
+        Self::with_rng(
+            num_neurons,
+            connectivity_type,
+            inhibitory_ratio,
+            initial_threshold,
+            NetworkConfig::default(),
+            &mut StdRng::seed_from_u64(seed),
+        )
+    }
+
+    /// Cria uma rede com parâmetros de dinâmica explícitos
+    ///
+    /// # Argumentos
+    /// * `num_neurons` - Número total de neurónios
+    /// * `connectivity_type` - Tipo de topologia
+    /// * `inhibitory_ratio` - Proporção de neurónios inibitórios (0.0 a 1.0)
+    /// * `initial_threshold` - Limiar de disparo inicial para todos os neurónios
+    /// * `config` - Parâmetros de dinâmica da rede e dos neurónios
+    ///
+    /// Com `config.threshold_distribution` diferente de `Fixed`,
+    /// `initial_threshold` é ignorado e cada neurónio sorteia o seu limiar.
+    ///
+    /// # Panics
+    /// Se `config` não passar em `NetworkConfig::validate` (ver `try_with_config`)
+    #[cfg(feature = "std")]
+    pub fn with_config(
+        num_neurons: usize,
+        connectivity_type: ConnectivityType,
+        inhibitory_ratio: f64,
+        initial_threshold: f64,
+        config: NetworkConfig,
+    ) -> Self {
+        Self::with_rng(
+            num_neurons,
+            connectivity_type,
+            inhibitory_ratio,
+            initial_threshold,
+            config,
+            &mut rand::thread_rng(),
+        )
+    }
+
+    /// Como `with_config`, com inicialização reprodutível a partir de uma semente
+    ///
+    /// # Panics
+    /// Se `config` não passar em `NetworkConfig::validate`
+    pub fn with_config_seeded(
+        num_neurons: usize,
+        connectivity_type: ConnectivityType,
+        inhibitory_ratio: f64,
+        initial_threshold: f64,
+        config: NetworkConfig,
+        seed: u64,
+    ) -> Self {
+        Self::with_rng(
+            num_neurons,
+            connectivity_type,
+            inhibitory_ratio,
+            initial_threshold,
+            config,
+            &mut StdRng::seed_from_u64(seed),
+        )
+    }
+
+    /// Versão de `with_config` que recusa redes sem neurónios e configurações inválidas
+    #[cfg(feature = "std")]
+    pub fn try_with_config(
+        num_neurons: usize,
+        connectivity_type: ConnectivityType,
+        inhibitory_ratio: f64,
+        initial_threshold: f64,
+        config: NetworkConfig,
+    ) -> Result<Self> {
+        if num_neurons == 0 {
+            return Err(NenvError::EmptyNetwork);
+        }
+        config.validate()?;
+        Ok(Self::with_config(
+            num_neurons,
+            connectivity_type,
+            inhibitory_ratio,
+            initial_threshold,
+            config,
+        ))
+    }
+
+    /// Versão de `with_config_seeded` que recusa redes sem neurónios e configurações inválidas
+    pub fn try_with_config_seeded(
+        num_neurons: usize,
+        connectivity_type: ConnectivityType,
+        inhibitory_ratio: f64,
+        initial_threshold: f64,
+        config: NetworkConfig,
+        seed: u64,
+    ) -> Result<Self> {
+        if num_neurons == 0 {
+            return Err(NenvError::EmptyNetwork);
+        }
+        config.validate()?;
+        Ok(Self::with_config_seeded(
+            num_neurons,
+            connectivity_type,
+            inhibitory_ratio,
+            initial_threshold,
+            config,
+            seed,
+        ))
+    }
+
+    /// Versão de `new` que recusa redes sem neurónios
+    #[cfg(feature = "std")]
+    pub fn try_new(
+        num_neurons: usize,
+        connectivity_type: ConnectivityType,
+        inhibitory_ratio: f64,
+        initial_threshold: f64,
+    ) -> Result<Self> {
+        if num_neurons == 0 {
+            return Err(NenvError::EmptyNetwork);
+        }
+        Ok(Self::new(num_neurons, connectivity_type, inhibitory_ratio, initial_threshold))
+    }
+
+    /// Versão de `new_seeded` que recusa redes sem neurónios
+    pub fn try_new_seeded(
+        num_neurons: usize,
+        connectivity_type: ConnectivityType,
+        inhibitory_ratio: f64,
+        initial_threshold: f64,
+        seed: u64,
+    ) -> Result<Self> {
+        if num_neurons == 0 {
+            return Err(NenvError::EmptyNetwork);
+        }
+        Ok(Self::new_seeded(
+            num_neurons,
+            connectivity_type,
+            inhibitory_ratio,
+            initial_threshold,
+            seed,
+        ))
+    }
+
+    /// Cria uma rede usando o gerador fornecido para os pesos iniciais
+    fn with_rng<R: Rng + ?Sized>(
+        num_neurons: usize,
+        connectivity_type: ConnectivityType,
+        inhibitory_ratio: f64,
+        initial_threshold: f64,
+        config: NetworkConfig,
+        rng: &mut R,
+    ) -> Self {
+        if let Err(error) = config.validate() {
+            panic!("{}", error);
+        }
+
         // Calcula dimensões da grade (para Grid2D)
         let (grid_width, grid_height) = match connectivity_type {
             ConnectivityType::Grid2D => {
@@ -87,22 +455,425 @@ impl Network {
                 NeuronType::Excitatory
             };
 
-            let neuron = NENV::new(i, num_neurons, initial_threshold, neuron_type);
+            let neuron = NENV::builder(i, num_neurons)
+                .with_type(neuron_type)
+                .with_threshold(initial_threshold)
+                .with_config(&config)
+                .build_with_rng(rng);
             neurons.push(neuron);
         }
 
-        Self {
+        // Limiares sorteados depois dos pesos: a distribuição não altera os pesos iniciais
+        for neuron in &mut neurons {
+            neuron.threshold = config.threshold_distribution.sample(initial_threshold, rng);
+        }
+
+        let mut network = Self {
             neurons,
             connectivity_matrix,
             current_time_step: 0,
             grid_width,
             grid_height,
             alert_level: 0.0, // Estado normal inicial
-            alert_decay_rate: 0.05, // Decai 5% por passo
+            config,
             current_avg_novelty: 0.0,
-            novelty_alert_threshold: 0.5, // Ativa alert quando novelty > 0.5
-            alert_sensitivity: 0.3, // Boost = novelty * 0.3
+            firing_rate_baseline: 0.0,
+            inhibition_pool: 0.0,
+            lesioned: vec![false; num_neurons],
+            dropout: None,
+            rewiring: None,
+            growth: None,
+            spike_failure: None,
+            failed_spikes: 0,
+            metabolic_cost: 0.0,
+            groups: BTreeMap::new(),
+            context: Vec::new(),
+            context_bindings: Vec::new(),
+            memory_alpha_schedule: Vec::new(),
+            spike_train: None,
+            history: None,
+            timing: None,
+            episodic: None,
+            forward_models: None,
+            tiles: None,
+            input_ports: BTreeMap::new(),
+            output_ports: BTreeMap::new(),
+            pending_inputs: Vec::new(),
+            input_buffer: InputBuffer::default(),
+            observers: Observers::default(),
+            synaptic_gains: None,
+            neighbor_lists: Vec::new(),
+            out_degrees: Vec::new(),
+            memory_support: Vec::new(),
+            external_seen: Vec::new(),
+            buffers: StepBuffers::default(),
+            plasticity: true,
+        };
+        network.refresh_connectivity();
+        network
+    }
+
+    /// Volta ao estado inicial mantendo os pesos aprendidos
+    ///
+    /// Limpa disparos, energias (ao máximo), memórias contextuais, alert_level
+    /// e novidade, e volta ao passo 0. Topologia, lesões, grupos e parâmetros
+    /// mantêm-se; o registo de disparos e o histórico, se ativos, recomeçam
+    /// vazios. Útil para repetir ensaios sem reconstruir a rede.
+    pub fn reset_state(&mut self) {
+        self.current_time_step = 0;
+        self.alert_level = 0.0;
+        self.current_avg_novelty = 0.0;
+        self.firing_rate_baseline = 0.0;
+        self.inhibition_pool = 0.0;
+        self.metabolic_cost = 0.0;
+        self.pending_inputs.clear();
+        for neuron in &mut self.neurons {
+            neuron.reset_state();
+        }
+        self.rebuild_memory_support();
+        if let Some(dropout) = &mut self.dropout
+            && dropout.mode == DropoutMode::PerTrial
+        {
+            dropout.resample();
+        }
+        if self.spike_train.is_some() {
+            self.spike_train = Some(SpikeTrain::new(self.neurons.len()));
+        }
+        if let Some(history) = &mut self.history {
+            history.clear();
+        }
+        for model in self.forward_models.iter_mut().flatten() {
+            model.reset_state();
         }
+        if let Some(tiles) = &mut self.tiles {
+            tiles.reset();
+        }
+    }
+
+    /// Como `reset_state`, mas também volta a sortear os pesos iniciais
+    #[cfg(feature = "std")]
+    pub fn reset_all(&mut self) {
+        self.reset_all_with_rng(&mut rand::thread_rng());
+    }
+
+    /// Como `reset_all`, com pesos reprodutíveis a partir de uma semente
+    ///
+    /// Uma rede criada com `new_seeded(.., seed)` e depois `reset_all_seeded(seed)`
+    /// volta exatamente aos pesos iniciais.
+    pub fn reset_all_seeded(&mut self, seed: u64) {
+        self.reset_all_with_rng(&mut StdRng::seed_from_u64(seed));
+    }
+
+    fn reset_all_with_rng<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.reset_state();
+        for neuron in &mut self.neurons {
+            neuron.dendritoma.reinitialize(rng);
+        }
+    }
+
+    /// Define o limiar de disparo de todos os neurónios
+    pub fn set_all_thresholds(&mut self, threshold: f64) {
+        for neuron in &mut self.neurons {
+            neuron.threshold = threshold;
+        }
+    }
+
+    /// Define o período refratário de todos os neurónios (em ms, convertido
+    /// para passos com `dt` como em `NetworkConfig::refractory_period`)
+    pub fn set_all_refractory_periods(&mut self, period: i64) {
+        let period = duration_in_steps(period, self.config.dt);
+        for neuron in &mut self.neurons {
+            neuron.set_refractory_period(period);
+        }
+    }
+
+    /// Define a taxa de atualização da memória por ms de todos os neurónios
+    /// (limitada a [0, 1] e convertida para o passo com `dt`)
+    pub fn set_all_memory_alpha(&mut self, alpha: f64) {
+        let alpha = decay_per_step(alpha, self.config.dt);
+        for neuron in &mut self.neurons {
+            neuron.set_memory_alpha(alpha);
+        }
+    }
+
+    /// Define a taxa de atualização da memória por ms dos neurónios de um grupo
+    ///
+    /// # Retorna
+    /// Erro se o grupo não existir
+    pub fn set_memory_alpha_for_group(&mut self, group: &str, alpha: f64) -> Result<()> {
+        let Some(members) = self.groups.get(group) else {
+            return Err(NenvError::UnknownGroup {
+                name: group.to_string(),
+            });
+        };
+        let alpha = decay_per_step(alpha, self.config.dt);
+        for &i in members {
+            self.neurons[i].set_memory_alpha(alpha);
+        }
+        Ok(())
+    }
+
+    /// Taxas de atualização da memória de todos os neurónios, por passo
+    pub fn get_memory_alphas(&self) -> Vec<f64> {
+        self.neurons.iter().map(|n| n.memory_alpha()).collect()
+    }
+
+    /// Agenda uma mudança de memory_alpha no início do passo `time`
+    ///
+    /// # Argumentos
+    /// * `time` - Passo em que a mudança é aplicada (antes da atualização)
+    /// * `group` - Grupo afetado, ou `None` para toda a rede
+    /// * `alpha` - Nova taxa por ms (limitada a [0, 1] e convertida com `dt`)
+    ///
+    /// # Retorna
+    /// Erro se o grupo não existir
+    pub fn schedule_memory_alpha(
+        &mut self,
+        time: i64,
+        group: Option<&str>,
+        alpha: f64,
+    ) -> Result<()> {
+        if let Some(name) = group
+            && !self.groups.contains_key(name)
+        {
+            return Err(NenvError::UnknownGroup {
+                name: name.to_string(),
+            });
+        }
+        let position = self.memory_alpha_schedule.partition_point(|&(t, _, _)| t <= time);
+        self.memory_alpha_schedule
+            .insert(position, (time, group.map(str::to_string), alpha));
+        Ok(())
+    }
+
+    /// Define o aumento de memory_alpha durante o alerta (0.0 desliga)
+    ///
+    /// Em cada passo o alpha efetivo é `memory_alpha * (1 + gain * alert_level)`,
+    /// pelo que a familiarização acelera enquanto a rede está em alerta.
+    pub fn set_memory_alpha_alert_gain(&mut self, gain: f64) {
+        self.config.memory_alpha_alert_gain = gain.max(0.0);
+    }
+
+    /// Aplica as mudanças de memory_alpha agendadas até ao passo atual
+    fn apply_memory_alpha_schedule(&mut self) {
+        let due = self
+            .memory_alpha_schedule
+            .partition_point(|&(t, _, _)| t <= self.current_time_step);
+        for (_, group, alpha) in self.memory_alpha_schedule.drain(..due).collect::<Vec<_>>() {
+            match group {
+                // O grupo pode ter sido removido entretanto; nesse caso é ignorado
+                Some(name) => {
+                    let _ = self.set_memory_alpha_for_group(&name, alpha);
+                }
+                None => self.set_all_memory_alpha(alpha),
+            }
+        }
+    }
+
+    /// Define as constantes metabólicas de todos os neurónios de um tipo
+    ///
+    /// A manutenção e a recuperação são por ms e convertidas para o passo
+    /// como em `NetworkConfig::glia`. O estado (energia, priority) é mantido;
+    /// ver `Glia::set_params`.
+    pub fn set_glia_params_for_type(&mut self, neuron_type: NeuronType, params: GliaParams) {
+        let params = params.per_step(self.config.dt);
+        for neuron in self.neurons.iter_mut().filter(|n| n.neuron_type == neuron_type) {
+            neuron.glia.set_params(params);
+        }
+    }
+
+    /// Neurónios dos quais `i` recebe input (sem o próprio `i`), em ordem crescente
+    ///
+    /// Retorna uma lista vazia para índices fora da rede.
+    pub fn neighbors(&self, i: usize) -> &[usize] {
+        self.neighbor_lists.get(i).map_or(&[], Vec::as_slice)
+    }
+
+    /// Verifica se o neurónio `i` recebe input do neurónio `j`
+    pub fn is_connected(&self, i: usize, j: usize) -> bool {
+        self.connectivity_matrix
+            .get(i)
+            .and_then(|row| row.get(j))
+            .is_some_and(|&c| c != 0)
+    }
+
+    /// Grau de entrada de `i` (número de neurónios pré-sinápticos)
+    pub fn in_degree(&self, i: usize) -> usize {
+        self.neighbors(i).len()
+    }
+
+    /// Grau de saída de `i` (número de neurónios que recebem input de `i`)
+    pub fn out_degree(&self, i: usize) -> usize {
+        self.out_degrees.get(i).copied().unwrap_or(0)
+    }
+
+    /// Recalcula as listas de vizinhos a partir de `connectivity_matrix`
+    ///
+    /// Os métodos da rede que alteram a conectividade já o fazem; só é
+    /// necessário depois de modificar a matriz diretamente. Também volta a
+    /// procurar as posições não nulas de `memory_trace`, pelo que deve ser
+    /// chamado depois de alterar a memória de um neurónio fora de `update`.
+    pub fn refresh_connectivity(&mut self) {
+        self.neighbor_lists = self
+            .connectivity_matrix
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|&(j, &c)| c != 0 && i != j)
+                    .map(|(j, _)| j)
+                    .collect()
+            })
+            .collect();
+
+        let mut out_degrees = vec![0; self.connectivity_matrix.len()];
+        for &j in self.neighbor_lists.iter().flatten() {
+            if j < out_degrees.len() {
+                out_degrees[j] += 1;
+            }
+        }
+        self.out_degrees = out_degrees;
+
+        self.rebuild_memory_support();
+    }
+
+    /// Recalcula `memory_support`: vizinhos, o próprio e memória não nula
+    ///
+    /// As posições de input externo voltam a ser acrescentadas quando
+    /// reaparecem em `update`.
+    fn rebuild_memory_support(&mut self) {
+        self.memory_support = self
+            .neurons
+            .iter()
+            .zip(&self.neighbor_lists)
+            .enumerate()
+            .map(|(i, (neuron, neighbors))| {
+                let trace = neuron.memory_trace.iter().enumerate();
+                let nonzero = trace.filter(|&(_, &m)| m != 0.0).map(|(j, _)| j);
+                let mut support: Vec<usize> =
+                    neighbors.iter().copied().chain([i]).chain(nonzero).collect();
+                support.sort_unstable();
+                support.dedup();
+                support.shrink_to_fit();
+                support
+            })
+            .collect();
+        self.external_seen = vec![false; self.neurons.len()];
+    }
+
+    /// Define o ganho estático de cada conexão
+    ///
+    /// `gains[i][j]` multiplica o sinal que o neurónio `i` recebe de `j`
+    /// antes do Dendritoma, separando a força anatómica da ligação (fixa) dos
+    /// pesos aprendidos. Só afeta conexões presentes na matriz de
+    /// conectividade; os inputs externos não são escalados.
+    ///
+    /// # Retorna
+    /// Erro se a matriz não for N×N ou tiver valores não finitos
+    pub fn set_synaptic_gains(&mut self, gains: Vec<Vec<f64>>) -> Result<()> {
+        let n = self.neurons.len();
+        check_len("synaptic_gains", n, gains.len())?;
+        for row in &gains {
+            check_len("synaptic_gains[i]", n, row.len())?;
+            check_finite("synaptic_gains[i]", row)?;
+        }
+        self.synaptic_gains = Some(gains);
+        Ok(())
+    }
+
+    /// Define ganhos que atenuam com a distância na grade: `exp(-d / length_scale)`
+    ///
+    /// `d` é a distância euclidiana entre as coordenadas dos neurónios (em
+    /// redes sem grade, a distância entre índices numa única linha).
+    pub fn set_distance_attenuation(&mut self, length_scale: f64) {
+        let width = self.grid_width;
+        let n = self.neurons.len();
+        let gains = (0..n)
+            .map(|i| {
+                let (ri, ci) = Self::coords_for(width, i);
+                (0..n)
+                    .map(|j| {
+                        let (rj, cj) = Self::coords_for(width, j);
+                        let dr = ri as f64 - rj as f64;
+                        let dc = ci as f64 - cj as f64;
+                        (-(dr * dr + dc * dc).sqrt() / length_scale).exp()
+                    })
+                    .collect()
+            })
+            .collect();
+        self.synaptic_gains = Some(gains);
+    }
+
+    /// Remove os ganhos estáticos (todas as conexões voltam a ganho 1.0)
+    pub fn clear_synaptic_gains(&mut self) {
+        self.synaptic_gains = None;
+    }
+
+    /// Ganho estático da conexão `j → i` (1.0 se não houver ganhos definidos)
+    pub fn synaptic_gain(&self, i: usize, j: usize) -> f64 {
+        self.synaptic_gains
+            .as_ref()
+            .and_then(|gains| gains.get(i))
+            .and_then(|row| row.get(j))
+            .copied()
+            .unwrap_or(1.0)
+    }
+
+    /// Matriz de ganhos estáticos, se definida
+    pub fn synaptic_gains(&self) -> Option<&[Vec<f64>]> {
+        self.synaptic_gains.as_deref()
+    }
+
+    /// Reforço de priority de cada neurónio vindo dos vizinhos até `radius`
+    ///
+    /// `halo[i] = spread * max(drive[j])` sobre os `j != i` a distância de
+    /// Chebyshev até `radius` na grade. Usa a novidade do passo (e não a
+    /// priority), pelo que o halo não se realimenta entre vizinhos.
+    ///
+    /// Na grade, cada neurónio com `drive > 0` espalha o seu valor pela
+    /// janela `(2r+1)²` à sua volta; redes sem grade (`width == 0`,
+    /// FullyConnected) usam a comparação de todos os pares.
+    fn priority_halo(drive: &[f64], width: usize, radius: usize, spread: f64) -> Vec<f64> {
+        let n = drive.len();
+        if width == 0 {
+            return Self::priority_halo_dense(drive, width, radius, spread);
+        }
+
+        let last_row = n.saturating_sub(1) / width;
+        let mut strongest = vec![0.0f64; n];
+        for (j, &d) in drive.iter().enumerate().filter(|&(_, &d)| d > 0.0) {
+            let (rj, cj) = Self::coords_for(width, j);
+            let rows = rj.saturating_sub(radius)..=rj.saturating_add(radius).min(last_row);
+            let cols = cj.saturating_sub(radius)..=cj.saturating_add(radius).min(width - 1);
+            for r in rows {
+                for i in cols.clone().map(|c| r * width + c).take_while(|&i| i < n) {
+                    if i != j {
+                        strongest[i] = strongest[i].max(d);
+                    }
+                }
+            }
+        }
+        strongest.into_iter().map(|d| spread * d).collect()
+    }
+
+    /// `priority_halo` comparando todos os pares (O(N²))
+    fn priority_halo_dense(drive: &[f64], width: usize, radius: usize, spread: f64) -> Vec<f64> {
+        (0..drive.len())
+            .map(|i| {
+                let (ri, ci) = Self::coords_for(width, i);
+                let strongest = drive
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| {
+                        let (rj, cj) = Self::coords_for(width, j);
+                        j != i && ri.abs_diff(rj) <= radius && ci.abs_diff(cj) <= radius
+                    })
+                    .map(|(_, &d)| d)
+                    .fold(0.0, f64::max);
+                spread * strongest
+            })
+            .collect()
     }
 
     /// Gera a matriz de conectividade baseada no tipo
@@ -126,7 +897,7 @@ impl Network {
     fn generate_2d_grid_connectivity(num_neurons: usize, width: usize) -> Vec<Vec<u8>> {
         let mut matrix = vec![vec![0; num_neurons]; num_neurons];
 
-        for i in 0..num_neurons {
+        for (i, row_connections) in matrix.iter_mut().enumerate() {
             let (row, col) = (i / width, i % width);
 
             // Conecta aos 8 vizinhos (Moore neighborhood)
@@ -147,7 +918,7 @@ impl Network {
                     {
                         let j = (new_row as usize) * width + (new_col as usize);
                         if j < num_neurons {
-                            matrix[i][j] = 1;
+                            row_connections[j] = 1;
                         }
                     }
                 }
@@ -166,6 +937,7 @@ impl Network {
     ///
     /// # Retorna
     /// Vetor de inputs combinados (rede + externos)
+    #[cfg(test)]
     fn gather_inputs(
         &self,
         neuron_idx: usize,
@@ -173,22 +945,71 @@ impl Network {
         external_inputs: &[f64],
     ) -> Vec<f64> {
         let mut inputs = vec![0.0; self.neurons.len()];
+        let mut support = Vec::new();
+        Self::external_support_into(external_inputs, inputs.len(), &mut support);
+        self.gather_inputs_into(neuron_idx, all_outputs, external_inputs, &support, &mut inputs);
+        inputs
+    }
 
-        // Coleta inputs da rede baseado na matriz de conectividade
-        for j in 0..self.neurons.len() {
-            if self.connectivity_matrix[neuron_idx][j] == 1 {
-                inputs[j] = all_outputs[j];
-            }
+    /// Posições de `external_inputs` (até `n`) com valor não nulo
+    fn external_support_into(external_inputs: &[f64], n: usize, support: &mut Vec<usize>) {
+        support.clear();
+        support.extend(
+            external_inputs
+                .iter()
+                .take(n)
+                .enumerate()
+                .filter(|&(_, &x)| x != 0.0)
+                .map(|(j, _)| j),
+        );
+    }
+
+    /// Como `gather_inputs`, escrevendo em `inputs` (com um valor por neurónio)
+    ///
+    /// Percorre apenas a lista de vizinhos do neurónio e as posições de
+    /// `external_support` (custo proporcional ao grau de entrada mais o
+    /// suporte externo, não a N). `inputs` tem de estar a zero nas restantes
+    /// posições: ver `clear_gathered`.
+    fn gather_inputs_into(
+        &self,
+        neuron_idx: usize,
+        all_outputs: &[f64],
+        external_inputs: &[f64],
+        external_support: &[usize],
+        inputs: &mut [f64],
+    ) {
+        // Coleta inputs da rede: só os pré-sinápticos (e a auto-conexão, se existir)
+        let gains = self.synaptic_gains.as_ref().map(|gains| &gains[neuron_idx]);
+        for j in self.input_sources(neuron_idx) {
+            inputs[j] = match gains {
+                Some(row) => all_outputs[j] * row[j],
+                None => all_outputs[j],
+            };
         }
 
         // Adiciona inputs externos
-        for (i, &external_input) in external_inputs.iter().enumerate() {
-            if i < inputs.len() {
-                inputs[i] += external_input;
-            }
+        for &j in external_support {
+            inputs[j] += external_inputs[j];
         }
+    }
 
-        inputs
+    /// Repõe a zero as posições de `inputs` escritas por `gather_inputs_into`
+    ///
+    /// Chamado logo a seguir a usar o input, com as mesmas listas de
+    /// vizinhos e o mesmo `external_support`.
+    fn clear_gathered(&self, neuron_idx: usize, external_support: &[usize], inputs: &mut [f64]) {
+        for j in self.input_sources(neuron_idx).chain(external_support.iter().copied()) {
+            inputs[j] = 0.0;
+        }
+    }
+
+    /// Pré-sinápticos de `neuron_idx`, incluindo a auto-conexão se existir
+    fn input_sources(&self, neuron_idx: usize) -> impl Iterator<Item = usize> + '_ {
+        let self_connected = self.connectivity_matrix[neuron_idx][neuron_idx] != 0;
+        self.neighbor_lists[neuron_idx]
+            .iter()
+            .copied()
+            .chain(self_connected.then_some(neuron_idx))
     }
 
     /// Executa um passo de atualização da rede
@@ -198,219 +1019,3006 @@ impl Network {
     /// 2. Para cada neurónio: integra, modula, decide disparar
     /// 3. Para cada neurónio: aplica aprendizado e atualiza estado
     ///
+    /// Um vetor mais curto que a rede deixa os neurónios restantes sem input
+    /// externo e valores a mais são ignorados; `try_update` recusa ambos os
+    /// casos e `update_with_events` / `update_from` aceitam inputs esparsos
+    /// ou iteradores. O input acumulado com `stimulate` soma-se a
+    /// `external_inputs` e é consumido neste passo.
+    ///
     /// # Argumentos
     /// * `external_inputs` - Vetor de inputs externos (um valor por neurónio)
     pub fn update(&mut self, external_inputs: &[f64]) {
+        let mut timer = StepTimer::start(self.timing.is_some());
+        let mut port_inputs = core::mem::take(&mut self.pending_inputs);
+        let external_inputs = if port_inputs.is_empty() {
+            external_inputs
+        } else {
+            for (input, &external) in port_inputs.iter_mut().zip(external_inputs) {
+                *input += external;
+            }
+            port_inputs.as_slice()
+        };
+
+        // Os observadores saem da rede durante o passo para poderem recebê-la
+        let mut observers = core::mem::take(&mut self.observers.0);
+        for observer in observers.iter_mut() {
+            observer.before_step(self, external_inputs);
+        }
+
         self.current_time_step += 1;
 
         // Fase 0: Atualiza alert_level (decaimento gradual)
         self.update_alert_level();
+        self.apply_memory_alpha_schedule();
 
-        // Coleta todas as saídas do passo anterior
-        let all_neuron_outputs: Vec<f64> = self.neurons.iter().map(|n| n.output_signal).collect();
-
-        // Cria vetores temporários para armazenar resultados da Fase 1-3
-        let mut integrated_potentials = Vec::with_capacity(self.neurons.len());
-        let mut modulated_potentials = Vec::with_capacity(self.neurons.len());
-        let mut gathered_inputs = Vec::with_capacity(self.neurons.len());
+        if let Some(dropout) = &mut self.dropout
+            && dropout.mode == DropoutMode::PerStep
+        {
+            dropout.resample();
+        }
+        // Neurónios silenciados neste passo: lesionados ou em dropout
+        let silenced: Cow<[bool]> = match &self.dropout {
+            Some(dropout) => Cow::Owned(
+                self.lesioned
+                    .iter()
+                    .zip(&dropout.mask)
+                    .map(|(&lesioned, &dropped)| lesioned || dropped)
+                    .collect(),
+            ),
+            None => Cow::Borrowed(&self.lesioned),
+        };
+
+        // Buffers reutilizados: saídas do passo anterior, potenciais e inputs
+        let mut buffers = core::mem::take(&mut self.buffers);
+        buffers.resize(self.neurons.len());
+        for (previous, neuron) in buffers.previous_outputs.iter_mut().zip(&self.neurons) {
+            *previous = neuron.output_signal;
+        }
+
+        // Input externo não nulo (visto por todos os neurónios); o resto do
+        // input de cada neurónio vem só dos seus vizinhos
+        Self::external_support_into(
+            external_inputs,
+            self.neurons.len(),
+            &mut buffers.external_support,
+        );
+
+        // Fase 1-2: Calcular potenciais para todos os neurónios
+        timer.lap(UpdatePhase::Other);
+        for (idx, neuron) in self.neurons.iter().enumerate() {
+            self.gather_inputs_into(
+                idx,
+                &buffers.previous_outputs,
+                external_inputs,
+                &buffers.external_support,
+                &mut buffers.row,
+            );
+            timer.lap(UpdatePhase::Gather);
+
+            let integrated = neuron.dendritoma.weighted_sum(&buffers.row);
+            self.clear_gathered(idx, &buffers.external_support, &mut buffers.row);
+            buffers.integrated[idx] = integrated;
+            timer.lap(UpdatePhase::Integrate);
+            buffers.modulated[idx] = neuron.glia.modulate(integrated);
+            timer.lap(UpdatePhase::Modulate);
+        }
+
+        for observer in observers.iter_mut() {
+            observer.after_integration(self, &buffers.integrated, &buffers.modulated);
+        }
+
+        // Fase 3: Decisão de disparo para todos os neurónios
+        timer.lap(UpdatePhase::Other);
+        // O pool inibitório (atividade do passo anterior) retira potencial a todos
+        let inhibition = self.config.global_inhibition_gain * self.inhibition_pool;
+        self.context_effects_into(&mut buffers.context_effects);
+        self.failed_spikes = 0;
+        for (((neuron, &modulated_potential), &silenced), effect) in self
+            .neurons
+            .iter_mut()
+            .zip(buffers.modulated.iter())
+            .zip(silenced.iter())
+            .zip(buffers.context_effects.iter())
+        {
+            if silenced {
+                // Neurónio lesionado (ou em dropout) não dispara nem emite sinal
+                neuron.is_firing = false;
+                neuron.output_signal = 0.0;
+                continue;
+            }
+
+            let last_fire_time = neuron.last_fire_time;
+            let potential = effect.apply(modulated_potential) - inhibition;
+            neuron.decide_to_fire(potential, self.current_time_step);
+
+            // Falha metabólica: o disparo não acontece (sem saída, custo nem refratário)
+            if neuron.is_firing
+                && let Some((failure, rng)) = &mut self.spike_failure
+                && !rng.gen_bool(failure.reliability(neuron.glia.energy_fraction()).clamp(0.0, 1.0))
+            {
+                neuron.is_firing = false;
+                neuron.output_signal = 0.0;
+                neuron.last_fire_time = last_fire_time;
+                self.failed_spikes += 1;
+            }
+        }
+
+        // O pool inibitório segue a fração de excitatórios a disparar
+        if self.config.global_inhibition_gain > 0.0 {
+            let rate = self.excitatory_firing_rate();
+            let tau = tau_in_steps(self.config.global_inhibition_tau, self.config.dt).max(1.0);
+            self.inhibition_pool += (rate - self.inhibition_pool) / tau;
+        }
+
+        // Regista os disparos deste passo (se ativado)
+        if let Some(spike_train) = &mut self.spike_train {
+            for neuron in self.neurons.iter().filter(|n| n.is_firing) {
+                spike_train.push(neuron.id, self.current_time_step);
+            }
+        }
+
+        if let Some(rewiring) = &mut self.rewiring {
+            let firing: Vec<bool> = self.neurons.iter().map(|n| n.is_firing).collect();
+            rewiring.observe(&firing);
+        }
+        if let Some(growth) = &mut self.growth {
+            let firing: Vec<bool> = self.neurons.iter().map(|n| n.is_firing).collect();
+            growth.observe(&firing);
+        }
+        timer.lap(UpdatePhase::Fire);
+
+        for observer in observers.iter_mut() {
+            observer.after_firing(self);
+        }
+
+        // Fase 4: Aprendizado e atualização de estado
+        let alpha_scale = 1.0 + self.config.memory_alpha_alert_gain * self.alert_level;
+        let strict = self.config.strict_determinism;
+        let plasticity = self.plasticity;
+        let priority_decay = decay_per_step(self.config.priority_decay, self.config.dt);
+        let mut total_novelty = 0.0;
+        let mut active_neurons = 0;
+        let mut forward_models = self.forward_models.take();
+
+        // Posições externas novas entram no suporte de todos os neurónios
+        // (incluindo os silenciados); as já vistas continuam lá
+        for &j in &buffers.external_support {
+            if let Some(seen) = self.external_seen.get_mut(j)
+                && !*seen
+            {
+                *seen = true;
+                for support in &mut self.memory_support {
+                    if let Err(pos) = support.binary_search(&j) {
+                        support.insert(pos, j);
+                    }
+                }
+            }
+        }
+
+        for (idx, &silenced) in silenced.iter().enumerate() {
+            // Neurónios silenciados não contribuem para novidade nem aprendem
+            if silenced {
+                continue;
+            }
+            active_neurons += 1;
+            timer.lap(UpdatePhase::Other);
+
+            // O input da fase 1-2 é recolhido de novo (as saídas anteriores não mudaram)
+            self.gather_inputs_into(
+                idx,
+                &buffers.previous_outputs,
+                external_inputs,
+                &buffers.external_support,
+                &mut buffers.row,
+            );
+            timer.lap(UpdatePhase::Gather);
+            let inputs = &buffers.row;
+            let support = &self.memory_support[idx];
+            let neuron = &mut self.neurons[idx];
+
+            // Calcula novidade ANTES de atualizar memória (ou o modelo direto)
+            let novelty = match &mut forward_models {
+                Some(models) => {
+                    let model = &mut models[neuron.id];
+                    let error = model.prediction_error(inputs);
+                    model.learn(inputs);
+                    error
+                }
+                None => neuron.compute_novelty_sparse(inputs, support),
+            };
+            neuron.novelty = novelty;
+            total_novelty += novelty;
+            timer.lap(UpdatePhase::Memory);
+
+            // Atualiza priority baseado na novidade
+            neuron.accumulate_priority(
+                novelty,
+                self.config.priority_sensitivity,
+                priority_decay,
+            );
+
+            // Aprendizado (se disparou)
+            if neuron.is_firing && plasticity {
+                neuron.dendritoma.apply_learning(inputs);
+            }
+
+            // Atualização de estado metabólico
+            if neuron.is_firing && !strict {
+                self.metabolic_cost += neuron.glia.params().energy_cost_fire;
+            }
+            neuron.glia.update_state_with_alert(neuron.is_firing, self.alert_level);
+            timer.lap(UpdatePhase::Learn);
+
+            // Atualiza memória DEPOIS de calcular novelty (mais rápida durante o alerta)
+            let alpha = neuron.memory_alpha() * alpha_scale;
+            neuron.update_memory_sparse(inputs, support, alpha.clamp(0.0, 1.0));
+            timer.lap(UpdatePhase::Memory);
+
+            self.clear_gathered(idx, &buffers.external_support, &mut buffers.row);
+        }
+
+        self.forward_models = forward_models;
+
+        // Reduções em blocos fixos combinados em árvore (ver `reduce`)
+        if strict {
+            total_novelty = Self::chunked_sum(&mut buffers.partials, &self.neurons, &silenced, |n| {
+                n.novelty
+            });
+            self.metabolic_cost +=
+                Self::chunked_sum(&mut buffers.partials, &self.neurons, &silenced, |n| {
+                    if n.is_firing { n.glia.params().energy_cost_fire } else { 0.0 }
+                });
+        }
+        self.buffers = buffers;
+
+        // Halo de atenção: vizinhos de neurónios novos recebem parte da priority
+        if self.config.priority_spread > 0.0 {
+            let drive: Vec<f64> = self
+                .neurons
+                .iter()
+                .zip(silenced.iter())
+                .map(|(n, &silenced)| {
+                    if silenced {
+                        0.0
+                    } else {
+                        n.novelty * self.config.priority_sensitivity
+                    }
+                })
+                .collect();
+            let halo = Self::priority_halo(
+                &drive,
+                self.grid_width,
+                self.config.priority_spread_radius,
+                self.config.priority_spread,
+            );
+            let targets = self.neurons.iter_mut().zip(halo).zip(silenced.iter());
+            for ((neuron, boost), &silenced) in targets {
+                if !silenced {
+                    neuron.glia.priority = (neuron.glia.priority + boost).min(3.0);
+                }
+            }
+        }
+
+        // Fase 5: Integração Novelty-Alert (v0.3.0)
+        // Calcula novidade média da rede (apenas neurónios ativos)
+        self.current_avg_novelty = if active_neurons > 0 {
+            total_novelty / active_neurons as f64
+        } else {
+            0.0
+        };
+
+        // Se novidade excede threshold, boost alert_level automaticamente
+        if self.current_avg_novelty > self.config.novelty_alert_threshold {
+            let alert_boost = self.current_avg_novelty * self.config.alert_sensitivity;
+            self.boost_alert_level(alert_boost);
+        }
+
+        // Novidade por blocos: mudanças distribuídas numa região também ativam o alerta
+        if let Some(tiles) = &mut self.tiles {
+            let activity: Vec<f64> = self
+                .neurons
+                .iter()
+                .enumerate()
+                .map(|(i, n)| external_inputs.get(i).copied().unwrap_or(0.0) + n.output_signal)
+                .collect();
+            let max_novelty = tiles.update(&activity);
+            if max_novelty > tiles.params.alert_threshold {
+                let alert_boost = max_novelty * tiles.params.alert_sensitivity;
+                self.boost_alert_level(alert_boost);
+            }
+        }
+
+        // Surtos de atividade (eventos familiares mas intensos) também ativam o alerta
+        let firing_rate = if active_neurons > 0 {
+            self.num_firing() as f64 / active_neurons as f64
+        } else {
+            0.0
+        };
+        let surge = firing_rate - self.firing_rate_baseline;
+        if self.config.rate_surge_sensitivity > 0.0 && surge > self.config.rate_surge_threshold {
+            self.boost_alert_level(surge * self.config.rate_surge_sensitivity);
+        }
+        let baseline_rate = decay_per_step(self.config.rate_baseline_rate, self.config.dt);
+        self.firing_rate_baseline += baseline_rate * surge;
+
+        // Religação periódica da topologia (se ativada)
+        if let Some(rewiring) = &self.rewiring
+            && rewiring.params.interval > 0
+            && self.current_time_step % rewiring.params.interval == 0
+        {
+            self.rewire_now();
+        }
+
+        // Ronda periódica de ativação da reserva e sinaptogénese (se ativada)
+        if let Some(growth) = &self.growth
+            && growth.params.interval > 0
+            && self.current_time_step % growth.params.interval == 0
+        {
+            self.grow_now();
+        }
+
+        // Guarda o padrão de input se o passo foi suficientemente novo
+        if let Some(episodic) = &mut self.episodic
+            && self.current_avg_novelty > episodic.threshold()
+        {
+            let mut inputs = vec![0.0; self.neurons.len()];
+            for (stored, &input) in inputs.iter_mut().zip(external_inputs) {
+                *stored = input;
+            }
+            episodic.offer(Episode {
+                time: self.current_time_step,
+                novelty: self.current_avg_novelty,
+                inputs,
+                firing: self.neurons.iter().filter(|n| n.is_firing).map(|n| n.id).collect(),
+            });
+        }
+
+        // Guarda o estado do passo no histórico (se ativado)
+        if let Some(history) = &mut self.history {
+            history.push(HistoryFrame {
+                time: self.current_time_step,
+                firing: self.neurons.iter().map(|n| n.is_firing).collect(),
+                energy: self.neurons.iter().map(|n| n.glia.energy).collect(),
+                novelty: self.neurons.iter().map(|n| n.novelty).collect(),
+            });
+        }
+
+        for observer in observers.iter_mut() {
+            observer.after_learning(self);
+        }
+        // Observadores adicionados durante os hooks ficam no fim da lista
+        observers.append(&mut self.observers.0);
+        self.observers.0 = observers;
+
+        timer.lap(UpdatePhase::Other);
+        if let Some(timing) = &mut self.timing {
+            timer.finish(timing);
+        }
+    }
+
+    /// Regista um observador chamado nas fases de cada `update`
+    pub fn add_observer(&mut self, observer: Box<dyn SimulationObserver>) {
+        self.observers.0.push(observer);
+    }
+
+    /// Remove e devolve todos os observadores registados
+    pub fn take_observers(&mut self) -> Vec<Box<dyn SimulationObserver>> {
+        core::mem::take(&mut self.observers.0)
+    }
+
+    /// Número de observadores registados
+    pub fn num_observers(&self) -> usize {
+        self.observers.0.len()
+    }
+
+    /// Executa `steps` passos com inputs gerados e um observador por passo
+    ///
+    /// Substitui o ciclo `for t in 0..steps { update(..); ... }` repetido nos
+    /// experimentos. `t` começa em 0; após o update de `t`, o passo de tempo
+    /// da rede é `t + 1` a partir de uma rede nova.
+    ///
+    /// # Argumentos
+    /// * `steps` - Número de passos
+    /// * `input_fn` - Inputs externos do passo `t` (p.ex. `schedule.inputs_at(n, t)`)
+    /// * `on_step` - Chamado após cada update com a rede e `t`
+    pub fn run<I, F, O>(&mut self, steps: i64, mut input_fn: F, mut on_step: O)
+    where
+        I: AsRef<[f64]>,
+        F: FnMut(i64) -> I,
+        O: FnMut(&Network, i64),
+    {
+        for t in 0..steps {
+            self.update(input_fn(t).as_ref());
+            on_step(self, t);
+        }
+    }
+
+    /// Como `run`, mas o observador pode falhar e interromper a simulação
+    ///
+    /// # Retorna
+    /// O primeiro erro devolvido por `on_step`
+    pub fn try_run<I, F, O, E>(
+        &mut self,
+        steps: i64,
+        mut input_fn: F,
+        mut on_step: O,
+    ) -> core::result::Result<(), E>
+    where
+        I: AsRef<[f64]>,
+        F: FnMut(i64) -> I,
+        O: FnMut(&Network, i64) -> core::result::Result<(), E>,
+    {
+        for t in 0..steps {
+            self.update(input_fn(t).as_ref());
+            on_step(self, t)?;
+        }
+        Ok(())
+    }
+
+    /// Executa passos de aquecimento antes das medições
+    ///
+    /// Deixa assentar memórias contextuais, energias e alerta sob um estímulo
+    /// de fundo, sem aprender nem registar (ver `WarmUpParams`), para que os
+    /// transientes iniciais não entrem nos resultados. O tempo da rede avança
+    /// `steps` passos.
+    ///
+    /// # Argumentos
+    /// * `steps` - Número de passos de aquecimento
+    /// * `background` - Input externo aplicado em todos os passos
+    pub fn warm_up(&mut self, steps: i64, background: &[f64]) {
+        self.warm_up_with(steps, background, WarmUpParams::default());
+    }
+
+    /// Como `warm_up`, escolhendo se o aquecimento aprende e regista
+    ///
+    /// Sem `recording`, o registo de disparos, o histórico, a memória
+    /// episódica e os observadores ficam suspensos e o custo metabólico volta
+    /// ao valor anterior no fim.
+    pub fn warm_up_with(&mut self, steps: i64, background: &[f64], params: WarmUpParams) {
+        let plasticity = core::mem::replace(&mut self.plasticity, params.learning);
+        let suspended = (!params.recording).then(|| {
+            (
+                self.spike_train.take(),
+                self.history.take(),
+                self.episodic.take(),
+                core::mem::take(&mut self.observers.0),
+                self.metabolic_cost,
+            )
+        });
+
+        for _ in 0..steps {
+            self.update(background);
+        }
+
+        self.plasticity = plasticity;
+        if let Some((spike_train, history, episodic, observers, metabolic_cost)) = suspended {
+            self.spike_train = spike_train;
+            self.history = history;
+            self.episodic = episodic;
+            self.observers.0 = observers;
+            self.metabolic_cost = metabolic_cost;
+        }
+    }
+
+    /// Liga ou desliga o aprendizado dos Dendritomas em `update`
+    ///
+    /// Com o aprendizado desligado a rede continua a calcular novidade,
+    /// memória e estado glial; só os pesos ficam fixos.
+    pub fn set_plasticity(&mut self, enabled: bool) {
+        self.plasticity = enabled;
+    }
+
+    /// Verdadeiro se `update` aplica o aprendizado dos Dendritomas
+    pub fn plasticity_enabled(&self) -> bool {
+        self.plasticity
+    }
+
+    /// Versão de `update` que exige exatamente um input externo por neurónio
+    ///
+    /// Em caso de erro a rede não avança.
+    pub fn try_update(&mut self, external_inputs: &[f64]) -> Result<()> {
+        check_len("external_inputs", self.neurons.len(), external_inputs.len())?;
+        self.update(external_inputs);
+        Ok(())
+    }
+
+    /// Executa um passo com inputs externos esparsos `(neurónio, valor)`
+    ///
+    /// Evita que o chamador construa um vetor denso quando poucos neurónios
+    /// recebem estímulo. Índices repetidos somam os seus valores; neurónios
+    /// não listados recebem zero.
+    ///
+    /// # Retorna
+    /// Erro (sem avançar a rede) se algum índice estiver fora da rede
+    pub fn update_with_events(&mut self, events: &[(usize, f64)]) -> Result<()> {
+        let len = self.neurons.len();
+        if let Some(&(index, _)) = events.iter().find(|&&(i, _)| i >= len) {
+            return Err(NenvError::IndexOutOfRange { index, len });
+        }
+        self.update_from(events);
+        Ok(())
+    }
+
+    /// Executa um passo com o input de qualquer `InputSource`
+    ///
+    /// Vetores densos vão diretos para `update`; pares esparsos e iteradores
+    /// são escritos num buffer da rede reutilizado entre passos, sem alocar
+    /// um vetor de N valores por passo (ver `input`).
+    pub fn update_from<S: InputSource>(&mut self, source: S) {
+        if let Some(dense) = source.as_dense() {
+            self.update(dense);
+            return;
+        }
+        let mut buffer = core::mem::take(&mut self.input_buffer);
+        self.update(buffer.load(self.neurons.len(), source));
+        self.input_buffer = buffer;
+    }
+
+    /// Retorna o número de neurónios na rede
+    pub fn num_neurons(&self) -> usize {
+        self.neurons.len()
+    }
+
+    /// Potencial modulado pela Glia de cada neurónio no último passo
+    ///
+    /// É o valor antes da inibição global e do contexto (o mesmo que os
+    /// observadores recebem em `after_integration`). Vazio antes do primeiro
+    /// `update`.
+    pub fn modulated_potentials(&self) -> &[f64] {
+        &self.buffers.modulated
+    }
+
+    /// Retorna o número de neurónios que estão disparando no momento
+    pub fn num_firing(&self) -> usize {
+        self.neurons.iter().filter(|n| n.is_firing).count()
+    }
+
+    /// Energia total gasta em disparos desde a criação ou o último `reset_state`
+    ///
+    /// Soma de `energy_cost_fire` por cada disparo transmitido (não inclui o
+    /// custo de manutenção); é o custo metabólico usado em `evaluation`.
+    pub fn metabolic_cost(&self) -> f64 {
+        self.metabolic_cost
+    }
+
+    /// Retorna a energia média da rede (0.0 numa rede vazia)
+    pub fn average_energy(&self) -> f64 {
+        if self.neurons.is_empty() {
+            return 0.0;
+        }
+        let total_energy: f64 = self.neurons.iter().map(|n| n.glia.energy).sum();
+        total_energy / self.neurons.len() as f64
+    }
+
+    /// Retorna vetor com estado de disparo de todos os neurónios
+    pub fn get_firing_states(&self) -> Vec<bool> {
+        self.neurons.iter().map(|n| n.is_firing).collect()
+    }
+
+    /// Retorna vetor com níveis de energia de todos os neurónios
+    pub fn get_energy_levels(&self) -> Vec<f64> {
+        self.neurons.iter().map(|n| n.glia.energy).collect()
+    }
+
+    /// Retorna vetor com o priority de todos os neurónios
+    pub fn get_priority_levels(&self) -> Vec<f64> {
+        self.neurons.iter().map(|n| n.glia.priority).collect()
+    }
+
+    /// Retorna vetor com o limiar de disparo de todos os neurónios
+    pub fn get_thresholds(&self) -> Vec<f64> {
+        self.neurons.iter().map(|n| n.threshold).collect()
+    }
+
+    /// Copia todos os vetores de estado por neurónio (ver `NetworkStateSnapshot`)
+    pub fn state_snapshot(&self) -> NetworkStateSnapshot {
+        NetworkStateSnapshot::capture(self)
+    }
+
+    /// Resumo legível da rede (o mesmo texto de `Display`)
+    ///
+    /// Prefira-o ao `Debug` derivado, que imprime as matrizes N×N inteiras.
+    pub fn summary(&self) -> String {
+        self.to_string()
+    }
+
+    /// Retorna estatísticas dos pesos de todos os Dendritomas (histograma com 20 bins)
+    ///
+    /// Inclui a rede inteira, a separação por tipo de neurónio e cada grupo
+    /// nomeado definido com `define_group`.
+    pub fn weight_statistics(&self) -> WeightStatistics {
+        self.weight_statistics_with_bins(20)
+    }
+
+    /// Retorna estatísticas dos pesos com um número de bins personalizado
+    pub fn weight_statistics_with_bins(&self, bins: usize) -> WeightStatistics {
+        let of_type = |neuron_type: NeuronType| {
+            WeightStats::from_weights(
+                self.neurons
+                    .iter()
+                    .filter(|n| n.neuron_type == neuron_type)
+                    .flat_map(|n| n.dendritoma.weights.iter()),
+                bins,
+            )
+        };
+
+        let groups = self
+            .groups
+            .iter()
+            .map(|(name, members)| {
+                let stats = WeightStats::from_weights(
+                    members
+                        .iter()
+                        .flat_map(|&i| self.neurons[i].dendritoma.weights.iter()),
+                    bins,
+                );
+                (name.clone(), stats)
+            })
+            .collect();
+
+        WeightStatistics {
+            overall: WeightStats::from_weights(
+                self.neurons.iter().flat_map(|n| n.dendritoma.weights.iter()),
+                bins,
+            ),
+            excitatory: of_type(NeuronType::Excitatory),
+            inhibitory: of_type(NeuronType::Inhibitory),
+            groups,
+        }
+    }
+
+    /// Matriz de pesos da rede: linha i = pesos de entrada do neurónio i
+    pub fn weight_matrix(&self) -> Vec<Vec<f64>> {
+        self.neurons
+            .iter()
+            .map(|n| n.dendritoma.weights.clone())
+            .collect()
+    }
+
+    /// Substitui todos os pesos pela matriz dada (linha i = entradas do neurónio i)
+    ///
+    /// A matriz tem de ser N×N. As entradas sem conexão na matriz de
+    /// conectividade são aceites mas não têm efeito. Nada é alterado se a
+    /// validação falhar.
+    ///
+    /// # Retorna
+    /// Erro se a forma ou algum valor forem inválidos
+    pub fn set_weight_matrix(&mut self, matrix: &[Vec<f64>]) -> Result<()> {
+        check_len("linhas da matriz de pesos", self.neurons.len(), matrix.len())?;
+        for (neuron, row) in self.neurons.iter().zip(matrix) {
+            check_len("colunas da matriz de pesos", neuron.dendritoma.num_inputs(), row.len())?;
+            check_finite("matriz de pesos", row)?;
+        }
+
+        for (neuron, row) in self.neurons.iter_mut().zip(matrix) {
+            neuron.dendritoma.set_weights_from(row)?;
+        }
+        Ok(())
+    }
+
+    /// Carrega os pesos de um ficheiro CSV ou `.npy` (ver `weights::load_weight_matrix`)
+    #[cfg(feature = "std")]
+    pub fn load_weights<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        let matrix = load_weight_matrix(path)?;
+        Ok(self.set_weight_matrix(&matrix)?)
+    }
+
+    /// Junta esta rede e `other` numa rede nova (ver módulo `ensemble`)
+    ///
+    /// Os neurónios de `other` ficam depois dos desta rede, com os mesmos
+    /// pesos, limiares, parâmetros, lesões e conexões internas; as duas
+    /// metades só se ligam pelos pares de `bridge`. Os pesos entre metades
+    /// começam a zero, exceto os da ponte (`bridge.weight`). Os grupos das
+    /// duas redes são mantidos (os de `other` com os índices deslocados; um
+    /// nome repetido fica com os membros de ambas). A rede nova usa a
+    /// configuração desta, começa no passo 0 com o estado dinâmico limpo e
+    /// não herda portas, observadores nem subsistemas opcionais.
+    ///
+    /// Se as duas grades tiverem a mesma largura e esta rede ocupar linhas
+    /// completas, `other` fica por baixo na grade; caso contrário a rede
+    /// nova não tem grade.
+    ///
+    /// # Retorna
+    /// Erro se algum par da ponte estiver fora da rede correspondente
+    pub fn merge(&self, other: &Network, bridge: &BridgeSpec) -> Result<Network> {
+        let (n1, n2) = (self.neurons.len(), other.neurons.len());
+        let n = n1 + n2;
+        for &(a, b) in &bridge.pairs {
+            if a >= n1 {
+                return Err(NenvError::IndexOutOfRange { index: a, len: n1 });
+            }
+            if b >= n2 {
+                return Err(NenvError::IndexOutOfRange { index: b, len: n2 });
+            }
+        }
+        check_finite("peso da ponte", &[bridge.weight])?;
+
+        let mut merged = Self::with_rng(
+            0,
+            ConnectivityType::FullyConnected,
+            0.0,
+            0.5,
+            self.config,
+            &mut StdRng::seed_from_u64(0),
+        );
+
+        let halves = [(self, 0), (other, n1)];
+        let embed = |vector: &[f64], offset: usize, fill: f64| {
+            let mut embedded = vec![fill; n];
+            embedded[offset..offset + vector.len()].copy_from_slice(vector);
+            embedded
+        };
+        for &(network, offset) in &halves {
+            for (i, neuron) in network.neurons.iter().enumerate() {
+                let mut neuron = neuron.clone();
+                neuron.id = offset + i;
+                neuron.dendritoma.weights = embed(&neuron.dendritoma.weights, offset, 0.0);
+                neuron.dendritoma.plasticity = embed(&neuron.dendritoma.plasticity, offset, 1.0);
+                neuron.memory_trace = embed(&neuron.memory_trace, offset, 0.0);
+                neuron.reset_state();
+                merged.neurons.push(neuron);
+            }
+            for row in &network.connectivity_matrix {
+                let mut merged_row = vec![0; n];
+                merged_row[offset..offset + row.len()].copy_from_slice(row);
+                merged.connectivity_matrix.push(merged_row);
+            }
+            merged.lesioned.extend_from_slice(&network.lesioned);
+            for (name, members) in &network.groups {
+                let group = merged.groups.entry(name.clone()).or_default();
+                group.extend(members.iter().map(|&i| offset + i));
+            }
+        }
+
+        for &(a, b) in &bridge.pairs {
+            let b = n1 + b;
+            merged.connectivity_matrix[a][b] = 1;
+            merged.connectivity_matrix[b][a] = 1;
+            merged.neurons[a].dendritoma.weights[b] = bridge.weight;
+            merged.neurons[b].dendritoma.weights[a] = bridge.weight;
+        }
+
+        if self.synaptic_gains.is_some() || other.synaptic_gains.is_some() {
+            let mut gains = vec![vec![1.0; n]; n];
+            for &(network, offset) in &halves {
+                let size = network.neurons.len();
+                for (i, row) in gains[offset..offset + size].iter_mut().enumerate() {
+                    for j in 0..size {
+                        row[offset + j] = network.synaptic_gain(i, j);
+                    }
+                }
+            }
+            merged.synaptic_gains = Some(gains);
+        }
+
+        let width = self.grid_width;
+        if width > 0 && width == other.grid_width && n1 % width == 0 {
+            merged.grid_width = width;
+            merged.grid_height = n.div_ceil(width);
+        }
+        merged.refresh_connectivity();
+        Ok(merged)
+    }
+
+    /// Digest estável do estado dinâmico da rede
+    ///
+    /// Combina (FNV-1a de 64 bits sobre a representação binária exata) o passo
+    /// de tempo, o alert_level e, para cada neurónio, pesos, plasticidade,
+    /// energia, prioridade, traço de memória, limiar, último disparo e novidade.
+    /// Ao contrário de `DefaultHasher`, o resultado não depende da versão do
+    /// compilador nem da plataforma, servindo para testes de regressão.
+    pub fn state_hash(&self) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+        let mut hash = FNV_OFFSET;
+        let mut feed = |value: u64| {
+            for byte in value.to_le_bytes() {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        };
+
+        feed(self.current_time_step as u64);
+        feed(self.alert_level.to_bits());
+
+        for neuron in &self.neurons {
+            for &w in &neuron.dendritoma.weights {
+                feed(w.to_bits());
+            }
+            for &p in &neuron.dendritoma.plasticity {
+                feed(p.to_bits());
+            }
+            feed(neuron.glia.energy.to_bits());
+            feed(neuron.glia.priority.to_bits());
+            for &m in &neuron.memory_trace {
+                feed(m.to_bits());
+            }
+            feed(neuron.threshold.to_bits());
+            feed(neuron.last_fire_time as u64);
+            feed(neuron.novelty.to_bits());
+        }
+
+        hash
+    }
+
+    /// Compara duas redes com tolerância absoluta `tol` nos valores reais
+    ///
+    /// Estruturas (dimensão, conectividade, tipos, lesões, último disparo e
+    /// passo de tempo) têm de ser idênticas; pesos, plasticidade, memória,
+    /// energia, priority, limiar, novidade, saída e alert_level podem
+    /// diferir até `tol`. Útil para validar serialização ou implementações
+    /// alternativas do ciclo de update.
+    pub fn approx_eq(&self, other: &Network, tol: f64) -> bool {
+        self.first_difference(other, tol).is_none()
+    }
+
+    /// Descreve a primeira diferença encontrada por `approx_eq`, se houver
+    pub fn first_difference(&self, other: &Network, tol: f64) -> Option<String> {
+        let close = |a: f64, b: f64| (a - b).abs() <= tol || (a.is_nan() && b.is_nan());
+        let close_all = |a: &[f64], b: &[f64]| {
+            a.len() == b.len() && a.iter().zip(b).all(|(&x, &y)| close(x, y))
+        };
+
+        if self.neurons.len() != other.neurons.len() {
+            return Some(format!(
+                "número de neurónios: {} vs {}",
+                self.neurons.len(),
+                other.neurons.len()
+            ));
+        }
+        if self.current_time_step != other.current_time_step {
+            return Some(format!(
+                "passo de tempo: {} vs {}",
+                self.current_time_step, other.current_time_step
+            ));
+        }
+        if self.connectivity_matrix != other.connectivity_matrix {
+            return Some("matriz de conectividade".to_string());
+        }
+        if self.lesioned != other.lesioned {
+            return Some("neurónios lesionados".to_string());
+        }
+        if self.synaptic_gains != other.synaptic_gains {
+            return Some("ganhos sinápticos".to_string());
+        }
+        if !close(self.alert_level, other.alert_level) {
+            return Some(format!("alert_level: {} vs {}", self.alert_level, other.alert_level));
+        }
+        if !close(self.current_avg_novelty, other.current_avg_novelty) {
+            return Some("novidade média".to_string());
+        }
+
+        for (i, (a, b)) in self.neurons.iter().zip(&other.neurons).enumerate() {
+            let field = if a.neuron_type != b.neuron_type {
+                "tipo"
+            } else if a.is_firing != b.is_firing || a.last_fire_time != b.last_fire_time {
+                "disparo"
+            } else if !close_all(&a.dendritoma.weights, &b.dendritoma.weights) {
+                "pesos"
+            } else if !close_all(&a.dendritoma.plasticity, &b.dendritoma.plasticity) {
+                "plasticidade"
+            } else if !close_all(&a.memory_trace, &b.memory_trace) {
+                "memória"
+            } else if !close(a.glia.energy, b.glia.energy) {
+                "energia"
+            } else if !close(a.glia.priority, b.glia.priority) {
+                "priority"
+            } else if !close(a.threshold, b.threshold) {
+                "limiar"
+            } else if !close(a.novelty, b.novelty) {
+                "novidade"
+            } else if !close(a.output_signal, b.output_signal) {
+                "saída"
+            } else {
+                continue;
+            };
+            return Some(format!("neurónio {}: {}", i, field));
+        }
+        None
+    }
+
+    /// Converte índice linear para coordenadas (row, col) na grade
+    pub fn index_to_coords(&self, index: usize) -> Option<(usize, usize)> {
+        if self.grid_width > 0 && index < self.neurons.len() {
+            Some((index / self.grid_width, index % self.grid_width))
+        } else {
+            None
+        }
+    }
+
+    /// Itera sobre os neurónios com as suas coordenadas `(row, col)` na grade
+    ///
+    /// Em redes sem grade (`FullyConnected`) todos os neurónios ficam numa
+    /// única linha: `(0, índice)`.
+    pub fn iter_with_coords(&self) -> impl Iterator<Item = ((usize, usize), &NENV)> {
+        let width = self.grid_width;
+        self.neurons
+            .iter()
+            .enumerate()
+            .map(move |(i, neuron)| (Self::coords_for(width, i), neuron))
+    }
+
+    /// Versão mutável de `iter_with_coords`
+    pub fn iter_with_coords_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut NENV)> {
+        let width = self.grid_width;
+        self.neurons
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, neuron)| (Self::coords_for(width, i), neuron))
+    }
+
+    fn coords_for(width: usize, index: usize) -> (usize, usize) {
+        match index.checked_div(width) {
+            Some(row) => (row, index % width),
+            None => (0, index),
+        }
+    }
+
+    /// Converte coordenadas (row, col) para índice linear
+    pub fn coords_to_index(&self, row: usize, col: usize) -> Option<usize> {
+        if self.grid_width > 0 && row < self.grid_height && col < self.grid_width {
+            let index = row * self.grid_width + col;
+            if index < self.neurons.len() {
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    /// Lesiona (silencia permanentemente) um conjunto de neurónios
+    ///
+    /// Neurónios lesionados deixam de disparar, emitem sinal zero e não
+    /// contribuem para a novidade média da rede. Opcionalmente, todas as
+    /// suas conexões de entrada e saída são removidas da matriz de
+    /// conectividade, simulando uma ablação completa do tecido.
+    ///
+    /// Índices fora do intervalo da rede são ignorados.
+    ///
+    /// # Argumentos
+    /// * `indices` - Neurónios a lesionar
+    /// * `remove_connections` - Se `true`, remove as conexões dos neurónios
+    pub fn lesion(&mut self, indices: &[usize], remove_connections: bool) {
+        let num_neurons = self.neurons.len();
+
+        for &i in indices.iter().filter(|&&i| i < num_neurons) {
+            self.lesioned[i] = true;
+            self.neurons[i].is_firing = false;
+            self.neurons[i].output_signal = 0.0;
+
+            if remove_connections {
+                for j in 0..num_neurons {
+                    self.connectivity_matrix[i][j] = 0;
+                    self.connectivity_matrix[j][i] = 0;
+                }
+            }
+        }
+
+        if remove_connections {
+            self.refresh_connectivity();
+        }
+    }
+
+    /// Verifica se um neurónio está lesionado
+    pub fn is_lesioned(&self, index: usize) -> bool {
+        self.lesioned.get(index).copied().unwrap_or(false)
+    }
+
+    /// Retorna os índices de todos os neurónios lesionados
+    pub fn lesioned_indices(&self) -> Vec<usize> {
+        self.lesioned
+            .iter()
+            .enumerate()
+            .filter(|&(_, &lesioned)| lesioned)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Ativa a falha estocástica de disparo dependente da energia
+    ///
+    /// # Argumentos
+    /// * `failure` - Curva de fiabilidade da transmissão
+    /// * `seed` - Semente do gerador das falhas
+    pub fn enable_spike_failure(&mut self, failure: SpikeFailure, seed: u64) {
+        self.spike_failure = Some((failure, StdRng::seed_from_u64(seed)));
+    }
+
+    /// Desativa a falha estocástica de disparo
+    pub fn disable_spike_failure(&mut self) {
+        self.spike_failure = None;
+    }
+
+    /// Número de disparos perdidos por falha metabólica no último passo
+    pub fn num_failed_spikes(&self) -> usize {
+        self.failed_spikes
+    }
+
+    /// Ativa a religação dependente de atividade (ver módulo `rewiring`)
+    ///
+    /// Os traços de coatividade começam a zero e são acumulados a partir do
+    /// próximo `update`.
+    pub fn enable_rewiring(&mut self, params: RewiringParams) {
+        self.rewiring = Some(Rewiring::new(self.neurons.len(), params));
+    }
+
+    /// Desativa a religação (a topologia atual mantém-se)
+    pub fn disable_rewiring(&mut self) {
+        self.rewiring = None;
+    }
+
+    /// Traço de coatividade do par `j → i` (0.0 sem religação ativa)
+    pub fn coactivity(&self, i: usize, j: usize) -> f64 {
+        self.rewiring
+            .as_ref()
+            .and_then(|rewiring| rewiring.coactivity.get(i))
+            .and_then(|row| row.get(j))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Executa já uma ronda de religação
+    ///
+    /// Cada conexão movida leva consigo o peso aprendido: o neurónio alvo
+    /// passa a usar para o novo pré-sináptico o peso que tinha no antigo.
+    /// Neurónios lesionados ficam de fora.
+    ///
+    /// # Retorna
+    /// As conexões movidas (vazio se a religação não estiver ativa)
+    pub fn rewire_now(&mut self) -> Vec<Rewire> {
+        let Some(rewiring) = &self.rewiring else {
+            return Vec::new();
+        };
+        let rewires = rewiring.plan(&self.connectivity_matrix, &self.lesioned);
+        for rewire in &rewires {
+            self.connectivity_matrix[rewire.target][rewire.from] = 0;
+            self.connectivity_matrix[rewire.target][rewire.to] = 1;
+            let weights = &mut self.neurons[rewire.target].dendritoma.weights;
+            weights[rewire.to] = weights[rewire.from];
+        }
+        if !rewires.is_empty() {
+            self.refresh_connectivity();
+        }
+        rewires
+    }
+
+    /// Ativa o crescimento desenvolvimental sobre uma reserva fixa (ver módulo `growth`)
+    ///
+    /// A rede não é redimensionada: todos os neurónios fora de `seeds` passam
+    /// à reserva, lesionados com remoção das conexões (e listados em
+    /// `lesioned_indices`), até serem ativados. A conectividade atual é
+    /// guardada como modelo para ligar os neurónios ativados.
+    ///
+    /// # Argumentos
+    /// * `params` - Parâmetros de ativação e sinaptogénese
+    /// * `seeds` - Neurónios ativos no início (índices fora da rede são ignorados)
+    pub fn enable_reserve_growth(&mut self, params: GrowthParams, seeds: &[usize]) {
+        let template = self.connectivity_matrix.clone();
+        let reserve: Vec<usize> = (0..self.neurons.len()).filter(|i| !seeds.contains(i)).collect();
+        self.lesion(&reserve, true);
+
+        let mut growth = Growth::new(template, params);
+        growth.trajectory.push(self.growth_snapshot());
+        self.growth = Some(growth);
+    }
+
+    /// Desativa o crescimento; a estrutura atingida mantém-se
+    pub fn disable_growth(&mut self) {
+        self.growth = None;
+    }
+
+    /// Executa já uma ronda de crescimento
+    ///
+    /// # Retorna
+    /// As ativações e sinapses novas desta ronda (vazio se inativo)
+    pub fn grow_now(&mut self) -> Vec<GrowthEvent> {
+        let Some(mut growth) = self.growth.take() else {
+            return Vec::new();
+        };
+        let time = self.current_time_step;
+        let mut events = Vec::new();
+
+        let novelty: Vec<f64> = self.neurons.iter().map(|n| n.novelty).collect();
+        for neuron in growth.plan_activations(&novelty, &self.lesioned) {
+            self.lesioned[neuron] = false;
+            self.neurons[neuron].reset_state();
+            for j in (0..self.neurons.len()).filter(|&j| !self.lesioned[j]) {
+                self.connectivity_matrix[neuron][j] |= growth.template[neuron][j];
+                self.connectivity_matrix[j][neuron] |= growth.template[j][neuron];
+            }
+            events.push(GrowthEvent::Activation { time, neuron });
+        }
+
+        for (target, source) in growth.plan_synapses(&self.connectivity_matrix, &self.lesioned) {
+            self.connectivity_matrix[target][source] = 1;
+            events.push(GrowthEvent::Synaptogenesis {
+                time,
+                target,
+                source,
+            });
+        }
+
+        if !events.is_empty() {
+            self.refresh_connectivity();
+        }
+        growth.events.extend_from_slice(&events);
+        growth.trajectory.push(self.growth_snapshot());
+        self.growth = Some(growth);
+        events
+    }
+
+    /// Todas as ativações e sinapses novas desde `enable_reserve_growth`
+    pub fn growth_events(&self) -> &[GrowthEvent] {
+        self.growth.as_ref().map_or(&[], |growth| growth.events.as_slice())
+    }
+
+    /// Tamanho da rede após cada ronda de crescimento (a primeira entrada é o início)
+    pub fn growth_trajectory(&self) -> &[GrowthSnapshot] {
+        self.growth.as_ref().map_or(&[], |growth| growth.trajectory.as_slice())
+    }
+
+    /// Neurónios ativos e conexões no passo atual
+    fn growth_snapshot(&self) -> GrowthSnapshot {
+        GrowthSnapshot {
+            time: self.current_time_step,
+            active_neurons: self.lesioned.iter().filter(|&&l| !l).count(),
+            synapses: self.neighbor_lists.iter().map(Vec::len).sum(),
+        }
+    }
+
+    /// Ativa o dropout: em cada passo, cada neurónio fica silenciado com probabilidade `p`
+    ///
+    /// Um neurónio em dropout comporta-se como lesionado nesse passo (não
+    /// dispara, não aprende nem conta para a novidade média).
+    #[cfg(feature = "std")]
+    pub fn set_dropout(&mut self, p: f64) {
+        self.set_dropout_with(p, DropoutMode::PerStep, rand::random());
+    }
+
+    /// Ativa o dropout com modo e semente explícitos
+    ///
+    /// # Argumentos
+    /// * `p` - Probabilidade de silenciar cada neurónio (limitada a [0, 1])
+    /// * `mode` - `PerStep` ou `PerTrial` (máscara mantida até `reset_state`)
+    /// * `seed` - Semente do gerador das máscaras
+    pub fn set_dropout_with(&mut self, p: f64, mode: DropoutMode, seed: u64) {
+        self.dropout = Some(Dropout::new(self.neurons.len(), p, mode, seed));
+    }
+
+    /// Desativa o dropout
+    pub fn clear_dropout(&mut self) {
+        self.dropout = None;
+    }
+
+    /// Probabilidade de dropout atual (0.0 se desativado)
+    pub fn dropout_probability(&self) -> f64 {
+        self.dropout.as_ref().map_or(0.0, |dropout| dropout.probability)
+    }
+
+    /// Neurónios em dropout na máscara atual
+    pub fn dropped_out_indices(&self) -> Vec<usize> {
+        self.dropout.as_ref().map_or_else(Vec::new, |dropout| {
+            dropout
+                .mask
+                .iter()
+                .enumerate()
+                .filter(|&(_, &dropped)| dropped)
+                .map(|(i, _)| i)
+                .collect()
+        })
+    }
+
+    /// Ativa o registo de disparos a partir do próximo passo
+    ///
+    /// Se o registo já estiver ativo, os eventos acumulados são mantidos.
+    pub fn enable_spike_recording(&mut self) {
+        if self.spike_train.is_none() {
+            self.spike_train = Some(SpikeTrain::new(self.neurons.len()));
+        }
+    }
+
+    /// Desativa o registo de disparos e devolve os eventos acumulados
+    pub fn disable_spike_recording(&mut self) -> Option<SpikeTrain> {
+        self.spike_train.take()
+    }
+
+    /// Retorna os disparos registados até agora, se o registo estiver ativo
+    pub fn spike_train(&self) -> Option<&SpikeTrain> {
+        self.spike_train.as_ref()
+    }
+
+    /// Ativa o histórico circular com os últimos `capacity` passos
+    ///
+    /// Reativar com outra capacidade descarta o histórico anterior.
+    pub fn enable_history(&mut self, capacity: usize) {
+        match &self.history {
+            Some(history) if history.capacity() == capacity.max(1) => {}
+            _ => self.history = Some(NetworkHistory::new(capacity)),
+        }
+    }
+
+    /// Desativa o histórico e devolve os passos guardados
+    pub fn disable_history(&mut self) -> Option<NetworkHistory> {
+        self.history.take()
+    }
+
+    /// Retorna o histórico recente, se ativo
+    pub fn history(&self) -> Option<&NetworkHistory> {
+        self.history.as_ref()
+    }
+
+    /// Ativa a medição do tempo de cada fase de `update` (ver módulo `profiling`)
+    ///
+    /// Se já estiver ativa, continua a acumular sobre o relatório atual.
+    #[cfg(feature = "std")]
+    pub fn enable_timing(&mut self) {
+        if self.timing.is_none() {
+            self.timing = Some(TimingReport::default());
+        }
+    }
+
+    /// Desativa a medição e devolve o tempo acumulado
+    pub fn disable_timing(&mut self) -> Option<TimingReport> {
+        self.timing.take()
+    }
+
+    /// Tempo acumulado por fase desde `enable_timing`, se a medição estiver ativa
+    pub fn timing_report(&self) -> Option<&TimingReport> {
+        self.timing.as_ref()
+    }
+
+    /// Bytes ocupados pelos vetores da rede, por componente (ver módulo `footprint`)
+    ///
+    /// Os buffers de `update` só existem depois do primeiro passo; para
+    /// estimar uma rede antes de a criar, usar `MemoryFootprint::estimate`.
+    pub fn memory_footprint(&self) -> MemoryFootprint {
+        let per_neuron = |bytes: fn(&NENV) -> usize| self.neurons.iter().map(bytes).sum::<usize>();
+
+        let mut optional = 0;
+        if let Some(rewiring) = &self.rewiring {
+            optional += rewiring.heap_bytes();
+        }
+        if let Some(growth) = &self.growth {
+            optional += growth.heap_bytes();
+        }
+        if let Some(models) = &self.forward_models {
+            optional += vec_bytes(models) + models.iter().map(|m| m.heap_bytes()).sum::<usize>();
+        }
+        if let Some(tiles) = &self.tiles {
+            optional += tiles.heap_bytes();
+        }
+        if let Some(dropout) = &self.dropout {
+            optional += dropout.heap_bytes();
+        }
+        if let Some(spike_train) = &self.spike_train {
+            optional += spike_train.heap_bytes();
+        }
+        if let Some(history) = &self.history {
+            optional += history.heap_bytes();
+        }
+        if let Some(episodic) = &self.episodic {
+            optional += episodic.heap_bytes();
+        }
+
+        MemoryFootprint {
+            neurons: vec_bytes(&self.neurons) + vec_bytes(&self.lesioned),
+            connectivity: matrix_bytes(&self.connectivity_matrix)
+                + matrix_bytes(&self.neighbor_lists)
+                + vec_bytes(&self.out_degrees),
+            weights: per_neuron(|n| vec_bytes(&n.dendritoma.weights))
+                + self.synaptic_gains.as_ref().map_or(0, matrix_bytes),
+            plasticity: per_neuron(|n| vec_bytes(&n.dendritoma.plasticity)),
+            memory_traces: per_neuron(|n| vec_bytes(&n.memory_trace))
+                + matrix_bytes(&self.memory_support)
+                + vec_bytes(&self.external_seen),
+            step_buffers: self.buffers.heap_bytes() + self.input_buffer.heap_bytes(),
+            optional,
+        }
+    }
+
+    /// Ativa o modo preditivo: a novidade passa a ser o erro de predição
+    ///
+    /// Cada neurónio recebe um `ForwardModel` que aprende a prever o seu
+    /// input seguinte; a memória contextual continua a ser atualizada mas
+    /// deixa de definir a novidade.
+    ///
+    /// # Argumentos
+    /// * `learning_rate` - Taxa de aprendizado LMS dos modelos
+    pub fn enable_predictive_novelty(&mut self, learning_rate: f64) {
+        let n = self.neurons.len();
+        self.forward_models = Some(vec![ForwardModel::new(n, learning_rate); n]);
+    }
+
+    /// Volta à novidade por desvio da memória contextual, descartando os modelos
+    pub fn disable_predictive_novelty(&mut self) -> Option<Vec<ForwardModel>> {
+        self.forward_models.take()
+    }
+
+    /// Modelo direto de um neurónio (apenas no modo preditivo)
+    pub fn forward_model(&self, index: usize) -> Option<&ForwardModel> {
+        self.forward_models.as_ref()?.get(index)
+    }
+
+    /// Ativa a camada de novidade por blocos da grade (ver módulo `tiles`)
+    pub fn enable_tile_novelty(&mut self, params: TileParams) {
+        self.tiles = Some(TileNovelty::new(self.neurons.len(), self.grid_width, params));
+    }
+
+    /// Desativa a camada de novidade por blocos
+    pub fn disable_tile_novelty(&mut self) {
+        self.tiles = None;
+    }
+
+    /// Novidade de cada bloco no último passo (vazio se desativada)
+    pub fn tile_novelty(&self) -> &[f64] {
+        self.tiles.as_ref().map_or(&[], |tiles| tiles.novelty.as_slice())
+    }
+
+    /// Neurónios de cada bloco, na mesma ordem de `tile_novelty`
+    pub fn tile_members(&self) -> &[Vec<usize>] {
+        self.tiles.as_ref().map_or(&[], |tiles| tiles.members.as_slice())
+    }
+
+    /// Ativa a memória episódica de eventos novos
+    ///
+    /// # Argumentos
+    /// * `capacity` - Número máximo de episódios guardados
+    /// * `threshold` - Novidade média mínima para guardar o input de um passo
+    pub fn enable_episodic_memory(&mut self, capacity: usize, threshold: f64) {
+        self.episodic = Some(EpisodicBuffer::new(capacity, threshold));
+    }
+
+    /// Desativa a memória episódica, devolvendo os episódios guardados
+    pub fn disable_episodic_memory(&mut self) -> Option<EpisodicBuffer> {
+        self.episodic.take()
+    }
+
+    /// Memória episódica (se ativada)
+    pub fn episodic_memory(&self) -> Option<&EpisodicBuffer> {
+        self.episodic.as_ref()
+    }
+
+    /// Reproduz um episódio: executa `repeats` passos com o input guardado
+    ///
+    /// A rede evolui normalmente (aprende e habitua) durante a reprodução,
+    /// mas os passos reproduzidos não são guardados como novos episódios.
+    pub fn replay_episode(&mut self, episode: &Episode, repeats: usize) {
+        let episodic = self.episodic.take();
+        for _ in 0..repeats {
+            self.update(&episode.inputs);
+        }
+        self.episodic = episodic;
+    }
+
+    /// Define (ou substitui) um grupo nomeado de neurónios
+    ///
+    /// Índices fora do intervalo da rede são descartados.
+    ///
+    /// # Argumentos
+    /// * `name` - Nome do grupo
+    /// * `indices` - Neurónios que pertencem ao grupo
+    pub fn define_group(&mut self, name: &str, indices: &[usize]) {
+        let members = indices
+            .iter()
+            .copied()
+            .filter(|&i| i < self.neurons.len())
+            .collect();
+        self.groups.insert(name.to_string(), members);
+    }
+
+    /// Retorna os membros de um grupo, se existir
+    pub fn group(&self, name: &str) -> Option<&[usize]> {
+        self.groups.get(name).map(|members| members.as_slice())
+    }
+
+    /// Novidade média dos membros de um grupo no último passo
+    ///
+    /// # Retorna
+    /// Erro se o grupo não existir; 0.0 para um grupo vazio
+    pub fn group_novelty(&self, name: &str) -> Result<f64> {
+        let Some(members) = self.groups.get(name) else {
+            return Err(NenvError::UnknownGroup {
+                name: name.to_string(),
+            });
+        };
+        if members.is_empty() {
+            return Ok(0.0);
+        }
+        let total: f64 = members.iter().map(|&i| self.neurons[i].novelty).sum();
+        Ok(total / members.len() as f64)
+    }
+
+    /// Divide a grade em duas modalidades e uma ponte (ver módulo `multimodal`)
+    ///
+    /// Define os grupos `first`, `second` e `BRIDGE_GROUP`, uma porta de
+    /// entrada com ganho 1.0 por modalidade, e remove as conexões diretas
+    /// entre as duas modalidades.
+    ///
+    /// # Argumentos
+    /// * `first`, `second` - Nomes das modalidades (colunas à esquerda e à direita)
+    /// * `bridge_columns` - Largura da região ponte em colunas
+    pub fn define_modalities(
+        &mut self,
+        first: &str,
+        second: &str,
+        bridge_columns: usize,
+    ) -> ModalityLayout {
+        let layout =
+            ModalityLayout::split_columns(self.neurons.len(), self.grid_width, bridge_columns);
+        self.define_group(first, &layout.first);
+        self.define_group(second, &layout.second);
+        self.define_group(BRIDGE_GROUP, &layout.bridge);
+        self.define_input_port(first, &layout.first, 1.0);
+        self.define_input_port(second, &layout.second, 1.0);
+
+        for &i in &layout.first {
+            for &j in &layout.second {
+                self.connectivity_matrix[i][j] = 0;
+                self.connectivity_matrix[j][i] = 0;
+            }
+        }
+        self.refresh_connectivity();
+        layout
+    }
+
+    /// Retorna os nomes de todos os grupos definidos (em ordem alfabética)
+    pub fn group_names(&self) -> Vec<&str> {
+        self.groups.keys().map(|name| name.as_str()).collect()
+    }
+
+    /// Define (ou substitui) uma porta de entrada nomeada
+    ///
+    /// Índices fora do intervalo da rede são descartados.
+    ///
+    /// # Argumentos
+    /// * `name` - Nome da porta
+    /// * `indices` - Neurónios que recebem o input da porta
+    /// * `gain` - Ganho aplicado aos valores passados a `stimulate`
+    pub fn define_input_port(&mut self, name: &str, indices: &[usize], gain: f64) {
+        let neurons = indices
+            .iter()
+            .copied()
+            .filter(|&i| i < self.neurons.len())
+            .collect();
+        self.input_ports.insert(name.to_string(), InputPort::new(neurons, gain));
+    }
+
+    /// Retorna uma porta de entrada, se existir
+    pub fn input_port(&self, name: &str) -> Option<&InputPort> {
+        self.input_ports.get(name)
+    }
+
+    /// Retorna os nomes das portas de entrada (em ordem alfabética)
+    pub fn input_port_names(&self) -> Vec<&str> {
+        self.input_ports.keys().map(|name| name.as_str()).collect()
+    }
+
+    /// Estimula uma porta de entrada no próximo `update`
+    ///
+    /// Cada neurónio da porta recebe `value * gain` de input externo,
+    /// somado ao vetor passado a `update`. Chamadas repetidas antes do
+    /// update acumulam-se.
+    ///
+    /// # Retorna
+    /// Erro se a porta não estiver definida
+    pub fn stimulate(&mut self, port: &str, value: f64) -> Result<()> {
+        let Some(input_port) = self.input_ports.get(port) else {
+            return Err(NenvError::UnknownPort {
+                name: port.to_string(),
+            });
+        };
+        if self.pending_inputs.is_empty() {
+            self.pending_inputs = vec![0.0; self.neurons.len()];
+        }
+        input_port.apply(&mut self.pending_inputs, value);
+        Ok(())
+    }
+
+    /// Estimula uma porta de entrada com um valor por neurónio no próximo `update`
+    ///
+    /// `values[k]` (multiplicado pelo ganho) vai para o k-ésimo neurónio
+    /// da porta; acumula com `stimulate` como este.
+    ///
+    /// # Retorna
+    /// Erro se a porta não existir ou `values` não tiver um valor por neurónio
+    pub fn stimulate_pattern(&mut self, port: &str, values: &[f64]) -> Result<()> {
+        let Some(input_port) = self.input_ports.get(port) else {
+            return Err(NenvError::UnknownPort {
+                name: port.to_string(),
+            });
+        };
+        check_len("padrão da porta", input_port.neurons.len(), values.len())?;
+        if self.pending_inputs.is_empty() {
+            self.pending_inputs = vec![0.0; self.neurons.len()];
+        }
+        input_port.apply_pattern(&mut self.pending_inputs, values);
+        Ok(())
+    }
+
+    /// Descarta o input acumulado com `stimulate` ainda não consumido
+    pub fn clear_stimulation(&mut self) {
+        self.pending_inputs.clear();
+    }
+
+    /// Define (ou substitui) uma porta de saída nomeada
+    ///
+    /// Índices fora do intervalo da rede são descartados.
+    ///
+    /// # Argumentos
+    /// * `name` - Nome da porta
+    /// * `indices` - Neurónios lidos, na ordem em que aparecem na saída
+    /// * `mode` - Sinal lido de cada neurónio
+    pub fn define_output_port(&mut self, name: &str, indices: &[usize], mode: OutputMode) {
+        let neurons = indices
+            .iter()
+            .copied()
+            .filter(|&i| i < self.neurons.len())
+            .collect();
+        self.output_ports.insert(name.to_string(), OutputPort::new(neurons, mode));
+    }
+
+    /// Retorna uma porta de saída, se existir
+    pub fn output_port(&self, name: &str) -> Option<&OutputPort> {
+        self.output_ports.get(name)
+    }
+
+    /// Retorna os nomes das portas de saída (em ordem alfabética)
+    pub fn output_port_names(&self) -> Vec<&str> {
+        self.output_ports.keys().map(|name| name.as_str()).collect()
+    }
+
+    /// Lê o sinal atual de uma porta de saída
+    ///
+    /// # Retorna
+    /// Um valor por neurónio da porta, ou erro se a porta não estiver definida
+    pub fn read_output(&self, port: &str) -> Result<Vec<f64>> {
+        match self.output_ports.get(port) {
+            Some(output_port) => Ok(output_port.read(&self.neurons)),
+            None => Err(NenvError::UnknownPort {
+                name: port.to_string(),
+            }),
+        }
+    }
+
+    /// Lê o sinal atual de todas as portas de saída, por nome
+    pub fn read_outputs(&self) -> BTreeMap<String, Vec<f64>> {
+        self.output_ports
+            .iter()
+            .map(|(name, port)| (name.clone(), port.read(&self.neurons)))
+            .collect()
+    }
+
+    /// Define o vetor de contexto externo
+    ///
+    /// O contexto mantém-se até à próxima chamada e atua nos grupos ligados
+    /// com `bind_context`. Canais sem valor contam como 0.0.
+    pub fn set_context(&mut self, context: &[f64]) {
+        self.context = context.to_vec();
+    }
+
+    /// Vetor de contexto atual
+    pub fn context(&self) -> &[f64] {
+        &self.context
+    }
+
+    /// Liga um canal do contexto a um grupo de neurónios
+    ///
+    /// # Argumentos
+    /// * `group` - Grupo afetado (tem de estar definido com `define_group`)
+    /// * `channel` - Índice do canal no vetor de contexto
+    /// * `target` - Limiar ou priority
+    /// * `gain` - Ganho aplicado ao valor do canal
+    ///
+    /// # Retorna
+    /// Erro se o grupo não existir
+    pub fn bind_context(
+        &mut self,
+        group: &str,
+        channel: usize,
+        target: ContextTarget,
+        gain: f64,
+    ) -> Result<()> {
+        if !self.groups.contains_key(group) {
+            return Err(NenvError::UnknownGroup {
+                name: group.to_string(),
+            });
+        }
+        self.context_bindings.push(ContextBinding {
+            group: group.to_string(),
+            channel,
+            target,
+            gain,
+        });
+        Ok(())
+    }
+
+    /// Ligações de contexto registadas
+    pub fn context_bindings(&self) -> &[ContextBinding] {
+        &self.context_bindings
+    }
+
+    /// Remove todas as ligações de contexto
+    pub fn clear_context_bindings(&mut self) {
+        self.context_bindings.clear();
+    }
+
+    /// Efeito do contexto atual em cada neurónio
+    fn context_effects_into(&self, effects: &mut [ContextEffect]) {
+        effects.fill(ContextEffect::default());
+        for binding in &self.context_bindings {
+            let value = self.context.get(binding.channel).copied().unwrap_or(0.0);
+            let Some(members) = self.groups.get(&binding.group) else {
+                continue;
+            };
+            for &i in members {
+                effects[i].add(binding.target, binding.gain * value);
+            }
+        }
+    }
+
+    /// Define o nível de alerta global da rede
+    ///
+    /// O alert_level afeta a recuperação de energia de todos os neurónios.
+    /// Valores altos fazem a rede responder mais rapidamente a eventos.
+    ///
+    /// # Argumentos
+    /// * `level` - Nível de alerta [0.0, 1.0]
+    pub fn set_alert_level(&mut self, level: f64) {
+        self.alert_level = level.clamp(0.0, 1.0);
+    }
+
+    /// Aumenta o alert_level baseado na atividade global da rede
+    ///
+    /// Chamado automaticamente quando detecta alta atividade ou novidade.
+    /// O alert_level decai gradualmente a cada passo de simulação.
+    ///
+    /// # Argumentos
+    /// * `boost` - Quantidade para aumentar o alert_level
+    pub fn boost_alert_level(&mut self, boost: f64) {
+        self.alert_level = (self.alert_level + boost).min(1.0);
+    }
+
+    /// Soma `value` dos neurónios não silenciados em blocos de `CHUNK_LEN`,
+    /// combinados em árvore pela ordem dos índices
+    fn chunked_sum(
+        partials: &mut Vec<f64>,
+        neurons: &[NENV],
+        silenced: &[bool],
+        value: impl Fn(&NENV) -> f64,
+    ) -> f64 {
+        partials.clear();
+        for (chunk, silenced) in neurons.chunks(CHUNK_LEN).zip(silenced.chunks(CHUNK_LEN)) {
+            let active = chunk.iter().zip(silenced).filter(|&(_, &s)| !s);
+            partials.push(active.fold(0.0, |acc, (neuron, _)| acc + value(neuron)));
+        }
+        reduce::combine(partials)
+    }
+
+    /// Fração dos neurónios excitatórios a disparar no passo atual
+    fn excitatory_firing_rate(&self) -> f64 {
+        let excitatory = self
+            .neurons
+            .iter()
+            .filter(|n| n.neuron_type == NeuronType::Excitatory);
+        let (count, firing) = excitatory.fold((0usize, 0usize), |(count, firing), n| {
+            (count + 1, firing + n.is_firing as usize)
+        });
+        if count > 0 {
+            firing as f64 / count as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// Atualiza o alert_level (decaimento gradual para baseline)
+    ///
+    /// Chamado automaticamente a cada passo de update()
+    fn update_alert_level(&mut self) {
+        // Decai gradualmente para zero (estado normal)
+        self.alert_level *= 1.0 - decay_per_step(self.config.alert_decay_rate, self.config.dt);
+    }
+
+    /// Retorna a novidade média da rede (calculada no último update)
+    ///
+    /// A novidade é a diferença média entre inputs atuais e memória contextual
+    /// de todos os neurônios. Valores altos indicam eventos inesperados.
+    ///
+    /// # Retorna
+    /// Novidade média [0.0, ∞), calculada automaticamente durante update()
+    pub fn average_novelty(&self) -> f64 {
+        self.current_avg_novelty
+    }
+
+    /// Retorna os parâmetros da integração novelty-alert `(threshold, sensitivity)`
+    pub fn novelty_alert_params(&self) -> (f64, f64) {
+        (self.config.novelty_alert_threshold, self.config.alert_sensitivity)
+    }
+
+    /// Configura os parâmetros da integração novelty-alert
+    ///
+    /// # Argumentos
+    /// * `threshold` - Novidade mínima para ativar alert_level [0.0, ∞)
+    /// * `sensitivity` - Multiplicador para calcular boost (boost = novelty * sensitivity)
+    pub fn set_novelty_alert_params(&mut self, threshold: f64, sensitivity: f64) {
+        self.config.novelty_alert_threshold = threshold.max(0.0);
+        self.config.alert_sensitivity = sensitivity.clamp(0.0, 1.0);
+    }
+
+    /// Define o decaimento da priority por ms (1.0 = instantânea, padrão)
+    ///
+    /// Com valores menores a priority integra a novidade como um acumulador
+    /// com fuga e a atenção persiste depois de o input se repetir.
+    pub fn set_priority_decay(&mut self, decay: f64) {
+        self.config.priority_decay = decay.clamp(0.0, 1.0);
+    }
+
+    /// Configura o pool inibitório global (gain 0.0 desliga)
+    ///
+    /// Um neurónio virtual integra a fração de neurónios excitatórios a
+    /// disparar com constante de tempo `tau` e retira `gain * pool` ao
+    /// potencial modulado de todos os neurónios no passo seguinte, travando
+    /// atividade descontrolada (sobretudo em redes `FullyConnected`). Os
+    /// observadores continuam a receber o potencial sem esta inibição.
+    ///
+    /// # Argumentos
+    /// * `gain` - Potencial retirado por unidade de atividade do pool [0.0, ∞)
+    /// * `tau` - Constante de tempo em ms (mínimo 1.0)
+    pub fn set_global_inhibition(&mut self, gain: f64, tau: f64) {
+        self.config.global_inhibition_gain = gain.max(0.0);
+        self.config.global_inhibition_tau = tau.max(1.0);
+    }
+
+    /// Atividade atual do pool inibitório global
+    pub fn global_inhibition(&self) -> f64 {
+        self.inhibition_pool
+    }
+
+    /// Configura o halo espacial de priority (spread 0.0 desliga)
+    ///
+    /// # Argumentos
+    /// * `spread` - Fração da novidade de um neurónio passada aos vizinhos [0.0, 1.0]
+    /// * `radius` - Raio do halo em células da grade
+    pub fn set_priority_spread(&mut self, spread: f64, radius: usize) {
+        self.config.priority_spread = spread.clamp(0.0, 1.0);
+        self.config.priority_spread_radius = radius;
+    }
+
+    /// Priority de cada neurónio disposta na grade (linhas × colunas)
+    ///
+    /// Redes sem grade devolvem uma única linha; células da grade sem
+    /// neurónio ficam fora (a última linha pode ser mais curta).
+    pub fn priority_map(&self) -> Vec<Vec<f64>> {
+        let width = if self.grid_width > 0 {
+            self.grid_width
+        } else {
+            self.neurons.len().max(1)
+        };
+        self.get_priority_levels()
+            .chunks(width)
+            .map(|row| row.to_vec())
+            .collect()
+    }
+
+    /// Retorna os parâmetros do alerta por surto de atividade `(threshold, sensitivity)`
+    pub fn rate_surge_alert_params(&self) -> (f64, f64) {
+        (self.config.rate_surge_threshold, self.config.rate_surge_sensitivity)
+    }
+
+    /// Configura o alerta por surto de atividade
+    ///
+    /// Um passo cuja taxa de disparo (fração dos neurónios ativos) excede a
+    /// linha de base em mais de `threshold` reforça o alert_level em
+    /// `surge * sensitivity`.
+    ///
+    /// # Argumentos
+    /// * `threshold` - Subida mínima da taxa sobre a linha de base [0.0, 1.0]
+    /// * `sensitivity` - Multiplicador do boost (0.0 desliga o mecanismo)
+    pub fn set_rate_surge_alert_params(&mut self, threshold: f64, sensitivity: f64) {
+        self.config.rate_surge_threshold = threshold.clamp(0.0, 1.0);
+        self.config.rate_surge_sensitivity = sensitivity.clamp(0.0, 1.0);
+    }
+
+    /// Linha de base atual da taxa de disparo
+    pub fn firing_rate_baseline(&self) -> f64 {
+        self.firing_rate_baseline
+    }
+
+    /// Parâmetros de dinâmica com que a rede foi criada
+    ///
+    /// Os parâmetros de alerta e de priority refletem os respetivos setters
+    /// (`set_novelty_alert_params`, `set_priority_decay`, ...); os
+    /// setters por neurónio (`set_all_*`) não alteram a configuração.
+    pub fn config(&self) -> &NetworkConfig {
+        &self.config
+    }
+
+    /// Duração de um passo em ms (`NetworkConfig::dt`)
+    pub fn dt(&self) -> f64 {
+        self.config.dt
+    }
+
+    /// Tempo simulado em ms (`current_time_step · dt`)
+    pub fn time_ms(&self) -> f64 {
+        self.current_time_step as f64 * self.config.dt
+    }
+}
+
+impl fmt::Display for Network {
+    /// Resumo legível: dimensão, topologia, parâmetros e estado atual
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.neurons.len();
+        let num_inhibitory = self
+            .neurons
+            .iter()
+            .filter(|neuron| neuron.neuron_type == NeuronType::Inhibitory)
+            .count();
+        let topology = if self.grid_width > 0 {
+            format!("Grid2D {}×{}", self.grid_height, self.grid_width)
+        } else {
+            "FullyConnected".to_string()
+        };
+        writeln!(
+            f,
+            "Network NEN-V: {} neurónios ({}), {} inibitórios / {} excitatórios",
+            n,
+            topology,
+            num_inhibitory,
+            n - num_inhibitory
+        )?;
+
+        let degrees: Vec<f64> = (0..n).map(|i| self.in_degree(i) as f64).collect();
+        let (min_degree, max_degree) = value_range(&degrees);
+        let connections: f64 = degrees.iter().sum();
+        writeln!(
+            f,
+            "  conexões: {} (grau de entrada {}–{}, média {:.2})",
+            connections,
+            min_degree,
+            max_degree,
+            connections / n.max(1) as f64
+        )?;
+
+        let (min_threshold, max_threshold) = value_range(&self.get_thresholds());
+        let refractory: Vec<f64> =
+            self.neurons.iter().map(|n| n.refractory_period() as f64).collect();
+        let (min_refractory, max_refractory) = value_range(&refractory);
+        let alphas: Vec<f64> = self.neurons.iter().map(|n| n.memory_alpha()).collect();
+        let (min_alpha, max_alpha) = value_range(&alphas);
+        writeln!(
+            f,
+            "  limiar: {:.3}–{:.3} | refratário: {}–{} | memory_alpha: {:.3}–{:.3}",
+            min_threshold, max_threshold, min_refractory, max_refractory, min_alpha, max_alpha
+        )?;
+
+        writeln!(
+            f,
+            "  passo {} | energia média {:.2} | novidade média {:.3} | alerta {:.3}",
+            self.current_time_step,
+            self.average_energy(),
+            self.current_avg_novelty,
+            self.alert_level
+        )?;
+        write!(
+            f,
+            "  lesionados: {} | grupos: {} | portas: {} entrada / {} saída | observadores: {}",
+            self.lesioned_indices().len(),
+            self.groups.len(),
+            self.input_ports.len(),
+            self.output_ports.len(),
+            self.observers.0.len()
+        )
+    }
+}
+
+/// Mínimo e máximo de um conjunto de valores (0 se vazio)
+fn value_range(values: &[f64]) -> (f64, f64) {
+    if values.is_empty() {
+        return (0.0, 0.0);
+    }
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    (min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ThresholdDistribution;
+
+    #[test]
+    fn test_state_hash_reproducible_with_seed() {
+        let run = |seed: u64| {
+            let mut network = Network::new_seeded(16, ConnectivityType::Grid2D, 0.2, 0.3, seed);
+            let mut inputs = vec![0.0; 16];
+            inputs[5] = 1.5;
+            for _ in 0..30 {
+                network.update(&inputs);
+            }
+            network.state_hash()
+        };
+
+        assert_eq!(run(7), run(7));
+        assert_ne!(run(7), run(8));
+    }
+
+    #[test]
+    fn test_weight_matrix_rows_are_incoming_weights() {
+        let network = Network::new_seeded(4, ConnectivityType::FullyConnected, 0.0, 0.5, 0);
+        let matrix = network.weight_matrix();
+
+        assert_eq!(matrix.len(), 4);
+        assert_eq!(matrix[2], network.neurons[2].dendritoma.weights);
+    }
+
+    #[test]
+    fn test_merge_keeps_halves_and_adds_bridge() {
+        let mut a = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.4, 1);
+        let b = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.6, 2);
+        a.define_group("centro", &[4]);
+        let merged = a.merge(&b, &BridgeSpec::new(vec![(4, 0)]).with_weight(0.25)).unwrap();
+
+        assert_eq!(merged.num_neurons(), 18);
+        assert_eq!((merged.grid_width, merged.grid_height), (3, 6));
+        assert_eq!(merged.neurons[12].threshold, 0.6);
+        assert_eq!(merged.neurons[12].dendritoma.weights[9..], b.neurons[3].dendritoma.weights);
+        assert_eq!(merged.neurons[4].dendritoma.weights[..9], a.neurons[4].dendritoma.weights);
+        assert_eq!(merged.group("centro"), Some(&[4][..]));
+
+        // As metades só se tocam pela ponte 4 ↔ 9
+        assert!(merged.is_connected(4, 9) && merged.is_connected(9, 4));
+        assert_eq!(merged.neurons[9].dendritoma.weights[4], 0.25);
+        assert!(!merged.is_connected(5, 10) && !merged.is_connected(10, 5));
+        assert_eq!(merged.neurons[5].dendritoma.weights[10], 0.0);
+        assert_eq!(merged.in_degree(9), b.in_degree(0) + 1);
+
+        assert!(a.merge(&b, &BridgeSpec::new(vec![(0, 9)])).is_err());
+    }
+
+    #[test]
+    fn test_memory_support_covers_nonzero_traces() {
+        let mut network = Network::new_seeded(25, ConnectivityType::Grid2D, 0.2, 0.3, 6);
+        let mut inputs = vec![0.0; 25];
+        inputs[12] = 1.0;
+        for _ in 0..20 {
+            network.update(&inputs);
+        }
+
+        for (i, neuron) in network.neurons.iter().enumerate() {
+            // Só os vizinhos, o próprio e o input externo entram no suporte
+            let support = &network.memory_support[i];
+            assert!(support.len() <= network.in_degree(i) + 2);
+            let mut outside = neuron.memory_trace.iter().enumerate();
+            assert!(outside.all(|(j, &m)| m == 0.0 || support.contains(&j)));
+        }
+        assert!(network.average_novelty() > 0.0);
+
+        // Com a memória a zero restam os vizinhos e o próprio
+        network.reset_state();
+        for (i, support) in network.memory_support.iter().enumerate() {
+            let mut expected = network.neighbor_lists[i].clone();
+            expected.push(i);
+            expected.sort_unstable();
+            assert_eq!(*support, expected);
+        }
+    }
+
+    #[test]
+    fn test_memory_support_merges_new_external_positions_once() {
+        let mut network = Network::new_seeded(25, ConnectivityType::Grid2D, 0.2, 0.3, 6);
+        let before = network.memory_support[0].clone();
+
+        let mut inputs = vec![0.0; 25];
+        inputs[24] = 1.0;
+        for t in 0..10 {
+            inputs[t % 3] = 0.5;
+            network.update(&inputs);
+        }
+
+        // O input distante entra uma única vez, em ordem crescente
+        let support = &network.memory_support[0];
+        assert_eq!(support.iter().filter(|&&j| j == 24).count(), 1);
+        assert!(support.windows(2).all(|w| w[0] < w[1]));
+        assert!(before.iter().all(|j| support.contains(j)));
+
+        // A novidade esparsa coincide com a densa bit a bit
+        let outputs: Vec<f64> = network.neurons.iter().map(|n| n.output_signal).collect();
+        for neuron in &network.neurons {
+            let inputs = network.gather_inputs(neuron.id, &outputs, &inputs);
+            let support = &network.memory_support[neuron.id];
+            assert_eq!(
+                neuron.compute_novelty_sparse(&inputs, support),
+                neuron.try_compute_novelty(&inputs).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_gather_inputs_follows_neighbor_lists() {
+        let mut network = Network::new_seeded(3, ConnectivityType::FullyConnected, 0.0, 0.5, 0);
+        network.connectivity_matrix[0][2] = 0;
+        network.refresh_connectivity();
+        let outputs = [1.0, 2.0, 3.0];
+
+        // A auto-conexão de FullyConnected conta; a conexão removida não
+        assert_eq!(network.gather_inputs(0, &outputs, &[0.5]), vec![1.5, 2.0, 0.0]);
+        assert_eq!(network.gather_inputs(2, &outputs, &[]), vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_step_row_is_clear_between_neurons() {
+        let mut network = Network::new_seeded(16, ConnectivityType::Grid2D, 0.2, 0.2, 3);
+        for t in 0..12 {
+            if t == 6 {
+                network.connectivity_matrix[5][6] = 0;
+                network.connectivity_matrix[5][5] = 1;
+                network.refresh_connectivity();
+            }
+            // O suporte externo muda a cada passo
+            let mut external = vec![0.0; 16];
+            external[t % 16] = 1.5;
+            external[(3 * t + 1) % 16] = 0.5;
+            network.update(&external);
+            assert!(network.buffers.row.iter().all(|&x| x == 0.0), "passo {}", t);
+
+            // Recolhido sobre a linha limpa, o input é o de `gather_inputs`
+            let mut buffers = core::mem::take(&mut network.buffers);
+            for i in 0..16 {
+                let fresh = network.gather_inputs(i, &buffers.previous_outputs, &external);
+                network.gather_inputs_into(
+                    i,
+                    &buffers.previous_outputs,
+                    &external,
+                    &buffers.external_support,
+                    &mut buffers.row,
+                );
+                assert_eq!(buffers.row, fresh, "passo {} neurónio {}", t, i);
+                network.clear_gathered(i, &buffers.external_support, &mut buffers.row);
+            }
+            network.buffers = buffers;
+        }
+    }
+
+    #[test]
+    fn test_update_reuses_step_buffers() {
+        let mut network = Network::new_seeded(16, ConnectivityType::Grid2D, 0.2, 0.5, 3);
+        network.update(&[1.0; 16]);
+        let row = network.buffers.row.as_ptr();
+        let integrated = network.buffers.integrated.as_ptr();
+
+        for _ in 0..5 {
+            network.update(&[1.0; 16]);
+        }
+        assert_eq!(network.buffers.row.as_ptr(), row);
+        assert_eq!(network.buffers.integrated.as_ptr(), integrated);
+    }
+
+    #[test]
+    fn test_memory_footprint_matches_estimate() {
+        for connectivity in [ConnectivityType::Grid2D, ConnectivityType::FullyConnected] {
+            let mut network = Network::new_seeded(144, connectivity, 0.2, 0.5, 3);
+            network.update(&[0.0; 144]);
+            let footprint = network.memory_footprint();
+            let estimate = MemoryFootprint::estimate(144, connectivity);
+
+            assert_eq!(footprint.weights, estimate.weights);
+            assert_eq!(footprint.step_buffers, estimate.step_buffers);
+            assert_eq!(footprint.optional, 0);
+            let error = footprint.total() as f64 / estimate.total() as f64 - 1.0;
+            assert!(error.abs() < 0.05, "{:?} {:?}", footprint, estimate);
+        }
+
+        let mut network = Network::new_seeded(36, ConnectivityType::Grid2D, 0.2, 0.5, 3);
+        network.enable_history(10);
+        network.enable_rewiring(RewiringParams::default());
+        network.update(&[0.0; 36]);
+        assert!(network.memory_footprint().optional >= 36 * 36 * 8);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_timing_report_covers_update_phases() {
+        let mut network = Network::new_seeded(100, ConnectivityType::Grid2D, 0.2, 0.5, 3);
+        network.update(&[1.0; 100]);
+        assert!(network.timing_report().is_none());
+
+        network.enable_timing();
+        for _ in 0..4 {
+            network.update(&[1.0; 100]);
+        }
+        let report = network.timing_report().unwrap();
+        assert_eq!(report.steps(), 4);
+        for phase in [UpdatePhase::Gather, UpdatePhase::Integrate, UpdatePhase::Memory] {
+            assert!(report.total(phase) > core::time::Duration::ZERO);
+        }
+
+        let report = network.disable_timing().unwrap();
+        network.update(&[1.0; 100]);
+        assert!(network.timing_report().is_none());
+        assert_eq!(report.steps(), 4);
+    }
+
+    #[test]
+    fn test_strict_determinism_uses_tree_reductions() {
+        let config = NetworkConfig {
+            strict_determinism: true,
+            ..NetworkConfig::default()
+        };
+        let run = |config: NetworkConfig| {
+            let mut network =
+                Network::with_config_seeded(300, ConnectivityType::Grid2D, 0.2, 0.3, config, 5);
+            network.lesion(&[3, 280], false);
+            for t in 0..10 {
+                network.update(&vec![if t % 2 == 0 { 1.0 } else { 0.0 }; 300]);
+            }
+            network
+        };
+
+        // Blocos pelo índice do neurónio; os lesionados contam como 0
+        let strict = run(config);
+        let novelty: Vec<f64> = strict
+            .neurons
+            .iter()
+            .map(|n| if [3, 280].contains(&n.id) { 0.0 } else { n.novelty })
+            .collect();
+        let expected = reduce::tree_sum(&novelty) / 298.0;
+        assert_eq!(strict.average_novelty().to_bits(), expected.to_bits());
+
+        let serial = run(NetworkConfig::default());
+        assert!((strict.metabolic_cost() - serial.metabolic_cost()).abs() < 1e-9);
+        assert_eq!(strict.metabolic_cost(), run(config).metabolic_cost());
+    }
+
+    #[test]
+    fn test_dt_keeps_dynamics_in_physical_time() {
+        let run = |dt: f64| {
+            let config = NetworkConfig {
+                dt,
+                ..NetworkConfig::default()
+            };
+            let mut network =
+                Network::with_config_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, config, 1);
+            network.boost_alert_level(0.8);
+            // 20 ms sem input
+            for _ in 0..(20.0 / dt) as usize {
+                network.update(&[0.0; 9]);
+            }
+            network
+        };
+
+        let reference = run(1.0);
+        let fine = run(0.25);
+        assert_eq!(fine.current_time_step, 80);
+        assert!((fine.time_ms() - reference.time_ms()).abs() < 1e-12);
+        assert!((fine.alert_level - reference.alert_level).abs() < 1e-9);
+        assert_eq!(fine.neurons[0].refractory_period(), 20);
+        assert_eq!(reference.neurons[0].refractory_period(), 5);
+        let rate = |n: &Network| n.neurons[0].glia.params().energy_recovery_rate;
+        assert_eq!(rate(&fine), 0.25 * rate(&reference));
+    }
+
+    #[test]
+    fn test_setters_keep_time_constants_across_dt() {
+        let build = |dt: f64| {
+            let config = NetworkConfig {
+                dt,
+                ..NetworkConfig::default()
+            };
+            let mut network =
+                Network::with_config_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, config, 1);
+            network.define_group("centro", &[4]);
+            network.set_all_refractory_periods(6);
+            network.set_all_memory_alpha(0.2);
+            let glia = GliaParams {
+                energy_recovery_rate: 3.0,
+                energy_cost_maintenance: 0.4,
+                ..GliaParams::default()
+            };
+            network.set_glia_params_for_type(NeuronType::Excitatory, glia);
+            network.schedule_memory_alpha(1, Some("centro"), 0.5).unwrap();
+            network.update(&[0.0; 9]);
+            network
+        };
+
+        let reference = build(1.0);
+        let fine = build(0.5);
+        // 6 ms de refratário: 6 passos de 1 ms ou 12 de 0.5 ms
+        assert_eq!(reference.neurons[0].refractory_period(), 6);
+        assert_eq!(fine.neurons[0].refractory_period(), 12);
+
+        // A fração da memória retida ao fim de 1 ms é a mesma
+        let retained =
+            |n: &Network, i: usize, steps: i32| (1.0 - n.neurons[i].memory_alpha()).powi(steps);
+        for i in [0, 4] {
+            assert!((retained(&fine, i, 2) - retained(&reference, i, 1)).abs() < 1e-12);
+        }
+        assert!((reference.neurons[4].memory_alpha() - 0.5).abs() < 1e-12);
+
+        // Recuperação e manutenção por ms: metade por passo de 0.5 ms
+        let params = |n: &Network| n.neurons[0].glia.params();
+        assert_eq!(params(&fine).energy_recovery_rate, 1.5);
+        assert_eq!(params(&fine).energy_cost_maintenance, 0.2);
+        assert_eq!(params(&reference).energy_recovery_rate, 3.0);
+        assert_eq!(params(&fine).energy_cost_fire, params(&reference).energy_cost_fire);
+    }
+
+    #[test]
+    fn test_invalid_dt_is_rejected() {
+        for dt in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let config = NetworkConfig {
+                dt,
+                ..NetworkConfig::default()
+            };
+            let error =
+                Network::try_with_config_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, config, 1)
+                    .unwrap_err();
+            assert!(matches!(error, NenvError::InvalidParameter { what: "dt", .. }));
+        }
+        let config = NetworkConfig::default();
+        assert!(
+            Network::try_with_config_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, config, 1).is_ok()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "dt: valor inválido 0")]
+    fn test_with_config_panics_on_zero_dt() {
+        let config = NetworkConfig {
+            dt: 0.0,
+            ..NetworkConfig::default()
+        };
+        Network::with_config_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, config, 1);
+    }
+
+    /// Observador que regista a ordem dos hooks e atua como controlador
+    struct PhaseLog(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+    impl SimulationObserver for PhaseLog {
+        fn before_step(&mut self, network: &mut Network, _: &[f64]) {
+            self.0.lock().unwrap().push(format!("before {}", network.current_time_step));
+        }
+
+        fn after_integration(&mut self, _: &Network, integrated: &[f64], _: &[f64]) {
+            self.0.lock().unwrap().push(format!("integration {}", integrated.len()));
+        }
+
+        fn after_firing(&mut self, network: &Network) {
+            self.0.lock().unwrap().push(format!("firing {}", network.num_firing()));
+        }
+
+        fn after_learning(&mut self, network: &mut Network) {
+            // Controlador: anula o alerta no fim de cada passo
+            network.alert_level = 0.0;
+            self.0.lock().unwrap().push("learning".to_string());
+        }
+    }
+
+    #[test]
+    fn test_observer_hooks_run_in_phase_order() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 5);
+        let log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        network.add_observer(Box::new(PhaseLog(log.clone())));
+
+        let mut inputs = vec![0.0; 9];
+        inputs[4] = 3.0;
+        network.set_alert_level(0.8);
+        network.update(&inputs);
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["before 0", "integration 9", "firing 9", "learning"]
+        );
+        assert_eq!(network.alert_level, 0.0);
+        assert_eq!(network.num_observers(), 1);
+        assert_eq!(network.take_observers().len(), 1);
+    }
+
+    #[test]
+    fn test_run_drives_inputs_and_observer() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 2);
+        let mut manual = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 2);
+        let stimulus = |t: i64| {
+            let mut inputs = vec![0.0; 9];
+            inputs[(t % 9) as usize] = 2.0;
+            inputs
+        };
+
+        let mut observed = Vec::new();
+        network.run(12, stimulus, |net, t| observed.push((t, net.current_time_step)));
+        for t in 0..12 {
+            manual.update(&stimulus(t));
+        }
+        assert_eq!(observed.first(), Some(&(0, 1)));
+        assert_eq!(observed.len(), 12);
+        assert_eq!(network.state_hash(), manual.state_hash());
+
+        let stopped =
+            network.try_run(10, |_| [0.0; 9], |_, t| if t == 2 { Err(t) } else { Ok(()) });
+        assert_eq!(stopped, Err(2));
+        assert_eq!(network.current_time_step, 15);
+    }
+
+    #[test]
+    fn test_reset_state_keeps_weights() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 8);
+        let initial_hash = network.state_hash();
+        network.enable_spike_recording();
+
+        let mut inputs = vec![0.0; 9];
+        inputs[4] = 3.0;
+        for _ in 0..20 {
+            network.update(&inputs);
+        }
+        let learned = network.weight_matrix();
+
+        network.reset_state();
+        assert_eq!(network.current_time_step, 0);
+        assert_eq!(network.weight_matrix(), learned);
+        assert_eq!(network.average_energy(), 100.0);
+        assert!(network.neurons.iter().all(|n| n.memory_trace.iter().all(|&m| m == 0.0)));
+        assert!(network.spike_train().unwrap().is_empty());
+
+        network.reset_all_seeded(8);
+        assert_eq!(network.state_hash(), initial_hash);
+    }
+
+    #[test]
+    fn test_try_update_and_sparse_events() {
+        let mut dense = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 4);
+        let mut sparse = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 4);
+
+        assert!(dense.try_update(&[1.0; 4]).is_err());
+        assert_eq!(dense.current_time_step, 0);
+
+        let mut inputs = vec![0.0; 9];
+        inputs[4] = 2.0;
+        dense.try_update(&inputs).unwrap();
+        sparse.update_with_events(&[(4, 1.5), (4, 0.5)]).unwrap();
+        assert_eq!(dense.state_hash(), sparse.state_hash());
+
+        assert_eq!(
+            sparse.update_with_events(&[(9, 1.0)]).unwrap_err(),
+            NenvError::IndexOutOfRange { index: 9, len: 9 }
+        );
+        assert_eq!(sparse.current_time_step, 1);
+    }
+
+    #[test]
+    fn test_try_new_rejects_empty_network() {
+        assert_eq!(
+            Network::try_new_seeded(0, ConnectivityType::Grid2D, 0.2, 0.5, 0).unwrap_err(),
+            NenvError::EmptyNetwork
+        );
+        assert!(Network::try_new_seeded(4, ConnectivityType::Grid2D, 0.2, 0.5, 1).is_ok());
+        let empty = Network::new_seeded(0, ConnectivityType::FullyConnected, 0.0, 0.5, 0);
+        assert_eq!(empty.average_energy(), 0.0);
+    }
+
+    #[test]
+    fn test_set_weight_matrix_validates_shape() {
+        let mut network = Network::new_seeded(4, ConnectivityType::FullyConnected, 0.0, 0.5, 0);
+        let before = network.weight_matrix();
+
+        let mut ragged = vec![vec![0.25; 4]; 4];
+        ragged[3].pop();
+        assert!(network.set_weight_matrix(&ragged).is_err());
+        assert!(network.set_weight_matrix(&[vec![0.25; 4]]).is_err());
+        assert_eq!(network.weight_matrix(), before);
+
+        let imported = vec![vec![0.25; 4]; 4];
+        network.set_weight_matrix(&imported).unwrap();
+        assert_eq!(network.weight_matrix(), imported);
+    }
+
+    #[test]
+    fn test_state_hash_tracks_state_changes() {
+        let mut network = Network::new_seeded(9, ConnectivityType::FullyConnected, 0.0, 0.3, 1);
+        let initial = network.state_hash();
+
+        network.neurons[4].glia.energy -= 1.0;
+        assert_ne!(network.state_hash(), initial);
+
+        network.neurons[4].glia.energy += 1.0;
+        assert_eq!(network.state_hash(), initial);
+    }
+
+    #[test]
+    fn test_network_initialization() {
+        let network = Network::new_seeded(100, ConnectivityType::Grid2D, 0.2, 0.5, 0);
 
-        // Fase 1-2: Calcular potenciais para todos os neurónios
-        for (idx, neuron) in self.neurons.iter().enumerate() {
-            let inputs = self.gather_inputs(idx, &all_neuron_outputs, external_inputs);
+        assert_eq!(network.num_neurons(), 100);
+        assert_eq!(network.current_time_step, 0);
 
-            let integrated = neuron.dendritoma.integrate(&inputs);
-            let modulated = neuron.glia.modulate(integrated);
+        // Verifica proporção de inibitórios
+        let num_inhibitory = network
+            .neurons
+            .iter()
+            .filter(|n| n.neuron_type == NeuronType::Inhibitory)
+            .count();
+        assert_eq!(num_inhibitory, 20);
+    }
 
-            integrated_potentials.push(integrated);
-            modulated_potentials.push(modulated);
-            gathered_inputs.push(inputs);
-        }
+    #[test]
+    fn test_grid_dimensions() {
+        let network = Network::new_seeded(100, ConnectivityType::Grid2D, 0.2, 0.5, 0);
 
-        // Fase 3: Decisão de disparo para todos os neurónios
-        for (neuron, &modulated_potential) in
-            self.neurons.iter_mut().zip(modulated_potentials.iter())
-        {
-            neuron.decide_to_fire(modulated_potential, self.current_time_step);
-        }
+        assert_eq!(network.grid_width, 10);
+        assert_eq!(network.grid_height, 10);
+    }
 
-        // Fase 4: Aprendizado e atualização de estado
-        let mut total_novelty = 0.0;
+    #[test]
+    fn test_approx_eq_within_tolerance() {
+        let mut a = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 6);
+        let mut b = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 6);
+        let inputs = [0.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0];
+        a.update(&inputs);
+        b.update(&inputs);
+        assert!(a.approx_eq(&b, 0.0));
 
-        for (neuron, inputs) in self.neurons.iter_mut().zip(gathered_inputs.iter()) {
-            // Calcula novidade ANTES de atualizar memória
-            let novelty = neuron.compute_novelty(inputs);
-            total_novelty += novelty;
+        b.neurons[2].dendritoma.weights[1] += 1e-9;
+        assert!(a.approx_eq(&b, 1e-6));
+        assert!(!a.approx_eq(&b, 1e-12));
+        assert_eq!(a.first_difference(&b, 1e-12).unwrap(), "neurónio 2: pesos");
 
-            // Atualiza priority baseado na novidade (sensitivity_factor = 1.0 por padrão)
-            neuron.update_priority(novelty, 1.0);
+        b.update(&inputs);
+        assert!(a.first_difference(&b, 1.0).unwrap().starts_with("passo de tempo"));
+    }
 
-            // Aprendizado (se disparou)
-            if neuron.is_firing {
-                neuron.dendritoma.apply_learning(inputs);
-            }
+    #[test]
+    fn test_stimulate_input_port() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 2);
+        network.define_input_port("centro", &[4, 40], 2.0);
+        assert_eq!(network.input_port("centro").unwrap().neurons, vec![4]);
+        assert_eq!(
+            network.stimulate("olho", 1.0),
+            Err(NenvError::UnknownPort {
+                name: "olho".to_string()
+            })
+        );
 
-            // Atualização de estado metabólico
-            neuron.glia.update_state(neuron.is_firing);
+        let mut reference = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 2);
+        let mut inputs = vec![0.0; 9];
+        inputs[4] = 2.0;
+        inputs[0] = 0.5;
+        reference.update(&inputs);
 
-            // Atualiza memória DEPOIS de calcular novelty
-            neuron.update_memory(inputs);
-        }
+        // 0.5 * 2.0 + 0.5 * 2.0 no neurónio 4, somado ao vetor explícito
+        network.stimulate("centro", 0.5).unwrap();
+        network.stimulate("centro", 0.5).unwrap();
+        network.update(&[0.5]);
+        assert!(network.approx_eq(&reference, 0.0));
 
-        // Fase 5: Integração Novelty-Alert (v0.3.0)
-        // Calcula novidade média da rede
-        self.current_avg_novelty = total_novelty / self.neurons.len() as f64;
+        // O estímulo é consumido pelo passo
+        network.update(&[]);
+        reference.update(&[]);
+        assert!(network.approx_eq(&reference, 0.0));
+    }
 
-        // Se novidade excede threshold, boost alert_level automaticamente
-        if self.current_avg_novelty > self.novelty_alert_threshold {
-            let alert_boost = self.current_avg_novelty * self.alert_sensitivity;
-            self.boost_alert_level(alert_boost);
-        }
+    #[test]
+    fn test_modalities_meet_only_through_bridge() {
+        let mut network = Network::new_seeded(20, ConnectivityType::FullyConnected, 0.0, 0.3, 2);
+        let layout = network.define_modalities("som", "imagem", 2);
+        assert_eq!(network.group("bridge").unwrap(), layout.bridge.as_slice());
+        assert!(!network.is_connected(layout.first[0], layout.second[0]));
+        assert!(network.is_connected(layout.first[0], layout.bridge[0]));
+
+        let size = layout.second.len();
+        assert!(network.stimulate_pattern("imagem", &[1.0]).is_err());
+        network.stimulate_pattern("imagem", &vec![2.0; size]).unwrap();
+        network.update(&[]);
+        assert!(network.group_novelty("imagem").unwrap() > 0.0);
+        assert!(network.group_novelty("olfato").is_err());
     }
 
-    /// Retorna o número de neurónios na rede
-    pub fn num_neurons(&self) -> usize {
-        self.neurons.len()
+    #[test]
+    fn test_read_outputs() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 2);
+        network.define_input_port("centro", &[4], 1.0);
+        network.define_output_port("motor", &[4, 0], OutputMode::Firing);
+        network.define_output_port("sinal", &[4], OutputMode::Signal);
+
+        network.stimulate("centro", 5.0).unwrap();
+        network.update(&[]);
+
+        let outputs = network.read_outputs();
+        assert_eq!(network.output_port_names(), vec!["motor", "sinal"]);
+        assert_eq!(outputs["motor"][0], 1.0);
+        assert_eq!(outputs["sinal"], vec![1.0]);
+        assert_eq!(network.read_output("motor").unwrap(), outputs["motor"]);
+        assert!(network.read_output("braço").is_err());
     }
 
-    /// Retorna o número de neurónios que estão disparando no momento
-    pub fn num_firing(&self) -> usize {
-        self.neurons.iter().filter(|n| n.is_firing).count()
+    #[test]
+    fn test_with_config_applies_parameters() {
+        let default = Network::new_seeded(9, ConnectivityType::Grid2D, 0.2, 0.3, 4);
+        let defaults = NetworkConfig::default();
+        let same = Network::with_config_seeded(9, ConnectivityType::Grid2D, 0.2, 0.3, defaults, 4);
+        assert!(default.approx_eq(&same, 0.0));
+
+        let config = NetworkConfig {
+            alert_decay_rate: 0.5,
+            learning_rate: 0.05,
+            refractory_period: 2,
+            memory_alpha: 0.3,
+            ..NetworkConfig::default()
+        };
+        let mut network =
+            Network::with_config_seeded(9, ConnectivityType::Grid2D, 0.2, 0.3, config, 4);
+        assert_eq!(network.config(), &config);
+        assert!(network.neurons.iter().all(|n| n.refractory_period() == 2
+            && n.memory_alpha() == 0.3
+            && n.dendritoma.learning_rate() == 0.05));
+
+        network.set_alert_level(0.8);
+        network.update(&[]);
+        assert!((network.alert_level - 0.4).abs() < 1e-12);
     }
 
-    /// Retorna a energia média da rede
-    pub fn average_energy(&self) -> f64 {
-        let total_energy: f64 = self.neurons.iter().map(|n| n.glia.energy).sum();
-        total_energy / self.neurons.len() as f64
+    #[test]
+    fn test_synaptic_gains_scale_recurrent_input() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 5);
+        assert_eq!(network.synaptic_gain(0, 1), 1.0);
+        assert!(network.set_synaptic_gains(vec![vec![1.0; 9]; 8]).is_err());
+
+        network.set_distance_attenuation(1.0);
+        assert!((network.synaptic_gain(0, 1) - (-1.0f64).exp()).abs() < 1e-12);
+        assert!((network.synaptic_gain(0, 4) - (-(2.0f64).sqrt()).exp()).abs() < 1e-12);
+
+        // Com ganho zero a rede não propaga atividade recorrente
+        network.set_synaptic_gains(vec![vec![0.0; 9]; 9]).unwrap();
+        let mut inputs = vec![0.0; 9];
+        inputs[4] = 5.0;
+        network.update(&inputs);
+        let outputs: Vec<f64> = network.neurons.iter().map(|n| n.output_signal).collect();
+        let inputs = network.gather_inputs(0, &outputs, &[]);
+        assert!(inputs.iter().all(|&x| x == 0.0));
+
+        network.clear_synaptic_gains();
+        assert_eq!(network.gather_inputs(0, &outputs, &[])[4], outputs[4]);
     }
 
-    /// Retorna vetor com estado de disparo de todos os neurónios
-    pub fn get_firing_states(&self) -> Vec<bool> {
-        self.neurons.iter().map(|n| n.is_firing).collect()
+    #[test]
+    fn test_threshold_distribution_keeps_weights() {
+        let config = NetworkConfig {
+            threshold_distribution: ThresholdDistribution::Uniform { min: 0.2, max: 0.8 },
+            ..NetworkConfig::default()
+        };
+        let fixed = Network::new_seeded(16, ConnectivityType::Grid2D, 0.2, 0.5, 9);
+        let varied = Network::with_config_seeded(16, ConnectivityType::Grid2D, 0.2, 0.5, config, 9);
+
+        assert_eq!(fixed.weight_matrix(), varied.weight_matrix());
+        let thresholds = varied.get_thresholds();
+        assert!(thresholds.iter().all(|t| (0.2..0.8).contains(t)));
+        assert!(thresholds.iter().any(|&t| t != thresholds[0]));
     }
 
-    /// Retorna vetor com níveis de energia de todos os neurónios
-    pub fn get_energy_levels(&self) -> Vec<f64> {
-        self.neurons.iter().map(|n| n.glia.energy).collect()
+    #[test]
+    fn test_dropout_silences_neurons() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 3);
+        network.set_dropout_with(1.0, DropoutMode::PerStep, 1);
+        network.update(&[5.0; 9]);
+        assert_eq!(network.num_firing(), 0);
+        assert_eq!(network.dropped_out_indices().len(), 9);
+
+        network.set_dropout_with(0.5, DropoutMode::PerTrial, 2);
+        let mask = network.dropped_out_indices();
+        network.update(&[5.0; 9]);
+        network.update(&[5.0; 9]);
+        assert_eq!(network.dropped_out_indices(), mask);
+        assert!(network.neurons.iter().all(|n| !(n.is_firing && mask.contains(&n.id))));
+
+        network.clear_dropout();
+        assert_eq!(network.dropout_probability(), 0.0);
+        assert!(network.dropped_out_indices().is_empty());
     }
 
-    /// Converte índice linear para coordenadas (row, col) na grade
-    pub fn index_to_coords(&self, index: usize) -> Option<(usize, usize)> {
-        if self.grid_width > 0 && index < self.neurons.len() {
-            Some((index / self.grid_width, index % self.grid_width))
-        } else {
-            None
+    #[test]
+    fn test_rewiring_keeps_in_degree() {
+        let mut network = Network::new_seeded(16, ConnectivityType::Grid2D, 0.0, 0.3, 8);
+        network.enable_rewiring(RewiringParams {
+            interval: 20,
+            trace_rate: 0.2,
+            ..RewiringParams::default()
+        });
+        let degrees: Vec<usize> = (0..16).map(|i| network.in_degree(i)).collect();
+
+        let mut inputs = vec![0.0; 16];
+        inputs[0] = 5.0;
+        for _ in 0..20 {
+            network.update(&inputs);
+        }
+        assert!(network.coactivity(0, 1) > 0.0);
+        assert_eq!((0..16).map(|i| network.in_degree(i)).collect::<Vec<_>>(), degrees);
+
+        // Os cantos opostos 0 e 15 não estão ligados; forçamos a coatividade entre eles
+        for row in &mut network.rewiring.as_mut().unwrap().coactivity {
+            row.fill(0.5);
         }
+        network.rewiring.as_mut().unwrap().coactivity[0][15] = 1.0;
+        let weights = network.neurons[0].dendritoma.weights.clone();
+
+        let rewires = network.rewire_now();
+        assert_eq!(rewires.len(), 1);
+        assert_eq!((rewires[0].target, rewires[0].to), (0, 15));
+        assert!(network.is_connected(0, 15));
+        assert!(!network.is_connected(0, rewires[0].from));
+        assert_eq!(network.neurons[0].dendritoma.weights[15], weights[rewires[0].from]);
+        assert_eq!(network.in_degree(0), degrees[0]);
+
+        network.disable_rewiring();
+        assert!(network.rewire_now().is_empty());
     }
 
-    /// Converte coordenadas (row, col) para índice linear
-    pub fn coords_to_index(&self, row: usize, col: usize) -> Option<usize> {
-        if self.grid_width > 0 && row < self.grid_height && col < self.grid_width {
-            let index = row * self.grid_width + col;
-            if index < self.neurons.len() {
-                return Some(index);
-            }
+    #[test]
+    fn test_spike_failure_at_low_energy() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 3);
+        network.enable_spike_failure(SpikeFailure::default(), 11);
+        for neuron in &mut network.neurons {
+            // Pesos enormes compensam a energia quase nula: o potencial passa o limiar
+            neuron.glia.energy = 1e-9;
+            neuron.dendritoma.weights.fill(1e12);
         }
-        None
+        network.update(&[1.0; 9]);
+        assert_eq!(network.num_firing(), 0);
+        assert_eq!(network.num_failed_spikes(), 9);
+        assert!(network.neurons.iter().all(|n| n.last_fire_time == -1));
+
+        network.disable_spike_failure();
+        network.update(&[1.0; 9]);
+        assert_eq!(network.num_failed_spikes(), 0);
+        assert!(network.num_firing() > 0);
     }
 
-    /// Define o nível de alerta global da rede
-    ///
-    /// O alert_level afeta a recuperação de energia de todos os neurónios.
-    /// Valores altos fazem a rede responder mais rapidamente a eventos.
-    ///
-    /// # Argumentos
-    /// * `level` - Nível de alerta [0.0, 1.0]
-    pub fn set_alert_level(&mut self, level: f64) {
-        self.alert_level = level.clamp(0.0, 1.0);
+    #[test]
+    fn test_rate_surge_boosts_alert() {
+        let mut quiet = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 3);
+        let mut surging = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 3);
+        for network in [&mut quiet, &mut surging] {
+            // Isola o mecanismo: a novidade nunca ativa o alerta
+            network.set_novelty_alert_params(f64::INFINITY, 0.0);
+        }
+        surging.set_rate_surge_alert_params(0.5, 0.5);
 
-        // Propaga alert_level para todos os neurónios
-        for neuron in &mut self.neurons {
-            neuron.glia.alert_level = self.alert_level;
+        quiet.update(&[5.0; 9]);
+        surging.update(&[5.0; 9]);
+        assert_eq!(quiet.alert_level, 0.0);
+        assert_eq!(surging.num_firing(), 9);
+        assert!((surging.alert_level - 0.5).abs() < 1e-12);
+        assert!((surging.firing_rate_baseline() - 0.05).abs() < 1e-12);
+
+        // Sem novo pico o alerta só decai, e a glia regista o valor usado
+        surging.update(&[0.0; 9]);
+        let level = surging.alert_level;
+        assert!(level > 0.0 && level < 0.5);
+        assert!(surging.neurons.iter().all(|n| n.glia.alert_level == level));
+    }
+
+    #[test]
+    fn test_persistent_priority() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 3);
+        network.set_priority_decay(0.1);
+        assert_eq!(network.config().priority_decay, 0.1);
+
+        network.lesion(&[0, 1, 2, 3, 5, 6, 7, 8], false);
+        network.update(&[0.0, 0.0, 0.0, 0.0, 3.0, 0.0, 0.0, 0.0, 0.0]);
+        let peak = network.neurons[4].glia.priority;
+        assert!(peak > 1.0);
+
+        // Input repetido: a novidade cai mas a priority decai devagar
+        network.update(&[0.0, 0.0, 0.0, 0.0, 3.0, 0.0, 0.0, 0.0, 0.0]);
+        let novelty = network.neurons[4].novelty;
+        assert!(network.neurons[4].glia.priority > 1.0 + novelty);
+    }
+
+    #[test]
+    fn test_priority_halo_window_matches_dense_scan() {
+        // Grade incompleta (23 neurónios em linhas de 5) com drives variados
+        let drive: Vec<f64> = (0..23).map(|i| ((i * 7) % 5) as f64 * 0.25).collect();
+        for radius in 0..4 {
+            assert_eq!(
+                Network::priority_halo(&drive, 5, radius, 0.5),
+                Network::priority_halo_dense(&drive, 5, radius, 0.5),
+                "raio {}",
+                radius
+            );
         }
+        assert_eq!(
+            Network::priority_halo(&drive, 0, 2, 0.5),
+            Network::priority_halo_dense(&drive, 0, 2, 0.5)
+        );
     }
 
-    /// Aumenta o alert_level baseado na atividade global da rede
-    ///
-    /// Chamado automaticamente quando detecta alta atividade ou novidade.
-    /// O alert_level decai gradualmente a cada passo de simulação.
-    ///
-    /// # Argumentos
-    /// * `boost` - Quantidade para aumentar o alert_level
-    pub fn boost_alert_level(&mut self, boost: f64) {
-        self.alert_level = (self.alert_level + boost).min(1.0);
+    #[test]
+    fn test_priority_halo_reaches_neighbors() {
+        let mut drive = vec![0.0; 25];
+        drive[12] = 1.0;
+        let halo = Network::priority_halo(&drive, 5, 1, 0.5);
+        assert_eq!(halo[12], 0.0);
+        assert_eq!(halo[6], 0.5);
+        assert_eq!(halo[18], 0.5);
+        assert_eq!(halo[0], 0.0);
+        assert_eq!(Network::priority_halo(&drive, 5, 2, 0.5)[0], 0.5);
 
-        // Propaga para todos os neurónios
-        for neuron in &mut self.neurons {
-            neuron.glia.alert_level = self.alert_level;
+        let mut plain = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 3);
+        let mut spread = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 3);
+        spread.set_priority_spread(0.5, 1);
+        let inputs = [0.0, 0.0, 0.0, 0.0, 3.0, 0.0, 0.0, 0.0, 0.0];
+        plain.update(&inputs);
+        spread.update(&inputs);
+
+        let (plain_map, spread_map) = (plain.priority_map(), spread.priority_map());
+        assert_eq!(spread_map.len(), 3);
+        assert!(spread_map.iter().all(|row| row.len() == 3));
+        assert!(spread_map[1][1] > plain_map[1][1]);
+        assert!(spread_map.iter().flatten().all(|&p| p <= 3.0));
+    }
+
+    #[test]
+    fn test_global_inhibition_limits_activity() {
+        let mut free = Network::new_seeded(16, ConnectivityType::FullyConnected, 0.0, 0.3, 3);
+        let mut pooled = Network::new_seeded(16, ConnectivityType::FullyConnected, 0.0, 0.3, 3);
+        pooled.set_global_inhibition(500.0, 10.0);
+
+        free.update(&[5.0; 16]);
+        pooled.update(&[5.0; 16]);
+        assert_eq!(pooled.num_firing(), 16);
+        assert!((pooled.global_inhibition() - 0.1).abs() < 1e-12);
+        assert_eq!(free.global_inhibition(), 0.0);
+
+        // Após o período refratário, o pool impede novos disparos
+        let (mut free_spikes, mut pooled_spikes) = (0, 0);
+        for _ in 0..20 {
+            free.update(&[5.0; 16]);
+            pooled.update(&[5.0; 16]);
+            free_spikes += free.num_firing();
+            pooled_spikes += pooled.num_firing();
         }
+        assert!(pooled_spikes < free_spikes);
     }
 
-    /// Atualiza o alert_level (decaimento gradual para baseline)
-    ///
-    /// Chamado automaticamente a cada passo de update()
-    fn update_alert_level(&mut self) {
-        // Decai gradualmente para zero (estado normal)
-        self.alert_level *= 1.0 - self.alert_decay_rate;
+    #[test]
+    fn test_context_gates_group() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 3);
+        network.define_group("centro", &[4]);
+        assert!(network.bind_context("borda", 0, ContextTarget::Threshold, 1.0).is_err());
+        network.bind_context("centro", 0, ContextTarget::Threshold, 100.0).unwrap();
+        network.bind_context("centro", 1, ContextTarget::Priority, -1.0).unwrap();
 
-        // Propaga para neurónios
-        for neuron in &mut self.neurons {
-            neuron.glia.alert_level = self.alert_level;
+        // Sem contexto o grupo comporta-se normalmente
+        let inputs = [0.0, 0.0, 0.0, 0.0, 5.0, 0.0, 0.0, 0.0, 0.0];
+        network.update(&inputs);
+        assert!(network.neurons[4].is_firing);
+
+        // Canal 0 sobe o limiar efetivo; o limiar guardado não muda
+        network.reset_state();
+        network.set_context(&[1.0]);
+        network.update(&inputs);
+        assert!(!network.neurons[4].is_firing);
+        assert!(network.neurons[0].is_firing);
+        assert_eq!(network.neurons[4].threshold, 0.3);
+
+        // Canal 1 com ganho -1 anula o potencial do grupo
+        network.reset_state();
+        network.set_context(&[0.0, 1.0]);
+        network.update(&inputs);
+        assert!(!network.neurons[4].is_firing);
+
+        network.clear_context_bindings();
+        network.reset_state();
+        network.update(&inputs);
+        assert!(network.neurons[4].is_firing);
+    }
+
+    #[test]
+    fn test_memory_alpha_by_group_and_schedule() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 3);
+        network.define_group("centro", &[4]);
+        network.set_memory_alpha_for_group("centro", 0.5).unwrap();
+        assert!(network.set_memory_alpha_for_group("borda", 0.5).is_err());
+        assert_eq!(network.get_memory_alphas()[4], 0.5);
+        assert_eq!(network.get_memory_alphas()[0], 0.1);
+
+        network.schedule_memory_alpha(3, None, 0.2).unwrap();
+        network.schedule_memory_alpha(2, Some("centro"), 0.9).unwrap();
+        network.update(&[]);
+        assert_eq!(network.get_memory_alphas()[4], 0.5);
+        network.update(&[]);
+        assert_eq!(network.get_memory_alphas()[4], 0.9);
+        network.update(&[]);
+        assert_eq!(network.get_memory_alphas(), vec![0.2; 9]);
+
+        // Durante o alerta a memória atualiza mais depressa
+        let mut calm = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 3);
+        let mut alert = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 3);
+        alert.set_memory_alpha_alert_gain(2.0);
+        for network in [&mut calm, &mut alert] {
+            network.set_alert_level(1.0);
+            network.update(&[0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0]);
         }
+        assert!(alert.neurons[0].memory_trace[4] > calm.neurons[0].memory_trace[4]);
     }
 
-    /// Retorna a novidade média da rede (calculada no último update)
-    ///
-    /// A novidade é a diferença média entre inputs atuais e memória contextual
-    /// de todos os neurônios. Valores altos indicam eventos inesperados.
-    ///
-    /// # Retorna
-    /// Novidade média [0.0, ∞), calculada automaticamente durante update()
-    pub fn average_novelty(&self) -> f64 {
-        self.current_avg_novelty
+    #[test]
+    fn test_episodic_memory_stores_novel_inputs() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 3);
+        network.enable_episodic_memory(4, 0.05);
+        let mut inputs = vec![0.0; 9];
+        inputs[4] = 2.0;
+        for _ in 0..30 {
+            network.update(&inputs);
+        }
+
+        // O buffer fica com os passos mais novos, por ordem cronológica
+        let buffer = network.episodic_memory().unwrap();
+        assert_eq!(buffer.len(), 4);
+        assert_eq!(buffer.episodes()[0].inputs, inputs);
+        assert!(buffer.episodes().windows(2).all(|w| w[0].time < w[1].time));
+        assert!(buffer.episodes().iter().all(|e| e.novelty > 0.05));
+
+        let episode = buffer.episodes()[0].clone();
+        let stored = buffer.len();
+        network.replay_episode(&episode, 3);
+        assert_eq!(network.current_time_step, 33);
+        assert_eq!(network.episodic_memory().unwrap().len(), stored);
     }
 
-    /// Configura os parâmetros da integração novelty-alert
-    ///
-    /// # Argumentos
-    /// * `threshold` - Novidade mínima para ativar alert_level [0.0, ∞)
-    /// * `sensitivity` - Multiplicador para calcular boost (boost = novelty * sensitivity)
-    pub fn set_novelty_alert_params(&mut self, threshold: f64, sensitivity: f64) {
-        self.novelty_alert_threshold = threshold.max(0.0);
-        self.alert_sensitivity = sensitivity.clamp(0.0, 1.0);
+    #[test]
+    fn test_predictive_novelty_habituates_to_sequences() {
+        let mut network = Network::new_seeded(4, ConnectivityType::FullyConnected, 0.0, 10.0, 3);
+        network.enable_predictive_novelty(0.2);
+        let alternating = |t: i64| {
+            let value = if t % 2 == 0 { 1.0 } else { 0.0 };
+            vec![value, 0.0, 0.0, 0.0]
+        };
+
+        // O limiar alto mantém a rede em silêncio: só o input externo conta
+        let mut novelty = Vec::new();
+        network.run(400, alternating, |network, _| novelty.push(network.average_novelty()));
+        assert!(novelty[1] > 10.0 * novelty[399]);
+        assert!(network.forward_model(0).unwrap().gain[0] < 0.0);
+
+        network.disable_predictive_novelty();
+        assert!(network.forward_model(0).is_none());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_tile_novelty_feeds_alert() {
+        let mut network = Network::new_seeded(36, ConnectivityType::Grid2D, 0.0, 0.3, 3);
+        network.set_novelty_alert_params(f64::INFINITY, 0.0);
+        network.enable_tile_novelty(TileParams {
+            alert_threshold: 0.1,
+            alert_sensitivity: 0.5,
+            ..TileParams::default()
+        });
+        assert_eq!(network.tile_members().len(), 4);
+        assert_eq!(network.tile_members()[1], vec![3, 4, 5, 9, 10, 11, 15, 16, 17]);
+
+        let mut inputs = vec![0.0; 36];
+        inputs[0] = 3.0;
+        network.update(&inputs);
+        let novelty = network.tile_novelty();
+        assert!(novelty[0] > novelty[3]);
+        assert!(network.alert_level > 0.0);
+
+        network.disable_tile_novelty();
+        assert!(network.tile_novelty().is_empty());
+    }
 
     #[test]
-    fn test_network_initialization() {
-        let network = Network::new(100, ConnectivityType::Grid2D, 0.2, 0.5);
+    fn test_reserve_growth_activates_from_seed_region() {
+        let mut network = Network::new_seeded(25, ConnectivityType::Grid2D, 0.0, 0.3, 4);
+        network.enable_reserve_growth(
+            GrowthParams {
+                interval: 5,
+                activation_threshold: 0.05,
+                ..GrowthParams::default()
+            },
+            &[12],
+        );
+        assert_eq!(network.lesioned_indices().len(), 24);
+        assert_eq!(network.in_degree(12), 0);
 
-        assert_eq!(network.num_neurons(), 100);
-        assert_eq!(network.current_time_step, 0);
+        let mut step = 0.0;
+        for _ in 0..60 {
+            step += 1.0;
+            let inputs: Vec<f64> = (0..25).map(|i| ((i as f64 + step) * 0.7).sin().abs()).collect();
+            network.update(&inputs);
+        }
 
-        // Verifica proporção de inibitórios
-        let num_inhibitory = network
-            .neurons
-            .iter()
-            .filter(|n| n.neuron_type == NeuronType::Inhibitory)
-            .count();
-        assert_eq!(num_inhibitory, 20);
+        let trajectory = network.growth_trajectory();
+        assert_eq!(trajectory[0].active_neurons, 1);
+        assert!(trajectory.windows(2).all(|w| w[1].active_neurons >= w[0].active_neurons));
+        assert!(trajectory.last().unwrap().active_neurons > 1);
+        // A capacidade é fixa: só muda quantos neurónios estão ativos
+        assert_eq!(network.num_neurons(), 25);
+
+        // A primeira ativação é vizinha da semente
+        let first = network.growth_events().iter().find_map(|e| match e {
+            GrowthEvent::Activation { neuron, .. } => Some(*neuron),
+            _ => None,
+        });
+        assert!(network.neighbors(12).contains(&first.unwrap()));
     }
 
     #[test]
-    fn test_grid_dimensions() {
-        let network = Network::new(100, ConnectivityType::Grid2D, 0.2, 0.5);
+    fn test_summary_describes_network() {
+        let mut network = Network::new_seeded(100, ConnectivityType::Grid2D, 0.2, 0.25, 0);
+        network.define_group("centro", &[44, 45]);
+        let summary = network.summary();
 
-        assert_eq!(network.grid_width, 10);
-        assert_eq!(network.grid_height, 10);
+        assert!(summary.starts_with("Network NEN-V: 100 neurónios (Grid2D 10×10)"));
+        assert!(summary.contains("20 inibitórios / 80 excitatórios"));
+        assert!(summary.contains("grau de entrada 3–8"));
+        assert!(summary.contains("limiar: 0.250–0.250"));
+        assert!(summary.contains("grupos: 1"));
+        assert_eq!(summary, format!("{}", network));
+    }
+
+    #[test]
+    fn test_bulk_setters() {
+        let mut network = Network::new_seeded(10, ConnectivityType::FullyConnected, 0.2, 0.5, 0);
+        network.set_all_thresholds(0.8);
+        network.set_all_refractory_periods(2);
+        network.set_all_memory_alpha(1.5);
+        let inhibitory_params = GliaParams {
+            energy_cost_fire: 4.0,
+            ..GliaParams::default()
+        };
+        network.set_glia_params_for_type(NeuronType::Inhibitory, inhibitory_params);
+
+        assert!(network.neurons.iter().all(|n| n.threshold == 0.8));
+        assert!(network.neurons.iter().all(|n| n.refractory_period() == 2));
+        assert!(network.neurons.iter().all(|n| n.memory_alpha() == 1.0));
+        assert_eq!(network.neurons[0].glia.params(), inhibitory_params);
+        assert_eq!(network.neurons[9].glia.params(), GliaParams::default());
+    }
+
+    #[test]
+    fn test_neighbor_queries_match_matrix() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 0);
+        assert_eq!(network.neighbors(0), &[1, 3, 4]);
+        assert_eq!(network.in_degree(4), 8);
+        assert_eq!(network.out_degree(4), 8);
+        assert!(network.is_connected(0, 4));
+        assert!(!network.is_connected(0, 8));
+        assert!(network.neighbors(99).is_empty());
+
+        #[cfg(feature = "std")]
+        assert_eq!(
+            (0..9).map(|i| network.out_degree(i)).collect::<Vec<_>>(),
+            crate::graph::out_degrees(&network.connectivity_matrix)
+        );
+
+        network.lesion(&[4], true);
+        assert!(!network.neighbors(0).contains(&4));
+        assert_eq!(network.out_degree(4), 0);
+    }
+
+    #[test]
+    fn test_iter_with_coords_selects_regions() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 0);
+        let coords: Vec<(usize, usize)> = network.iter_with_coords().map(|(c, _)| c).collect();
+        assert_eq!(coords[4], (1, 1));
+        assert_eq!(coords[8], (2, 2));
+
+        // Esvazia a energia da coluna da esquerda
+        for ((_, col), neuron) in network.iter_with_coords_mut() {
+            if col == 0 {
+                neuron.glia.energy = 0.0;
+            }
+        }
+        let drained: Vec<usize> = network
+            .iter_with_coords()
+            .filter(|(_, n)| n.glia.energy == 0.0)
+            .map(|(_, n)| n.id)
+            .collect();
+        assert_eq!(drained, vec![0, 3, 6]);
+
+        let flat = Network::new_seeded(3, ConnectivityType::FullyConnected, 0.0, 0.5, 0);
+        assert_eq!(flat.iter_with_coords().last().unwrap().0, (0, 2));
     }
 
     #[test]
     fn test_coords_conversion() {
-        let network = Network::new(100, ConnectivityType::Grid2D, 0.2, 0.5);
+        let network = Network::new_seeded(100, ConnectivityType::Grid2D, 0.2, 0.5, 0);
 
         // Testa conversão de ida e volta
         let index = network.coords_to_index(5, 5).unwrap();
@@ -423,7 +4031,7 @@ mod tests {
 
     #[test]
     fn test_2d_grid_connectivity() {
-        let network = Network::new(9, ConnectivityType::Grid2D, 0.0, 0.5);
+        let network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 0);
 
         // Grade 3x3: neurónio central (idx 4) deve ter 8 conexões
         let connections: usize = network.connectivity_matrix[4].iter().map(|&x| x as usize).sum();
@@ -436,7 +4044,7 @@ mod tests {
 
     #[test]
     fn test_fully_connected() {
-        let network = Network::new(10, ConnectivityType::FullyConnected, 0.0, 0.5);
+        let network = Network::new_seeded(10, ConnectivityType::FullyConnected, 0.0, 0.5, 0);
 
         // Cada neurónio deve conectar a todos os outros
         for i in 0..10 {
@@ -450,7 +4058,7 @@ mod tests {
 
     #[test]
     fn test_network_update_increments_time() {
-        let mut network = Network::new(10, ConnectivityType::Grid2D, 0.2, 0.5);
+        let mut network = Network::new_seeded(10, ConnectivityType::Grid2D, 0.2, 0.5, 0);
         let external_inputs = vec![0.0; 10];
 
         assert_eq!(network.current_time_step, 0);
@@ -464,7 +4072,7 @@ mod tests {
 
     #[test]
     fn test_network_stats() {
-        let network = Network::new(10, ConnectivityType::Grid2D, 0.2, 0.5);
+        let network = Network::new_seeded(10, ConnectivityType::Grid2D, 0.2, 0.5, 0);
 
         // Energia inicial deve ser 100% (MAX_ENERGY)
         assert_eq!(network.average_energy(), 100.0);
@@ -472,4 +4080,128 @@ mod tests {
         // Nenhum neurónio deve estar disparando inicialmente
         assert_eq!(network.num_firing(), 0);
     }
+
+    #[test]
+    fn test_define_group_filters_out_of_range() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 0);
+        network.define_group("centro", &[4, 20]);
+
+        assert_eq!(network.group("centro"), Some(&[4][..]));
+        assert_eq!(network.group("inexistente"), None);
+        assert_eq!(network.group_names(), vec!["centro"]);
+    }
+
+    #[test]
+    fn test_spike_recording_is_opt_in() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.1, 0);
+        let mut external_inputs = vec![0.0; 9];
+        external_inputs[4] = 5.0;
+
+        network.update(&external_inputs);
+        assert!(network.spike_train().is_none());
+
+        network.enable_spike_recording();
+        for _ in 0..5 {
+            network.update(&external_inputs);
+        }
+
+        // Apenas passos posteriores à ativação devem estar registados
+        let spikes = network.disable_spike_recording().unwrap();
+        assert!(!spikes.is_empty());
+        assert!(spikes.events().iter().all(|&(_, t)| t >= 2));
+        assert!(network.spike_train().is_none());
+    }
+
+    #[test]
+    fn test_history_keeps_last_steps() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 0);
+        network.enable_history(4);
+
+        for _ in 0..10 {
+            network.update(&[0.0; 9]);
+        }
+
+        let history = network.history().unwrap();
+        assert_eq!(history.len(), 4);
+        assert_eq!(history.latest().unwrap().time, 10);
+        assert_eq!(history.steps_back(3).unwrap().time, 7);
+        assert_eq!(history.latest().unwrap().energy.len(), 9);
+    }
+
+    #[test]
+    fn test_warm_up_settles_state_without_learning_or_recording() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.2, 5);
+        network.enable_spike_recording();
+        network.enable_history(4);
+        let weights = network.weight_matrix();
+
+        network.warm_up(30, &[1.0; 9]);
+
+        assert_eq!(network.current_time_step, 30);
+        assert_eq!(network.weight_matrix(), weights);
+        assert!(network.spike_train().unwrap().is_empty());
+        assert!(network.history().unwrap().is_empty());
+        assert_eq!(network.metabolic_cost(), 0.0);
+        assert!(network.plasticity_enabled());
+        assert!(network.neurons.iter().any(|n| n.memory_trace.iter().any(|&m| m != 0.0)));
+
+        let params = WarmUpParams {
+            learning: true,
+            recording: true,
+        };
+        network.warm_up_with(30, &[1.0; 9], params);
+        assert_ne!(network.weight_matrix(), weights);
+        assert!(!network.spike_train().unwrap().is_empty());
+        assert!(network.metabolic_cost() > 0.0);
+    }
+
+    #[test]
+    fn test_weight_statistics_by_type_and_group() {
+        let mut network = Network::new_seeded(10, ConnectivityType::FullyConnected, 0.2, 0.5, 0);
+        network.define_group("primeiro", &[0]);
+
+        let stats = network.weight_statistics();
+
+        // 10 neurónios × 10 pesos, 2 inibitórios
+        assert_eq!(stats.overall.count, 100);
+        assert_eq!(stats.inhibitory.count, 20);
+        assert_eq!(stats.excitatory.count, 80);
+        assert_eq!(stats.groups["primeiro"].count, 10);
+
+        // Pesos iniciais estão em [0.1, 0.3]
+        assert!(stats.overall.min >= 0.1 && stats.overall.max <= 0.3);
+        assert_eq!(stats.overall.histogram.counts.iter().sum::<usize>(), 100);
+    }
+
+    #[test]
+    fn test_lesioned_neuron_never_fires() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.1, 0);
+        network.lesion(&[4], false);
+
+        let mut external_inputs = vec![0.0; 9];
+        external_inputs[4] = 5.0;
+
+        for _ in 0..10 {
+            network.update(&external_inputs);
+            assert!(!network.neurons[4].is_firing);
+            assert_eq!(network.neurons[4].output_signal, 0.0);
+        }
+
+        assert!(network.is_lesioned(4));
+        assert_eq!(network.lesioned_indices(), vec![4]);
+    }
+
+    #[test]
+    fn test_lesion_removes_connections() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 0);
+        network.lesion(&[4], true);
+
+        // Neurónio central perde todas as conexões de entrada e saída
+        assert!(network.connectivity_matrix[4].iter().all(|&x| x == 0));
+        assert!(network.connectivity_matrix.iter().all(|row| row[4] == 0));
+
+        // Canto (idx 0) perde apenas a ligação ao centro: 3 → 2
+        let connections: usize = network.connectivity_matrix[0].iter().map(|&x| x as usize).sum();
+        assert_eq!(connections, 2);
+    }
 }
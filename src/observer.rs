@@ -0,0 +1,51 @@
+//! Módulo de observadores da simulação
+//!
+//! Um `SimulationObserver` registado com `Network::add_observer` é chamado
+//! nas fases de cada `Network::update`, permitindo ligar registadores,
+//! visualizadores ou controladores homeostáticos sem modificar `network.rs`.
+//!
+//! Ordem das chamadas num passo:
+//!
+//! ```text
+//! before_step        antes de avançar o tempo (rede mutável)
+//! after_integration  potenciais integrados e modulados de todos os neurónios
+//! after_firing       decisões de disparo tomadas, antes do aprendizado
+//! after_learning     fim do passo (rede mutável)
+//! ```
+//!
+//! Durante as chamadas a lista de observadores da rede está vazia; um
+//! observador adicionado dentro de um hook passa a ser chamado no passo
+//! seguinte.
+
+use crate::network::Network;
+use crate::prelude::*;
+use core::fmt;
+
+/// Ganchos chamados pela rede durante `update` (todos opcionais)
+///
+/// `Send` é exigido para que a rede continue a poder mudar de thread (p.ex.
+/// no `SimulationServer`); estado partilhado com o chamador deve usar
+/// `Arc<Mutex<..>>`.
+pub trait SimulationObserver: Send {
+    /// Início do passo, antes de incrementar `current_time_step`
+    fn before_step(&mut self, _network: &mut Network, _external_inputs: &[f64]) {}
+
+    /// Após integração e modulação glial, indexados por neurónio
+    fn after_integration(&mut self, _network: &Network, _integrated: &[f64], _modulated: &[f64]) {}
+
+    /// Após a decisão de disparo (`is_firing` e `output_signal` atualizados)
+    fn after_firing(&mut self, _network: &Network) {}
+
+    /// Fim do passo: aprendizado, memória, novidade e alerta atualizados
+    fn after_learning(&mut self, _network: &mut Network) {}
+}
+
+/// Lista de observadores registados numa rede
+#[derive(Default)]
+pub(crate) struct Observers(pub(crate) Vec<Box<dyn SimulationObserver>>);
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Observers({})", self.0.len())
+    }
+}
@@ -0,0 +1,184 @@
+//! Módulo de saída Open Sound Control (OSC)
+//!
+//! `OscEventSink` envia os eventos da simulação como mensagens OSC 1.0 sobre
+//! UDP, para conduzir síntese sonora e instalações audiovisuais em tempo real
+//! (SuperCollider, Max/MSP, Pure Data, TouchDesigner, ...).
+//!
+//! Mapeamento padrão (endereços configuráveis em `OscConfig`):
+//!
+//! ```text
+//! endereço          argumentos                      origem
+//! /nenv/fire        i neurónio                      NeuronFired
+//! /nenv/alert       f alert_level                   AlertActivated
+//! /nenv/novelty     f avg_novelty                   NoveltyAboveThreshold
+//! /nenv/state       f alert_level, f avg_novelty,   send_state (a cada passo)
+//!                   i num_firing
+//! ```
+//!
+//! Disponível com a feature `osc`.
+
+use crate::events::{EventSink, SimulationEvent};
+use crate::network::Network;
+use std::io;
+use std::net::{ToSocketAddrs, UdpSocket};
+
+/// Endereços OSC de cada tipo de mensagem (`None` desliga a mensagem)
+#[derive(Debug, Clone)]
+pub struct OscConfig {
+    pub fire_address: Option<String>,
+    pub alert_address: Option<String>,
+    pub novelty_address: Option<String>,
+    pub state_address: Option<String>,
+}
+
+impl Default for OscConfig {
+    fn default() -> Self {
+        Self {
+            fire_address: Some("/nenv/fire".to_string()),
+            alert_address: Some("/nenv/alert".to_string()),
+            novelty_address: Some("/nenv/novelty".to_string()),
+            state_address: Some("/nenv/state".to_string()),
+        }
+    }
+}
+
+/// Argumento de uma mensagem OSC
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OscArg {
+    Int(i32),
+    Float(f32),
+}
+
+/// Codifica uma mensagem OSC 1.0 (endereço, type tags e argumentos big-endian)
+pub fn encode_message(address: &str, args: &[OscArg]) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(address.len() + 8 + args.len() * 5);
+    write_padded(&mut packet, address.as_bytes());
+
+    let mut tags = vec![b','];
+    tags.extend(args.iter().map(|arg| match arg {
+        OscArg::Int(_) => b'i',
+        OscArg::Float(_) => b'f',
+    }));
+    write_padded(&mut packet, &tags);
+
+    for arg in args {
+        match arg {
+            OscArg::Int(v) => packet.extend_from_slice(&v.to_be_bytes()),
+            OscArg::Float(v) => packet.extend_from_slice(&v.to_be_bytes()),
+        }
+    }
+    packet
+}
+
+/// Escreve uma string OSC: terminada em zero e alinhada a 4 bytes
+fn write_padded(packet: &mut Vec<u8>, bytes: &[u8]) {
+    packet.extend_from_slice(bytes);
+    let padding = 4 - bytes.len() % 4;
+    packet.extend(std::iter::repeat_n(0u8, padding));
+}
+
+/// Destino de eventos que envia mensagens OSC por UDP
+pub struct OscEventSink {
+    socket: UdpSocket,
+    config: OscConfig,
+}
+
+impl OscEventSink {
+    /// Cria um destino que envia para `target` (p.ex. "127.0.0.1:57120")
+    pub fn connect<A: ToSocketAddrs>(target: A, config: OscConfig) -> io::Result<Self> {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        socket.connect(target)?;
+        Ok(Self { socket, config })
+    }
+
+    /// Configuração em uso
+    pub fn config(&self) -> &OscConfig {
+        &self.config
+    }
+
+    /// Envia uma mensagem arbitrária
+    pub fn send(&self, address: &str, args: &[OscArg]) -> io::Result<()> {
+        self.socket.send(&encode_message(address, args))?;
+        Ok(())
+    }
+
+    /// Envia o estado contínuo da rede (alert level, novidade, disparos)
+    ///
+    /// Pensado para ser chamado após cada `Network::update`, como controlo
+    /// contínuo de parâmetros de síntese.
+    pub fn send_state(&self, network: &Network) -> io::Result<()> {
+        let Some(address) = &self.config.state_address else {
+            return Ok(());
+        };
+        self.send(
+            address,
+            &[
+                OscArg::Float(network.alert_level as f32),
+                OscArg::Float(network.average_novelty() as f32),
+                OscArg::Int(network.num_firing() as i32),
+            ],
+        )
+    }
+}
+
+impl EventSink for OscEventSink {
+    fn emit(&mut self, event: &SimulationEvent) -> io::Result<()> {
+        let (address, arg) = match event {
+            SimulationEvent::NeuronFired { neuron, .. } => {
+                (&self.config.fire_address, OscArg::Int(*neuron as i32))
+            }
+            SimulationEvent::AlertActivated { alert_level, .. } => {
+                (&self.config.alert_address, OscArg::Float(*alert_level as f32))
+            }
+            SimulationEvent::NoveltyAboveThreshold { avg_novelty, .. } => {
+                (&self.config.novelty_address, OscArg::Float(*avg_novelty as f32))
+            }
+            SimulationEvent::CheckpointWritten { .. } => return Ok(()),
+        };
+        match address {
+            Some(address) => self.send(address, &[arg]),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_message_encoding_is_4_byte_aligned() {
+        let packet = encode_message("/nenv/fire", &[OscArg::Int(55)]);
+        // "/nenv/fire" (10) + 2 zeros, ",i" + 2 zeros, int32
+        assert_eq!(packet.len(), 12 + 4 + 4);
+        assert_eq!(&packet[..12], b"/nenv/fire\0\0");
+        assert_eq!(&packet[12..16], b",i\0\0");
+        assert_eq!(&packet[16..], &55i32.to_be_bytes());
+
+        // Endereço múltiplo de 4 recebe 4 zeros de terminação
+        assert_eq!(encode_message("/abc", &[]).len(), 8 + 4);
+    }
+
+    #[test]
+    fn test_events_reach_udp_receiver() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+
+        let config = OscConfig {
+            fire_address: None,
+            ..OscConfig::default()
+        };
+        let mut sink = OscEventSink::connect(receiver.local_addr().unwrap(), config).unwrap();
+        sink.emit(&SimulationEvent::NeuronFired { time: 1, neuron: 2 }).unwrap();
+        sink.emit(&SimulationEvent::AlertActivated {
+            time: 2,
+            alert_level: 0.5,
+        })
+        .unwrap();
+
+        let mut buf = [0u8; 64];
+        let len = receiver.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..len], encode_message("/nenv/alert", &[OscArg::Float(0.5)]));
+    }
+}
@@ -0,0 +1,272 @@
+//! Módulo de geração de gráficos em segundo plano
+//!
+//! Desenhar os gráficos de um experimento grande (ou um mapa de calor a
+//! cada K passos) pode demorar mais do que a própria simulação. O
+//! `PlotWorker` recebe tarefas de desenho por um canal e executa-as em
+//! threads dedicados, como o `AsyncSink` faz com a escrita de registos: o
+//! loop de simulação só copia os dados de que o gráfico precisa e continua.
+//!
+//! ```text
+//! let mut plots = PlotWorker::new();
+//! for t in 0..steps {
+//!     network.update(&inputs);
+//!     dumper.dump_in(&network, &mut plots);   // mapa copiado, desenhado depois
+//! }
+//! plots.submit_all_plots(snapshots, network.spike_train().cloned(), "exp", &config);
+//! let report = plots.finish();                // espera pelas tarefas pendentes
+//! ```
+//!
+//! Erros (e pânicos) de cada tarefa ficam no `PlotReport` devolvido por
+//! `finish`; uma tarefa falhada não interrompe as seguintes. Largar o worker
+//! sem `finish` também espera pelas tarefas, descartando o relatório.
+//!
+//! Disponível apenas com a feature `plots`.
+
+use crate::snapshot::SimulationSnapshot;
+use crate::spikes::SpikeTrain;
+use crate::visualization::{generate_all_plots, PlotConfig};
+use std::error::Error;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+/// Tarefa de desenho executada num thread do worker
+type PlotJob = Box<dyn FnOnce() -> Result<(), Box<dyn Error>> + Send>;
+
+/// Tarefa com o nome usado no relatório
+type NamedJob = (String, PlotJob);
+
+/// Tarefa de desenho que falhou
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlotFailure {
+    /// Nome dado à tarefa em `submit` (tipicamente o caminho do arquivo)
+    pub name: String,
+    /// Mensagem do erro ou do pânico
+    pub message: String,
+}
+
+/// Resultado das tarefas executadas por um `PlotWorker`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlotReport {
+    /// Tarefas concluídas sem erro
+    pub completed: usize,
+    /// Tarefas que falharam
+    pub failures: Vec<PlotFailure>,
+}
+
+impl PlotReport {
+    /// Verifica se todas as tarefas foram concluídas
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    fn merge(&mut self, other: PlotReport) {
+        self.completed += other.completed;
+        self.failures.extend(other.failures);
+    }
+}
+
+/// Fila de tarefas de desenho executadas em threads dedicados
+pub struct PlotWorker {
+    sender: Option<Sender<NamedJob>>,
+    workers: Vec<JoinHandle<PlotReport>>,
+    submitted: usize,
+}
+
+impl PlotWorker {
+    /// Worker com um thread de desenho
+    pub fn new() -> Self {
+        Self::with_threads(1)
+    }
+
+    /// Worker com `threads` threads de desenho (mínimo 1)
+    pub fn with_threads(threads: usize) -> Self {
+        let (sender, receiver) = mpsc::channel::<NamedJob>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..threads.max(1))
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                thread::spawn(move || run_jobs(&receiver))
+            })
+            .collect();
+
+        Self {
+            sender: Some(sender),
+            workers,
+            submitted: 0,
+        }
+    }
+
+    /// Número de threads de desenho
+    pub fn num_threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Número de tarefas submetidas até agora
+    pub fn submitted(&self) -> usize {
+        self.submitted
+    }
+
+    /// Agenda uma tarefa de desenho
+    ///
+    /// # Argumentos
+    /// * `name` - Identificação da tarefa no relatório (ex: caminho do arquivo)
+    /// * `job` - Função que desenha o gráfico a partir de dados já copiados
+    pub fn submit<F>(&mut self, name: impl Into<String>, job: F)
+    where
+        F: FnOnce() -> Result<(), Box<dyn Error>> + Send + 'static,
+    {
+        if let Some(sender) = &self.sender {
+            // Os threads só terminam depois de o canal fechar
+            let _ = sender.send((name.into(), Box::new(job)));
+            self.submitted += 1;
+        }
+    }
+
+    /// Agenda os gráficos padrão de um experimento (ver `generate_all_plots`)
+    pub fn submit_all_plots(
+        &mut self,
+        snapshots: Vec<SimulationSnapshot>,
+        spikes: Option<SpikeTrain>,
+        prefix: &str,
+        config: &PlotConfig,
+    ) {
+        let (owned_prefix, config) = (prefix.to_string(), config.clone());
+        self.submit(prefix, move || {
+            generate_all_plots(&snapshots, spikes.as_ref(), &owned_prefix, &config)
+        });
+    }
+
+    /// Espera que todas as tarefas terminem e devolve o relatório
+    pub fn finish(mut self) -> PlotReport {
+        self.join()
+    }
+
+    fn join(&mut self) -> PlotReport {
+        // Fechar o canal sinaliza o fim aos threads de desenho
+        self.sender.take();
+
+        let mut report = PlotReport::default();
+        for worker in self.workers.drain(..) {
+            match worker.join() {
+                Ok(partial) => report.merge(partial),
+                Err(_) => report.failures.push(PlotFailure {
+                    name: String::new(),
+                    message: "thread de desenho entrou em pânico".to_string(),
+                }),
+            }
+        }
+        report
+    }
+}
+
+impl Default for PlotWorker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for PlotWorker {
+    fn drop(&mut self) {
+        self.join();
+    }
+}
+
+/// Executa tarefas da fila partilhada até o canal fechar
+fn run_jobs(receiver: &Mutex<Receiver<NamedJob>>) -> PlotReport {
+    let mut report = PlotReport::default();
+    loop {
+        // O lock é libertado antes de desenhar, para os outros threads
+        let next = receiver.lock().map_err(|_| ()).and_then(|r| r.recv().map_err(|_| ()));
+        let Ok((name, job)) = next else {
+            return report;
+        };
+
+        let message = match panic::catch_unwind(AssertUnwindSafe(job)) {
+            Ok(Ok(())) => {
+                report.completed += 1;
+                continue;
+            }
+            Ok(Err(error)) => error.to_string(),
+            Err(payload) => panic_message(payload.as_ref()),
+        };
+        report.failures.push(PlotFailure { name, message });
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "pânico sem mensagem".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_jobs_run_and_failures_are_reported() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let mut worker = PlotWorker::with_threads(3);
+        for i in 0..20 {
+            let counter = Arc::clone(&counter);
+            worker.submit(format!("job{}", i), move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            });
+        }
+        worker.submit("erro", || Err("sem dados".into()));
+        worker.submit("pânico", || panic!("falhou"));
+        worker.submit("depois", || Ok(()));
+        assert_eq!(worker.submitted(), 23);
+
+        let report = worker.finish();
+        assert_eq!(counter.load(Ordering::SeqCst), 20);
+        assert_eq!(report.completed, 21);
+        assert!(!report.is_ok());
+        let mut failures: Vec<(String, String)> =
+            report.failures.into_iter().map(|f| (f.name, f.message)).collect();
+        failures.sort();
+        assert_eq!(
+            failures,
+            vec![
+                ("erro".to_string(), "sem dados".to_string()),
+                ("pânico".to_string(), "falhou".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_heatmaps_rendered_in_background() {
+        use crate::network::{ConnectivityType, Network};
+        use crate::recorder::NeuronField;
+        use crate::visualization::HeatmapDumper;
+
+        let dir = std::env::temp_dir().join(format!("nenv_plot_worker_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let prefix = dir.join("grid").to_string_lossy().into_owned();
+
+        let mut network = Network::new_seeded(16, ConnectivityType::Grid2D, 0.0, 0.5, 1);
+        let dumper = HeatmapDumper::new(NeuronField::Energy, 2, &prefix);
+        let mut worker = PlotWorker::with_threads(2);
+        let mut paths = Vec::new();
+        for _ in 0..6 {
+            network.update(&[1.0; 16]);
+            paths.extend(dumper.dump_in(&network, &mut worker));
+        }
+
+        assert_eq!(paths.len(), 3);
+        assert!(worker.finish().is_ok());
+        for path in &paths {
+            assert!(std::fs::metadata(path).unwrap().len() > 0);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
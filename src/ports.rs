@@ -0,0 +1,142 @@
+//! Módulo de portas de entrada e saída da rede
+//!
+//! Uma `InputPort` é um conjunto nomeado de neurónios que recebe input
+//! externo escalado por um ganho próprio. Com portas definidas, os
+//! experimentos estimulam a rede por nome (`network.stimulate("visão", 1.0)`)
+//! em vez de construir um vetor completo com índices fixos. Com
+//! `stimulate_pattern`, a porta recebe um valor por neurónio (um fluxo
+//! sensorial completo, p.ex. uma imagem ou um espectro).
+//!
+//! Uma `OutputPort` marca o grupo simétrico de neurónios cuja atividade é
+//! lida como resultado (`network.read_outputs()`), para que descodificadores
+//! e agentes consumam a rede sempre da mesma forma.
+
+use crate::nenv::NENV;
+use crate::prelude::*;
+
+/// Conjunto nomeado de neurónios que recebe input externo
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputPort {
+    /// Neurónios que recebem o input da porta
+    pub neurons: Vec<usize>,
+
+    /// Fator aplicado a cada valor antes de chegar aos neurónios
+    pub gain: f64,
+}
+
+impl InputPort {
+    /// Cria uma nova porta de entrada
+    ///
+    /// # Argumentos
+    /// * `neurons` - Neurónios alvo
+    /// * `gain` - Ganho aplicado aos valores recebidos
+    pub fn new(neurons: Vec<usize>, gain: f64) -> Self {
+        Self { neurons, gain }
+    }
+
+    /// Soma `value * gain` ao input de cada neurónio da porta
+    ///
+    /// Neurónios fora de `inputs` são ignorados.
+    pub fn apply(&self, inputs: &mut [f64], value: f64) {
+        for &i in &self.neurons {
+            if let Some(input) = inputs.get_mut(i) {
+                *input += value * self.gain;
+            }
+        }
+    }
+
+    /// Soma `values[k] * gain` ao input do k-ésimo neurónio da porta
+    ///
+    /// Valores a mais são ignorados; neurónios sem valor não recebem input.
+    pub fn apply_pattern(&self, inputs: &mut [f64], values: &[f64]) {
+        for (&i, &value) in self.neurons.iter().zip(values) {
+            if let Some(input) = inputs.get_mut(i) {
+                *input += value * self.gain;
+            }
+        }
+    }
+}
+
+/// Sinal lido de cada neurónio de uma porta de saída
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputMode {
+    /// 1.0 se o neurónio disparou no último passo, 0.0 caso contrário
+    Firing,
+    /// Sinal de saída do neurónio (+1 excitatório, -1 inibitório, 0 em repouso)
+    Signal,
+    /// Novidade do neurónio no último passo (sinal graduado)
+    Novelty,
+}
+
+/// Conjunto nomeado de neurónios lidos como saída da rede
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputPort {
+    /// Neurónios que compõem a saída
+    pub neurons: Vec<usize>,
+
+    /// Sinal lido de cada neurónio
+    pub mode: OutputMode,
+}
+
+impl OutputPort {
+    /// Cria uma nova porta de saída
+    ///
+    /// # Argumentos
+    /// * `neurons` - Neurónios lidos
+    /// * `mode` - Sinal lido de cada neurónio
+    pub fn new(neurons: Vec<usize>, mode: OutputMode) -> Self {
+        Self { neurons, mode }
+    }
+
+    /// Lê o sinal de cada neurónio da porta (na ordem de `neurons`)
+    ///
+    /// Índices sem neurónio correspondente valem 0.0.
+    pub fn read(&self, neurons: &[NENV]) -> Vec<f64> {
+        self.neurons
+            .iter()
+            .map(|&i| match neurons.get(i) {
+                Some(neuron) => match self.mode {
+                    OutputMode::Firing => neuron.is_firing as u8 as f64,
+                    OutputMode::Signal => neuron.output_signal,
+                    OutputMode::Novelty => neuron.novelty,
+                },
+                None => 0.0,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nenv::NeuronType;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_apply_scales_and_accumulates() {
+        let port = InputPort::new(vec![0, 2, 7], 0.5);
+        let mut inputs = vec![1.0; 4];
+        port.apply(&mut inputs, 2.0);
+        port.apply(&mut inputs, 2.0);
+        assert_eq!(inputs, vec![3.0, 1.0, 3.0, 1.0]);
+
+        port.apply_pattern(&mut inputs, &[2.0, -2.0]);
+        assert_eq!(inputs, vec![4.0, 1.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn test_output_modes() {
+        let mut neurons: Vec<NENV> = (0..2)
+            .map(|i| NENV::with_rng(i, 2, 0.5, NeuronType::Inhibitory, &mut StdRng::seed_from_u64(0)))
+            .collect();
+        neurons[1].is_firing = true;
+        neurons[1].output_signal = -1.0;
+        neurons[1].novelty = 0.25;
+
+        let read = |mode| OutputPort::new(vec![1, 0, 5], mode).read(&neurons);
+        assert_eq!(read(OutputMode::Firing), vec![1.0, 0.0, 0.0]);
+        assert_eq!(read(OutputMode::Signal), vec![-1.0, 0.0, 0.0]);
+        assert_eq!(read(OutputMode::Novelty), vec![0.25, 0.0, 0.0]);
+    }
+}
@@ -0,0 +1,110 @@
+//! Módulo de novidade por erro de predição
+//!
+//! No modo preditivo, cada neurónio aprende um modelo direto que prevê o
+//! input do próximo passo a partir do input atual, e a novidade passa a ser
+//! o erro de predição em vez do desvio em relação à média móvel. Uma
+//! sequência regular (p.ex. um estímulo que alterna) deixa de ser nova
+//! quando se torna previsível, mesmo que cada valor isolado varie.
+//!
+//! O modelo é autoregressivo de primeira ordem por canal de entrada,
+//! treinado por LMS (regra delta):
+//!
+//! ```text
+//! predição   x̂_j(t) = gain_j * x_j(t-1) + bias_j
+//! novidade   mean_j |x_j(t) - x̂_j(t)|
+//! aprendizado gain_j += lr * e_j * x_j(t-1);  bias_j += lr * e_j
+//! ```
+
+use crate::footprint::vec_bytes;
+use crate::prelude::*;
+
+/// Modelo direto de um neurónio (um preditor por canal de entrada)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForwardModel {
+    /// Ganho autoregressivo de cada canal
+    pub gain: Vec<f64>,
+
+    /// Termo constante de cada canal
+    pub bias: Vec<f64>,
+
+    /// Taxa de aprendizado LMS
+    pub learning_rate: f64,
+
+    previous: Vec<f64>,
+}
+
+impl ForwardModel {
+    /// Cria um modelo que começa por prever persistência (gain 1, bias 0)
+    pub fn new(num_inputs: usize, learning_rate: f64) -> Self {
+        Self {
+            gain: vec![1.0; num_inputs],
+            bias: vec![0.0; num_inputs],
+            learning_rate,
+            previous: vec![0.0; num_inputs],
+        }
+    }
+
+    /// Bytes reservados pelos vetores (ver `footprint`)
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.gain) + vec_bytes(&self.bias) + vec_bytes(&self.previous)
+    }
+
+    /// Predição do input atual a partir do input do passo anterior
+    pub fn predict(&self) -> Vec<f64> {
+        self.previous
+            .iter()
+            .zip(self.gain.iter().zip(&self.bias))
+            .map(|(&x, (&g, &b))| g * x + b)
+            .collect()
+    }
+
+    /// Erro de predição absoluto médio para `inputs`
+    pub fn prediction_error(&self, inputs: &[f64]) -> f64 {
+        if inputs.is_empty() {
+            return 0.0;
+        }
+        let total: f64 = self
+            .predict()
+            .iter()
+            .zip(inputs)
+            .map(|(predicted, input)| (input - predicted).abs())
+            .sum();
+        total / inputs.len() as f64
+    }
+
+    /// Ajusta o modelo ao input observado e guarda-o como estado atual
+    pub fn learn(&mut self, inputs: &[f64]) {
+        for (j, &input) in inputs.iter().enumerate().take(self.gain.len()) {
+            let x = self.previous[j];
+            let error = input - (self.gain[j] * x + self.bias[j]);
+            self.gain[j] += self.learning_rate * error * x;
+            self.bias[j] += self.learning_rate * error;
+            self.previous[j] = input;
+        }
+    }
+
+    /// Esquece o estado atual (mantém o modelo aprendido)
+    pub fn reset_state(&mut self) {
+        self.previous.fill(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alternating_sequence_becomes_predictable() {
+        let mut model = ForwardModel::new(1, 0.2);
+        let mut errors = Vec::new();
+        for t in 0..400 {
+            let input = [if t % 2 == 0 { 1.0 } else { 0.0 }];
+            errors.push(model.prediction_error(&input));
+            model.learn(&input);
+        }
+        // Alternância: x(t) = 1 - x(t-1), ou seja gain -> -1, bias -> 1
+        assert!(errors[1] > 0.5);
+        assert!(errors[399] < 0.05);
+        assert!((model.gain[0] + 1.0).abs() < 0.1);
+    }
+}
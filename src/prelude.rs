@@ -0,0 +1,11 @@
+//! Itens de `alloc` incluídos no prelúdio de `std`
+//!
+//! Os módulos do núcleo importam `use crate::prelude::*;` para compilarem
+//! também sem a feature `std` (`no_std`), onde estes nomes não estão no
+//! prelúdio.
+
+pub(crate) use alloc::boxed::Box;
+pub(crate) use alloc::format;
+pub(crate) use alloc::string::{String, ToString};
+pub(crate) use alloc::vec;
+pub(crate) use alloc::vec::Vec;
@@ -0,0 +1,204 @@
+//! Módulo de medição do tempo por fase de `Network::update`
+//!
+//! Com `Network::enable_timing`, cada passo mede o tempo gasto em cada fase
+//! e acumula-o num `TimingReport` (`Network::timing_report`), para ver onde
+//! uma topologia concreta gasta o tempo:
+//!
+//! - `Gather`: montagem do input de cada neurónio (externo + vizinhos)
+//! - `Integrate`: soma ponderada do Dendritoma
+//! - `Modulate`: modulação pela Glia
+//! - `Fire`: decisão de disparo, inibição global e registo de disparos
+//! - `Learn`: priority, aprendizado Hebbiano e estado metabólico
+//! - `Memory`: novidade e atualização da memória contextual
+//! - `Other`: o restante (alert_level, dropout, halo de priority, novidade
+//!   por blocos, plasticidade estrutural, histórico e observadores)
+//!
+//! As fases por neurónio são cronometradas à volta de cada chamada, o que
+//! custa algumas leituras do relógio por neurónio e passo; desativada, a
+//! medição não lê o relógio. Sem a feature `std` não há relógio e a medição
+//! não está disponível.
+
+use core::fmt;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// Fase de um passo de simulação
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UpdatePhase {
+    Gather,
+    Integrate,
+    Modulate,
+    Fire,
+    Learn,
+    Memory,
+    Other,
+}
+
+impl UpdatePhase {
+    /// Todas as fases, pela ordem em que ocorrem no passo
+    pub const ALL: [UpdatePhase; 7] = [
+        UpdatePhase::Gather,
+        UpdatePhase::Integrate,
+        UpdatePhase::Modulate,
+        UpdatePhase::Fire,
+        UpdatePhase::Learn,
+        UpdatePhase::Memory,
+        UpdatePhase::Other,
+    ];
+
+    /// Nome curto, usado no relatório
+    pub fn name(self) -> &'static str {
+        match self {
+            UpdatePhase::Gather => "gather",
+            UpdatePhase::Integrate => "integrate",
+            UpdatePhase::Modulate => "modulate",
+            UpdatePhase::Fire => "fire",
+            UpdatePhase::Learn => "learn",
+            UpdatePhase::Memory => "memory",
+            UpdatePhase::Other => "other",
+        }
+    }
+}
+
+/// Tempo acumulado por fase ao longo de vários passos
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimingReport {
+    steps: u64,
+    totals: [Duration; 7],
+}
+
+impl TimingReport {
+    /// Número de passos medidos
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Tempo total gasto na fase
+    pub fn total(&self, phase: UpdatePhase) -> Duration {
+        self.totals[phase as usize]
+    }
+
+    /// Tempo médio por passo gasto na fase
+    pub fn mean(&self, phase: UpdatePhase) -> Duration {
+        if self.steps == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_nanos((self.total(phase).as_nanos() / self.steps as u128) as u64)
+    }
+
+    /// Tempo total de todas as fases
+    pub fn total_time(&self) -> Duration {
+        self.totals.iter().sum()
+    }
+
+    /// Fração do tempo total gasta na fase (0.0 sem medições)
+    pub fn fraction(&self, phase: UpdatePhase) -> f64 {
+        let total = self.total_time().as_secs_f64();
+        if total > 0.0 { self.total(phase).as_secs_f64() / total } else { 0.0 }
+    }
+
+    /// Pares `(fase, tempo total)` pela ordem do passo
+    pub fn phases(&self) -> impl Iterator<Item = (UpdatePhase, Duration)> + '_ {
+        UpdatePhase::ALL.into_iter().map(|phase| (phase, self.total(phase)))
+    }
+}
+
+impl fmt::Display for TimingReport {
+    /// Tabela com tempo total, médio por passo e fração de cada fase
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} passos, {:.2?} no total", self.steps, self.total_time())?;
+        for phase in UpdatePhase::ALL {
+            writeln!(
+                f,
+                "{:<10} {:>12.2?} {:>12.2?}/passo {:>6.1}%",
+                phase.name(),
+                self.total(phase),
+                self.mean(phase),
+                100.0 * self.fraction(phase)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Cronómetro de um passo: atribui o tempo desde a última marca a uma fase
+#[derive(Debug)]
+pub(crate) struct StepTimer {
+    #[cfg(feature = "std")]
+    mark: Option<Instant>,
+    totals: [Duration; 7],
+}
+
+impl StepTimer {
+    /// Começa a medir o passo (sem ler o relógio se `enabled` for falso)
+    pub(crate) fn start(enabled: bool) -> Self {
+        #[cfg(not(feature = "std"))]
+        let _ = enabled;
+        Self {
+            #[cfg(feature = "std")]
+            mark: enabled.then(Instant::now),
+            totals: [Duration::ZERO; 7],
+        }
+    }
+
+    /// Atribui a `phase` o tempo desde a marca anterior
+    #[inline]
+    pub(crate) fn lap(&mut self, phase: UpdatePhase) {
+        #[cfg(feature = "std")]
+        if let Some(mark) = &mut self.mark {
+            let now = Instant::now();
+            self.totals[phase as usize] += now - *mark;
+            *mark = now;
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = phase;
+    }
+
+    /// Soma o passo medido ao relatório
+    pub(crate) fn finish(self, report: &mut TimingReport) {
+        for (total, step) in report.totals.iter_mut().zip(self.totals) {
+            *total += step;
+        }
+        report.steps += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_accumulates_laps() {
+        let empty = TimingReport::default();
+        assert_eq!(empty.mean(UpdatePhase::Gather), Duration::ZERO);
+        assert_eq!(empty.fraction(UpdatePhase::Gather), 0.0);
+
+        // Sem `std` não há relógio: só o caso desativado é testável
+        #[cfg(feature = "std")]
+        {
+            let mut report = TimingReport::default();
+            for _ in 0..3 {
+                let mut timer = StepTimer::start(true);
+                std::thread::sleep(Duration::from_millis(2));
+                timer.lap(UpdatePhase::Integrate);
+                timer.lap(UpdatePhase::Other);
+                timer.finish(&mut report);
+            }
+            assert_eq!(report.steps(), 3);
+            assert!(report.total(UpdatePhase::Integrate) >= Duration::from_millis(6));
+            assert!(report.mean(UpdatePhase::Integrate) >= Duration::from_millis(2));
+            assert!(report.fraction(UpdatePhase::Integrate) > 0.9);
+            assert_eq!(report.total(UpdatePhase::Gather), Duration::ZERO);
+            assert_eq!(report.phases().map(|(_, d)| d).sum::<Duration>(), report.total_time());
+            assert!(report.to_string().contains("integrate"));
+        }
+
+        // Desativado: o passo conta mas nenhuma fase acumula tempo
+        let mut timer = StepTimer::start(false);
+        timer.lap(UpdatePhase::Gather);
+        let mut idle = TimingReport::default();
+        timer.finish(&mut idle);
+        assert_eq!(idle.total_time(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,242 @@
+//! Módulo de exportação de métricas no formato Prometheus
+//!
+//! Para execuções longas (p.ex. o detetor de anomalias num servidor), o
+//! `PrometheusExporter` serve `GET /metrics` num thread dedicado, usando o
+//! formato de texto de exposição do Prometheus. O loop de simulação chama
+//! `record_step` a cada passo (ou com a frequência desejada) e o scraper
+//! recolhe os últimos valores no seu próprio intervalo.
+//!
+//! Disponível com a feature `prometheus`.
+
+use crate::network::Network;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Últimos valores publicados como gauges
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkGauges {
+    /// Passo de tempo da última atualização
+    pub time_step: i64,
+
+    /// Fração de neurónios que dispararam no último passo
+    pub firing_rate: f64,
+
+    /// Novidade média da rede
+    pub avg_novelty: f64,
+
+    /// Nível de alerta global
+    pub alert_level: f64,
+
+    /// Energia média dos neurónios
+    pub avg_energy: f64,
+
+    /// Duração do último passo de simulação em segundos
+    pub step_latency_seconds: f64,
+}
+
+impl NetworkGauges {
+    /// Lê os gauges do estado atual da rede
+    pub fn from_network(network: &Network, step_latency: Duration) -> Self {
+        let num_neurons = network.num_neurons();
+        let firing_rate = if num_neurons == 0 {
+            0.0
+        } else {
+            network.num_firing() as f64 / num_neurons as f64
+        };
+
+        Self {
+            time_step: network.current_time_step,
+            firing_rate,
+            avg_novelty: network.average_novelty(),
+            alert_level: network.alert_level,
+            avg_energy: network.average_energy(),
+            step_latency_seconds: step_latency.as_secs_f64(),
+        }
+    }
+
+    /// Formata os gauges no formato de texto de exposição do Prometheus
+    pub fn render(&self) -> String {
+        let gauges = [
+            ("nenv_time_step", "Passo de tempo atual da simulação", self.time_step as f64),
+            ("nenv_firing_rate", "Fração de neurónios que dispararam", self.firing_rate),
+            ("nenv_avg_novelty", "Novidade média da rede", self.avg_novelty),
+            ("nenv_alert_level", "Nível de alerta global da rede", self.alert_level),
+            ("nenv_avg_energy", "Energia média dos neurónios", self.avg_energy),
+            (
+                "nenv_step_latency_seconds",
+                "Duração do último passo de simulação",
+                self.step_latency_seconds,
+            ),
+        ];
+
+        let mut out = String::new();
+        for (name, help, value) in gauges {
+            out.push_str(&format!("# HELP {} {}\n", name, help));
+            out.push_str(&format!("# TYPE {} gauge\n", name));
+            out.push_str(&format!("{} {}\n", name, value));
+        }
+        out
+    }
+}
+
+/// Servidor HTTP mínimo que publica os gauges da rede em `/metrics`
+pub struct PrometheusExporter {
+    gauges: Arc<Mutex<NetworkGauges>>,
+    local_addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl PrometheusExporter {
+    /// Abre o socket e inicia o thread servidor
+    ///
+    /// # Argumentos
+    /// * `addr` - Endereço de escuta (p.ex. "0.0.0.0:9184"; porta 0 escolhe uma livre)
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let gauges = Arc::new(Mutex::new(NetworkGauges::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let gauges = Arc::clone(&gauges);
+            let shutdown = Arc::clone(&shutdown);
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        // Erros de um cliente não derrubam o servidor
+                        let _ = serve_connection(stream, &gauges);
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            gauges,
+            local_addr,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// Endereço efetivo de escuta
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Atualiza os gauges com o estado da rede após um passo
+    ///
+    /// # Argumentos
+    /// * `network` - Rede após `update`
+    /// * `step_latency` - Tempo gasto no passo (medido pelo chamador)
+    pub fn record_step(&self, network: &Network, step_latency: Duration) {
+        self.set(NetworkGauges::from_network(network, step_latency));
+    }
+
+    /// Substitui diretamente os valores publicados
+    pub fn set(&self, gauges: NetworkGauges) {
+        if let Ok(mut current) = self.gauges.lock() {
+            *current = gauges;
+        }
+    }
+
+    /// Cópia dos valores atualmente publicados
+    pub fn gauges(&self) -> NetworkGauges {
+        self.gauges.lock().map(|g| g.clone()).unwrap_or_default()
+    }
+}
+
+impl Drop for PrometheusExporter {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Acorda o thread bloqueado em accept
+        let _ = TcpStream::connect(self.local_addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Responde a um pedido HTTP: `/metrics` devolve os gauges, o resto 404
+fn serve_connection(stream: TcpStream, gauges: &Mutex<NetworkGauges>) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Consome os cabeçalhos até à linha vazia
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header.trim_end() != "" {
+        header.clear();
+    }
+
+    let path = request_line.split_whitespace().nth(1).unwrap_or("");
+    let (status, body) = if path == "/metrics" || path.starts_with("/metrics?") {
+        let body = gauges.lock().map(|g| g.render()).unwrap_or_default();
+        ("200 OK", body)
+    } else {
+        ("404 Not Found", String::from("not found\n"))
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ConnectivityType;
+    use std::io::Read;
+
+    #[test]
+    fn test_render_exposition_format() {
+        let gauges = NetworkGauges {
+            time_step: 12,
+            firing_rate: 0.25,
+            alert_level: 0.5,
+            ..Default::default()
+        };
+        let text = gauges.render();
+
+        assert!(text.contains("# TYPE nenv_firing_rate gauge\n"));
+        assert!(text.contains("nenv_firing_rate 0.25\n"));
+        assert!(text.contains("nenv_alert_level 0.5\n"));
+        assert!(text.contains("nenv_time_step 12\n"));
+    }
+
+    #[test]
+    fn test_exporter_serves_metrics() {
+        let exporter = PrometheusExporter::bind("127.0.0.1:0").unwrap();
+        let mut network = Network::new(9, ConnectivityType::Grid2D, 0.0, 0.5);
+        network.update(&[0.0; 9]);
+        network.set_alert_level(0.75);
+        exporter.record_step(&network, Duration::from_millis(2));
+
+        let mut stream = TcpStream::connect(exporter.local_addr()).unwrap();
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("nenv_alert_level 0.75\n"));
+        assert!(response.contains("nenv_step_latency_seconds 0.002\n"));
+    }
+}
@@ -0,0 +1,391 @@
+//! Módulo de protocolos de estímulo declarativos
+//!
+//! Descreve um protocolo experimental num pequeno formato de texto em vez de
+//! ramos `if t < ...` no código. Cada fase nomeada compila num `Stimulus`:
+//!
+//! ```text
+//! # Experimento 4: familiarização com A, depois o padrão novo B
+//! phase "familiarize" { t = 0..50, target = [33], amplitude = 2.0 }
+//! phase "novel" {
+//!     t = 50..150
+//!     target = [66, 70..73]
+//!     amplitude = 2.0
+//! }
+//! ```
+//!
+//! `t` é a janela semiaberta `[início, fim)` de `Stimulus`; `target` aceita
+//! índices e intervalos `a..b` (fim exclusivo). Os campos separam-se por
+//! vírgulas ou mudanças de linha e `#` inicia um comentário. Fases
+//! sobrepostas somam-se, como em `StimulusSchedule`.
+
+use crate::stimulus::{Stimulus, StimulusSchedule};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Fase nomeada de um protocolo
+#[derive(Debug, Clone, PartialEq)]
+pub struct Phase {
+    /// Nome da fase (p.ex. "familiarize")
+    pub name: String,
+    /// Estímulo aplicado durante a fase
+    pub stimulus: Stimulus,
+}
+
+/// Protocolo de estímulos lido do formato declarativo
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Protocol {
+    /// Fases pela ordem em que aparecem no texto
+    pub phases: Vec<Phase>,
+}
+
+impl Protocol {
+    /// Lê um protocolo de texto (ver a documentação do módulo)
+    ///
+    /// # Retorna
+    /// Erro `InvalidData` com a linha do primeiro problema encontrado
+    pub fn parse(text: &str) -> io::Result<Self> {
+        let tokens = tokenize(text)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let mut phases = Vec::new();
+        while parser.peek().is_some() {
+            phases.push(parser.phase()?);
+        }
+        Ok(Self { phases })
+    }
+
+    /// Lê um protocolo de um ficheiro
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Compila as fases num `StimulusSchedule`
+    pub fn schedule(&self) -> StimulusSchedule {
+        StimulusSchedule {
+            stimuli: self.phases.iter().map(|p| p.stimulus.clone()).collect(),
+        }
+    }
+
+    /// Fase com o nome indicado
+    pub fn phase(&self, name: &str) -> Option<&Phase> {
+        self.phases.iter().find(|p| p.name == name)
+    }
+
+    /// Nome da primeira fase ativa no passo `time`
+    pub fn phase_at(&self, time: i64) -> Option<&str> {
+        self.phases
+            .iter()
+            .find(|p| p.stimulus.is_active(time))
+            .map(|p| p.name.as_str())
+    }
+
+    /// Fim da última fase (exclusivo)
+    pub fn duration(&self) -> i64 {
+        self.schedule().duration()
+    }
+
+    /// Cópia sem as fases com o nome indicado (p.ex. o controlo de um protocolo)
+    pub fn without_phase(&self, name: &str) -> Self {
+        Self {
+            phases: self.phases.iter().filter(|p| p.name != name).cloned().collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(f64),
+    Symbol(&'static str),
+    Newline,
+}
+
+fn invalid(line: usize, message: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("protocolo, linha {}: {}", line, message),
+    )
+}
+
+/// Divide o texto em tokens com a linha de cada um
+fn tokenize(text: &str) -> io::Result<Vec<(Token, usize)>> {
+    let mut tokens = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.split('#').next().unwrap_or("");
+        let mut chars = line.char_indices().peekable();
+        while let Some(&(start, c)) = chars.peek() {
+            let token = match c {
+                c if c.is_whitespace() => {
+                    chars.next();
+                    continue;
+                }
+                '{' | '}' | '[' | ']' | '=' | ',' => {
+                    chars.next();
+                    let symbols = ["{", "}", "[", "]", "=", ","];
+                    Token::Symbol(symbols.into_iter().find(|s| s.starts_with(c)).unwrap())
+                }
+                '.' if line[start..].starts_with("..") => {
+                    chars.next();
+                    chars.next();
+                    Token::Symbol("..")
+                }
+                '"' => {
+                    chars.next();
+                    let rest = &line[start + 1..];
+                    let end = rest
+                        .find('"')
+                        .ok_or_else(|| invalid(line_number, "nome sem aspas de fecho"))?;
+                    for _ in 0..rest[..end].chars().count() + 1 {
+                        chars.next();
+                    }
+                    Token::Str(rest[..end].to_string())
+                }
+                c if c.is_ascii_digit() || c == '-' || c == '+' => {
+                    let mut end = start + c.len_utf8();
+                    chars.next();
+                    while let Some(&(i, c)) = chars.peek() {
+                        // `..` termina o número (intervalos como `0..50`)
+                        let is_range = c == '.' && line[i..].starts_with("..");
+                        let exponent_sign = (c == '-' || c == '+')
+                            && matches!(line[..i].chars().last(), Some('e' | 'E'));
+                        if is_range || !(c.is_ascii_alphanumeric() || c == '.' || exponent_sign) {
+                            break;
+                        }
+                        end = i + c.len_utf8();
+                        chars.next();
+                    }
+                    let literal = &line[start..end];
+                    let value = literal.parse().map_err(|_| {
+                        invalid(line_number, format!("número inválido '{}'", literal))
+                    })?;
+                    Token::Number(value)
+                }
+                c if c.is_alphabetic() || c == '_' => {
+                    let mut end = start;
+                    while let Some(&(i, c)) = chars.peek() {
+                        if !(c.is_alphanumeric() || c == '_') {
+                            break;
+                        }
+                        end = i + c.len_utf8();
+                        chars.next();
+                    }
+                    Token::Ident(line[start..end].to_string())
+                }
+                c => return Err(invalid(line_number, format!("carácter inesperado '{}'", c))),
+            };
+            tokens.push((token, line_number));
+        }
+        tokens.push((Token::Newline, line_number));
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl Parser {
+    /// Próximo token que não seja mudança de linha
+    fn peek(&mut self) -> Option<&Token> {
+        self.skip_newlines();
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn skip_newlines(&mut self) {
+        while matches!(self.tokens.get(self.pos), Some((Token::Newline, _))) {
+            self.pos += 1;
+        }
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or(self.tokens.last())
+            .map_or(1, |&(_, line)| line)
+    }
+
+    fn next(&mut self) -> io::Result<Token> {
+        self.skip_newlines();
+        let token = self
+            .tokens
+            .get(self.pos)
+            .map(|(token, _)| token.clone())
+            .ok_or_else(|| invalid(self.line(), "fim inesperado do protocolo"))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, symbol: &str) -> io::Result<()> {
+        match self.next()? {
+            Token::Symbol(s) if s == symbol => Ok(()),
+            other => Err(self.unexpected(&other, symbol)),
+        }
+    }
+
+    fn unexpected(&self, token: &Token, wanted: &str) -> io::Error {
+        let line = self.tokens[self.pos.saturating_sub(1)].1;
+        invalid(line, format!("esperado {}, encontrado {:?}", wanted, token))
+    }
+
+    fn number(&mut self) -> io::Result<f64> {
+        match self.next()? {
+            Token::Number(value) => Ok(value),
+            other => Err(self.unexpected(&other, "número")),
+        }
+    }
+
+    fn integer(&mut self) -> io::Result<i64> {
+        let value = self.number()?;
+        if value.fract() != 0.0 {
+            return Err(invalid(
+                self.line(),
+                format!("esperado inteiro, encontrado {}", value),
+            ));
+        }
+        Ok(value as i64)
+    }
+
+    fn index(&mut self) -> io::Result<usize> {
+        let value = self.integer()?;
+        usize::try_from(value)
+            .map_err(|_| invalid(self.line(), format!("índice negativo {}", value)))
+    }
+
+    /// `phase "nome" { campo = valor, ... }`
+    fn phase(&mut self) -> io::Result<Phase> {
+        match self.next()? {
+            Token::Ident(keyword) if keyword == "phase" => {}
+            other => return Err(self.unexpected(&other, "'phase'")),
+        }
+        let name = match self.next()? {
+            Token::Str(name) => name,
+            other => return Err(self.unexpected(&other, "nome da fase entre aspas")),
+        };
+        let line = self.line();
+        self.expect("{")?;
+
+        let mut window = None;
+        let mut targets = None;
+        let mut amplitude = None;
+        loop {
+            let field = match self.next()? {
+                Token::Symbol("}") => break,
+                Token::Symbol(",") => continue,
+                Token::Ident(field) => field,
+                other => return Err(self.unexpected(&other, "campo ou '}'")),
+            };
+            self.expect("=")?;
+            match field.as_str() {
+                "t" => {
+                    let start = self.integer()?;
+                    self.expect("..")?;
+                    window = Some((start, self.integer()?));
+                }
+                "target" => targets = Some(self.targets()?),
+                "amplitude" => amplitude = Some(self.number()?),
+                _ => {
+                    return Err(invalid(
+                        self.line(),
+                        format!("campo desconhecido '{}'", field),
+                    ));
+                }
+            }
+        }
+
+        let missing = |what| invalid(line, format!("fase \"{}\" sem '{}'", name, what));
+        let (start, end) = window.ok_or_else(|| missing("t"))?;
+        let targets = targets.ok_or_else(|| missing("target"))?;
+        let amplitude = amplitude.ok_or_else(|| missing("amplitude"))?;
+        if end < start {
+            return Err(invalid(
+                line,
+                format!("fase \"{}\" termina antes de começar", name),
+            ));
+        }
+        Ok(Phase {
+            name,
+            stimulus: Stimulus::new(targets, amplitude, start, end),
+        })
+    }
+
+    /// `[i, a..b, ...]`
+    fn targets(&mut self) -> io::Result<Vec<usize>> {
+        self.expect("[")?;
+        let mut targets = Vec::new();
+        loop {
+            if self.peek() == Some(&Token::Symbol("]")) {
+                self.pos += 1;
+                return Ok(targets);
+            }
+            let first = self.index()?;
+            if self.peek() == Some(&Token::Symbol("..")) {
+                self.pos += 1;
+                targets.extend(first..self.index()?);
+            } else {
+                targets.push(first);
+            }
+            match self.next()? {
+                Token::Symbol(",") => {}
+                Token::Symbol("]") => return Ok(targets),
+                other => return Err(self.unexpected(&other, "',' ou ']'")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_phases_into_schedule() {
+        let protocol = Protocol::parse(
+            r#"
+            # comentário
+            phase "familiarize" { t = 0..100, target = [33], amplitude = 2.0 }
+            phase "novel" {
+                t = 100..150
+                target = [1, 4..6]
+                amplitude = -0.5e1
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(protocol.phases.len(), 2);
+        assert_eq!(
+            protocol.phase("novel").unwrap().stimulus,
+            Stimulus::new(vec![1, 4, 5], -5.0, 100, 150)
+        );
+        assert_eq!(protocol.phase_at(99), Some("familiarize"));
+        assert_eq!(protocol.phase_at(150), None);
+        assert_eq!(protocol.duration(), 150);
+
+        let control = protocol.without_phase("novel");
+        assert_eq!(control.phases, protocol.phases[..1]);
+        assert_eq!(control.duration(), 100);
+
+        let schedule = protocol.schedule();
+        assert_eq!(schedule.inputs_at(40, 0)[33], 2.0);
+        assert_eq!(
+            schedule.inputs_at(6, 120),
+            vec![0.0, -5.0, 0.0, 0.0, -5.0, -5.0]
+        );
+    }
+
+    #[test]
+    fn test_reports_line_of_invalid_protocol() {
+        let error = Protocol::parse("phase \"a\" { t = 0..10, target = [1] }\n").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("linha 1"));
+        assert!(error.to_string().contains("amplitude"));
+
+        let error = Protocol::parse("\nphase \"a\" {\n  t = 0..10\n  gain = 1\n}").unwrap_err();
+        assert!(error.to_string().contains("linha 4"), "{}", error);
+        assert!(Protocol::parse("phase \"a\" { t = 5..2, target = [], amplitude = 1 }").is_err());
+        assert!(Protocol::parse("").unwrap().phases.is_empty());
+    }
+}
@@ -0,0 +1,272 @@
+//! Módulo de execução em tempo real com input ao vivo
+//!
+//! `RealTimeRunner` chama `Network::update` a um ritmo fixo de relógio
+//! (p.ex. 100 passos/s) e lê os inputs externos de um canal, para que um
+//! sensor ao vivo alimente a rede em demonstrações de deteção de anomalias
+//! online. O produtor corre noutro thread e envia `LiveInput`s quando os
+//! tem; o runner não espera por eles:
+//!
+//! ```text
+//! let (sender, receiver) = realtime::input_channel();
+//! std::thread::spawn(move || for frame in sensor { sender.send(LiveInput::Dense(frame)) });
+//! let stats = RealTimeRunner::new(100.0).run(&mut network, &receiver, |network, step| {
+//!     if network.alert_level > 0.5 { println!("anomalia em {:?}", step.wall_time); }
+//!     true
+//! });
+//! ```
+//!
+//! Em cada passo são consumidas todas as mensagens chegadas desde o passo
+//! anterior: de vários `Dense` vale o último e os `Events` acumulam. Com
+//! `InputHold::Hold` (o padrão) o último vetor denso mantém-se até chegar
+//! outro, como um sensor amostrado; com `InputHold::Once` aplica-se a um
+//! só passo. A execução termina quando `on_step` devolve `false`, quando se
+//! atinge `max_steps` ou quando todos os emissores do canal são largados.
+//!
+//! Passos atrasados (update mais lento do que o período) não são saltados:
+//! o runner segue sem dormir até recuperar e conta-os em `RealTimeStats`.
+//!
+//! Usa os canais `std::sync::mpsc`, como `AsyncSink` e `PlotWorker`.
+
+use crate::input::Sparse;
+use crate::network::Network;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::time::{Duration, Instant};
+
+/// Input enviado ao runner por um produtor ao vivo
+#[derive(Debug, Clone, PartialEq)]
+pub enum LiveInput {
+    /// Vetor com um valor por neurónio
+    Dense(Vec<f64>),
+    /// Pares `(neurónio, valor)` somados ao input do passo seguinte
+    Events(Vec<(usize, f64)>),
+}
+
+/// Cria o canal de inputs de um `RealTimeRunner`
+pub fn input_channel() -> (Sender<LiveInput>, Receiver<LiveInput>) {
+    mpsc::channel()
+}
+
+/// Duração de um vetor denso recebido
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputHold {
+    /// Mantém o último vetor até chegar outro
+    #[default]
+    Hold,
+    /// Aplica o vetor só no passo seguinte à sua chegada
+    Once,
+}
+
+/// Informação de um passo passada a `on_step`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RealTimeStep {
+    /// Índice do passo desde o início da execução
+    pub index: u64,
+    /// Tempo de relógio desde o início da execução
+    pub wall_time: Duration,
+    /// Atraso do início do passo em relação ao instante previsto
+    pub lag: Duration,
+    /// Mensagens consumidas neste passo
+    pub messages: usize,
+}
+
+/// Resumo de uma execução em tempo real
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RealTimeStats {
+    /// Passos executados
+    pub steps: u64,
+    /// Passos iniciados depois do instante previsto (mais de 1 ms)
+    pub late_steps: u64,
+    /// Maior atraso observado
+    pub max_lag: Duration,
+    /// Mensagens consumidas
+    pub messages: u64,
+    /// Duração total
+    pub elapsed: Duration,
+}
+
+impl RealTimeStats {
+    /// Ritmo efetivo em passos por segundo
+    pub fn steps_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 { self.steps as f64 / seconds } else { 0.0 }
+    }
+}
+
+/// Tolerância abaixo da qual um passo não conta como atrasado
+const LATE_TOLERANCE: Duration = Duration::from_millis(1);
+
+/// Executa a rede a um ritmo fixo de relógio
+#[derive(Debug, Clone)]
+pub struct RealTimeRunner {
+    period: Duration,
+    hold: InputHold,
+    max_steps: Option<u64>,
+}
+
+impl RealTimeRunner {
+    /// Runner a `steps_per_second` passos por segundo (positivo)
+    pub fn new(steps_per_second: f64) -> Self {
+        Self {
+            period: Duration::from_secs_f64(1.0 / steps_per_second.max(1e-6)),
+            hold: InputHold::default(),
+            max_steps: None,
+        }
+    }
+
+    /// Define a duração de um vetor denso recebido
+    pub fn with_input_hold(mut self, hold: InputHold) -> Self {
+        self.hold = hold;
+        self
+    }
+
+    /// Limita a execução a `steps` passos
+    pub fn with_max_steps(mut self, steps: u64) -> Self {
+        self.max_steps = Some(steps);
+        self
+    }
+
+    /// Intervalo de relógio entre passos
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Executa até `on_step` devolver `false`, `max_steps` ou o canal fechar
+    ///
+    /// # Argumentos
+    /// * `network` - Rede a simular
+    /// * `inputs` - Canal de inputs (ver `input_channel`)
+    /// * `on_step` - Chamado após cada update; `false` termina a execução
+    pub fn run<F>(
+        &self,
+        network: &mut Network,
+        inputs: &Receiver<LiveInput>,
+        mut on_step: F,
+    ) -> RealTimeStats
+    where
+        F: FnMut(&Network, &RealTimeStep) -> bool,
+    {
+        let n = network.num_neurons();
+        let mut held: Option<Vec<f64>> = None;
+        let mut events: Vec<(usize, f64)> = Vec::new();
+        let mut stats = RealTimeStats::default();
+        let start = Instant::now();
+
+        loop {
+            if self.max_steps.is_some_and(|max| stats.steps >= max) {
+                break;
+            }
+
+            // Espera pelo instante previsto do passo; atrasado, segue logo
+            let due = start + self.period.mul_f64(stats.steps as f64);
+            let now = Instant::now();
+            if now < due {
+                std::thread::sleep(due - now);
+            }
+            let lag = Instant::now().saturating_duration_since(due);
+
+            let mut fresh = None;
+            let mut messages = 0;
+            let mut closed = false;
+            loop {
+                match inputs.try_recv() {
+                    Ok(LiveInput::Dense(frame)) => fresh = Some(frame),
+                    Ok(LiveInput::Events(batch)) => events.extend(batch),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        closed = true;
+                        break;
+                    }
+                }
+                messages += 1;
+            }
+            if closed && messages == 0 {
+                break;
+            }
+            if fresh.is_some() || self.hold == InputHold::Once {
+                held = fresh;
+            }
+
+            match &held {
+                Some(frame) if events.is_empty() => network.update(frame),
+                frame => {
+                    let dense = frame.iter().flat_map(|f| f.iter().copied().enumerate());
+                    let values = dense.chain(events.drain(..)).filter(|&(i, _)| i < n);
+                    network.update_from(Sparse(values));
+                }
+            }
+            events.clear();
+
+            let step = RealTimeStep {
+                index: stats.steps,
+                wall_time: start.elapsed(),
+                lag,
+                messages,
+            };
+            stats.steps += 1;
+            stats.messages += messages as u64;
+            stats.max_lag = stats.max_lag.max(lag);
+            if lag > LATE_TOLERANCE {
+                stats.late_steps += 1;
+            }
+            if !on_step(network, &step) {
+                break;
+            }
+        }
+
+        stats.elapsed = start.elapsed();
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ConnectivityType;
+
+    #[test]
+    fn test_paces_steps_and_consumes_live_inputs() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 1);
+        let (sender, receiver) = input_channel();
+        sender.send(LiveInput::Dense(vec![1.0; 9])).unwrap();
+        sender.send(LiveInput::Events(vec![(4, 2.0), (20, 1.0)])).unwrap();
+
+        let mut seen = Vec::new();
+        let stats = RealTimeRunner::new(200.0).with_max_steps(10).run(
+            &mut network,
+            &receiver,
+            |network, step| {
+                seen.push((step.index, step.messages, network.current_time_step));
+                true
+            },
+        );
+
+        assert_eq!(stats.steps, 10);
+        assert_eq!(stats.messages, 2);
+        assert_eq!(seen[0], (0, 2, 1));
+        assert_eq!(seen[9], (9, 0, 10));
+        // 10 passos a 200/s: pelo menos 9 períodos de 5 ms
+        assert!(stats.elapsed >= Duration::from_millis(45));
+        assert!(seen.windows(2).all(|w| w[1].0 == w[0].0 + 1));
+    }
+
+    #[test]
+    fn test_stops_when_producer_disconnects_or_callback_stops() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.5, 1);
+        let (sender, receiver) = input_channel();
+        let producer = std::thread::spawn(move || {
+            for t in 0..5 {
+                sender.send(LiveInput::Events(vec![(t, 1.0)])).unwrap();
+                std::thread::sleep(Duration::from_millis(2));
+            }
+        });
+        let stats = RealTimeRunner::new(1000.0).run(&mut network, &receiver, |_, _| true);
+        producer.join().unwrap();
+        assert_eq!(stats.messages, 5);
+        assert!(stats.steps > 0);
+
+        let (_sender, receiver) = input_channel();
+        let runner = RealTimeRunner::new(1000.0).with_input_hold(InputHold::Once);
+        let stats = runner.run(&mut network, &receiver, |_, step| step.index < 2);
+        assert_eq!(stats.steps, 3);
+    }
+}
@@ -0,0 +1,844 @@
+//! Módulo de registo de dados da simulação
+//!
+//! O `Recorder` amostra um conjunto de sondas (`Probe`) a cada N passos e
+//! entrega os valores a um ou mais destinos (`RecordSink`), substituindo o
+//! código de recolha de snapshots escrito à mão em cada experimento.
+//!
+//! Sondas disponíveis:
+//! - Campos de um neurónio individual (energia, priority, novidade, ...)
+//! - Agregados sobre um grupo nomeado da rede (média, soma, mínimo, máximo)
+//! - Escalares globais da rede (alert_level, novidade média, ...)
+//!
+//! Para simulações grandes, `CsvSink` escreve em buffer e `AsyncSink` move
+//! qualquer destino para um thread dedicado, tirando o I/O do loop. Para
+//! milhões de passos, `chunked::ChunkedSink` grava registos binários com
+//! índice que podem ser lidos por partes. Para análises que precisam de
+//! todo o estado, `with_state_tensor` grava também um tensor binário com
+//! todos os neurónios em cada amostragem (ver `tensor`). Com a feature
+//! `polars`, `to_dataframe` entrega as séries em memória como DataFrame e
+//! `ParquetSink` grava-as em Parquet.
+
+use crate::nenv::NENV;
+use crate::network::Network;
+use crate::tensor::StateTensorWriter;
+#[cfg(feature = "polars")]
+use polars::prelude::{Column, DataFrame, ParquetWriter, PolarsError, PolarsResult};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::JoinHandle;
+
+/// Campo de estado de um neurónio que pode ser registado
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NeuronField {
+    /// 1.0 se o neurónio disparou no último passo, 0.0 caso contrário
+    Firing,
+    /// Energia atual da Glia
+    Energy,
+    /// Priority atual da Glia
+    Priority,
+    /// Limiar de disparo
+    Threshold,
+    /// Novidade calculada no último passo
+    Novelty,
+    /// Sinal de saída (+1.0, -1.0 ou 0.0)
+    OutputSignal,
+}
+
+impl NeuronField {
+    /// Lê o valor do campo num neurónio
+    pub fn read(&self, neuron: &NENV) -> f64 {
+        match self {
+            NeuronField::Firing => {
+                if neuron.is_firing {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            NeuronField::Energy => neuron.glia.energy,
+            NeuronField::Priority => neuron.glia.priority,
+            NeuronField::Threshold => neuron.threshold,
+            NeuronField::Novelty => neuron.novelty,
+            NeuronField::OutputSignal => neuron.output_signal,
+        }
+    }
+
+    /// Nome curto do campo (usado nos nomes de coluna)
+    pub fn name(&self) -> &'static str {
+        match self {
+            NeuronField::Firing => "firing",
+            NeuronField::Energy => "energy",
+            NeuronField::Priority => "priority",
+            NeuronField::Threshold => "threshold",
+            NeuronField::Novelty => "novelty",
+            NeuronField::OutputSignal => "output",
+        }
+    }
+}
+
+/// Função de agregação aplicada aos membros de um grupo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Mean,
+    Sum,
+    Min,
+    Max,
+}
+
+impl Aggregate {
+    /// Aplica a agregação a uma sequência de valores
+    ///
+    /// Retorna NaN para grupos vazios.
+    pub fn apply(&self, values: impl Iterator<Item = f64>) -> f64 {
+        let mut count = 0usize;
+        let mut acc = match self {
+            Aggregate::Mean | Aggregate::Sum => 0.0,
+            Aggregate::Min => f64::INFINITY,
+            Aggregate::Max => f64::NEG_INFINITY,
+        };
+
+        for value in values {
+            count += 1;
+            acc = match self {
+                Aggregate::Mean | Aggregate::Sum => acc + value,
+                Aggregate::Min => acc.min(value),
+                Aggregate::Max => acc.max(value),
+            };
+        }
+
+        if count == 0 {
+            return f64::NAN;
+        }
+
+        match self {
+            Aggregate::Mean => acc / count as f64,
+            _ => acc,
+        }
+    }
+
+    /// Nome curto da agregação (usado nos nomes de coluna)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Aggregate::Mean => "mean",
+            Aggregate::Sum => "sum",
+            Aggregate::Min => "min",
+            Aggregate::Max => "max",
+        }
+    }
+}
+
+/// Escalar global da rede que pode ser registado
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkScalar {
+    AlertLevel,
+    AverageNovelty,
+    AverageEnergy,
+    NumFiring,
+}
+
+impl NetworkScalar {
+    /// Lê o valor do escalar na rede
+    pub fn read(&self, network: &Network) -> f64 {
+        match self {
+            NetworkScalar::AlertLevel => network.alert_level,
+            NetworkScalar::AverageNovelty => network.average_novelty(),
+            NetworkScalar::AverageEnergy => network.average_energy(),
+            NetworkScalar::NumFiring => network.num_firing() as f64,
+        }
+    }
+
+    /// Nome curto do escalar (usado nos nomes de coluna)
+    pub fn name(&self) -> &'static str {
+        match self {
+            NetworkScalar::AlertLevel => "alert_level",
+            NetworkScalar::AverageNovelty => "avg_novelty",
+            NetworkScalar::AverageEnergy => "avg_energy",
+            NetworkScalar::NumFiring => "total_firing",
+        }
+    }
+}
+
+/// Sonda que extrai um valor da rede a cada amostragem
+#[derive(Debug, Clone, PartialEq)]
+pub enum Probe {
+    /// Campo de um neurónio individual
+    Neuron { index: usize, field: NeuronField },
+    /// Agregado de um campo sobre um grupo nomeado da rede
+    Group {
+        group: String,
+        field: NeuronField,
+        aggregate: Aggregate,
+    },
+    /// Escalar global da rede
+    Network(NetworkScalar),
+}
+
+impl Probe {
+    /// Sonda de um campo de neurónio individual
+    pub fn neuron(index: usize, field: NeuronField) -> Self {
+        Probe::Neuron { index, field }
+    }
+
+    /// Sonda agregada sobre um grupo nomeado
+    pub fn group(group: &str, field: NeuronField, aggregate: Aggregate) -> Self {
+        Probe::Group {
+            group: group.to_string(),
+            field,
+            aggregate,
+        }
+    }
+
+    /// Nome de coluna padrão da sonda
+    ///
+    /// Exemplos: `n55_energy`, `centro_mean_priority`, `alert_level`
+    pub fn default_name(&self) -> String {
+        match self {
+            Probe::Neuron { index, field } => format!("n{}_{}", index, field.name()),
+            Probe::Group {
+                group,
+                field,
+                aggregate,
+            } => format!("{}_{}_{}", group, aggregate.name(), field.name()),
+            Probe::Network(scalar) => scalar.name().to_string(),
+        }
+    }
+
+    /// Amostra o valor atual da sonda
+    ///
+    /// Retorna NaN para neurónios ou grupos inexistentes.
+    pub fn sample(&self, network: &Network) -> f64 {
+        match self {
+            Probe::Neuron { index, field } => network
+                .neurons
+                .get(*index)
+                .map(|neuron| field.read(neuron))
+                .unwrap_or(f64::NAN),
+            Probe::Group {
+                group,
+                field,
+                aggregate,
+            } => match network.group(group) {
+                Some(members) => {
+                    aggregate.apply(members.iter().map(|&i| field.read(&network.neurons[i])))
+                }
+                None => f64::NAN,
+            },
+            Probe::Network(scalar) => scalar.read(network),
+        }
+    }
+}
+
+/// Destino dos dados registados
+pub trait RecordSink {
+    /// Chamado uma vez antes da primeira linha, com os nomes das colunas
+    fn begin(&mut self, columns: &[String]) -> io::Result<()>;
+
+    /// Regista uma linha de valores (na mesma ordem das colunas)
+    fn record(&mut self, time: i64, values: &[f64]) -> io::Result<()>;
+
+    /// Conclui o registo (flush de buffers, fecho de ficheiros, ...)
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Destino CSV: uma linha por amostragem, com cabeçalho `time,<colunas>`
+pub struct CsvSink<W: Write = BufWriter<File>> {
+    writer: W,
+}
+
+impl CsvSink<BufWriter<File>> {
+    /// Cria (ou substitui) um ficheiro CSV com escrita em buffer
+    ///
+    /// As linhas só chegam ao disco quando o buffer enche ou em `finish()`.
+    pub fn create(path: &str) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> CsvSink<W> {
+    /// Cria um destino CSV sobre qualquer `Write`
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Consome o destino e devolve o writer subjacente
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> RecordSink for CsvSink<W> {
+    fn begin(&mut self, columns: &[String]) -> io::Result<()> {
+        writeln!(self.writer, "time,{}", columns.join(","))
+    }
+
+    fn record(&mut self, time: i64, values: &[f64]) -> io::Result<()> {
+        write!(self.writer, "{}", time)?;
+        for value in values {
+            write!(self.writer, ",{}", value)?;
+        }
+        writeln!(self.writer)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Destino em memória: guarda todas as linhas para análise posterior
+#[derive(Debug, Clone, Default)]
+pub struct MemorySink {
+    /// Nomes das colunas
+    pub columns: Vec<String>,
+
+    /// Passo de tempo de cada linha
+    pub times: Vec<i64>,
+
+    /// Valores de cada linha (na ordem de `columns`)
+    pub rows: Vec<Vec<f64>>,
+}
+
+impl MemorySink {
+    /// Cria um destino em memória vazio
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retorna a série temporal de uma coluna pelo nome
+    pub fn column(&self, name: &str) -> Option<Vec<f64>> {
+        let idx = self.columns.iter().position(|c| c == name)?;
+        Some(self.rows.iter().map(|row| row[idx]).collect())
+    }
+
+    /// Número de linhas registadas
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Verifica se nenhuma linha foi registada
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Converte as linhas para um layout colunar (uma série por sonda)
+    ///
+    /// É o formato que bibliotecas de DataFrame esperam: cada par
+    /// `(nome, valores)` corresponde diretamente a uma série, e `times` à
+    /// coluna de índice temporal.
+    pub fn to_columns(&self) -> RecordedColumns {
+        RecordedColumns {
+            times: self.times.clone(),
+            columns: self
+                .columns
+                .iter()
+                .enumerate()
+                .map(|(idx, name)| (name.clone(), self.rows.iter().map(|row| row[idx]).collect()))
+                .collect(),
+        }
+    }
+}
+
+/// Séries temporais registadas, em layout colunar
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecordedColumns {
+    /// Passo de tempo de cada linha
+    pub times: Vec<i64>,
+
+    /// Nome e valores de cada coluna, na ordem de registo das sondas
+    pub columns: Vec<(String, Vec<f64>)>,
+}
+
+#[cfg(feature = "polars")]
+impl RecordedColumns {
+    /// Converte para um DataFrame do polars
+    ///
+    /// A primeira coluna, `time` (i64), é o passo de tempo; seguem-se as
+    /// sondas (f64), pela ordem de registo.
+    pub fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        let mut columns = Vec::with_capacity(self.columns.len() + 1);
+        columns.push(Column::new("time".into(), &self.times));
+        for (name, values) in &self.columns {
+            columns.push(Column::new(name.as_str().into(), values));
+        }
+        DataFrame::new(columns)
+    }
+}
+
+/// Destino Parquet: acumula as linhas e grava um ficheiro Parquet em `finish()`
+///
+/// O esquema é o de `RecordedColumns::to_dataframe` (`time` seguido das
+/// sondas). Parquet é colunar, por isso nada chega ao writer antes do fim.
+#[cfg(feature = "polars")]
+pub struct ParquetSink<W: Write = File> {
+    writer: W,
+    rows: MemorySink,
+}
+
+#[cfg(feature = "polars")]
+impl ParquetSink<File> {
+    /// Cria (ou substitui) um ficheiro Parquet
+    pub fn create(path: &str) -> io::Result<Self> {
+        Ok(Self::new(File::create(path)?))
+    }
+}
+
+#[cfg(feature = "polars")]
+impl<W: Write> ParquetSink<W> {
+    /// Cria um destino Parquet sobre qualquer `Write`
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            rows: MemorySink::new(),
+        }
+    }
+
+    /// Consome o destino e devolve o writer subjacente
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(feature = "polars")]
+impl<W: Write> RecordSink for ParquetSink<W> {
+    fn begin(&mut self, columns: &[String]) -> io::Result<()> {
+        self.rows.begin(columns)
+    }
+
+    fn record(&mut self, time: i64, values: &[f64]) -> io::Result<()> {
+        self.rows.record(time, values)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        let mut frame = self.rows.to_columns().to_dataframe().map_err(io::Error::other)?;
+        ParquetWriter::new(&mut self.writer)
+            .finish(&mut frame)
+            .map_err(io::Error::other)?;
+        self.writer.flush()
+    }
+}
+
+impl RecordSink for MemorySink {
+    fn begin(&mut self, columns: &[String]) -> io::Result<()> {
+        self.columns = columns.to_vec();
+        Ok(())
+    }
+
+    fn record(&mut self, time: i64, values: &[f64]) -> io::Result<()> {
+        self.times.push(time);
+        self.rows.push(values.to_vec());
+        Ok(())
+    }
+}
+
+/// Mensagens enviadas ao thread de escrita do `AsyncSink`
+enum SinkMessage {
+    Begin(Vec<String>),
+    Record(i64, Vec<f64>),
+}
+
+/// Destino assíncrono: delega a escrita a outro destino num thread dedicado
+///
+/// O loop de simulação apenas envia as linhas por um canal; a formatação e
+/// o I/O em disco acontecem em segundo plano. Erros de escrita do destino
+/// interno são reportados em `finish()`.
+pub struct AsyncSink {
+    sender: Option<Sender<SinkMessage>>,
+    worker: Option<JoinHandle<io::Result<()>>>,
+}
+
+impl AsyncSink {
+    /// Move um destino para um thread de escrita dedicado
+    pub fn new<S: RecordSink + Send + 'static>(mut inner: S) -> Self {
+        let (sender, receiver): (Sender<SinkMessage>, Receiver<SinkMessage>) = mpsc::channel();
+
+        let worker = std::thread::spawn(move || {
+            for message in receiver {
+                match message {
+                    SinkMessage::Begin(columns) => inner.begin(&columns)?,
+                    SinkMessage::Record(time, values) => inner.record(time, &values)?,
+                }
+            }
+            inner.finish()
+        });
+
+        Self {
+            sender: Some(sender),
+            worker: Some(worker),
+        }
+    }
+
+    fn send(&self, message: SinkMessage) -> io::Result<()> {
+        let sender = self.sender.as_ref().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::BrokenPipe, "AsyncSink já foi concluído")
+        })?;
+
+        // Falha no envio significa que o thread terminou com erro
+        sender.send(message).map_err(|_| {
+            io::Error::new(io::ErrorKind::BrokenPipe, "Thread de escrita terminou")
+        })
+    }
+}
+
+impl RecordSink for AsyncSink {
+    fn begin(&mut self, columns: &[String]) -> io::Result<()> {
+        self.send(SinkMessage::Begin(columns.to_vec()))
+    }
+
+    fn record(&mut self, time: i64, values: &[f64]) -> io::Result<()> {
+        self.send(SinkMessage::Record(time, values.to_vec()))
+    }
+
+    /// Aguarda o thread de escrita esvaziar a fila e conclui o destino interno
+    fn finish(&mut self) -> io::Result<()> {
+        // Fechar o canal sinaliza o fim ao thread de escrita
+        self.sender.take();
+
+        match self.worker.take() {
+            Some(worker) => worker
+                .join()
+                .map_err(|_| io::Error::other("Thread de escrita entrou em pânico"))?,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for AsyncSink {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+/// Registador de sondas com intervalo de amostragem e destinos plugáveis
+pub struct Recorder {
+    /// Sondas registadas e os respetivos nomes de coluna
+    probes: Vec<(String, Probe)>,
+
+    /// Intervalo de amostragem em passos de simulação
+    interval: i64,
+
+    /// Destinos externos (CSV, etc.)
+    sinks: Vec<Box<dyn RecordSink>>,
+
+    /// Destino em memória opcional, acessível após o registo
+    memory: Option<MemorySink>,
+
+    /// Tensor opcional com o estado completo da rede
+    tensor: Option<StateTensorWriter>,
+
+    /// Indica se o cabeçalho já foi enviado aos destinos
+    started: bool,
+}
+
+impl Recorder {
+    /// Cria um registador que amostra a cada `interval` passos
+    ///
+    /// Intervalos menores que 1 são tratados como 1.
+    pub fn new(interval: i64) -> Self {
+        Self {
+            probes: Vec::new(),
+            interval: interval.max(1),
+            sinks: Vec::new(),
+            memory: None,
+            tensor: None,
+            started: false,
+        }
+    }
+
+    /// Ativa o destino em memória (estilo encadeado)
+    pub fn with_memory(mut self) -> Self {
+        self.memory = Some(MemorySink::new());
+        self
+    }
+
+    /// Grava também o estado completo da rede em `path` (estilo encadeado)
+    ///
+    /// Cada amostragem acrescenta um frame `N × F` ao tensor, com as sondas
+    /// registadas ou sem nenhuma.
+    pub fn with_state_tensor(mut self, path: &str) -> io::Result<Self> {
+        self.tensor = Some(StateTensorWriter::create(path)?);
+        Ok(self)
+    }
+
+    /// Adiciona um destino externo (estilo encadeado)
+    pub fn with_sink(mut self, sink: Box<dyn RecordSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Adiciona um destino externo
+    pub fn add_sink(&mut self, sink: Box<dyn RecordSink>) {
+        self.sinks.push(sink);
+    }
+
+    /// Regista uma sonda com o nome de coluna padrão
+    pub fn add_probe(&mut self, probe: Probe) {
+        let name = probe.default_name();
+        self.add_named_probe(&name, probe);
+    }
+
+    /// Regista uma sonda com um nome de coluna personalizado
+    pub fn add_named_probe(&mut self, name: &str, probe: Probe) {
+        self.probes.push((name.to_string(), probe));
+    }
+
+    /// Nomes das colunas, na ordem de registo das sondas
+    pub fn columns(&self) -> Vec<String> {
+        self.probes.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// Amostra todas as sondas se o passo atual coincidir com o intervalo
+    ///
+    /// Deve ser chamado após cada `Network::update`.
+    pub fn record(&mut self, network: &Network) -> io::Result<()> {
+        let time = network.current_time_step;
+        if time % self.interval != 0 {
+            return Ok(());
+        }
+
+        if !self.started {
+            let columns = self.columns();
+            for sink in &mut self.sinks {
+                sink.begin(&columns)?;
+            }
+            if let Some(memory) = &mut self.memory {
+                memory.begin(&columns)?;
+            }
+            self.started = true;
+        }
+
+        let values: Vec<f64> = self
+            .probes
+            .iter()
+            .map(|(_, probe)| probe.sample(network))
+            .collect();
+
+        for sink in &mut self.sinks {
+            sink.record(time, &values)?;
+        }
+        if let Some(memory) = &mut self.memory {
+            memory.record(time, &values)?;
+        }
+        if let Some(tensor) = &mut self.tensor {
+            tensor.record(network)?;
+        }
+
+        Ok(())
+    }
+
+    /// Conclui o registo em todos os destinos
+    pub fn finish(&mut self) -> io::Result<()> {
+        for sink in &mut self.sinks {
+            sink.finish()?;
+        }
+        if let Some(tensor) = &mut self.tensor {
+            tensor.finish()?;
+        }
+        Ok(())
+    }
+
+    /// Retorna os dados do destino em memória, se ativo
+    pub fn memory(&self) -> Option<&MemorySink> {
+        self.memory.as_ref()
+    }
+
+    /// Séries registadas em layout colunar (requer `with_memory`)
+    pub fn to_columns(&self) -> Option<RecordedColumns> {
+        self.memory.as_ref().map(MemorySink::to_columns)
+    }
+
+    /// Séries registadas como DataFrame do polars (requer `with_memory`)
+    ///
+    /// # Retorna
+    /// Erro `NoData` se o destino em memória não estiver ativo
+    #[cfg(feature = "polars")]
+    pub fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        self.to_columns()
+            .ok_or_else(|| PolarsError::NoData("Recorder sem destino em memória".into()))?
+            .to_dataframe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ConnectivityType;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_aggregates() {
+        let values = [1.0, 2.0, 6.0];
+
+        assert_relative_eq!(Aggregate::Mean.apply(values.iter().copied()), 3.0);
+        assert_relative_eq!(Aggregate::Sum.apply(values.iter().copied()), 9.0);
+        assert_relative_eq!(Aggregate::Min.apply(values.iter().copied()), 1.0);
+        assert_relative_eq!(Aggregate::Max.apply(values.iter().copied()), 6.0);
+        assert!(Aggregate::Mean.apply(std::iter::empty()).is_nan());
+    }
+
+    #[test]
+    fn test_default_column_names() {
+        assert_eq!(Probe::neuron(55, NeuronField::Energy).default_name(), "n55_energy");
+        assert_eq!(
+            Probe::group("centro", NeuronField::Priority, Aggregate::Mean).default_name(),
+            "centro_mean_priority"
+        );
+        assert_eq!(
+            Probe::Network(NetworkScalar::AlertLevel).default_name(),
+            "alert_level"
+        );
+    }
+
+    #[test]
+    fn test_recorder_respects_interval() {
+        let mut network = Network::new(9, ConnectivityType::Grid2D, 0.0, 0.5);
+        let mut recorder = Recorder::new(3).with_memory();
+        recorder.add_probe(Probe::Network(NetworkScalar::AverageEnergy));
+
+        for _ in 0..10 {
+            network.update(&[0.0; 9]);
+            recorder.record(&network).unwrap();
+        }
+
+        let memory = recorder.memory().unwrap();
+        assert_eq!(memory.times, vec![3, 6, 9]);
+        assert_eq!(memory.columns, vec!["avg_energy".to_string()]);
+    }
+
+    #[test]
+    fn test_state_tensor_mode_follows_interval() {
+        let path = std::env::temp_dir().join(format!("nenv_tensor_{}.bin", std::process::id()));
+        let mut network = Network::new(9, ConnectivityType::Grid2D, 0.0, 0.5);
+        let mut recorder = Recorder::new(2)
+            .with_state_tensor(path.to_str().unwrap())
+            .unwrap();
+
+        for _ in 0..7 {
+            network.update(&[1.0; 9]);
+            recorder.record(&network).unwrap();
+        }
+        recorder.finish().unwrap();
+
+        let tensor = crate::tensor::StateTensor::read(&path).unwrap();
+        assert_eq!(tensor.times, vec![2, 4, 6]);
+        assert_eq!(tensor.shape().1, 9);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_to_columns_transposes_rows() {
+        let mut sink = MemorySink::new();
+        sink.begin(&["a".to_string(), "b".to_string()]).unwrap();
+        sink.record(1, &[1.0, 10.0]).unwrap();
+        sink.record(2, &[2.0, 20.0]).unwrap();
+
+        let columns = sink.to_columns();
+        assert_eq!(columns.times, vec![1, 2]);
+        assert_eq!(columns.columns[1], ("b".to_string(), vec![10.0, 20.0]));
+        assert!(Recorder::new(1).to_columns().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "polars")]
+    fn test_to_dataframe_has_time_and_probe_columns() {
+        let mut network = Network::new(9, ConnectivityType::Grid2D, 0.0, 0.5);
+        let mut recorder = Recorder::new(2).with_memory();
+        recorder.add_probe(Probe::Network(NetworkScalar::AverageEnergy));
+        recorder.add_probe(Probe::Network(NetworkScalar::AlertLevel));
+        for _ in 0..6 {
+            network.update(&[1.0; 9]);
+            recorder.record(&network).unwrap();
+        }
+
+        let frame = recorder.to_dataframe().unwrap();
+        assert_eq!(frame.shape(), (3, 3));
+        assert_eq!(frame.get_column_names(), ["time", "avg_energy", "alert_level"]);
+        let times = frame.column("time").unwrap().i64().unwrap();
+        assert_eq!(times.into_no_null_iter().collect::<Vec<_>>(), vec![2, 4, 6]);
+        let energy = frame.column("avg_energy").unwrap().f64().unwrap();
+        assert_eq!(energy.get(2), Some(network.average_energy()));
+        assert!(Recorder::new(1).to_dataframe().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "polars")]
+    fn test_parquet_sink_round_trips_dataframe() {
+        use polars::prelude::{ParquetReader, SerReader};
+
+        let path = std::env::temp_dir().join(format!("nenv_parquet_{}.parquet", std::process::id()));
+        let mut network = Network::new(9, ConnectivityType::Grid2D, 0.0, 0.5);
+        let mut recorder = Recorder::new(1)
+            .with_memory()
+            .with_sink(Box::new(ParquetSink::create(path.to_str().unwrap()).unwrap()));
+        recorder.add_probe(Probe::Network(NetworkScalar::AverageEnergy));
+        recorder.add_probe(Probe::neuron(4, NeuronField::Firing));
+        for _ in 0..5 {
+            network.update(&[1.0; 9]);
+            recorder.record(&network).unwrap();
+        }
+        recorder.finish().unwrap();
+
+        let frame = ParquetReader::new(File::open(&path).unwrap()).finish().unwrap();
+        assert_eq!(frame.shape(), (5, 3));
+        assert!(frame.equals(&recorder.to_dataframe().unwrap()));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_group_probe_aggregates_members() {
+        let mut network = Network::new(9, ConnectivityType::Grid2D, 0.0, 0.5);
+        network.neurons[0].glia.energy = 20.0;
+        network.neurons[1].glia.energy = 40.0;
+        network.define_group("par", &[0, 1]);
+
+        let probe = Probe::group("par", NeuronField::Energy, Aggregate::Mean);
+        assert_relative_eq!(probe.sample(&network), 30.0);
+
+        let missing = Probe::group("nenhum", NeuronField::Energy, Aggregate::Mean);
+        assert!(missing.sample(&network).is_nan());
+    }
+
+    /// Writer partilhado para inspecionar o que um thread escreveu
+    #[derive(Clone, Default)]
+    struct SharedBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_async_sink_writes_all_rows() {
+        let buffer = SharedBuffer::default();
+        let mut recorder =
+            Recorder::new(1).with_sink(Box::new(AsyncSink::new(CsvSink::new(buffer.clone()))));
+        recorder.add_probe(Probe::Network(NetworkScalar::NumFiring));
+
+        let mut network = Network::new(9, ConnectivityType::Grid2D, 0.0, 0.5);
+        for _ in 0..100 {
+            network.update(&[0.0; 9]);
+            recorder.record(&network).unwrap();
+        }
+        recorder.finish().unwrap();
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(output.lines().count(), 101);
+        assert_eq!(output.lines().next(), Some("time,total_firing"));
+    }
+
+    #[test]
+    fn test_csv_sink_format() {
+        let mut sink = CsvSink::new(Vec::new());
+        sink.begin(&["a".to_string(), "b".to_string()]).unwrap();
+        sink.record(1, &[1.0, 0.5]).unwrap();
+
+        let output = String::from_utf8(sink.into_inner()).unwrap();
+        assert_eq!(output, "time,a,b\n1,1,0.5\n");
+    }
+}
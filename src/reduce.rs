@@ -0,0 +1,88 @@
+//! Módulo de reduções em ordem determinística
+//!
+//! A soma de `f64` não é associativa: somar os mesmos valores noutra ordem
+//! (por exemplo, repartidos por threads) muda os últimos bits do resultado,
+//! e numa rede com alerta por limiar isso basta para as trajetórias
+//! divergirem. Estas funções fixam a ordem: os valores são somados em
+//! blocos consecutivos de `CHUNK_LEN` e as somas parciais combinadas aos
+//! pares numa árvore equilibrada.
+//!
+//! ```text
+//! [b0 b1 b2 b3 b4]  →  [(b0+b1) (b2+b3) b4]  →  [((b0+b1)+(b2+b3)) b4]  →  total
+//! ```
+//!
+//! A ordem depende só do número de valores, nunca do número de threads: um
+//! caminho paralelo que calcule cada bloco com `chunk_sum` (em qualquer
+//! thread) e junte as parciais com `combine` obtém exatamente os mesmos
+//! bits que `tree_sum` em série.
+//!
+//! `Network::update` corre num único thread; com
+//! `NetworkConfig::strict_determinism` as suas reduções já usam esta ordem,
+//! mas não há (ainda) um caminho paralelo do passo com que comparar. O que
+//! está garantido hoje é que o mesmo modo, seed e inputs dão os mesmos bits.
+
+/// Número de valores somados em série em cada bloco
+pub const CHUNK_LEN: usize = 256;
+
+/// Soma em série de um bloco
+#[inline]
+pub fn chunk_sum(chunk: &[f64]) -> f64 {
+    chunk.iter().fold(0.0, |acc, &x| acc + x)
+}
+
+/// Combina somas parciais aos pares numa árvore (reutiliza `partials`)
+pub fn combine(partials: &mut [f64]) -> f64 {
+    let mut len = partials.len();
+    if len == 0 {
+        return 0.0;
+    }
+    while len > 1 {
+        let half = len / 2;
+        for i in 0..half {
+            partials[i] = partials[2 * i] + partials[2 * i + 1];
+        }
+        if len % 2 == 1 {
+            partials[half] = partials[len - 1];
+        }
+        len = len.div_ceil(2);
+    }
+    partials[0]
+}
+
+/// Soma determinística: blocos de `CHUNK_LEN` combinados em árvore
+pub fn tree_sum(values: &[f64]) -> f64 {
+    let mut partials: crate::prelude::Vec<f64> = values.chunks(CHUNK_LEN).map(chunk_sum).collect();
+    combine(&mut partials)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree_sum_is_independent_of_thread_split() {
+        let values: Vec<f64> =
+            (0..5000).map(|i| ((i * 7919) % 1000) as f64 * 1e-3 + 1e-9).collect();
+        let expected = tree_sum(&values);
+        assert!((expected - values.iter().sum::<f64>()).abs() < 1e-9);
+
+        for threads in [1, 2, 3, 8] {
+            let chunks: Vec<&[f64]> = values.chunks(CHUNK_LEN).collect();
+            let per_thread = chunks.len().div_ceil(threads);
+            let mut partials: Vec<f64> = std::thread::scope(|scope| {
+                let handles: Vec<_> = chunks
+                    .chunks(per_thread)
+                    .map(|group| {
+                        scope.spawn(move || group.iter().map(|c| chunk_sum(c)).collect::<Vec<_>>())
+                    })
+                    .collect();
+                handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+            });
+            assert_eq!(combine(&mut partials).to_bits(), expected.to_bits());
+        }
+
+        assert_eq!(tree_sum(&[]), 0.0);
+        assert_eq!(tree_sum(&[1.5]), 1.5);
+        assert_eq!(combine(&mut [1.0, 2.0, 3.0]), 6.0);
+    }
+}
@@ -0,0 +1,149 @@
+//! Módulo de religação dependente de atividade
+//!
+//! A rede acumula, para cada par `(i, j)`, um traço de coatividade: a média
+//! móvel exponencial de "i disparou agora e j disparou agora ou no passo
+//! anterior" (sincronia ou a relação causal que a conexão `j → i`
+//! transmite). A cada
+//! `interval` passos, cada neurónio move até `max_per_neuron` das suas
+//! conexões de entrada menos correlacionadas para os pré-sinápticos não
+//! conectados mais coativos, desde que a diferença exceda `min_gain`.
+//!
+//! O grau de entrada de cada neurónio mantém-se exatamente; o grau de saída
+//! varia com a estatística dos inputs. Ao longo do tempo, a topologia
+//! auto-organiza-se em direção às correlações do estímulo.
+
+use crate::footprint::{matrix_bytes, vec_bytes};
+use crate::prelude::*;
+
+/// Parâmetros da regra de religação
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RewiringParams {
+    /// Passos entre rondas de religação
+    pub interval: i64,
+
+    /// Taxa da média móvel de coatividade (0, 1]
+    pub trace_rate: f64,
+
+    /// Diferença mínima de coatividade para mover uma conexão
+    pub min_gain: f64,
+
+    /// Conexões movidas por neurónio em cada ronda
+    pub max_per_neuron: usize,
+}
+
+impl Default for RewiringParams {
+    fn default() -> Self {
+        Self {
+            interval: 100,
+            trace_rate: 0.01,
+            min_gain: 0.05,
+            max_per_neuron: 1,
+        }
+    }
+}
+
+/// Conexão movida: o neurónio `target` deixa de receber de `from` e passa a receber de `to`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rewire {
+    pub target: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// Estado da religação de uma rede
+#[derive(Debug, Clone)]
+pub(crate) struct Rewiring {
+    pub(crate) params: RewiringParams,
+    pub(crate) coactivity: Vec<Vec<f64>>,
+    previous_firing: Vec<bool>,
+}
+
+impl Rewiring {
+    pub(crate) fn new(num_neurons: usize, params: RewiringParams) -> Self {
+        Self {
+            params,
+            coactivity: vec![vec![0.0; num_neurons]; num_neurons],
+            previous_firing: vec![false; num_neurons],
+        }
+    }
+
+    /// Bytes reservados pelos vetores (ver `footprint`)
+    pub(crate) fn heap_bytes(&self) -> usize {
+        matrix_bytes(&self.coactivity) + vec_bytes(&self.previous_firing)
+    }
+
+    /// Atualiza os traços de coatividade com os disparos do passo atual
+    pub(crate) fn observe(&mut self, firing: &[bool]) {
+        let rate = self.params.trace_rate;
+        for (row, &post) in self.coactivity.iter_mut().zip(firing) {
+            let pres = firing.iter().zip(&self.previous_firing);
+            for (trace, (&pre_now, &pre_before)) in row.iter_mut().zip(pres) {
+                let event = if post && (pre_now || pre_before) { 1.0 } else { 0.0 };
+                *trace += rate * (event - *trace);
+            }
+        }
+        self.previous_firing.copy_from_slice(firing);
+    }
+
+    /// Calcula as conexões a mover dada a conectividade atual
+    ///
+    /// Neurónios marcados em `excluded` (p.ex. lesionados) não ganham nem
+    /// perdem conexões.
+    pub(crate) fn plan(&self, connectivity: &[Vec<u8>], excluded: &[bool]) -> Vec<Rewire> {
+        let mut rewires = Vec::new();
+        for (target, row) in connectivity.iter().enumerate() {
+            if excluded[target] {
+                continue;
+            }
+            let trace = &self.coactivity[target];
+            let candidates = |connected: bool| {
+                let mut pres: Vec<usize> = (0..row.len())
+                    .filter(|&j| j != target && !excluded[j] && (row[j] != 0) == connected)
+                    .collect();
+                pres.sort_by(|&a, &b| trace[a].total_cmp(&trace[b]));
+                pres
+            };
+            let weakest = candidates(true);
+            let mut strongest = candidates(false);
+            strongest.reverse();
+
+            for (&from, &to) in weakest.iter().zip(&strongest).take(self.params.max_per_neuron) {
+                if trace[to] - trace[from] < self.params.min_gain {
+                    break;
+                }
+                rewires.push(Rewire { target, from, to });
+            }
+        }
+        rewires
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_moves_weakest_edge_to_most_coactive_pair() {
+        let params = RewiringParams {
+            trace_rate: 1.0,
+            ..RewiringParams::default()
+        };
+        let mut rewiring = Rewiring::new(4, params);
+        // 0 recebe de 1 e 2; 3 dispara sempre antes de 0
+        rewiring.observe(&[false, false, true, true]);
+        rewiring.observe(&[true, false, false, true]);
+
+        let connectivity = vec![
+            vec![0, 1, 1, 0],
+            vec![1, 0, 0, 0],
+            vec![0, 0, 0, 0],
+            vec![0, 0, 0, 0],
+        ];
+        let plan = rewiring.plan(&connectivity, &[false; 4]);
+        assert_eq!(plan, vec![Rewire { target: 0, from: 1, to: 3 }]);
+
+        // Neurónios excluídos não ganham nem perdem conexões
+        let excluded = [false, false, false, true];
+        assert!(rewiring.plan(&connectivity, &excluded).is_empty());
+    }
+}
@@ -0,0 +1,583 @@
+//! Módulo do serviço REST de simulação remota
+//!
+//! Expõe redes NEN-V por HTTP/JSON, para orquestrar experimentos a partir
+//! de outras linguagens ou distribuí-los por várias máquinas. Cada rede
+//! criada recebe um identificador e guarda o seu vetor de entradas, que é
+//! reaplicado a cada passo até ser substituído.
+//!
+//! ```text
+//! POST   /networks                {"num_neurons":100,"connectivity":"grid2d",
+//!                                  "inhibitory_ratio":0.2,"threshold":0.2,"seed":42}
+//! GET    /networks                lista de identificadores
+//! DELETE /networks/{id}
+//! PUT    /networks/{id}/inputs    {"inputs":[...]}
+//! POST   /networks/{id}/step      {"steps":10}  (opcional "inputs":[...])
+//! GET    /networks/{id}/state     estado por neurónio
+//! GET    /networks/{id}/metrics   métricas globais
+//! ```
+//!
+//! Erros devolvem `{"error": "..."}` com o código HTTP correspondente.
+//! O binário `nenv-server` serve este módulo; disponível com a feature `server`.
+
+use crate::network::{ConnectivityType, Network};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Tamanho máximo aceite para o corpo de um pedido
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Número máximo de passos num único pedido `step`
+const MAX_STEPS_PER_REQUEST: usize = 1_000_000;
+
+/// Pedido de criação de uma rede
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateRequest {
+    pub num_neurons: usize,
+    /// "grid2d" (omissão) ou "fully_connected"
+    #[serde(default)]
+    pub connectivity: Option<String>,
+    #[serde(default = "default_inhibitory_ratio")]
+    pub inhibitory_ratio: f64,
+    #[serde(default = "default_threshold")]
+    pub threshold: f64,
+    /// Semente; sem semente a rede é inicializada aleatoriamente
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+fn default_inhibitory_ratio() -> f64 {
+    0.2
+}
+
+fn default_threshold() -> f64 {
+    0.2
+}
+
+#[derive(Debug, Deserialize)]
+struct InputsRequest {
+    inputs: Vec<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StepRequest {
+    #[serde(default = "default_steps")]
+    steps: usize,
+    #[serde(default)]
+    inputs: Option<Vec<f64>>,
+}
+
+fn default_steps() -> usize {
+    1
+}
+
+/// Métricas globais de uma rede
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NetworkMetrics {
+    pub time: i64,
+    pub num_firing: usize,
+    pub avg_energy: f64,
+    pub avg_novelty: f64,
+    pub alert_level: f64,
+}
+
+impl NetworkMetrics {
+    fn from_network(network: &Network) -> Self {
+        Self {
+            time: network.current_time_step,
+            num_firing: network.num_firing(),
+            avg_energy: network.average_energy(),
+            avg_novelty: network.average_novelty(),
+            alert_level: network.alert_level,
+        }
+    }
+}
+
+/// Estado por neurónio de uma rede
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NetworkState {
+    pub time: i64,
+    pub grid_width: usize,
+    pub firing: Vec<bool>,
+    pub energy: Vec<f64>,
+    pub priority: Vec<f64>,
+    pub novelty: Vec<f64>,
+}
+
+impl NetworkState {
+    fn from_network(network: &Network) -> Self {
+        let neurons = &network.neurons;
+        Self {
+            time: network.current_time_step,
+            grid_width: network.grid_width,
+            firing: neurons.iter().map(|n| n.is_firing).collect(),
+            energy: neurons.iter().map(|n| n.glia.energy).collect(),
+            priority: neurons.iter().map(|n| n.glia.priority).collect(),
+            novelty: neurons.iter().map(|n| n.novelty).collect(),
+        }
+    }
+}
+
+/// Rede gerida pelo serviço com o seu vetor de entradas persistente
+#[derive(Debug)]
+struct Session {
+    network: Network,
+    inputs: Vec<f64>,
+}
+
+/// Erro de uma operação do serviço, comum aos transportes REST e gRPC
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServiceError {
+    /// Não existe rede com o identificador pedido
+    NotFound,
+    /// Pedido inválido (mensagem para o cliente)
+    InvalidArgument(String),
+}
+
+impl ServiceError {
+    /// Código HTTP correspondente
+    pub fn status(&self) -> u16 {
+        match self {
+            ServiceError::NotFound => 404,
+            ServiceError::InvalidArgument(_) => 400,
+        }
+    }
+}
+
+impl std::fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServiceError::NotFound => write!(f, "rede inexistente"),
+            ServiceError::InvalidArgument(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ServiceError {}
+
+fn invalid(message: impl Into<String>) -> ServiceError {
+    ServiceError::InvalidArgument(message.into())
+}
+
+/// Resposta a um pedido: código HTTP e corpo JSON
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        let body = serde_json::to_string(value).unwrap_or_else(|_| "null".to_string());
+        Self { status, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, &serde_json::json!({ "error": message }))
+    }
+
+    fn from_result<T: Serialize>(status: u16, result: Result<T, ServiceError>) -> Self {
+        match result {
+            Ok(value) => Self::json(status, &value),
+            Err(e) => Self::error(e.status(), &e.to_string()),
+        }
+    }
+}
+
+/// Lê o corpo JSON de um pedido ou devolve a resposta 400
+fn parse_body<'a, T: Deserialize<'a>>(body: &'a str) -> Result<T, Response> {
+    serde_json::from_str(body).map_err(|e| Response::error(400, &e.to_string()))
+}
+
+/// Conjunto de redes geridas remotamente
+///
+/// Independente do transporte: as operações tipadas (`create`, `step`, ...)
+/// servem o gRPC, e `handle` recebe método, caminho e corpo HTTP e devolve
+/// a resposta, o que permite testá-lo sem sockets.
+#[derive(Debug, Default)]
+pub struct SimulationService {
+    sessions: BTreeMap<u64, Session>,
+    next_id: u64,
+}
+
+impl SimulationService {
+    /// Cria um serviço sem redes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Número de redes ativas
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Verifica se não há redes ativas
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// Identificadores das redes ativas, por ordem de criação
+    pub fn ids(&self) -> Vec<u64> {
+        self.sessions.keys().copied().collect()
+    }
+
+    /// Cria uma rede e devolve o seu identificador
+    pub fn create(&mut self, request: CreateRequest) -> Result<u64, ServiceError> {
+        if request.num_neurons == 0 {
+            return Err(invalid("num_neurons tem de ser maior que zero"));
+        }
+        if !(0.0..=1.0).contains(&request.inhibitory_ratio) {
+            return Err(invalid("inhibitory_ratio tem de estar em [0, 1]"));
+        }
+        let connectivity = match request.connectivity.as_deref() {
+            None | Some("grid2d") => ConnectivityType::Grid2D,
+            Some("fully_connected") => ConnectivityType::FullyConnected,
+            Some(_) => return Err(invalid("connectivity desconhecida")),
+        };
+
+        let network = match request.seed {
+            Some(seed) => Network::new_seeded(
+                request.num_neurons,
+                connectivity,
+                request.inhibitory_ratio,
+                request.threshold,
+                seed,
+            ),
+            None => Network::new(
+                request.num_neurons,
+                connectivity,
+                request.inhibitory_ratio,
+                request.threshold,
+            ),
+        };
+
+        let id = self.next_id;
+        self.next_id += 1;
+        let inputs = vec![0.0; network.num_neurons()];
+        self.sessions.insert(id, Session { network, inputs });
+        Ok(id)
+    }
+
+    /// Remove uma rede
+    pub fn delete(&mut self, id: u64) -> Result<(), ServiceError> {
+        self.sessions.remove(&id).map(|_| ()).ok_or(ServiceError::NotFound)
+    }
+
+    /// Substitui o vetor de entradas reaplicado a cada passo
+    pub fn set_inputs(&mut self, id: u64, inputs: Vec<f64>) -> Result<(), ServiceError> {
+        let session = self.session_mut(id)?;
+        check_inputs(&session.network, &inputs)?;
+        session.inputs = inputs;
+        Ok(())
+    }
+
+    /// Avança `steps` passos (opcionalmente com novas entradas)
+    ///
+    /// # Retorna
+    /// Métricas da rede após o último passo
+    pub fn step(
+        &mut self,
+        id: u64,
+        steps: usize,
+        inputs: Option<Vec<f64>>,
+    ) -> Result<NetworkMetrics, ServiceError> {
+        if steps > MAX_STEPS_PER_REQUEST {
+            return Err(invalid("steps excede o máximo por pedido"));
+        }
+        let session = self.session_mut(id)?;
+        if let Some(inputs) = inputs {
+            check_inputs(&session.network, &inputs)?;
+            session.inputs = inputs;
+        }
+        for _ in 0..steps {
+            session.network.update(&session.inputs);
+        }
+        Ok(NetworkMetrics::from_network(&session.network))
+    }
+
+    /// Estado por neurónio de uma rede
+    pub fn state(&self, id: u64) -> Result<NetworkState, ServiceError> {
+        self.session(id).map(|s| NetworkState::from_network(&s.network))
+    }
+
+    /// Métricas globais de uma rede
+    pub fn metrics(&self, id: u64) -> Result<NetworkMetrics, ServiceError> {
+        self.session(id).map(|s| NetworkMetrics::from_network(&s.network))
+    }
+
+    fn session(&self, id: u64) -> Result<&Session, ServiceError> {
+        self.sessions.get(&id).ok_or(ServiceError::NotFound)
+    }
+
+    fn session_mut(&mut self, id: u64) -> Result<&mut Session, ServiceError> {
+        self.sessions.get_mut(&id).ok_or(ServiceError::NotFound)
+    }
+
+    /// Encaminha um pedido para o endpoint correspondente
+    ///
+    /// # Argumentos
+    /// * `method` - Método HTTP ("GET", "POST", ...)
+    /// * `path` - Caminho do pedido (a query string é ignorada)
+    /// * `body` - Corpo do pedido (JSON, pode ser vazio)
+    pub fn handle(&mut self, method: &str, path: &str, body: &str) -> Response {
+        let path = path.split('?').next().unwrap_or("");
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        let result = match (method, segments.as_slice()) {
+            ("POST", ["networks"]) => parse_body(body).map(|request| {
+                let id = self.create(request);
+                Response::from_result(201, id.map(|id| serde_json::json!({ "id": id })))
+            }),
+            ("GET", ["networks"]) => {
+                Ok(Response::json(200, &serde_json::json!({ "networks": self.ids() })))
+            }
+            (_, ["networks", id, rest @ ..]) => match id.parse::<u64>() {
+                Ok(id) => self.handle_network(method, id, rest, body),
+                Err(_) => Err(Response::error(400, "identificador inválido")),
+            },
+            _ => Err(Response::error(404, "endpoint desconhecido")),
+        };
+        result.unwrap_or_else(|response| response)
+    }
+
+    fn handle_network(
+        &mut self,
+        method: &str,
+        id: u64,
+        rest: &[&str],
+        body: &str,
+    ) -> Result<Response, Response> {
+        let response = match (method, rest) {
+            ("DELETE", []) => Response::from_result(
+                200,
+                self.delete(id).map(|_| serde_json::json!({ "deleted": id })),
+            ),
+            ("PUT", ["inputs"]) => {
+                let request: InputsRequest = parse_body(body)?;
+                let result = self.set_inputs(id, request.inputs);
+                Response::from_result(200, result.map(|_| serde_json::json!({ "id": id })))
+            }
+            ("POST", ["step"]) => {
+                let body = if body.trim().is_empty() { "{}" } else { body };
+                let request: StepRequest = parse_body(body)?;
+                Response::from_result(200, self.step(id, request.steps, request.inputs))
+            }
+            ("GET", ["state"]) => Response::from_result(200, self.state(id)),
+            ("GET", ["metrics"]) => Response::from_result(200, self.metrics(id)),
+            _ if !self.sessions.contains_key(&id) => Response::error(404, "rede inexistente"),
+            _ => Response::error(404, "endpoint desconhecido"),
+        };
+        Ok(response)
+    }
+}
+
+/// Valida o comprimento do vetor de entradas contra a rede
+fn check_inputs(network: &Network, inputs: &[f64]) -> Result<(), ServiceError> {
+    if inputs.len() == network.num_neurons() {
+        Ok(())
+    } else {
+        Err(invalid(format!(
+            "esperadas {} entradas, recebidas {}",
+            network.num_neurons(),
+            inputs.len()
+        )))
+    }
+}
+
+/// Servidor HTTP que atende o `SimulationService` num thread dedicado
+pub struct SimulationServer {
+    service: Arc<Mutex<SimulationService>>,
+    local_addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl SimulationServer {
+    /// Abre o socket e inicia o thread servidor
+    ///
+    /// # Argumentos
+    /// * `addr` - Endereço de escuta (p.ex. "0.0.0.0:8080"; porta 0 escolhe uma livre)
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let service = Arc::new(Mutex::new(SimulationService::new()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let service = Arc::clone(&service);
+            let shutdown = Arc::clone(&shutdown);
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        // Erros de um cliente não derrubam o servidor
+                        let _ = serve_connection(stream, &service);
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            service,
+            local_addr,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// Endereço efetivo de escuta
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Serviço partilhado com o thread servidor
+    pub fn service(&self) -> Arc<Mutex<SimulationService>> {
+        Arc::clone(&self.service)
+    }
+
+    /// Bloqueia até o thread servidor terminar
+    pub fn wait(mut self) {
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for SimulationServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Acorda o thread bloqueado em accept
+        let _ = TcpStream::connect(self.local_addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Lê um pedido HTTP/1.1, despacha-o para o serviço e escreve a resposta
+fn serve_connection(stream: TcpStream, service: &Mutex<SimulationService>) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("").to_string();
+    let path = parts.next().unwrap_or("").to_string();
+
+    let mut content_length = 0usize;
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header.trim_end() != "" {
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+        header.clear();
+    }
+
+    let response = if content_length > MAX_BODY_BYTES {
+        Response::error(413, "corpo demasiado grande")
+    } else {
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+        let body = String::from_utf8_lossy(&body);
+        match service.lock() {
+            Ok(mut service) => service.handle(&method, &path, &body),
+            Err(_) => Response::error(500, "serviço indisponível"),
+        }
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason_phrase(response.status),
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        404 => "Not Found",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_lifecycle() {
+        let mut service = SimulationService::new();
+
+        let created = service.handle(
+            "POST",
+            "/networks",
+            r#"{"num_neurons": 9, "inhibitory_ratio": 0.0, "seed": 1}"#,
+        );
+        assert_eq!(created.status, 201);
+        assert_eq!(created.body, r#"{"id":0}"#);
+
+        let inputs = r#"{"inputs": [0, 0, 0, 0, 5, 0, 0, 0, 0]}"#;
+        assert_eq!(service.handle("PUT", "/networks/0/inputs", inputs).status, 200);
+        assert_eq!(service.handle("PUT", "/networks/0/inputs", r#"{"inputs":[1]}"#).status, 400);
+
+        let stepped = service.handle("POST", "/networks/0/step", r#"{"steps": 3}"#);
+        assert_eq!(stepped.status, 200);
+        let metrics: serde_json::Value = serde_json::from_str(&stepped.body).unwrap();
+        assert_eq!(metrics["time"], 3);
+
+        let state = service.handle("GET", "/networks/0/state", "");
+        let state: serde_json::Value = serde_json::from_str(&state.body).unwrap();
+        assert_eq!(state["energy"].as_array().unwrap().len(), 9);
+        assert_eq!(state["grid_width"], 3);
+
+        assert_eq!(service.handle("DELETE", "/networks/0", "").status, 200);
+        assert_eq!(service.handle("GET", "/networks/0/metrics", "").status, 404);
+        assert!(service.is_empty());
+    }
+
+    #[test]
+    fn test_http_round_trip() {
+        let server = SimulationServer::bind("127.0.0.1:0").unwrap();
+
+        let request = |raw: String| -> String {
+            let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+            stream.write_all(raw.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        let body = r#"{"num_neurons": 4, "connectivity": "fully_connected", "seed": 2}"#;
+        let response = request(format!(
+            "POST /networks HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        ));
+        assert!(response.starts_with("HTTP/1.1 201 Created"));
+        assert!(response.ends_with(r#"{"id":0}"#));
+
+        let response = request("GET /networks/0/metrics HTTP/1.1\r\n\r\n".to_string());
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains(r#""time":0"#));
+        assert_eq!(server.service().lock().unwrap().len(), 1);
+    }
+}
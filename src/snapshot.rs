@@ -0,0 +1,108 @@
+//! Módulo de snapshots da simulação
+//!
+//! Estado resumido da rede a cada passo, recolhido pelos experimentos. Fica
+//! fora de `visualization` para estar disponível mesmo sem a feature `plots`.
+//!
+//! `NetworkStateSnapshot` guarda de uma só vez todos os vetores por neurónio
+//! (disparo, energia, priority, limiar, novidade, saída) para registo.
+
+use crate::network::Network;
+use crate::prelude::*;
+use serde::Serialize;
+
+/// Dados de um passo de simulação para visualização
+#[derive(Debug, Clone)]
+pub struct SimulationSnapshot {
+    pub time: i64,
+    pub target_firing: bool,
+    pub target_energy: f64,
+    pub target_priority: f64,
+    pub total_firing: usize,
+    pub avg_energy: f64,
+    pub alert_level: f64,
+    pub avg_novelty: f64,
+}
+
+impl SimulationSnapshot {
+    /// Resume o estado atual da rede centrado num neurónio alvo
+    ///
+    /// # Argumentos
+    /// * `network` - Rede após o update do passo
+    /// * `target` - Índice do neurónio alvo
+    /// * `time` - Tempo a registar (nos experimentos, o índice do ciclo)
+    pub fn capture(network: &Network, target: usize, time: i64) -> Self {
+        let neuron = &network.neurons[target];
+        Self {
+            time,
+            target_firing: neuron.is_firing,
+            target_energy: neuron.glia.energy,
+            target_priority: neuron.glia.priority,
+            total_firing: network.num_firing(),
+            avg_energy: network.average_energy(),
+            alert_level: network.alert_level,
+            avg_novelty: network.average_novelty(),
+        }
+    }
+}
+
+/// Estado completo por neurónio num passo (um valor por neurónio em cada vetor)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NetworkStateSnapshot {
+    /// Passo de tempo da rede
+    pub time: i64,
+    pub alert_level: f64,
+    pub avg_novelty: f64,
+    pub firing: Vec<bool>,
+    pub energy: Vec<f64>,
+    pub priority: Vec<f64>,
+    pub threshold: Vec<f64>,
+    pub novelty: Vec<f64>,
+    pub output_signal: Vec<f64>,
+}
+
+impl NetworkStateSnapshot {
+    /// Copia o estado atual de todos os neurónios
+    pub fn capture(network: &Network) -> Self {
+        let neurons = &network.neurons;
+        Self {
+            time: network.current_time_step,
+            alert_level: network.alert_level,
+            avg_novelty: network.average_novelty(),
+            firing: network.get_firing_states(),
+            energy: network.get_energy_levels(),
+            priority: network.get_priority_levels(),
+            threshold: network.get_thresholds(),
+            novelty: neurons.iter().map(|n| n.novelty).collect(),
+            output_signal: neurons.iter().map(|n| n.output_signal).collect(),
+        }
+    }
+
+    /// Número de neurónios registados
+    pub fn num_neurons(&self) -> usize {
+        self.firing.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ConnectivityType;
+
+    #[test]
+    fn test_capture_copies_every_neuron() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.3, 0);
+        let mut inputs = vec![0.0; 9];
+        inputs[4] = 3.0;
+        network.update(&inputs);
+
+        let state = network.state_snapshot();
+        assert_eq!(state.num_neurons(), 9);
+        assert_eq!(state.time, 1);
+        assert_eq!(state.threshold, vec![0.3; 9]);
+        assert_eq!(state.priority[4], network.neurons[4].glia.priority);
+
+        let target = SimulationSnapshot::capture(&network, 4, 0);
+        assert_eq!(target.target_energy, state.energy[4]);
+        assert_eq!(target.total_firing, state.firing.iter().filter(|&&f| f).count());
+    }
+}
@@ -0,0 +1,292 @@
+//! Módulo de trens de disparos (spike trains)
+//!
+//! O `SpikeTrain` acumula eventos `(neurónio, tempo)` durante a simulação e
+//! oferece consultas comuns de análise: contagens, taxas, intervalos entre
+//! disparos (ISI) e recortes por grupo de neurónios ou janela temporal.
+//!
+//! Com a feature `std`, trens gerados por outros simuladores podem ser
+//! importados (formato `.gdf` do NEST ou CSV `(time, neuron)`) para
+//! comparação com as funções `analysis::rate_difference` e
+//! `analysis::van_rossum_distance`.
+
+use crate::footprint::vec_bytes;
+use crate::prelude::*;
+#[cfg(feature = "std")]
+use std::io::{self, BufRead, BufReader, Read};
+
+/// Coleção de eventos de disparo ordenados por tempo
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpikeTrain {
+    /// Número de neurónios da rede de origem
+    num_neurons: usize,
+
+    /// Eventos (neurónio, tempo), na ordem em que ocorreram
+    events: Vec<(usize, i64)>,
+}
+
+impl SpikeTrain {
+    /// Cria um trem vazio para uma rede com `num_neurons` neurónios
+    pub fn new(num_neurons: usize) -> Self {
+        Self {
+            num_neurons,
+            events: Vec::new(),
+        }
+    }
+
+    /// Bytes reservados pelos vetores (ver `footprint`)
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.events)
+    }
+
+    /// Cria um trem a partir de eventos `(neurónio, tempo)` em qualquer ordem
+    ///
+    /// Os eventos são ordenados por tempo (ordem estável entre empates).
+    pub fn from_events(num_neurons: usize, mut events: Vec<(usize, i64)>) -> Self {
+        events.sort_by_key(|&(_, t)| t);
+        Self {
+            num_neurons,
+            events,
+        }
+    }
+
+    /// Lê um ficheiro `.gdf` do NEST: uma linha `id tempo_ms` por disparo
+    ///
+    /// # Argumentos
+    /// * `reader` - Conteúdo do ficheiro
+    /// * `dt` - Duração de um passo em ms (o tempo é arredondado ao passo mais próximo)
+    /// * `first_id` - Id NEST que corresponde ao neurónio 0 (tipicamente 1)
+    ///
+    /// # Retorna
+    /// Trem com `num_neurons` = maior índice + 1
+    #[cfg(feature = "std")]
+    pub fn read_gdf<R: Read>(reader: R, dt: f64, first_id: usize) -> io::Result<Self> {
+        let mut events = Vec::new();
+        for (line_no, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let mut fields = line.split_whitespace();
+            let (Some(id), Some(time)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            let parsed = id
+                .parse::<usize>()
+                .ok()
+                .and_then(|id| id.checked_sub(first_id))
+                .zip(time.parse::<f64>().ok());
+            let Some((neuron, time_ms)) = parsed else {
+                return Err(invalid_line(line_no, &line));
+            };
+            events.push((neuron, (time_ms / dt).round() as i64));
+        }
+        Ok(Self::from_events(max_neuron(&events), events))
+    }
+
+    /// Lê um CSV com colunas `time,neuron` (tempos em passos)
+    ///
+    /// Uma primeira linha não numérica é tratada como cabeçalho.
+    #[cfg(feature = "std")]
+    pub fn read_csv<R: Read>(reader: R) -> io::Result<Self> {
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+
+        let mut events = Vec::new();
+        for (line_no, record) in csv_reader.records().enumerate() {
+            let record = record.map_err(io::Error::other)?;
+            let parsed = record
+                .get(0)
+                .and_then(|t| t.parse::<i64>().ok())
+                .zip(record.get(1).and_then(|n| n.parse::<usize>().ok()));
+            match parsed {
+                Some((time, neuron)) => events.push((neuron, time)),
+                None if line_no == 0 => continue,
+                None => {
+                    let line = record.iter().collect::<Vec<_>>().join(",");
+                    return Err(invalid_line(line_no, &line));
+                }
+            }
+        }
+        Ok(Self::from_events(max_neuron(&events), events))
+    }
+
+    /// Regista um disparo
+    ///
+    /// Eventos devem ser adicionados em ordem temporal não decrescente.
+    pub fn push(&mut self, neuron: usize, time: i64) {
+        debug_assert!(
+            self.events.last().is_none_or(|&(_, last)| last <= time),
+            "Eventos devem ser adicionados em ordem temporal"
+        );
+        self.events.push((neuron, time));
+    }
+
+    /// Todos os eventos (neurónio, tempo)
+    pub fn events(&self) -> &[(usize, i64)] {
+        &self.events
+    }
+
+    /// Número de neurónios da rede de origem
+    pub fn num_neurons(&self) -> usize {
+        self.num_neurons
+    }
+
+    /// Número total de disparos
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Verifica se não há disparos registados
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Intervalo `(primeiro, último)` de tempos com disparos
+    pub fn time_range(&self) -> Option<(i64, i64)> {
+        Some((self.events.first()?.1, self.events.last()?.1))
+    }
+
+    /// Tempos de disparo de um neurónio, em ordem crescente
+    pub fn spike_times(&self, neuron: usize) -> Vec<i64> {
+        self.events
+            .iter()
+            .filter(|&&(n, _)| n == neuron)
+            .map(|&(_, t)| t)
+            .collect()
+    }
+
+    /// Número de disparos de cada neurónio
+    pub fn spike_counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.num_neurons];
+        for &(neuron, _) in &self.events {
+            if neuron < counts.len() {
+                counts[neuron] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Taxa de disparo de cada neurónio (disparos por passo)
+    ///
+    /// # Argumentos
+    /// * `duration` - Duração da observação em passos (mínimo 1)
+    pub fn rates(&self, duration: i64) -> Vec<f64> {
+        let duration = duration.max(1) as f64;
+        self.spike_counts()
+            .into_iter()
+            .map(|count| count as f64 / duration)
+            .collect()
+    }
+
+    /// Intervalos entre disparos consecutivos (ISI) de um neurónio
+    pub fn isis(&self, neuron: usize) -> Vec<i64> {
+        self.spike_times(neuron)
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .collect()
+    }
+
+    /// Recorta os eventos na janela temporal `[start, end)`
+    pub fn time_window(&self, start: i64, end: i64) -> SpikeTrain {
+        SpikeTrain {
+            num_neurons: self.num_neurons,
+            events: self
+                .events
+                .iter()
+                .copied()
+                .filter(|&(_, t)| t >= start && t < end)
+                .collect(),
+        }
+    }
+
+    /// Mantém apenas os eventos de um grupo de neurónios
+    ///
+    /// Os índices originais dos neurónios são preservados.
+    pub fn select(&self, neurons: &[usize]) -> SpikeTrain {
+        let mut member = vec![false; self.num_neurons];
+        for &n in neurons.iter().filter(|&&n| n < self.num_neurons) {
+            member[n] = true;
+        }
+
+        SpikeTrain {
+            num_neurons: self.num_neurons,
+            events: self
+                .events
+                .iter()
+                .copied()
+                .filter(|&(n, _)| member.get(n).copied().unwrap_or(false))
+                .collect(),
+        }
+    }
+}
+
+/// Número de neurónios implícito nos eventos (maior índice + 1)
+#[cfg(feature = "std")]
+fn max_neuron(events: &[(usize, i64)]) -> usize {
+    events.iter().map(|&(n, _)| n + 1).max().unwrap_or(0)
+}
+
+#[cfg(feature = "std")]
+fn invalid_line(line_no: usize, line: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("linha {}: disparo inválido '{}'", line_no + 1, line),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_train() -> SpikeTrain {
+        let mut train = SpikeTrain::new(3);
+        train.push(0, 1);
+        train.push(2, 2);
+        train.push(0, 4);
+        train.push(0, 9);
+        train.push(1, 9);
+        train
+    }
+
+    #[test]
+    fn test_counts_and_rates() {
+        let train = sample_train();
+
+        assert_eq!(train.spike_counts(), vec![3, 1, 1]);
+        assert_eq!(train.rates(10), vec![0.3, 0.1, 0.1]);
+        assert_eq!(train.time_range(), Some((1, 9)));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_read_gdf_and_csv() {
+        let gdf = "1\t0.5\n3\t0.2\n\n1\t2.0\n";
+        let train = SpikeTrain::read_gdf(gdf.as_bytes(), 0.1, 1).unwrap();
+        assert_eq!(train.events(), &[(2, 2), (0, 5), (0, 20)]);
+        assert_eq!(train.num_neurons(), 3);
+
+        let csv = "time,neuron\n4,1\n1,0\n";
+        let train = SpikeTrain::read_csv(csv.as_bytes()).unwrap();
+        assert_eq!(train.events(), &[(0, 1), (1, 4)]);
+        assert!(SpikeTrain::read_csv("time,neuron\n1,x\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_isis() {
+        let train = sample_train();
+
+        assert_eq!(train.isis(0), vec![3, 5]);
+        assert!(train.isis(1).is_empty());
+    }
+
+    #[test]
+    fn test_time_window_and_select() {
+        let train = sample_train();
+
+        let window = train.time_window(2, 9);
+        assert_eq!(window.events(), &[(2, 2), (0, 4)]);
+
+        let group = train.select(&[0]);
+        assert_eq!(group.spike_times(0), vec![1, 4, 9]);
+        assert_eq!(group.len(), 3);
+    }
+}
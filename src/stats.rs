@@ -0,0 +1,549 @@
+//! Módulo de comparação estatística entre condições
+//!
+//! Testes simples sobre vetores de uma métrica por semente (p.ex. o priority
+//! de B e de A em cada execução de um lote), para que os relatórios digam se
+//! uma diferença é significativa e não só o seu sinal:
+//!
+//! - `paired_t_test`: amostras emparelhadas (a mesma semente nas duas condições)
+//! - `mann_whitney_u`: amostras independentes, sem supor normalidade
+//! - `cohens_d` / `paired_cohens_d`: tamanho do efeito
+//! - `bootstrap_ci`: intervalo de confiança da média por reamostragem
+//! - `d_prime`: sensibilidade de um detetor (teoria de deteção de sinal)
+//!
+//! Os p-valores são bilaterais. O de Mann-Whitney usa a aproximação normal
+//! (com correção de empates e de continuidade), adequada a partir de ~8
+//! amostras por grupo.
+
+use crate::metrics::roc_auc;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+
+/// Resultado de um teste de hipótese
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TestResult {
+    /// Estatística do teste (t, ou z para Mann-Whitney)
+    pub statistic: f64,
+    /// P-valor bilateral
+    pub p_value: f64,
+    /// Tamanho do efeito (d de Cohen, ou correlação rank-biserial)
+    pub effect_size: f64,
+}
+
+impl TestResult {
+    /// Verdadeiro se `p_value < alpha`
+    pub fn is_significant(&self, alpha: f64) -> bool {
+        self.p_value < alpha
+    }
+}
+
+/// Intervalo de confiança
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfidenceInterval {
+    /// Estimativa pontual
+    pub estimate: f64,
+    /// Limite inferior
+    pub lower: f64,
+    /// Limite superior
+    pub upper: f64,
+    /// Nível de confiança (p.ex. 0.95)
+    pub confidence: f64,
+}
+
+impl ConfidenceInterval {
+    /// Verdadeiro se o intervalo contém `value`
+    pub fn contains(&self, value: f64) -> bool {
+        self.lower <= value && value <= self.upper
+    }
+}
+
+/// Média e desvio padrão amostral (n - 1)
+fn mean_and_std(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, variance.sqrt())
+}
+
+/// Diferenças `a[i] - b[i]` (`None` se os comprimentos diferirem)
+fn differences(a: &[f64], b: &[f64]) -> Option<Vec<f64>> {
+    (a.len() == b.len()).then(|| a.iter().zip(b).map(|(x, y)| x - y).collect())
+}
+
+/// Teste t para amostras emparelhadas
+///
+/// # Retorna
+/// `None` com comprimentos diferentes ou menos de 2 pares. Com diferenças
+/// todas iguais, t é infinito (p = 0) ou 0 se forem nulas (p = 1).
+pub fn paired_t_test(a: &[f64], b: &[f64]) -> Option<TestResult> {
+    let diffs = differences(a, b)?;
+    if diffs.len() < 2 {
+        return None;
+    }
+    let n = diffs.len() as f64;
+    let (mean, std) = mean_and_std(&diffs);
+    let (statistic, effect_size) = if std > 0.0 {
+        (mean / (std / n.sqrt()), mean / std)
+    } else if mean == 0.0 {
+        (0.0, 0.0)
+    } else {
+        (mean.signum() * f64::INFINITY, mean.signum() * f64::INFINITY)
+    };
+    Some(TestResult {
+        statistic,
+        p_value: student_t_two_sided(statistic, n - 1.0),
+        effect_size,
+    })
+}
+
+/// Teste U de Mann-Whitney para amostras independentes
+///
+/// A estatística é o z da aproximação normal (positivo se `a` tende a ser
+/// maior) e o efeito é a correlação rank-biserial `2·AUC - 1`, em [-1, 1].
+///
+/// # Retorna
+/// `None` se algum grupo estiver vazio
+pub fn mann_whitney_u(a: &[f64], b: &[f64]) -> Option<TestResult> {
+    let auc = roc_auc(a, b)?;
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    let n = n1 + n2;
+    let u = auc * n1 * n2;
+
+    // Correção de empates: Σ(t³ - t) sobre os grupos de valores iguais
+    let mut pooled: Vec<f64> = a.iter().chain(b).copied().collect();
+    pooled.sort_by(f64::total_cmp);
+    let ties: f64 = pooled
+        .chunk_by(|x, y| x == y)
+        .map(|group| {
+            let t = group.len() as f64;
+            t * t * t - t
+        })
+        .sum();
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - ties / (n * (n - 1.0)).max(1.0));
+
+    let deviation = u - n1 * n2 / 2.0;
+    let statistic = if variance > 0.0 {
+        // Correção de continuidade de meio ponto em direção a zero
+        (deviation.abs() - 0.5).max(0.0).copysign(deviation) / variance.sqrt()
+    } else {
+        0.0
+    };
+    Some(TestResult {
+        statistic,
+        p_value: erfc(statistic.abs() / std::f64::consts::SQRT_2).min(1.0),
+        effect_size: 2.0 * auc - 1.0,
+    })
+}
+
+/// d de Cohen para grupos independentes (desvio padrão combinado)
+///
+/// # Retorna
+/// `None` se algum grupo tiver menos de 2 valores ou a variância for nula
+pub fn cohens_d(a: &[f64], b: &[f64]) -> Option<f64> {
+    if a.len() < 2 || b.len() < 2 {
+        return None;
+    }
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    let (mean_a, std_a) = mean_and_std(a);
+    let (mean_b, std_b) = mean_and_std(b);
+    let pooled =
+        (((n1 - 1.0) * std_a.powi(2) + (n2 - 1.0) * std_b.powi(2)) / (n1 + n2 - 2.0)).sqrt();
+    (pooled > 0.0).then(|| (mean_a - mean_b) / pooled)
+}
+
+/// d de Cohen para amostras emparelhadas (média / desvio das diferenças)
+pub fn paired_cohens_d(a: &[f64], b: &[f64]) -> Option<f64> {
+    let diffs = differences(a, b)?;
+    if diffs.len() < 2 {
+        return None;
+    }
+    let (mean, std) = mean_and_std(&diffs);
+    (std > 0.0).then(|| mean / std)
+}
+
+/// Intervalo de confiança bootstrap (percentis) da média de `values`
+///
+/// Para amostras emparelhadas passe as diferenças por semente.
+///
+/// # Argumentos
+/// * `values` - Métrica por semente
+/// * `resamples` - Número de reamostragens (p.ex. 10_000)
+/// * `confidence` - Nível de confiança em (0, 1)
+/// * `seed` - Semente das reamostragens
+///
+/// # Retorna
+/// `None` se `values` estiver vazio ou `resamples` for 0
+pub fn bootstrap_ci(
+    values: &[f64],
+    resamples: usize,
+    confidence: f64,
+    seed: u64,
+) -> Option<ConfidenceInterval> {
+    if values.is_empty() || resamples == 0 {
+        return None;
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let means = (0..resamples)
+        .map(|_| resample_mean(values, &mut rng))
+        .collect();
+    Some(percentile_interval(means, mean(values), confidence))
+}
+
+/// Média de uma reamostragem com reposição
+fn resample_mean<R: Rng>(values: &[f64], rng: &mut R) -> f64 {
+    let n = values.len();
+    (0..n).map(|_| values[rng.gen_range(0..n)]).sum::<f64>() / n as f64
+}
+
+/// Média de uma série (não vazia)
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Intervalo dos percentis centrais de `samples` (não vazio)
+fn percentile_interval(
+    mut samples: Vec<f64>,
+    estimate: f64,
+    confidence: f64,
+) -> ConfidenceInterval {
+    samples.sort_by(f64::total_cmp);
+    let tail = (1.0 - confidence.clamp(0.0, 1.0)) / 2.0;
+    let last = samples.len() - 1;
+    let quantile = |q: f64| samples[((q * samples.len() as f64) as usize).min(last)];
+    ConfidenceInterval {
+        estimate,
+        lower: quantile(tail),
+        upper: quantile(1.0 - tail),
+        confidence,
+    }
+}
+
+/// Sensibilidade d' = z(acertos) - z(falsos alarmes)
+///
+/// As taxas 0 e 1 são corrigidas para `1 / (2·trials)` e `1 - 1 / (2·trials)`
+/// para que d' fique finito.
+///
+/// # Argumentos
+/// * `hit_rate` - Fração de eventos novos detetados
+/// * `false_alarm_rate` - Fração de eventos familiares detetados
+/// * `trials` - Ensaios por classe
+pub fn d_prime(hit_rate: f64, false_alarm_rate: f64, trials: usize) -> f64 {
+    let edge = 0.5 / trials.max(1) as f64;
+    let clamp = |rate: f64| rate.clamp(edge, 1.0 - edge);
+    normal_quantile(clamp(hit_rate)) - normal_quantile(clamp(false_alarm_rate))
+}
+
+/// Quantil da normal padrão (algoritmo de Acklam, erro relativo < 1.2e-9)
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const LOW: f64 = 0.024_25;
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Comparação completa de duas condições, para relatórios
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    /// Média da condição A
+    pub mean_a: f64,
+    /// Média da condição B
+    pub mean_b: f64,
+    /// Teste aplicado (t emparelhado ou Mann-Whitney)
+    pub test: TestResult,
+    /// Intervalo bootstrap de 95% da diferença média B - A
+    pub difference: ConfidenceInterval,
+    /// Se as amostras foram tratadas como emparelhadas
+    pub paired: bool,
+}
+
+impl Comparison {
+    /// Compara B com A por semente (`paired`) ou como grupos independentes
+    ///
+    /// Emparelhado: t emparelhado e bootstrap das diferenças. Independente:
+    /// Mann-Whitney e bootstrap de cada grupo, com o intervalo da diferença
+    /// obtido dos percentis das diferenças entre reamostragens.
+    ///
+    /// # Retorna
+    /// `None` se o teste não for aplicável (ver `paired_t_test` e `mann_whitney_u`)
+    pub fn new(a: &[f64], b: &[f64], paired: bool, seed: u64) -> Option<Self> {
+        const RESAMPLES: usize = 10_000;
+        let (test, difference) = if paired {
+            let test = paired_t_test(b, a)?;
+            (
+                test,
+                bootstrap_ci(&differences(b, a)?, RESAMPLES, 0.95, seed)?,
+            )
+        } else {
+            let test = mann_whitney_u(b, a)?;
+            let mut rng = StdRng::seed_from_u64(seed);
+            let diffs = (0..RESAMPLES)
+                .map(|_| resample_mean(b, &mut rng) - resample_mean(a, &mut rng))
+                .collect();
+            (test, percentile_interval(diffs, mean(b) - mean(a), 0.95))
+        };
+        Some(Self {
+            mean_a: mean(a),
+            mean_b: mean(b),
+            test,
+            difference,
+            paired,
+        })
+    }
+
+    /// Verdadeiro se B difere de A com `p < alpha`
+    pub fn is_significant(&self, alpha: f64) -> bool {
+        self.test.is_significant(alpha)
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (test, statistic) = if self.paired {
+            ("t emparelhado", "t")
+        } else {
+            ("Mann-Whitney", "z")
+        };
+        let verdict = if self.is_significant(0.05) {
+            "significativa"
+        } else {
+            "não significativa"
+        };
+        write!(
+            f,
+            "B - A = {:.4} [IC95% {:.4}, {:.4}] | {}: {} = {:.3}, p = {:.4}, efeito = {:.3} ({} a 5%)",
+            self.difference.estimate,
+            self.difference.lower,
+            self.difference.upper,
+            test,
+            statistic,
+            self.test.statistic,
+            self.test.p_value,
+            self.test.effect_size,
+            verdict
+        )
+    }
+}
+
+/// P-valor bilateral de t com `df` graus de liberdade
+fn student_t_two_sided(t: f64, df: f64) -> f64 {
+    if t.is_infinite() {
+        return 0.0;
+    }
+    incomplete_beta(df / 2.0, 0.5, df / (df + t * t))
+}
+
+/// Função beta incompleta regularizada I_x(a, b)
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    // A fração contínua converge depressa para x < (a + 1) / (a + b + 2)
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Fração contínua de Lentz para a beta incompleta
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for m in 1..300 {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+        for numerator in [
+            m * (b - m) * x / ((a + m2 - 1.0) * (a + m2)),
+            -(a + m) * (a + b + m) * x / ((a + m2) * (a + m2 + 1.0)),
+        ] {
+            d = 1.0 + numerator * d;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = 1.0 + numerator / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            h *= d * c;
+        }
+        if (d * c - 1.0).abs() < 1e-14 {
+            break;
+        }
+    }
+    h
+}
+
+/// Logaritmo da função gama (aproximação de Lanczos, x > 0)
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.001_208_650_973_866_179,
+        -0.000_005_395_239_384_953,
+    ];
+    let tmp = x + 5.5 - (x + 0.5) * (x + 5.5).ln();
+    let series: f64 = COEFFICIENTS
+        .iter()
+        .enumerate()
+        .map(|(j, c)| c / (x + 1.0 + j as f64))
+        .sum();
+    -tmp + (2.506_628_274_631_000_5 * (1.000_000_000_190_015 + series) / x).ln()
+}
+
+/// Função erro complementar (erro relativo < 1.2e-7)
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let result = t * poly.exp();
+    if x >= 0.0 { result } else { 2.0 - result }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_distributions_match_reference_values() {
+        // t = 2.0 e t = 2.571 com 5 graus de liberdade; z = 1.96
+        assert_relative_eq!(student_t_two_sided(2.0, 5.0), 0.1019, epsilon = 1e-4);
+        assert_relative_eq!(student_t_two_sided(2.5706, 5.0), 0.05, epsilon = 1e-4);
+        assert_relative_eq!(student_t_two_sided(0.0, 5.0), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(erfc(1.96 / std::f64::consts::SQRT_2), 0.05, epsilon = 1e-4);
+        assert_relative_eq!(normal_quantile(0.975), 1.959_964, epsilon = 1e-6);
+        assert_relative_eq!(normal_quantile(0.01), -2.326_348, epsilon = 1e-6);
+        assert_relative_eq!(d_prime(0.975, 0.5, 100), 1.959_964, epsilon = 1e-6);
+        assert_relative_eq!(d_prime(1.0, 0.0, 10), 2.0 * normal_quantile(0.95), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_paired_t_and_effect_size() {
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let b = [1.5, 2.0, 3.5, 5.0, 5.5];
+        let test = paired_t_test(&b, &a).unwrap();
+        // Diferenças 0.5, 0, 0.5, 1, 0.5: média 0.5, desvio sqrt(0.125)
+        assert_relative_eq!(
+            test.statistic,
+            0.5 / (0.125f64.sqrt() / 5f64.sqrt()),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(test.effect_size, paired_cohens_d(&b, &a).unwrap());
+        assert!(test.is_significant(0.05));
+        assert!(paired_t_test(&a, &[1.0]).is_none());
+        assert_eq!(paired_t_test(&a, &a).unwrap().p_value, 1.0);
+        // Variâncias 2.5 e 3.125: desvio combinado sqrt(2.8125)
+        assert_relative_eq!(
+            cohens_d(&b, &a).unwrap(),
+            0.5 / 2.8125f64.sqrt(),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_mann_whitney_separated_and_overlapping_groups() {
+        let low: Vec<f64> = (0..10).map(f64::from).collect();
+        let high: Vec<f64> = (10..20).map(f64::from).collect();
+        let separated = mann_whitney_u(&high, &low).unwrap();
+        assert_eq!(separated.effect_size, 1.0);
+        assert!(separated.statistic > 0.0);
+        assert!(separated.p_value < 0.001);
+
+        let same = mann_whitney_u(&low, &low).unwrap();
+        assert_eq!(same.effect_size, 0.0);
+        assert_eq!(same.p_value, 1.0);
+        assert!(mann_whitney_u(&low, &[]).is_none());
+    }
+
+    #[test]
+    fn test_bootstrap_ci_and_comparison_report() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let ci = bootstrap_ci(&values, 2000, 0.95, 1).unwrap();
+        assert_eq!(ci.estimate, 4.5);
+        assert!(ci.contains(4.5) && ci.lower > 2.0 && ci.upper < 7.0);
+        assert_eq!(ci, bootstrap_ci(&values, 2000, 0.95, 1).unwrap());
+
+        let priority_a = [1.0, 1.1, 0.9, 1.05, 0.95, 1.0, 1.02, 0.98];
+        let priority_b = [1.4, 1.5, 1.3, 1.45, 1.35, 1.6, 1.42, 1.38];
+        let paired = Comparison::new(&priority_a, &priority_b, true, 3).unwrap();
+        assert!(paired.is_significant(0.01));
+        assert!(paired.difference.lower > 0.0);
+        assert!(!paired.to_string().contains("não significativa"));
+
+        let independent = Comparison::new(&priority_a, &priority_b, false, 3).unwrap();
+        assert!(independent.is_significant(0.01));
+        assert!(
+            independent
+                .difference
+                .contains(independent.mean_b - independent.mean_a)
+        );
+    }
+}
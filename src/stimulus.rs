@@ -0,0 +1,169 @@
+//! Módulo de agendamento de estímulos externos
+//!
+//! Permite descrever protocolos experimentais como uma sequência de estímulos
+//! (alvos, amplitude e janela temporal) em vez de ramos `if` codificados à mão.
+//! `add_gaussian_noise` junta ruído de fundo a qualquer vetor de inputs.
+
+use rand::Rng;
+
+/// Estímulo aplicado a um conjunto de neurónios durante uma janela de tempo
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stimulus {
+    /// Índices dos neurónios que recebem o estímulo
+    pub targets: Vec<usize>,
+
+    /// Intensidade do input externo aplicado a cada alvo
+    pub amplitude: f64,
+
+    /// Primeiro passo (inclusivo) em que o estímulo está ativo
+    pub start: i64,
+
+    /// Último passo (exclusivo) em que o estímulo está ativo
+    pub end: i64,
+}
+
+impl Stimulus {
+    /// Cria um novo estímulo
+    ///
+    /// # Argumentos
+    /// * `targets` - Neurónios alvo
+    /// * `amplitude` - Intensidade do input
+    /// * `start` - Passo inicial (inclusivo)
+    /// * `end` - Passo final (exclusivo)
+    pub fn new(targets: Vec<usize>, amplitude: f64, start: i64, end: i64) -> Self {
+        Self {
+            targets,
+            amplitude,
+            start,
+            end,
+        }
+    }
+
+    /// Verifica se o estímulo está ativo no passo indicado
+    pub fn is_active(&self, time: i64) -> bool {
+        time >= self.start && time < self.end
+    }
+}
+
+/// Sequência de estímulos que compõem um protocolo experimental
+///
+/// Estímulos sobrepostos somam-se no mesmo neurónio.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StimulusSchedule {
+    /// Estímulos registados no protocolo
+    pub stimuli: Vec<Stimulus>,
+}
+
+impl StimulusSchedule {
+    /// Cria um protocolo vazio
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adiciona um estímulo ao protocolo (estilo encadeado)
+    pub fn with(mut self, stimulus: Stimulus) -> Self {
+        self.stimuli.push(stimulus);
+        self
+    }
+
+    /// Adiciona um estímulo ao protocolo
+    pub fn add(&mut self, stimulus: Stimulus) {
+        self.stimuli.push(stimulus);
+    }
+
+    /// Gera o vetor de inputs externos para um passo de tempo
+    ///
+    /// Alvos fora do intervalo `[0, num_neurons)` são ignorados.
+    ///
+    /// # Argumentos
+    /// * `num_neurons` - Tamanho da rede
+    /// * `time` - Passo de tempo atual
+    pub fn inputs_at(&self, num_neurons: usize, time: i64) -> Vec<f64> {
+        let mut inputs = vec![0.0; num_neurons];
+
+        for stimulus in self.stimuli.iter().filter(|s| s.is_active(time)) {
+            for &target in &stimulus.targets {
+                if target < num_neurons {
+                    inputs[target] += stimulus.amplitude;
+                }
+            }
+        }
+
+        inputs
+    }
+
+    /// Retorna o último passo em que algum estímulo está ativo (exclusivo)
+    pub fn duration(&self) -> i64 {
+        self.stimuli.iter().map(|s| s.end).max().unwrap_or(0)
+    }
+}
+
+/// Soma ruído gaussiano de média zero a cada input
+///
+/// # Argumentos
+/// * `inputs` - Vetor de inputs externos de um passo
+/// * `std_dev` - Desvio padrão do ruído (0.0 deixa os inputs inalterados)
+/// * `rng` - Fonte de aleatoriedade (semeada para resultados reprodutíveis)
+pub fn add_gaussian_noise<R: Rng + ?Sized>(inputs: &mut [f64], std_dev: f64, rng: &mut R) {
+    if std_dev <= 0.0 {
+        return;
+    }
+    for input in inputs {
+        // Box-Muller; 1 - u evita ln(0)
+        let u1: f64 = 1.0 - rng.gen_range(0.0..1.0);
+        let u2: f64 = rng.gen_range(0.0..1.0);
+        *input += std_dev * (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stimulus_window_is_half_open() {
+        let stimulus = Stimulus::new(vec![0], 1.0, 10, 20);
+
+        assert!(!stimulus.is_active(9));
+        assert!(stimulus.is_active(10));
+        assert!(stimulus.is_active(19));
+        assert!(!stimulus.is_active(20));
+    }
+
+    #[test]
+    fn test_overlapping_stimuli_sum() {
+        let schedule = StimulusSchedule::new()
+            .with(Stimulus::new(vec![1], 2.0, 0, 10))
+            .with(Stimulus::new(vec![1, 2], 0.5, 5, 10));
+
+        assert_eq!(schedule.inputs_at(3, 0), vec![0.0, 2.0, 0.0]);
+        assert_eq!(schedule.inputs_at(3, 5), vec![0.0, 2.5, 0.5]);
+        assert_eq!(schedule.inputs_at(3, 10), vec![0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_out_of_range_targets_ignored() {
+        let schedule = StimulusSchedule::new().with(Stimulus::new(vec![0, 10], 1.0, 0, 5));
+
+        assert_eq!(schedule.inputs_at(2, 0), vec![1.0, 0.0]);
+        assert_eq!(schedule.duration(), 5);
+    }
+
+    #[test]
+    fn test_gaussian_noise_statistics() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut inputs = vec![1.0; 20_000];
+        add_gaussian_noise(&mut inputs, 0.5, &mut rng);
+        let mean = inputs.iter().sum::<f64>() / inputs.len() as f64;
+        let variance = inputs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / inputs.len() as f64;
+        assert!((mean - 1.0).abs() < 0.02);
+        assert!((variance.sqrt() - 0.5).abs() < 0.02);
+
+        let mut silent = vec![2.0; 3];
+        add_gaussian_noise(&mut silent, 0.0, &mut rng);
+        assert_eq!(silent, vec![2.0; 3]);
+    }
+}
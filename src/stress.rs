@@ -0,0 +1,235 @@
+//! Módulo de teste de escala da rede
+//!
+//! Constrói redes de 1k, 10k, 100k e 1M neurónios para cada topologia,
+//! executa um número fixo de passos e reporta passos por segundo e memória,
+//! para dar uma ideia realista da escala atingível numa máquina concreta.
+//! Executar com:
+//!
+//! ```text
+//! cargo run --release --bin nen-v-prototipo -- --stress [limite em MiB]
+//! ```
+//!
+//! A rede guarda várias matrizes N×N (ver `footprint`), pelo que a memória
+//! cresce com o quadrado do tamanho: 10k neurónios já precisam de ~2.5 GB e
+//! 100k de ~250 GB. Antes de construir cada rede, a estimativa de
+//! `MemoryFootprint::estimate` é comparada com o limite e os tamanhos que o
+//! excedem são reportados como ignorados em vez de esgotarem a memória.
+//!
+//! O pico de memória é o máximo residente do processo (`VmHWM` em Linux);
+//! noutros sistemas só a estimativa é reportada.
+
+use crate::footprint::{format_bytes, MemoryFootprint};
+use crate::network::{ConnectivityType, Network};
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Tamanhos testados por omissão
+pub const DEFAULT_SIZES: [usize; 4] = [1_000, 10_000, 100_000, 1_000_000];
+
+/// Parâmetros do teste de escala
+#[derive(Debug, Clone)]
+pub struct StressConfig {
+    /// Números de neurónios a testar, por ordem crescente
+    pub sizes: Vec<usize>,
+    /// Topologias a testar em cada tamanho
+    pub topologies: Vec<ConnectivityType>,
+    /// Passos de aquecimento antes da medição (alocam os buffers)
+    pub warmup_steps: usize,
+    /// Passos medidos
+    pub steps: usize,
+    /// Memória máxima estimada por rede, em bytes
+    pub memory_budget: usize,
+    /// Semente de todas as redes
+    pub seed: u64,
+}
+
+impl Default for StressConfig {
+    fn default() -> Self {
+        Self {
+            sizes: DEFAULT_SIZES.to_vec(),
+            topologies: vec![ConnectivityType::Grid2D, ConnectivityType::FullyConnected],
+            warmup_steps: 2,
+            steps: 20,
+            memory_budget: 2 << 30,
+            seed: 42,
+        }
+    }
+}
+
+/// Resultado de um tamanho e topologia
+#[derive(Debug, Clone)]
+pub struct StressResult {
+    pub num_neurons: usize,
+    pub topology: ConnectivityType,
+    /// Memória estimada da rede
+    pub estimated_bytes: usize,
+    /// Medição, ou `None` se a estimativa excedeu o limite
+    pub measurement: Option<StressMeasurement>,
+}
+
+/// Tempos e memória de uma rede construída
+#[derive(Debug, Clone, PartialEq)]
+pub struct StressMeasurement {
+    /// Tempo de construção da rede
+    pub build_time: Duration,
+    /// Tempo dos passos medidos
+    pub run_time: Duration,
+    pub steps: usize,
+    /// Memória contada por `Network::memory_footprint` no fim
+    pub footprint_bytes: usize,
+    /// Máximo residente do processo até ao fim da medição
+    pub peak_rss_bytes: Option<usize>,
+}
+
+impl StressMeasurement {
+    /// Passos por segundo
+    pub fn steps_per_second(&self) -> f64 {
+        let seconds = self.run_time.as_secs_f64();
+        if seconds > 0.0 { self.steps as f64 / seconds } else { f64::INFINITY }
+    }
+}
+
+/// Resultados de um teste de escala
+#[derive(Debug, Clone, Default)]
+pub struct StressReport {
+    pub results: Vec<StressResult>,
+}
+
+impl fmt::Display for StressReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>9}  {:<10} {:>11} {:>10} {:>11} {:>11} {:>11}",
+            "N", "topologia", "estimativa", "construção", "passos/s", "rede", "pico RSS"
+        )?;
+        for result in &self.results {
+            write!(
+                f,
+                "{:>9}  {:<10} {:>11}",
+                result.num_neurons,
+                topology_name(result.topology),
+                format_bytes(result.estimated_bytes)
+            )?;
+            match &result.measurement {
+                Some(m) => writeln!(
+                    f,
+                    " {:>10.2?} {:>11.2} {:>11} {:>11}",
+                    m.build_time,
+                    m.steps_per_second(),
+                    format_bytes(m.footprint_bytes),
+                    m.peak_rss_bytes.map_or("-".to_string(), format_bytes)
+                )?,
+                None => writeln!(f, " ignorado: acima do limite de memória")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn topology_name(topology: ConnectivityType) -> &'static str {
+    match topology {
+        ConnectivityType::FullyConnected => "completa",
+        ConnectivityType::Grid2D => "grade",
+    }
+}
+
+/// Executa o teste de escala, imprimindo cada resultado quando termina
+pub fn run_stress(config: &StressConfig) -> StressReport {
+    let mut report = StressReport::default();
+    for &n in &config.sizes {
+        for &topology in &config.topologies {
+            let result = stress_one(config, n, topology);
+            match &result.measurement {
+                Some(m) => println!(
+                    "N={} {}: {:.2} passos/s",
+                    n,
+                    topology_name(topology),
+                    m.steps_per_second()
+                ),
+                None => println!("N={} {}: ignorado", n, topology_name(topology)),
+            }
+            report.results.push(result);
+        }
+    }
+    report
+}
+
+fn stress_one(config: &StressConfig, n: usize, topology: ConnectivityType) -> StressResult {
+    let estimated_bytes = MemoryFootprint::estimate(n, topology).total();
+    if estimated_bytes > config.memory_budget {
+        return StressResult {
+            num_neurons: n,
+            topology,
+            estimated_bytes,
+            measurement: None,
+        };
+    }
+
+    let start = Instant::now();
+    let mut network = Network::new_seeded(n, topology, 0.2, 0.5, config.seed);
+    let build_time = start.elapsed();
+
+    // Estímulo fixo num décimo dos neurónios
+    let inputs: Vec<f64> = (0..n).map(|i| if i % 10 == 0 { 1.0 } else { 0.0 }).collect();
+    for _ in 0..config.warmup_steps {
+        network.update(&inputs);
+    }
+    let start = Instant::now();
+    for _ in 0..config.steps {
+        network.update(black_box(&inputs));
+    }
+    let run_time = start.elapsed();
+
+    StressResult {
+        num_neurons: n,
+        topology,
+        estimated_bytes,
+        measurement: Some(StressMeasurement {
+            build_time,
+            run_time,
+            steps: config.steps,
+            footprint_bytes: network.memory_footprint().total(),
+            peak_rss_bytes: peak_rss_bytes(),
+        }),
+    }
+}
+
+/// Máximo residente do processo (`VmHWM` de `/proc/self/status`)
+pub fn peak_rss_bytes() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kib: usize = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stress_measures_and_skips_over_budget() {
+        let config = StressConfig {
+            sizes: vec![100, 400, 1_000_000],
+            warmup_steps: 1,
+            steps: 3,
+            memory_budget: 64 << 20,
+            ..StressConfig::default()
+        };
+        let report = run_stress(&config);
+        assert_eq!(report.results.len(), 6);
+
+        for result in &report.results[..4] {
+            let m = result.measurement.as_ref().unwrap();
+            assert_eq!(m.steps, 3);
+            assert!(m.steps_per_second() > 0.0);
+            // O input externo acrescenta posições à memória além da estimativa
+            assert!(m.footprint_bytes >= result.estimated_bytes);
+            assert!(m.footprint_bytes < 2 * result.estimated_bytes);
+        }
+        assert!(report.results[4..].iter().all(|r| r.measurement.is_none()));
+        let table = report.to_string();
+        assert_eq!(table.lines().count(), 7);
+        assert!(table.contains("ignorado"));
+    }
+}
@@ -0,0 +1,353 @@
+//! Módulo de execução paralela de sweeps de experimentos
+//!
+//! `SweepRunner` distribui execuções independentes (sementes e
+//! configurações diferentes) por um conjunto de threads e junta os
+//! resultados num `SweepResults`. Cada execução recebe um diretório de saída
+//! próprio, `{raiz}/{índice}_{nome}`, para que os ficheiros de execuções
+//! simultâneas não colidam; no fim é escrito `{raiz}/summary.csv` com as
+//! métricas de todas:
+//!
+//! ```text
+//! let jobs = SweepJob::replicate("base", config, 0..8);
+//! let results = SweepRunner::new("runs").run(jobs, |run| {
+//!     let mut network = Network::with_config_seeded(..., run.config, run.seed);
+//!     ...
+//!     recorder.save(run.output_dir.join("log.csv"))?;
+//!     Ok(BTreeMap::from([("auc".to_string(), auc)]))
+//! })?;
+//! println!("{}", results);
+//! ```
+//!
+//! As execuções são entregues aos threads uma a uma (um thread livre pega
+//! na próxima), o que equilibra a carga quando umas demoram mais do que
+//! outras. Erros e pânicos de uma execução ficam registados no resultado
+//! dela sem interromper as restantes. Os experimentos de `experiments`
+//! escrevem no diretório atual e não são isolados; a função de execução
+//! deve escrever em `output_dir`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Métricas devolvidas por uma execução (nome → valor)
+pub type Metrics = BTreeMap<String, f64>;
+
+/// Uma execução do sweep
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepJob<C> {
+    /// Nome da execução (também usado no diretório de saída)
+    pub name: String,
+    /// Configuração própria da execução
+    pub config: C,
+    /// Semente da execução
+    pub seed: u64,
+}
+
+impl<C: Clone> SweepJob<C> {
+    pub fn new(name: &str, config: C, seed: u64) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            seed,
+        }
+    }
+
+    /// Uma execução por semente, com nomes `{name}_s{semente}`
+    pub fn replicate(name: &str, config: C, seeds: Range<u64>) -> Vec<Self> {
+        seeds
+            .map(|seed| Self::new(&format!("{}_s{}", name, seed), config.clone(), seed))
+            .collect()
+    }
+}
+
+/// Contexto passado à função de execução
+#[derive(Debug, Clone)]
+pub struct SweepRun<'a, C> {
+    /// Posição da execução na lista do sweep
+    pub index: usize,
+    pub name: &'a str,
+    pub config: &'a C,
+    pub seed: u64,
+    /// Diretório exclusivo da execução (já criado)
+    pub output_dir: PathBuf,
+}
+
+/// Resultado de uma execução
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepRecord {
+    pub index: usize,
+    pub name: String,
+    pub seed: u64,
+    pub output_dir: PathBuf,
+    /// Tempo de relógio da execução
+    pub duration: Duration,
+    /// Métricas, ou a mensagem do erro ou pânico
+    pub outcome: Result<Metrics, String>,
+}
+
+/// Resultados de todas as execuções, pela ordem das execuções
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SweepResults {
+    pub records: Vec<SweepRecord>,
+}
+
+impl SweepResults {
+    /// Execuções concluídas sem erro
+    pub fn successes(&self) -> impl Iterator<Item = (&SweepRecord, &Metrics)> {
+        self.records
+            .iter()
+            .filter_map(|record| record.outcome.as_ref().ok().map(|metrics| (record, metrics)))
+    }
+
+    /// Execuções que falharam
+    pub fn failures(&self) -> impl Iterator<Item = &SweepRecord> {
+        self.records.iter().filter(|record| record.outcome.is_err())
+    }
+
+    /// Nomes de todas as métricas devolvidas, por ordem alfabética
+    pub fn metric_names(&self) -> Vec<&str> {
+        let names: BTreeSet<&str> =
+            self.successes().flat_map(|(_, m)| m.keys().map(String::as_str)).collect();
+        names.into_iter().collect()
+    }
+
+    /// Média e desvio padrão de uma métrica nas execuções que a devolveram
+    pub fn mean_std(&self, metric: &str) -> Option<(f64, f64)> {
+        let values: Vec<f64> =
+            self.successes().filter_map(|(_, m)| m.get(metric)).copied().collect();
+        if values.is_empty() {
+            return None;
+        }
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+        Some((mean, variance.sqrt()))
+    }
+
+    /// Escreve uma linha por execução em CSV (uma coluna por métrica)
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let names = self.metric_names();
+        writeln!(writer, "run,name,seed,seconds,{},error", names.join(","))?;
+        for record in &self.records {
+            let (values, error) = match &record.outcome {
+                Ok(metrics) => {
+                    let values: Vec<String> = names
+                        .iter()
+                        .map(|&name| metrics.get(name).map_or(String::new(), |v| v.to_string()))
+                        .collect();
+                    (values, String::new())
+                }
+                Err(message) => {
+                    (vec![String::new(); names.len()], message.replace([',', '\n'], ";"))
+                }
+            };
+            writeln!(
+                writer,
+                "{},{},{},{:.3},{},{}",
+                record.index,
+                record.name,
+                record.seed,
+                record.duration.as_secs_f64(),
+                values.join(","),
+                error
+            )?;
+        }
+        writer.flush()
+    }
+
+    /// Guarda o CSV num ficheiro
+    pub fn save_csv<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_csv(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl fmt::Display for SweepResults {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let failures = self.failures().count();
+        writeln!(f, "{} execuções, {} com erro", self.records.len(), failures)?;
+        for name in self.metric_names() {
+            if let Some((mean, std)) = self.mean_std(name) {
+                writeln!(f, "{:<20} {:>12.4} ± {:.4}", name, mean, std)?;
+            }
+        }
+        for record in self.failures() {
+            if let Err(message) = &record.outcome {
+                writeln!(f, "  erro em {}: {}", record.name, message)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Executa as execuções de um sweep em paralelo
+#[derive(Debug, Clone)]
+pub struct SweepRunner {
+    root: PathBuf,
+    threads: usize,
+}
+
+impl SweepRunner {
+    /// Runner que escreve em `root`, com um thread por núcleo disponível
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Self {
+            root: root.as_ref().to_path_buf(),
+            threads,
+        }
+    }
+
+    /// Define o número de threads (mínimo 1)
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Diretório raiz do sweep
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Executa todas as execuções e escreve `summary.csv` na raiz
+    ///
+    /// # Retorna
+    /// Os resultados pela ordem de `jobs`; erro apenas se não for possível
+    /// criar os diretórios ou escrever o resumo
+    pub fn run<C, F>(&self, jobs: Vec<SweepJob<C>>, run: F) -> io::Result<SweepResults>
+    where
+        C: Sync,
+        F: Fn(&SweepRun<C>) -> io::Result<Metrics> + Sync,
+    {
+        let dirs: Vec<PathBuf> = jobs
+            .iter()
+            .enumerate()
+            .map(|(index, job)| self.root.join(format!("{:03}_{}", index, sanitize(&job.name))))
+            .collect();
+        for dir in &dirs {
+            fs::create_dir_all(dir)?;
+        }
+
+        let next = AtomicUsize::new(0);
+        let records = Mutex::new(Vec::with_capacity(jobs.len()));
+        std::thread::scope(|scope| {
+            for _ in 0..self.threads.min(jobs.len()) {
+                scope.spawn(|| {
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(job) = jobs.get(index) else {
+                            break;
+                        };
+                        let record = execute(index, job, &dirs[index], &run);
+                        records.lock().unwrap_or_else(|e| e.into_inner()).push(record);
+                    }
+                });
+            }
+        });
+
+        let mut records = records.into_inner().unwrap_or_else(|e| e.into_inner());
+        records.sort_by_key(|record| record.index);
+        let results = SweepResults { records };
+        results.save_csv(self.root.join("summary.csv"))?;
+        Ok(results)
+    }
+}
+
+/// Executa uma execução, registando erros e pânicos
+fn execute<C, F>(index: usize, job: &SweepJob<C>, dir: &Path, run: &F) -> SweepRecord
+where
+    F: Fn(&SweepRun<C>) -> io::Result<Metrics>,
+{
+    let context = SweepRun {
+        index,
+        name: &job.name,
+        config: &job.config,
+        seed: job.seed,
+        output_dir: dir.to_path_buf(),
+    };
+    let start = Instant::now();
+    let outcome = match panic::catch_unwind(AssertUnwindSafe(|| run(&context))) {
+        Ok(Ok(metrics)) => Ok(metrics),
+        Ok(Err(error)) => Err(error.to_string()),
+        Err(payload) => Err(payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "pânico sem mensagem".to_string())),
+    };
+
+    SweepRecord {
+        index,
+        name: job.name.clone(),
+        seed: job.seed,
+        output_dir: dir.to_path_buf(),
+        duration: start.elapsed(),
+        outcome,
+    }
+}
+
+/// Nome utilizável como diretório (letras, dígitos, `-`, `_` e `.`)
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphanumeric() || "-_.".contains(c) { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{ConnectivityType, Network};
+
+    #[test]
+    fn test_runs_in_parallel_with_isolated_dirs() {
+        let root = std::env::temp_dir().join(format!("nenv_sweep_{}", std::process::id()));
+        let mut jobs = SweepJob::replicate("grade 3x3", 0.5, 0..6);
+        jobs.push(SweepJob::new("erro", 0.5, 99));
+        jobs.push(SweepJob::new("pânico", 0.5, 100));
+
+        let results = SweepRunner::new(&root)
+            .with_threads(3)
+            .run(jobs, |run| {
+                match run.name {
+                    "erro" => return Err(io::Error::other("falhou")),
+                    "pânico" => panic!("rebentou"),
+                    _ => {}
+                }
+                let mut network =
+                    Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, *run.config, run.seed);
+                for _ in 0..20 {
+                    network.update(&[1.0; 9]);
+                }
+                fs::write(run.output_dir.join("seed.txt"), run.seed.to_string())?;
+                Ok(Metrics::from([
+                    ("seed".to_string(), run.seed as f64),
+                    ("cost".to_string(), network.metabolic_cost()),
+                ]))
+            })
+            .unwrap();
+
+        assert_eq!(results.records.len(), 8);
+        assert!(results.records.iter().enumerate().all(|(i, r)| r.index == i));
+        assert_eq!(results.successes().count(), 6);
+        assert_eq!(results.metric_names(), vec!["cost", "seed"]);
+        assert_eq!(results.mean_std("seed").unwrap().0, 2.5);
+        let failures: Vec<_> = results.failures().map(|r| r.outcome.clone()).collect();
+        assert_eq!(failures, vec![Err("falhou".to_string()), Err("rebentou".to_string())]);
+
+        for (record, _) in results.successes() {
+            let dir = format!("{:03}_grade_3x3_s{}", record.index, record.seed);
+            assert!(record.output_dir.ends_with(dir));
+            let written = fs::read_to_string(record.output_dir.join("seed.txt")).unwrap();
+            assert_eq!(written, record.seed.to_string());
+        }
+        let summary = fs::read_to_string(root.join("summary.csv")).unwrap();
+        assert_eq!(summary.lines().count(), 9);
+        assert!(summary.starts_with("run,name,seed,seconds,cost,seed,error"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,295 @@
+//! Módulo de exportação do estado completo da rede como tensor binário
+//!
+//! As sondas do `Recorder` registam alvos escolhidos à partida. Para
+//! pipelines de análise offline que precisam de tudo, `StateTensorWriter`
+//! grava a cada amostragem o estado de todos os neurónios, formando um
+//! tensor `T × N × F` (tempos × neurónios × campos de `STATE_FEATURES`):
+//!
+//! ```text
+//! "NENVTNS1" · T (u64) · N (u32) · F (u32) · [nome (u32 + UTF-8)]... · frames...
+//! frame      tempo (i64) · N·F valores f64 (neurónio a neurónio)
+//! ```
+//!
+//! Tudo em little-endian. `T` é escrito em `finish`; até lá vale 0 e o
+//! leitor conta os frames completos pelo tamanho do ficheiro. Em NumPy, os
+//! frames leem-se com `np.fromfile(f, dtype=[("time", "<i8"), ("state",
+//! "<f8", (N, F))], offset=cabeçalho)`.
+//!
+//! O writer é ativado como modo do `Recorder` (`with_state_tensor`) ou
+//! usado diretamente com `record` após cada `Network::update`.
+
+use crate::chunked::{invalid_data, read_u32};
+use crate::network::Network;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+const TENSOR_MAGIC: &[u8; 8] = b"NENVTNS1";
+
+/// Posição do número de frames no cabeçalho
+const FRAMES_OFFSET: u64 = 8;
+
+/// Campos gravados por neurónio, pela ordem do último eixo do tensor
+pub const STATE_FEATURES: [&str; 5] = ["firing", "energy", "priority", "novelty", "potential"];
+
+/// Destino do estado completo da rede, frame a frame
+pub struct StateTensorWriter<W: Write + Seek = BufWriter<File>> {
+    writer: W,
+    num_neurons: Option<usize>,
+    frames: u64,
+    /// Frame atual serializado
+    buffer: Vec<u8>,
+}
+
+impl StateTensorWriter<BufWriter<File>> {
+    /// Cria (ou substitui) um ficheiro de tensor com escrita em buffer
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write + Seek> StateTensorWriter<W> {
+    /// Cria um writer sobre qualquer destino com `Seek`
+    ///
+    /// O cabeçalho é escrito no primeiro frame, quando `N` é conhecido.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            num_neurons: None,
+            frames: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Frames gravados até agora
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Grava o estado de todos os neurónios no passo atual
+    ///
+    /// # Retorna
+    /// Erro `InvalidInput` se o número de neurónios mudar durante a gravação
+    pub fn record(&mut self, network: &Network) -> io::Result<()> {
+        let n = network.num_neurons();
+        match self.num_neurons {
+            None => {
+                self.write_header(n)?;
+                self.num_neurons = Some(n);
+            }
+            Some(expected) if expected != n => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("rede com {} neurónios num tensor de {}", n, expected),
+                ));
+            }
+            Some(_) => {}
+        }
+
+        let potentials = network.modulated_potentials();
+        self.buffer.clear();
+        self.buffer
+            .extend_from_slice(&network.current_time_step.to_le_bytes());
+        for (i, neuron) in network.neurons.iter().enumerate() {
+            let state = [
+                if neuron.is_firing { 1.0 } else { 0.0 },
+                neuron.glia.energy,
+                neuron.glia.priority,
+                neuron.novelty,
+                potentials.get(i).copied().unwrap_or(0.0),
+            ];
+            for value in state {
+                self.buffer.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        self.writer.write_all(&self.buffer)?;
+        self.frames += 1;
+        Ok(())
+    }
+
+    fn write_header(&mut self, num_neurons: usize) -> io::Result<()> {
+        let num_neurons = u32::try_from(num_neurons).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} neurónios não cabem no cabeçalho do tensor", num_neurons),
+            )
+        })?;
+        let mut header = TENSOR_MAGIC.to_vec();
+        header.extend_from_slice(&0u64.to_le_bytes());
+        header.extend_from_slice(&num_neurons.to_le_bytes());
+        header.extend_from_slice(&(STATE_FEATURES.len() as u32).to_le_bytes());
+        for name in STATE_FEATURES {
+            header.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            header.extend_from_slice(name.as_bytes());
+        }
+        self.writer.write_all(&header)
+    }
+
+    /// Escreve o número de frames no cabeçalho e faz flush
+    pub fn finish(&mut self) -> io::Result<()> {
+        if self.num_neurons.is_some() {
+            self.writer.seek(SeekFrom::Start(FRAMES_OFFSET))?;
+            self.writer.write_all(&self.frames.to_le_bytes())?;
+            self.writer.seek(SeekFrom::End(0))?;
+        }
+        self.writer.flush()
+    }
+
+    /// Consome o writer e devolve o destino subjacente
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Tensor de estado lido de um ficheiro de `StateTensorWriter`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateTensor {
+    /// Passo de tempo de cada frame
+    pub times: Vec<i64>,
+
+    /// Número de neurónios (`N`)
+    pub num_neurons: usize,
+
+    /// Nomes dos campos (`F`)
+    pub features: Vec<String>,
+
+    /// Valores em ordem `[frame][neurónio][campo]`
+    pub data: Vec<f64>,
+}
+
+impl StateTensor {
+    /// Lê um tensor completo para a memória
+    pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Lê um tensor de qualquer fonte
+    ///
+    /// Frames incompletos no fim (escrita interrompida) são ignorados.
+    pub fn from_reader<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != TENSOR_MAGIC {
+            return Err(invalid_data("não é um tensor de estado NEN-V".to_string()));
+        }
+        let mut frames = [0u8; 8];
+        reader.read_exact(&mut frames)?;
+        let num_neurons = read_u32(&mut reader)? as usize;
+        let count = read_u32(&mut reader)? as usize;
+        let mut features = Vec::with_capacity(count);
+        for _ in 0..count {
+            let len = read_u32(&mut reader)? as usize;
+            let mut name = vec![0u8; len];
+            reader.read_exact(&mut name)?;
+            features.push(String::from_utf8(name).map_err(|e| invalid_data(e.to_string()))?);
+        }
+
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        let values_per_frame = num_neurons * count;
+        let mut times = Vec::new();
+        let mut data = Vec::new();
+        for frame in bytes.chunks_exact(8 * (1 + values_per_frame)) {
+            times.push(i64::from_le_bytes(frame[..8].try_into().unwrap()));
+            data.extend(
+                frame[8..]
+                    .chunks_exact(8)
+                    .map(|value| f64::from_le_bytes(value.try_into().unwrap())),
+            );
+        }
+
+        Ok(Self {
+            times,
+            num_neurons,
+            features,
+            data,
+        })
+    }
+
+    /// Número de frames (`T`)
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    /// Verifica se o tensor não tem frames
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+
+    /// Dimensões `(T, N, F)`
+    pub fn shape(&self) -> (usize, usize, usize) {
+        (self.len(), self.num_neurons, self.features.len())
+    }
+
+    /// Valor do campo `feature` do neurónio `neuron` no frame `frame`
+    pub fn get(&self, frame: usize, neuron: usize, feature: usize) -> Option<f64> {
+        let (t, n, f) = self.shape();
+        if frame >= t || neuron >= n || feature >= f {
+            return None;
+        }
+        self.data.get((frame * n + neuron) * f + feature).copied()
+    }
+
+    /// Série temporal de um campo (pelo nome) de um neurónio
+    pub fn series(&self, neuron: usize, feature: &str) -> Option<Vec<f64>> {
+        let feature = self.features.iter().position(|f| f == feature)?;
+        (0..self.len())
+            .map(|frame| self.get(frame, neuron, feature))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ConnectivityType;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_state_tensor_round_trip() {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.0, 0.2, 3);
+        let mut writer = StateTensorWriter::new(Cursor::new(Vec::new()));
+        let mut potentials = Vec::new();
+        for t in 0..6 {
+            let mut inputs = vec![0.0; 9];
+            inputs[4] = if t % 2 == 0 { 2.0 } else { 0.0 };
+            network.update(&inputs);
+            potentials.push(network.modulated_potentials()[4]);
+            writer.record(&network).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let bytes = writer.into_inner().into_inner();
+        assert_eq!(u64::from_le_bytes(bytes[8..16].try_into().unwrap()), 6);
+
+        let tensor = StateTensor::from_reader(bytes.as_slice()).unwrap();
+        assert_eq!(tensor.shape(), (6, 9, STATE_FEATURES.len()));
+        assert_eq!(tensor.times, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(tensor.series(4, "potential").unwrap(), potentials);
+        assert_eq!(tensor.get(5, 4, 1), Some(network.neurons[4].glia.energy));
+        assert!(tensor.series(4, "firing").unwrap().contains(&1.0));
+        assert_eq!(tensor.get(6, 0, 0), None);
+
+        // Um frame truncado no fim é descartado
+        let truncated = StateTensor::from_reader(&bytes[..bytes.len() - 3]).unwrap();
+        assert_eq!(truncated.len(), 5);
+    }
+
+    #[test]
+    fn test_state_tensor_rejects_resized_network() {
+        let network = |n| Network::new_seeded(n, ConnectivityType::FullyConnected, 0.0, 0.2, 1);
+        let mut writer = StateTensorWriter::new(Cursor::new(Vec::new()));
+        writer.record(&network(4)).unwrap();
+        let error = writer.record(&network(5)).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(StateTensor::from_reader(&b"NENVREC1"[..]).is_err());
+
+        #[cfg(target_pointer_width = "64")]
+        {
+            let mut writer = StateTensorWriter::new(Cursor::new(Vec::new()));
+            let error = writer.write_header(u32::MAX as usize + 1).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+            assert!(writer.into_inner().into_inner().is_empty());
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Módulo de novidade hierárquica por regiões
+//!
+//! A grade é dividida em blocos (tiles) de `tile_size × tile_size`
+//! neurónios. Cada bloco mantém a sua própria memória contextual do padrão
+//! de atividade dos seus membros (input externo mais sinal de saída) e
+//! calcula uma novidade agregada. Uma mudança distribuída (p.ex. a mesma
+//! quantidade de input deslocada dentro de uma região) pode passar abaixo
+//! do limiar de cada neurónio e ainda assim ser nova ao nível do bloco.
+//!
+//! A novidade máxima dos blocos reforça o alert_level quando excede
+//! `alert_threshold`. Em redes sem grade, os blocos são segmentos de
+//! `tile_size` índices consecutivos.
+
+use crate::footprint::{matrix_bytes, vec_bytes};
+use crate::prelude::*;
+
+/// Parâmetros da camada de novidade por blocos
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileParams {
+    /// Lado de cada bloco em neurónios (mínimo 1)
+    pub tile_size: usize,
+
+    /// Taxa de atualização da memória de cada bloco [0, 1]
+    pub memory_alpha: f64,
+
+    /// Novidade de bloco acima da qual o alert_level é reforçado
+    pub alert_threshold: f64,
+
+    /// Multiplicador do reforço de alerta (boost = novelty * sensitivity)
+    pub alert_sensitivity: f64,
+}
+
+impl Default for TileParams {
+    fn default() -> Self {
+        Self {
+            tile_size: 3,
+            memory_alpha: 0.1,
+            alert_threshold: 0.5,
+            alert_sensitivity: 0.3,
+        }
+    }
+}
+
+/// Estado da camada de novidade por blocos
+#[derive(Debug, Clone)]
+pub(crate) struct TileNovelty {
+    pub(crate) params: TileParams,
+    /// Membros de cada bloco
+    pub(crate) members: Vec<Vec<usize>>,
+    memories: Vec<Vec<f64>>,
+    pub(crate) novelty: Vec<f64>,
+}
+
+impl TileNovelty {
+    /// Divide `num_neurons` neurónios numa grade de largura `width` (0 = sem grade)
+    pub(crate) fn new(num_neurons: usize, width: usize, params: TileParams) -> Self {
+        let size = params.tile_size.max(1);
+        let (cols, rows) = if width > 0 {
+            (width.div_ceil(size), num_neurons.div_ceil(width).div_ceil(size))
+        } else {
+            (num_neurons.div_ceil(size), 1)
+        };
+
+        let mut members = vec![Vec::new(); cols * rows];
+        for i in 0..num_neurons {
+            let tile = match i.checked_div(width) {
+                Some(row) => (row / size) * cols + (i % width) / size,
+                None => i / size,
+            };
+            members[tile].push(i);
+        }
+        members.retain(|m| !m.is_empty());
+
+        let memories = members.iter().map(|m| vec![0.0; m.len()]).collect();
+        let novelty = vec![0.0; members.len()];
+        Self {
+            params: TileParams {
+                tile_size: size,
+                ..params
+            },
+            members,
+            memories,
+            novelty,
+        }
+    }
+
+    /// Bytes reservados pelos vetores (ver `footprint`)
+    pub(crate) fn heap_bytes(&self) -> usize {
+        matrix_bytes(&self.members) + matrix_bytes(&self.memories) + vec_bytes(&self.novelty)
+    }
+
+    /// Atualiza a novidade de cada bloco com a atividade `activity` (um valor por neurónio)
+    ///
+    /// # Retorna
+    /// A maior novidade de bloco do passo
+    pub(crate) fn update(&mut self, activity: &[f64]) -> f64 {
+        let alpha = self.params.memory_alpha.clamp(0.0, 1.0);
+        let mut max_novelty: f64 = 0.0;
+        for ((members, memory), novelty) in self
+            .members
+            .iter()
+            .zip(self.memories.iter_mut())
+            .zip(self.novelty.iter_mut())
+        {
+            let mut total = 0.0;
+            for (&i, trace) in members.iter().zip(memory.iter_mut()) {
+                let value = activity.get(i).copied().unwrap_or(0.0);
+                total += (value - *trace).abs();
+                *trace = (1.0 - alpha) * *trace + alpha * value;
+            }
+            *novelty = total / members.len() as f64;
+            max_novelty = max_novelty.max(*novelty);
+        }
+        max_novelty
+    }
+
+    /// Limpa memórias e novidades
+    pub(crate) fn reset(&mut self) {
+        for memory in &mut self.memories {
+            memory.fill(0.0);
+        }
+        self.novelty.fill(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_and_novelty() {
+        let params = TileParams {
+            tile_size: 2,
+            memory_alpha: 0.5,
+            ..TileParams::default()
+        };
+        // Grade 3×3 em blocos 2×2: [0,1,3,4], [2,5], [6,7], [8]
+        let mut tiles = TileNovelty::new(9, 3, params);
+        assert_eq!(tiles.members, vec![vec![0, 1, 3, 4], vec![2, 5], vec![6, 7], vec![8]]);
+
+        let mut activity = vec![0.0; 9];
+        activity[0] = 4.0;
+        assert_eq!(tiles.update(&activity), 1.0);
+        assert_eq!(tiles.novelty, vec![1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(tiles.update(&activity), 0.5);
+
+        let line = TileNovelty::new(5, 0, params);
+        assert_eq!(line.members, vec![vec![0, 1], vec![2, 3], vec![4]]);
+    }
+}
@@ -0,0 +1,79 @@
+//! Módulo de conversão entre tempo físico e passos de simulação
+//!
+//! `NetworkConfig::dt` é a duração de um passo em milissegundos. Os
+//! parâmetros temporais da configuração são definidos por milissegundo e
+//! convertidos para o passo conforme o tipo de dinâmica:
+//!
+//! - frações perdidas por ms (decaimentos, médias móveis): decaimento
+//!   exponencial equivalente, `1 - (1 - r)^dt`
+//! - quantidades por ms (recuperação e manutenção de energia): `a · dt`
+//! - durações e constantes de tempo em ms: divididas por `dt`
+//!
+//! Os setters da `Network` que mudam estes parâmetros depois da criação
+//! (`set_all_refractory_periods`, `set_all_memory_alpha`,
+//! `schedule_memory_alpha`, `set_glia_params_for_type`) também recebem
+//! valores por ms. `NetworkConfig::validate` recusa `dt` não positivo ou
+//! não finito.
+//!
+//! Assim, 10 ms de decaimento do alert_level dão o mesmo resultado com
+//! `dt = 1.0` (10 passos) e com `dt = 0.25` (40 passos). Com `dt = 1.0`
+//! (o valor por omissão) as conversões devolvem os valores sem alteração e
+//! um passo equivale a um milissegundo.
+//!
+//! Grandezas por evento (custo de um disparo, taxa de aprendizado por
+//! disparo, sensibilidades do alerta) não dependem de `dt`.
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::F64Ext;
+
+/// Fração perdida por passo equivalente a `rate` por ms (`rate` em [0, 1])
+pub fn decay_per_step(rate: f64, dt: f64) -> f64 {
+    if dt == 1.0 {
+        return rate;
+    }
+    1.0 - (1.0 - rate.clamp(0.0, 1.0)).powf(dt)
+}
+
+/// Quantidade por passo equivalente a `amount` por ms
+pub fn amount_per_step(amount: f64, dt: f64) -> f64 {
+    if dt == 1.0 { amount } else { amount * dt }
+}
+
+/// Constante de tempo em passos equivalente a `tau` ms
+pub fn tau_in_steps(tau: f64, dt: f64) -> f64 {
+    if dt == 1.0 { tau } else { tau / dt }
+}
+
+/// Número inteiro de passos mais próximo de `duration` ms (pelo menos 1 se
+/// a duração for positiva)
+pub fn duration_in_steps(duration: i64, dt: f64) -> i64 {
+    if dt == 1.0 || duration <= 0 {
+        return duration;
+    }
+    ((duration as f64 / dt).round() as i64).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions_are_consistent_across_resolutions() {
+        for dt in [0.1, 0.5, 1.0, 2.0] {
+            // 10 ms de decaimento a 5%/ms, em passos de dt ms
+            let steps = (10.0 / dt) as usize;
+            let retained = (0..steps).fold(1.0, |x, _| x * (1.0 - decay_per_step(0.05, dt)));
+            assert!((retained - 0.95f64.powi(10)).abs() < 1e-12);
+
+            let total = steps as f64 * amount_per_step(2.0, dt);
+            assert!((total - 20.0).abs() < 1e-9);
+        }
+        assert_eq!(decay_per_step(0.05, 1.0), 0.05);
+        assert_eq!(decay_per_step(1.0, 0.5), 1.0);
+        assert_eq!(tau_in_steps(10.0, 0.5), 20.0);
+        assert_eq!(duration_in_steps(5, 0.5), 10);
+        assert_eq!(duration_in_steps(5, 2.0), 3);
+        assert_eq!(duration_in_steps(1, 10.0), 1);
+        assert_eq!(duration_in_steps(0, 0.5), 0);
+    }
+}
@@ -0,0 +1,174 @@
+//! Módulo de painel ao vivo com ratatui
+//!
+//! Versão do `dashboard::Dashboard` desenhada com widgets do ratatui: a grade
+//! como mapa de calor em caracteres coloridos e um gráfico móvel por série
+//! (disparos, energia, novidade e alert_level). Corre em ecrã alternativo e
+//! termina com `q` ou `Esc`.
+
+use crate::dashboard::{heat_char, Dashboard};
+use crate::network::Network;
+use ratatui::backend::Backend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Sparkline};
+use ratatui::{Frame, Terminal};
+use std::io;
+use std::time::Duration;
+
+/// Resolução vertical das séries móveis (valores escalados para `0..=SPARK_MAX`)
+const SPARK_MAX: u64 = 100;
+
+/// Painel ao vivo sobre um terminal ratatui
+pub struct TuiDashboard<B: Backend> {
+    terminal: Terminal<B>,
+    dashboard: Dashboard,
+}
+
+impl<B: Backend> TuiDashboard<B> {
+    /// Cria um painel sobre um terminal já inicializado
+    ///
+    /// # Argumentos
+    /// * `terminal` - Terminal de destino (por exemplo `ratatui::init()`)
+    /// * `dashboard` - Campo, escala e janela das séries a mostrar
+    pub fn new(terminal: Terminal<B>, dashboard: Dashboard) -> Self {
+        Self { terminal, dashboard }
+    }
+
+    /// Atualiza as séries e redesenha o painel
+    pub fn draw(&mut self, network: &Network) -> io::Result<()> {
+        self.dashboard.update(network);
+        let dashboard = &self.dashboard;
+        self.terminal.draw(|frame| render(frame, dashboard, network))?;
+        Ok(())
+    }
+
+    /// Terminal subjacente
+    pub fn terminal(&self) -> &Terminal<B> {
+        &self.terminal
+    }
+}
+
+/// Cor do mapa de calor para um valor na escala `[low, high]`
+fn heat_color(value: f64, low: f64, high: f64) -> Color {
+    let span = high - low;
+    let x = if span > 0.0 { ((value - low) / span).clamp(0.0, 1.0) } else { 0.0 };
+    // Azul escuro → amarelo
+    Color::Rgb((x * 255.0) as u8, (x * 200.0) as u8, ((1.0 - x) * 120.0) as u8)
+}
+
+/// Desenha a grade e as séries móveis numa frame
+fn render(frame: &mut Frame, dashboard: &Dashboard, network: &Network) {
+    let (values, width, (low, high)) = dashboard.heat_grid(network);
+    let grid_rows = values.len().div_ceil(width);
+
+    let mut constraints = vec![Constraint::Length(grid_rows as u16 + 2)];
+    constraints.extend(dashboard.series().map(|_| Constraint::Length(3)));
+    constraints.push(Constraint::Min(0));
+    let areas = Layout::vertical(constraints).split(frame.area());
+
+    let lines: Vec<Line> = values
+        .chunks(width)
+        .map(|row| {
+            Line::from(
+                row.iter()
+                    .map(|&v| {
+                        // Dois caracteres por célula para compensar a proporção do terminal
+                        let c = heat_char(v, low, high);
+                        Span::styled(
+                            format!("{}{}", c, c),
+                            Style::default().fg(heat_color(v, low, high)),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+    let title = format!(
+        " NEN-V  t = {}  |  {} [{:.2}, {:.2}]  (q para sair) ",
+        network.current_time_step,
+        dashboard.field().name(),
+        low,
+        high
+    );
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title)),
+        areas[0],
+    );
+
+    for ((label, values), &area) in dashboard.series().zip(&areas[1..]) {
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let span = max - min;
+        let data: Vec<u64> = values
+            .iter()
+            .map(|&v| if span > 0.0 { ((v - min) / span * SPARK_MAX as f64).round() as u64 } else { 0 })
+            .collect();
+        let last = values.back().copied().unwrap_or(0.0);
+
+        frame.render_widget(
+            Sparkline::default()
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(format!(" {} {:.3} ", label, last)),
+                )
+                .data(&data)
+                .max(SPARK_MAX)
+                .style(Style::default().fg(Color::Cyan)),
+            area,
+        );
+    }
+}
+
+/// Aguarda até `timeout` por uma tecla e indica se foi pedido para sair
+///
+/// Em modo raw o Ctrl-C não gera SIGINT, por isso também conta como saída.
+pub fn quit_requested(timeout: Duration) -> io::Result<bool> {
+    if !event::poll(timeout)? {
+        return Ok(false);
+    }
+    Ok(match event::read()? {
+        Event::Key(key) if key.kind == KeyEventKind::Press => {
+            matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+                || (key.code == KeyCode::Char('c')
+                    && key.modifiers.contains(event::KeyModifiers::CONTROL))
+        }
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ConnectivityType;
+    use crate::recorder::NeuronField;
+    use ratatui::backend::TestBackend;
+
+    #[test]
+    fn test_draws_grid_and_series_into_backend() {
+        let mut network = Network::new(9, ConnectivityType::Grid2D, 0.0, 0.5);
+        let terminal = Terminal::new(TestBackend::new(40, 20)).unwrap();
+        let mut tui = TuiDashboard::new(
+            terminal,
+            Dashboard::new(NeuronField::Energy, 10).with_range(0.0, 100.0),
+        );
+
+        for _ in 0..4 {
+            network.update(&[0.0; 9]);
+            tui.draw(&network).unwrap();
+        }
+
+        let buffer = tui.terminal().backend().buffer();
+        let text: Vec<String> = (0..buffer.area.height)
+            .map(|y| (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect();
+        assert!(text[0].contains("t = 4"), "{:?}", text);
+        // Grade 3x3: três linhas de 6 caracteres dentro da moldura
+        let cell = heat_char(network.neurons[0].glia.energy, 0.0, 100.0);
+        assert!(text[1].starts_with(&format!("│{}{}", cell, cell)), "{:?}", text);
+        assert!(text[5].contains("disparos"));
+        assert!(text[14].contains("alerta"));
+    }
+}
@@ -0,0 +1,169 @@
+//! Módulo de afinação de parâmetros por caixa-preta
+//!
+//! `Objective` abstrai qualquer avaliação "configuração → score" (maior é
+//! melhor); closures `Fn(&Genome) -> f64` implementam-no diretamente, e é
+//! também o que o algoritmo genético de `evolution` maximiza.
+//!
+//! `RandomSearch` sorteia genomas uniformemente dentro dos limites de
+//! `Genome::GENES` e avalia-os em paralelo. Cada avaliação fica registada
+//! num `SweepLog`, que pode ser exportado em CSV:
+//!
+//! ```text
+//! trial,initial_threshold,learning_rate,...,energy_recovery_rate,score
+//! 0,0.5,0.01,...,2,0.71
+//! ```
+
+use crate::evolution::{evaluate_parallel, Genome};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Função objetivo a maximizar
+pub trait Objective: Sync {
+    /// Score de um conjunto de parâmetros (NaN é tratado como o pior)
+    fn evaluate(&self, genome: &Genome) -> f64;
+}
+
+impl<F> Objective for F
+where
+    F: Fn(&Genome) -> f64 + Sync,
+{
+    fn evaluate(&self, genome: &Genome) -> f64 {
+        self(genome)
+    }
+}
+
+/// Uma avaliação registada
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trial {
+    pub genome: Genome,
+    pub score: f64,
+}
+
+/// Registo de todas as avaliações de uma procura, pela ordem de avaliação
+#[derive(Debug, Clone, Default)]
+pub struct SweepLog {
+    trials: Vec<Trial>,
+}
+
+impl SweepLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Regista uma avaliação
+    pub fn push(&mut self, genome: Genome, score: f64) {
+        self.trials.push(Trial { genome, score });
+    }
+
+    pub fn trials(&self) -> &[Trial] {
+        &self.trials
+    }
+
+    pub fn len(&self) -> usize {
+        self.trials.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trials.is_empty()
+    }
+
+    /// Avaliação com maior score (NaN nunca é escolhido)
+    pub fn best(&self) -> Option<&Trial> {
+        self.trials
+            .iter()
+            .filter(|t| !t.score.is_nan())
+            .max_by(|a, b| a.score.total_cmp(&b.score))
+    }
+
+    /// Escreve o registo em CSV (uma coluna por gene, mais o score)
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let names: Vec<&str> = Genome::GENES.iter().map(|&(name, _, _)| name).collect();
+        writeln!(writer, "trial,{},score", names.join(","))?;
+        for (i, trial) in self.trials.iter().enumerate() {
+            let genes: Vec<String> =
+                trial.genome.to_genes().iter().map(|g| g.to_string()).collect();
+            writeln!(writer, "{},{},{}", i, genes.join(","), trial.score)?;
+        }
+        writer.flush()
+    }
+
+    /// Guarda o registo em CSV num ficheiro
+    pub fn save_csv<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_csv(BufWriter::new(File::create(path)?))
+    }
+}
+
+/// Procura aleatória uniforme no espaço de `Genome::GENES`
+#[derive(Debug, Clone, Copy)]
+pub struct RandomSearch {
+    /// Número de genomas avaliados (o genoma base conta como o primeiro)
+    pub samples: usize,
+
+    /// Threads usados nas avaliações
+    pub threads: usize,
+
+    /// Semente do gerador
+    pub seed: u64,
+
+    /// Parâmetros fora dos genes e primeira amostra
+    pub base: Genome,
+}
+
+impl Default for RandomSearch {
+    fn default() -> Self {
+        Self {
+            samples: 50,
+            threads: 4,
+            seed: 42,
+            base: Genome::default(),
+        }
+    }
+}
+
+impl RandomSearch {
+    /// Avalia `samples` genomas e devolve o registo completo
+    pub fn run<O: Objective + ?Sized>(&self, objective: &O) -> SweepLog {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut candidates = vec![self.base];
+        candidates.extend((1..self.samples).map(|_| Genome::random(&self.base, &mut rng)));
+        candidates.truncate(self.samples);
+
+        let scores = evaluate_parallel(&candidates, objective, self.threads);
+        let mut log = SweepLog::new();
+        for (genome, score) in candidates.into_iter().zip(scores) {
+            log.push(genome, score);
+        }
+        log
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_search_logs_every_trial() {
+        let objective = |g: &Genome| -(g.config.alert_sensitivity - 0.8).powi(2);
+        let search = RandomSearch {
+            samples: 40,
+            threads: 3,
+            ..RandomSearch::default()
+        };
+        let log = search.run(&objective);
+
+        assert_eq!(log.len(), 40);
+        assert_eq!(log.trials()[0].genome, Genome::default());
+        let best = log.best().unwrap();
+        assert!(best.score > objective.evaluate(&Genome::default()));
+
+        let mut csv = Vec::new();
+        log.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 41);
+        assert!(csv.starts_with("trial,initial_threshold,learning_rate,"));
+        assert!(csv.lines().nth(1).unwrap().starts_with("0,0.5,0.01,"));
+    }
+}
@@ -1,147 +1,618 @@
-/// Módulo de visualização para experimentos NEN-V
-///
-/// Gera gráficos para análise de priority, alert_level e outras métricas.
+//! Módulo de visualização para experimentos NEN-V
+//!
+//! Gera gráficos para análise de priority, alert_level e outras métricas.
+//! Todas as funções `plot_*` escolhem o formato pela extensão do caminho:
+//! `.svg` produz gráficos vetoriais, qualquer outra extensão produz PNG.
+//!
+//! Dimensões, fontes, cores, resolução e idioma dos rótulos vêm de um
+//! `PlotConfig`, passado como último argumento; `PlotConfig::paper` e
+//! `PlotConfig::slides` cobrem os usos mais comuns.
+//!
+//! Disponível apenas com a feature `plots` (ativa por omissão).
 
+use crate::error::{check_len, NenvError};
+use crate::nenv::NeuronType;
+use crate::network::Network;
+use crate::plot_worker::PlotWorker;
+use crate::recorder::NeuronField;
+pub use crate::snapshot::SimulationSnapshot;
+use crate::spikes::SpikeTrain;
+use plotters::coord::Shift;
 use plotters::prelude::*;
+use std::collections::BTreeMap;
 use std::error::Error;
 
-/// Dados de um passo de simulação para visualização
-#[derive(Debug, Clone)]
-pub struct SimulationSnapshot {
-    pub time: i64,
-    pub target_firing: bool,
-    pub target_energy: f64,
-    pub target_priority: f64,
-    pub total_firing: usize,
-    pub avg_energy: f64,
-    pub alert_level: f64,
+/// Formato do arquivo de saída dos gráficos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Imagem bitmap (BitMapBackend)
+    #[default]
+    Png,
+    /// Gráfico vetorial (SVGBackend), indicado para publicações
+    Svg,
+}
+
+impl OutputFormat {
+    /// Deduz o formato pela extensão do caminho (`.svg` → SVG, resto → PNG)
+    pub fn from_path(path: &str) -> Self {
+        let is_svg = std::path::Path::new(path)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+        if is_svg { OutputFormat::Svg } else { OutputFormat::Png }
+    }
+
+    /// Extensão de arquivo (sem ponto)
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Png => "png",
+            OutputFormat::Svg => "svg",
+        }
+    }
+}
+
+/// Idioma dos rótulos dos gráficos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    Portuguese,
+    English,
+}
+
+impl Language {
+    /// Textos fixos dos gráficos neste idioma
+    pub fn labels(&self) -> &'static PlotLabels {
+        match self {
+            Language::Portuguese => &PT_LABELS,
+            Language::English => &EN_LABELS,
+        }
+    }
+}
+
+/// Textos fixos dos gráficos: descrições dos eixos, legendas e títulos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlotLabels {
+    pub time: &'static str,
+    pub value: &'static str,
+    pub priority: &'static str,
+    pub target_priority: &'static str,
+    pub alert_level: &'static str,
+    pub global_alert: &'static str,
+    pub energy: &'static str,
+    pub target_energy: &'static str,
+    pub mean_energy: &'static str,
+    pub firing_neurons: &'static str,
+    pub firing_rate: &'static str,
+    pub mean_novelty: &'static str,
+    pub neuron: &'static str,
+    pub excitatory: &'static str,
+    pub inhibitory: &'static str,
+    pub column: &'static str,
+    pub row: &'static str,
+    pub input: &'static str,
+    pub before: &'static str,
+    pub after: &'static str,
+    pub priority_alert_title: &'static str,
+    pub energy_title: &'static str,
+    pub activity_title: &'static str,
+    pub novelty_title: &'static str,
+    pub raster_title: &'static str,
+}
+
+const PT_LABELS: PlotLabels = PlotLabels {
+    time: "Tempo (passos)",
+    value: "Valor",
+    priority: "Priority",
+    target_priority: "Priority (neurônio alvo)",
+    alert_level: "Alert Level",
+    global_alert: "Alert Level (global)",
+    energy: "Energia",
+    target_energy: "Energia (neurônio alvo)",
+    mean_energy: "Energia (média da rede)",
+    firing_neurons: "Neurônios disparando",
+    firing_rate: "Taxa de disparo (fração da rede)",
+    mean_novelty: "Novidade (média da rede)",
+    neuron: "Neurônio",
+    excitatory: "Excitatório",
+    inhibitory: "Inibitório",
+    column: "Coluna",
+    row: "Linha",
+    input: "Entrada",
+    before: "Antes",
+    after: "Depois",
+    priority_alert_title: "Priority e Alert Level",
+    energy_title: "Energia",
+    activity_title: "Atividade da Rede",
+    novelty_title: "Novidade e Alert Level",
+    raster_title: "Raster de Disparos",
+};
+
+const EN_LABELS: PlotLabels = PlotLabels {
+    time: "Time (steps)",
+    value: "Value",
+    priority: "Priority",
+    target_priority: "Priority (target neuron)",
+    alert_level: "Alert Level",
+    global_alert: "Alert Level (global)",
+    energy: "Energy",
+    target_energy: "Energy (target neuron)",
+    mean_energy: "Energy (network mean)",
+    firing_neurons: "Firing neurons",
+    firing_rate: "Firing rate (fraction of network)",
+    mean_novelty: "Novelty (network mean)",
+    neuron: "Neuron",
+    excitatory: "Excitatory",
+    inhibitory: "Inhibitory",
+    column: "Column",
+    row: "Row",
+    input: "Input",
+    before: "Before",
+    after: "After",
+    priority_alert_title: "Priority and Alert Level",
+    energy_title: "Energy",
+    activity_title: "Network Activity",
+    novelty_title: "Novelty and Alert Level",
+    raster_title: "Spike Raster",
+};
+
+/// Cores das séries e do fundo
+///
+/// Os mapas de calor usam sempre a escala Viridis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlotPalette {
+    /// Priority, atividade e disparos excitatórios
+    pub primary: RGBColor,
+    /// Alert level e disparos inibitórios
+    pub secondary: RGBColor,
+    /// Energia média
+    pub tertiary: RGBColor,
+    /// Novidade
+    pub accent: RGBColor,
+    /// Texto, bordas e raster sem tipos
+    pub foreground: RGBColor,
+    pub background: RGBColor,
+}
+
+impl Default for PlotPalette {
+    fn default() -> Self {
+        Self {
+            primary: BLUE,
+            secondary: RED,
+            tertiary: GREEN,
+            accent: MAGENTA,
+            foreground: BLACK,
+            background: WHITE,
+        }
+    }
+}
+
+/// Aparência dos gráficos
+///
+/// `width`, `height`, tamanhos de fonte e `line_width` são dados a 96 DPI;
+/// um `dpi` maior multiplica todos estes valores (e portanto os pixels do
+/// arquivo) sem mudar as proporções da figura.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlotConfig {
+    pub width: u32,
+    pub height: u32,
+    pub dpi: u32,
+    pub font_family: String,
+    pub title_font_size: u32,
+    pub label_font_size: u32,
+    pub line_width: u32,
+    pub palette: PlotPalette,
+    pub language: Language,
+    /// Formato dos arquivos cujo nome é gerado (`generate_all_plots`, `HeatmapDumper`)
+    pub format: OutputFormat,
+}
+
+impl Default for PlotConfig {
+    fn default() -> Self {
+        Self {
+            width: 1024,
+            height: 768,
+            dpi: 96,
+            font_family: "sans-serif".to_string(),
+            title_font_size: 30,
+            label_font_size: 15,
+            line_width: 1,
+            palette: PlotPalette::default(),
+            language: Language::Portuguese,
+            format: OutputFormat::Png,
+        }
+    }
+}
+
+impl PlotConfig {
+    /// Figuras para artigos: compactas, serifadas, 300 DPI e rótulos em inglês
+    pub fn paper() -> Self {
+        Self {
+            width: 640,
+            height: 480,
+            dpi: 300,
+            font_family: "serif".to_string(),
+            title_font_size: 18,
+            label_font_size: 13,
+            language: Language::English,
+            ..Self::default()
+        }
+    }
+
+    /// Figuras para apresentações: 16:9, fontes grandes e linhas grossas
+    pub fn slides() -> Self {
+        Self {
+            width: 1280,
+            height: 720,
+            title_font_size: 40,
+            label_font_size: 22,
+            line_width: 3,
+            ..Self::default()
+        }
+    }
+
+    /// Textos fixos no idioma configurado
+    pub fn labels(&self) -> &'static PlotLabels {
+        self.language.labels()
+    }
+
+    /// Dimensões do arquivo em pixels, já escaladas pelo DPI
+    pub fn pixel_size(&self) -> (u32, u32) {
+        (self.px(self.width), self.px(self.height))
+    }
+
+    /// Dimensões das figuras com dois painéis lado a lado
+    fn wide_pixel_size(&self) -> (u32, u32) {
+        (self.px(self.width * 25 / 16), self.px(self.height))
+    }
+
+    /// Converte um comprimento a 96 DPI para pixels do arquivo
+    fn px(&self, length: u32) -> u32 {
+        (length as f64 * self.dpi.max(1) as f64 / 96.0).round() as u32
+    }
+
+    /// Altura da área dos rótulos do eixo x, proporcional à fonte
+    fn x_label_area(&self) -> u32 {
+        self.px(self.label_font_size * 8 / 3)
+    }
+
+    /// Largura da área dos rótulos dos eixos y, proporcional à fonte
+    fn y_label_area(&self) -> u32 {
+        self.px(self.label_font_size * 10 / 3)
+    }
+
+    fn title_style(&self) -> TextStyle<'_> {
+        self.text_style(self.title_font_size)
+    }
+
+    fn label_style(&self) -> TextStyle<'_> {
+        self.text_style(self.label_font_size)
+    }
+
+    fn text_style(&self, size: u32) -> TextStyle<'_> {
+        (self.font_family.as_str(), self.px(size) as f64)
+            .into_font()
+            .color(&self.palette.foreground)
+    }
+
+    fn line_style(&self, color: RGBColor) -> ShapeStyle {
+        color.stroke_width(self.px(self.line_width).max(1))
+    }
+}
+
+/// Erro das saídas bitmap (PNG, GIF) em wasm32, onde o plotters não escreve
+/// ficheiros; o SVG continua disponível
+#[cfg(target_arch = "wasm32")]
+fn bitmap_unavailable() -> Box<dyn Error> {
+    "saída PNG/GIF indisponível em wasm32; use um caminho .svg".into()
+}
+
+/// Desenha no backend correspondente ao formato deduzido de `output_path`
+///
+/// `$draw` é uma função genérica no backend que recebe a área de desenho
+/// como primeiro argumento, seguida dos argumentos indicados.
+macro_rules! render {
+    ($path:expr, $size:expr, $draw:ident($($arg:expr),* $(,)?)) => {{
+        let path: &str = $path;
+        match OutputFormat::from_path(path) {
+            #[cfg(not(target_arch = "wasm32"))]
+            OutputFormat::Png => {
+                let root = BitMapBackend::new(path, $size).into_drawing_area();
+                $draw(&root, $($arg),*)?;
+                root.present()?;
+            }
+            #[cfg(target_arch = "wasm32")]
+            OutputFormat::Png => return Err(bitmap_unavailable()),
+            OutputFormat::Svg => {
+                let root = SVGBackend::new(path, $size).into_drawing_area();
+                $draw(&root, $($arg),*)?;
+                root.present()?;
+            }
+        }
+        Ok(())
+    }};
 }
 
 /// Gera gráfico de priority e alert_level ao longo do tempo
 ///
-/// Cria um gráfico com duas séries temporais:
-/// - Priority do neurônio alvo (linha azul)
-/// - Alert level global da rede (linha vermelha)
+/// Cria um gráfico com duas séries temporais em eixos independentes:
+/// - Priority do neurônio alvo (cor primária, eixo esquerdo)
+/// - Alert level global da rede (cor secundária, eixo direito [0, 1])
 ///
 /// # Argumentos
 /// * `snapshots` - Vetor de snapshots da simulação
-/// * `output_path` - Caminho para salvar o gráfico PNG
+/// * `output_path` - Caminho para salvar o gráfico (.png ou .svg)
 /// * `title` - Título do gráfico
+/// * `config` - Aparência do gráfico
 pub fn plot_priority_and_alert(
     snapshots: &[SimulationSnapshot],
     output_path: &str,
     title: &str,
+    config: &PlotConfig,
 ) -> Result<(), Box<dyn Error>> {
-    let root = BitMapBackend::new(output_path, (1024, 768)).into_drawing_area();
-    root.fill(&WHITE)?;
+    render!(output_path, config.pixel_size(), draw_priority_and_alert(snapshots, title, config))
+}
 
-    let max_time = snapshots.last().map(|s| s.time).unwrap_or(100);
+fn draw_priority_and_alert<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    snapshots: &[SimulationSnapshot],
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let labels = config.labels();
     let max_priority = snapshots
         .iter()
         .map(|s| s.target_priority)
         .fold(1.0, f64::max);
 
-    let mut chart = ChartBuilder::on(&root)
-        .caption(title, ("sans-serif", 30).into_font())
-        .margin(10)
-        .x_label_area_size(40)
-        .y_label_area_size(50)
-        .build_cartesian_2d(0..max_time, 0.0..max_priority.max(1.5))?;
+    draw_dual_axis(
+        root,
+        title,
+        AxisSeries {
+            label: labels.target_priority,
+            axis_desc: labels.priority,
+            color: config.palette.primary,
+            points: snapshots.iter().map(|s| (s.time, s.target_priority)).collect(),
+            range: 0.0..max_priority.max(1.5),
+        },
+        AxisSeries {
+            label: labels.global_alert,
+            axis_desc: labels.alert_level,
+            color: config.palette.secondary,
+            points: snapshots.iter().map(|s| (s.time, s.alert_level)).collect(),
+            range: 0.0..1.0,
+        },
+        config,
+    )
+}
+
+/// Série temporal associada a um eixo y próprio
+struct AxisSeries<'a> {
+    label: &'a str,
+    axis_desc: &'a str,
+    color: RGBColor,
+    points: Vec<(i64, f64)>,
+    range: std::ops::Range<f64>,
+}
+
+/// Desenha duas séries com eixos y independentes (esquerdo e direito)
+fn draw_dual_axis<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    title: &str,
+    left: AxisSeries,
+    right: AxisSeries,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    root.fill(&config.palette.background)?;
+
+    let max_time = left
+        .points
+        .iter()
+        .chain(right.points.iter())
+        .map(|&(t, _)| t)
+        .max()
+        .unwrap_or(100);
+
+    let mut chart = ChartBuilder::on(root)
+        .caption(title, config.title_style())
+        .margin(config.px(10))
+        .x_label_area_size(config.x_label_area())
+        .y_label_area_size(config.y_label_area())
+        .right_y_label_area_size(config.y_label_area())
+        .build_cartesian_2d(0..max_time, left.range.clone())?
+        .set_secondary_coord(0..max_time, right.range.clone());
 
     chart
         .configure_mesh()
-        .x_desc("Tempo (passos)")
-        .y_desc("Valor")
+        .x_desc(config.labels().time)
+        .y_desc(left.axis_desc)
+        .label_style(config.label_style())
+        .axis_desc_style(config.label_style())
         .draw()?;
 
-    // Linha de priority (azul)
     chart
-        .draw_series(LineSeries::new(
-            snapshots
-                .iter()
-                .map(|s| (s.time, s.target_priority)),
-            &BLUE,
-        ))?
-        .label("Priority (neurônio alvo)")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLUE));
+        .configure_secondary_axes()
+        .y_desc(right.axis_desc)
+        .label_style(config.label_style())
+        .axis_desc_style(config.label_style())
+        .draw()?;
 
-    // Linha de alert_level (vermelho)
     chart
-        .draw_series(LineSeries::new(
-            snapshots.iter().map(|s| (s.time, s.alert_level)),
-            &RED,
-        ))?
-        .label("Alert Level (global)")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
+        .draw_series(LineSeries::new(left.points, config.line_style(left.color)))?
+        .label(left.label)
+        .legend(legend_line(config, left.color));
+
+    chart
+        .draw_secondary_series(LineSeries::new(right.points, config.line_style(right.color)))?
+        .label(right.label)
+        .legend(legend_line(config, right.color));
 
     chart
         .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .label_font(config.label_style())
+        .legend_area_size(config.px(30))
+        .background_style(config.palette.background.mix(0.8))
+        .border_style(config.palette.foreground)
         .draw()?;
 
-    root.present()?;
     Ok(())
 }
 
+/// Amostra de linha usada na legenda
+fn legend_line(
+    config: &PlotConfig,
+    color: RGBColor,
+) -> impl Fn((i32, i32)) -> PathElement<(i32, i32)> + 'static {
+    let style = config.line_style(color);
+    let length = config.px(20) as i32;
+    move |(x, y)| PathElement::new(vec![(x, y), (x + length, y)], style)
+}
+
+/// Desenha a caixa de legenda com as cores e a fonte da configuração
+fn draw_legend<'a, DB: DrawingBackend + 'a, CT: CoordTranslate>(
+    chart: &mut ChartContext<'a, DB, CT>,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    chart
+        .configure_series_labels()
+        .label_font(config.label_style())
+        .legend_area_size(config.px(30))
+        .background_style(config.palette.background.mix(0.8))
+        .border_style(config.palette.foreground)
+        .draw()?;
+    Ok(())
+}
+
+/// Gera gráfico de energia média e atividade com eixos independentes
+///
+/// Mostra:
+/// - Energia média da rede (cor terciária, eixo esquerdo)
+/// - Neurônios disparando (cor primária, eixo direito)
+///
+/// # Argumentos
+/// * `snapshots` - Vetor de snapshots da simulação
+/// * `output_path` - Caminho para salvar o gráfico (.png ou .svg)
+/// * `title` - Título do gráfico
+/// * `config` - Aparência do gráfico
+pub fn plot_energy_vs_firing(
+    snapshots: &[SimulationSnapshot],
+    output_path: &str,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>> {
+    render!(output_path, config.pixel_size(), draw_energy_vs_firing(snapshots, title, config))
+}
+
+fn draw_energy_vs_firing<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    snapshots: &[SimulationSnapshot],
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let labels = config.labels();
+    let max_firing = snapshots.iter().map(|s| s.total_firing).max().unwrap_or(0);
+
+    draw_dual_axis(
+        root,
+        title,
+        AxisSeries {
+            label: labels.mean_energy,
+            axis_desc: labels.energy,
+            color: config.palette.tertiary,
+            points: snapshots.iter().map(|s| (s.time, s.avg_energy)).collect(),
+            range: 0.0..100.0,
+        },
+        AxisSeries {
+            label: labels.firing_neurons,
+            axis_desc: labels.firing_neurons,
+            color: config.palette.primary,
+            points: snapshots.iter().map(|s| (s.time, s.total_firing as f64)).collect(),
+            range: 0.0..(max_firing as f64 + 1.0),
+        },
+        config,
+    )
+}
+
 /// Gera gráfico de energia ao longo do tempo
 ///
 /// Mostra:
-/// - Energia do neurônio alvo (linha azul)
-/// - Energia média da rede (linha verde)
+/// - Energia do neurônio alvo (cor primária)
+/// - Energia média da rede (cor terciária)
 ///
 /// # Argumentos
 /// * `snapshots` - Vetor de snapshots da simulação
-/// * `output_path` - Caminho para salvar o gráfico PNG
+/// * `output_path` - Caminho para salvar o gráfico (.png ou .svg)
 /// * `title` - Título do gráfico
+/// * `config` - Aparência do gráfico
 pub fn plot_energy(
     snapshots: &[SimulationSnapshot],
     output_path: &str,
     title: &str,
+    config: &PlotConfig,
 ) -> Result<(), Box<dyn Error>> {
-    let root = BitMapBackend::new(output_path, (1024, 768)).into_drawing_area();
-    root.fill(&WHITE)?;
+    render!(output_path, config.pixel_size(), draw_energy(snapshots, title, config))
+}
+
+fn draw_energy<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    snapshots: &[SimulationSnapshot],
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let labels = config.labels();
+    let palette = config.palette;
+    root.fill(&palette.background)?;
 
     let max_time = snapshots.last().map(|s| s.time).unwrap_or(100);
 
-    let mut chart = ChartBuilder::on(&root)
-        .caption(title, ("sans-serif", 30).into_font())
-        .margin(10)
-        .x_label_area_size(40)
-        .y_label_area_size(50)
+    let mut chart = ChartBuilder::on(root)
+        .caption(title, config.title_style())
+        .margin(config.px(10))
+        .x_label_area_size(config.x_label_area())
+        .y_label_area_size(config.y_label_area())
         .build_cartesian_2d(0..max_time, 0.0..100.0)?;
 
     chart
         .configure_mesh()
-        .x_desc("Tempo (passos)")
-        .y_desc("Energia")
+        .x_desc(labels.time)
+        .y_desc(labels.energy)
+        .label_style(config.label_style())
+        .axis_desc_style(config.label_style())
         .draw()?;
 
-    // Linha de energia do alvo (azul)
+    // Linha de energia do alvo
     chart
         .draw_series(LineSeries::new(
             snapshots.iter().map(|s| (s.time, s.target_energy)),
-            &BLUE,
+            config.line_style(palette.primary),
         ))?
-        .label("Energia (neurônio alvo)")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLUE));
+        .label(labels.target_energy)
+        .legend(legend_line(config, palette.primary));
 
-    // Linha de energia média (verde)
+    // Linha de energia média
     chart
         .draw_series(LineSeries::new(
             snapshots.iter().map(|s| (s.time, s.avg_energy)),
-            &GREEN,
+            config.line_style(palette.tertiary),
         ))?
-        .label("Energia (média da rede)")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &GREEN));
+        .label(labels.mean_energy)
+        .legend(legend_line(config, palette.tertiary));
 
-    chart
-        .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
-        .draw()?;
+    draw_legend(&mut chart, config)?;
 
-    root.present()?;
     Ok(())
 }
 
@@ -149,30 +620,47 @@ pub fn plot_energy(
 ///
 /// # Argumentos
 /// * `snapshots` - Vetor de snapshots da simulação
-/// * `output_path` - Caminho para salvar o gráfico PNG
+/// * `output_path` - Caminho para salvar o gráfico (.png ou .svg)
 /// * `title` - Título do gráfico
+/// * `config` - Aparência do gráfico
 pub fn plot_firing_activity(
     snapshots: &[SimulationSnapshot],
     output_path: &str,
     title: &str,
+    config: &PlotConfig,
 ) -> Result<(), Box<dyn Error>> {
-    let root = BitMapBackend::new(output_path, (1024, 768)).into_drawing_area();
-    root.fill(&WHITE)?;
+    render!(output_path, config.pixel_size(), draw_firing_activity(snapshots, title, config))
+}
+
+fn draw_firing_activity<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    snapshots: &[SimulationSnapshot],
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let labels = config.labels();
+    let color = config.palette.primary;
+    root.fill(&config.palette.background)?;
 
     let max_time = snapshots.last().map(|s| s.time).unwrap_or(100);
     let max_firing = snapshots.iter().map(|s| s.total_firing).max().unwrap_or(100);
 
-    let mut chart = ChartBuilder::on(&root)
-        .caption(title, ("sans-serif", 30).into_font())
-        .margin(10)
-        .x_label_area_size(40)
-        .y_label_area_size(50)
+    let mut chart = ChartBuilder::on(root)
+        .caption(title, config.title_style())
+        .margin(config.px(10))
+        .x_label_area_size(config.x_label_area())
+        .y_label_area_size(config.y_label_area())
         .build_cartesian_2d(0..max_time, 0..(max_firing + 10))?;
 
     chart
         .configure_mesh()
-        .x_desc("Tempo (passos)")
-        .y_desc("Neurônios disparando")
+        .x_desc(labels.time)
+        .y_desc(labels.firing_neurons)
+        .label_style(config.label_style())
+        .axis_desc_style(config.label_style())
         .draw()?;
 
     // Área de atividade
@@ -181,58 +669,1392 @@ pub fn plot_firing_activity(
             .iter()
             .map(|s| (s.time, s.total_firing)),
         0,
-        &BLUE.mix(0.3),
+        color.mix(0.3),
     ))?;
 
     // Linha de atividade
     chart
         .draw_series(LineSeries::new(
             snapshots.iter().map(|s| (s.time, s.total_firing)),
-            &BLUE,
+            config.line_style(color),
+        ))?
+        .label(labels.firing_neurons);
+
+    draw_legend(&mut chart, config)?;
+
+    Ok(())
+}
+
+/// Gera gráfico de novidade média e alert_level ao longo do tempo
+///
+/// Mostra:
+/// - Novidade média da rede (cor de destaque)
+/// - Alert level global da rede (cor secundária)
+///
+/// # Argumentos
+/// * `snapshots` - Vetor de snapshots da simulação
+/// * `output_path` - Caminho para salvar o gráfico (.png ou .svg)
+/// * `title` - Título do gráfico
+/// * `config` - Aparência do gráfico
+pub fn plot_novelty(
+    snapshots: &[SimulationSnapshot],
+    output_path: &str,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>> {
+    render!(output_path, config.pixel_size(), draw_novelty(snapshots, title, config))
+}
+
+fn draw_novelty<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    snapshots: &[SimulationSnapshot],
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let labels = config.labels();
+    let palette = config.palette;
+    root.fill(&palette.background)?;
+
+    let max_time = snapshots.last().map(|s| s.time).unwrap_or(100);
+    let max_novelty = snapshots
+        .iter()
+        .map(|s| s.avg_novelty)
+        .fold(1.0, f64::max);
+
+    let mut chart = ChartBuilder::on(root)
+        .caption(title, config.title_style())
+        .margin(config.px(10))
+        .x_label_area_size(config.x_label_area())
+        .y_label_area_size(config.y_label_area())
+        .build_cartesian_2d(0..max_time, 0.0..max_novelty * 1.1)?;
+
+    chart
+        .configure_mesh()
+        .x_desc(labels.time)
+        .y_desc(labels.value)
+        .label_style(config.label_style())
+        .axis_desc_style(config.label_style())
+        .draw()?;
+
+    // Linha de novidade média
+    chart
+        .draw_series(LineSeries::new(
+            snapshots.iter().map(|s| (s.time, s.avg_novelty)),
+            config.line_style(palette.accent),
         ))?
-        .label("Neurônios disparando");
+        .label(labels.mean_novelty)
+        .legend(legend_line(config, palette.accent));
 
+    // Linha de alert_level
     chart
-        .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .draw_series(LineSeries::new(
+            snapshots.iter().map(|s| (s.time, s.alert_level)),
+            config.line_style(palette.secondary),
+        ))?
+        .label(labels.global_alert)
+        .legend(legend_line(config, palette.secondary));
+
+    draw_legend(&mut chart, config)?;
+
+    Ok(())
+}
+
+/// Grandeza de um `SimulationSnapshot` a comparar entre execuções
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotField {
+    TargetEnergy,
+    TargetPriority,
+    TotalFiring,
+    AverageEnergy,
+    AlertLevel,
+    AverageNovelty,
+}
+
+impl SnapshotField {
+    /// Lê o valor da grandeza num snapshot
+    pub fn read(&self, snapshot: &SimulationSnapshot) -> f64 {
+        match self {
+            SnapshotField::TargetEnergy => snapshot.target_energy,
+            SnapshotField::TargetPriority => snapshot.target_priority,
+            SnapshotField::TotalFiring => snapshot.total_firing as f64,
+            SnapshotField::AverageEnergy => snapshot.avg_energy,
+            SnapshotField::AlertLevel => snapshot.alert_level,
+            SnapshotField::AverageNovelty => snapshot.avg_novelty,
+        }
+    }
+
+    /// Descrição da grandeza no idioma dos rótulos
+    pub fn label(&self, labels: &PlotLabels) -> &'static str {
+        match self {
+            SnapshotField::TargetEnergy => labels.target_energy,
+            SnapshotField::TargetPriority => labels.target_priority,
+            SnapshotField::TotalFiring => labels.firing_neurons,
+            SnapshotField::AverageEnergy => labels.mean_energy,
+            SnapshotField::AlertLevel => labels.global_alert,
+            SnapshotField::AverageNovelty => labels.mean_novelty,
+        }
+    }
+}
+
+/// Condição experimental: uma ou mais execuções com a mesma legenda
+///
+/// Com várias execuções (por exemplo, sementes diferentes), a condição é
+/// desenhada como a média passo a passo e, opcionalmente, a faixa de ±1
+/// desvio padrão.
+#[derive(Debug, Clone)]
+pub struct RunGroup<'a> {
+    pub label: &'a str,
+    pub runs: Vec<&'a [SimulationSnapshot]>,
+}
+
+impl<'a> RunGroup<'a> {
+    /// Cria uma condição com várias execuções
+    pub fn new(label: &'a str, runs: Vec<&'a [SimulationSnapshot]>) -> Self {
+        Self { label, runs }
+    }
+
+    /// Cria uma condição com uma única execução
+    pub fn single(label: &'a str, snapshots: &'a [SimulationSnapshot]) -> Self {
+        Self::new(label, vec![snapshots])
+    }
+
+    /// Média e desvio padrão da grandeza por passo, sobre as execuções
+    ///
+    /// Os snapshots são alinhados pelo campo `time`; passos presentes em
+    /// apenas algumas execuções usam só essas execuções.
+    ///
+    /// # Retorna
+    /// Triplos `(tempo, média, desvio padrão)` em ordem cronológica
+    pub fn statistics(&self, field: SnapshotField) -> Vec<(i64, f64, f64)> {
+        let mut by_time: BTreeMap<i64, Vec<f64>> = BTreeMap::new();
+        for snapshot in self.runs.iter().flat_map(|run| run.iter()) {
+            by_time.entry(snapshot.time).or_default().push(field.read(snapshot));
+        }
+
+        by_time
+            .into_iter()
+            .map(|(time, values)| {
+                let n = values.len() as f64;
+                let mean = values.iter().sum::<f64>() / n;
+                let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+                (time, mean, variance.sqrt())
+            })
+            .collect()
+    }
+}
+
+/// Sobrepõe várias condições ou execuções de uma mesma grandeza
+///
+/// Cada condição tem uma linha (a média das suas execuções) e uma entrada
+/// na legenda; as cores seguem a paleta da configuração e, para mais de
+/// quatro condições, a `Palette99`.
+///
+/// # Argumentos
+/// * `groups` - Condições a comparar (ex: alert ligado × desligado)
+/// * `field` - Grandeza dos snapshots a desenhar
+/// * `show_band` - Desenha a faixa de ±1 desvio padrão entre execuções
+/// * `output_path` - Caminho para salvar o gráfico (.png ou .svg)
+/// * `title` - Título do gráfico
+/// * `config` - Aparência do gráfico
+///
+/// # Retorna
+/// Erro `EmptyInput` se não houver nenhuma condição
+pub fn plot_run_comparison(
+    groups: &[RunGroup],
+    field: SnapshotField,
+    show_band: bool,
+    output_path: &str,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>> {
+    if groups.is_empty() {
+        return Err(NenvError::EmptyInput { what: "groups" }.into());
+    }
+    render!(
+        output_path,
+        config.pixel_size(),
+        draw_run_comparison(groups, field, show_band, title, config)
+    )
+}
+
+fn draw_run_comparison<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    groups: &[RunGroup],
+    field: SnapshotField,
+    show_band: bool,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let labels = config.labels();
+    let palette = config.palette;
+    root.fill(&palette.background)?;
+
+    let stats: Vec<Vec<(i64, f64, f64)>> = groups.iter().map(|g| g.statistics(field)).collect();
+
+    let times = stats.iter().flatten().map(|&(t, _, _)| t);
+    let min_time = times.clone().min().unwrap_or(0);
+    let max_time = times.max().unwrap_or(100).max(min_time + 1);
+
+    let spread = |&(_, mean, std): &(i64, f64, f64)| {
+        if show_band { (mean - std, mean + std) } else { (mean, mean) }
+    };
+    let low = stats.iter().flatten().map(|s| spread(s).0).fold(f64::INFINITY, f64::min);
+    let high = stats.iter().flatten().map(|s| spread(s).1).fold(f64::NEG_INFINITY, f64::max);
+    let (low, high) = value_range(&[low, high]);
+    let margin = (high - low) * 0.05;
+
+    let mut chart = ChartBuilder::on(root)
+        .caption(title, config.title_style())
+        .margin(config.px(10))
+        .x_label_area_size(config.x_label_area())
+        .y_label_area_size(config.y_label_area())
+        .build_cartesian_2d(min_time..max_time, (low - margin)..(high + margin))?;
+
+    chart
+        .configure_mesh()
+        .x_desc(labels.time)
+        .y_desc(field.label(labels))
+        .label_style(config.label_style())
+        .axis_desc_style(config.label_style())
+        .draw()?;
+
+    let base_colors = [palette.primary, palette.secondary, palette.tertiary, palette.accent];
+    for (i, (group, points)) in groups.iter().zip(&stats).enumerate() {
+        let color = base_colors.get(i).copied().unwrap_or_else(|| {
+            let (r, g, b) = Palette99::pick(i).rgb();
+            RGBColor(r, g, b)
+        });
+
+        // Faixa de ±1 desvio padrão: borda superior seguida da inferior invertida
+        if show_band && group.runs.len() > 1 {
+            let outline: Vec<(i64, f64)> = points
+                .iter()
+                .map(|&(t, mean, std)| (t, mean + std))
+                .chain(points.iter().rev().map(|&(t, mean, std)| (t, mean - std)))
+                .collect();
+            chart.draw_series(std::iter::once(Polygon::new(outline, color.mix(0.2).filled())))?;
+        }
+
+        chart
+            .draw_series(LineSeries::new(
+                points.iter().map(|&(t, mean, _)| (t, mean)),
+                config.line_style(color),
+            ))?
+            .label(group.label)
+            .legend(legend_line(config, color));
+    }
+
+    draw_legend(&mut chart, config)?;
+
+    Ok(())
+}
+
+/// Grandeza usada para colorir a trajetória do plano de fase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PhaseColoring {
+    /// Tempo da simulação (do primeiro ao último snapshot)
+    #[default]
+    Time,
+    /// Alert level global, na escala fixa [0, 1]
+    AlertLevel,
+}
+
+/// Gera o plano de fase energia média × taxa de disparo da população
+///
+/// A trajetória liga os snapshots em ordem cronológica e cada segmento é
+/// colorido pelo tempo ou pelo alert level, tornando visíveis ciclos limite
+/// e a deriva lenta da habituação, difíceis de ler em séries separadas.
+///
+/// # Argumentos
+/// * `snapshots` - Vetor de snapshots da simulação
+/// * `num_neurons` - Tamanho da rede, para converter disparos em taxa
+/// * `coloring` - Grandeza que define a cor de cada segmento
+/// * `output_path` - Caminho para salvar o gráfico (.png ou .svg)
+/// * `title` - Título do gráfico
+/// * `config` - Aparência do gráfico
+///
+/// # Retorna
+/// Erro `EmptyInput` sem snapshots, ou `EmptyNetwork` se `num_neurons` for 0
+pub fn plot_phase_plane(
+    snapshots: &[SimulationSnapshot],
+    num_neurons: usize,
+    coloring: PhaseColoring,
+    output_path: &str,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>> {
+    if snapshots.is_empty() {
+        return Err(NenvError::EmptyInput { what: "snapshots" }.into());
+    }
+    if num_neurons == 0 {
+        return Err(NenvError::EmptyNetwork.into());
+    }
+    render!(
+        output_path,
+        config.pixel_size(),
+        draw_phase_plane(snapshots, num_neurons, coloring, title, config)
+    )
+}
+
+fn draw_phase_plane<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    snapshots: &[SimulationSnapshot],
+    num_neurons: usize,
+    coloring: PhaseColoring,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let labels = config.labels();
+    root.fill(&config.palette.background)?;
+
+    let rate = |s: &SimulationSnapshot| s.total_firing as f64 / num_neurons.max(1) as f64;
+    let color_value = |s: &SimulationSnapshot| match coloring {
+        PhaseColoring::Time => s.time as f64,
+        PhaseColoring::AlertLevel => s.alert_level,
+    };
+    let color_range = match coloring {
+        PhaseColoring::Time => {
+            let times: Vec<f64> = snapshots.iter().map(|s| s.time as f64).collect();
+            value_range(&times)
+        }
+        PhaseColoring::AlertLevel => (0.0, 1.0),
+    };
+
+    let max_rate = snapshots.iter().map(rate).fold(0.0, f64::max);
+
+    let (x_size, _) = root.dim_in_pixel();
+    let (plane_area, bar_area) = root.split_horizontally(x_size - x_size / 8);
+
+    let mut chart = ChartBuilder::on(&plane_area)
+        .caption(title, config.title_style())
+        .margin(config.px(10))
+        .x_label_area_size(config.x_label_area())
+        .y_label_area_size(config.y_label_area())
+        .build_cartesian_2d(0.0..(max_rate * 1.1).max(0.01), 0.0..100.0)?;
+
+    chart
+        .configure_mesh()
+        .x_desc(labels.firing_rate)
+        .y_desc(labels.mean_energy)
+        .label_style(config.label_style())
+        .axis_desc_style(config.label_style())
         .draw()?;
 
-    root.present()?;
+    // Cada segmento leva a cor do seu ponto final
+    chart.draw_series(snapshots.windows(2).map(|pair| {
+        let color = heat_color(color_value(&pair[1]), color_range);
+        PathElement::new(
+            vec![(rate(&pair[0]), pair[0].avg_energy), (rate(&pair[1]), pair[1].avg_energy)],
+            config.line_style(color),
+        )
+    }))?;
+
+    // Marca o início da trajetória
+    if let Some(first) = snapshots.first() {
+        chart.draw_series(std::iter::once(Circle::new(
+            (rate(first), first.avg_energy),
+            config.px(4),
+            config.palette.foreground.filled(),
+        )))?;
+    }
+
+    draw_color_bar(&bar_area, color_range, x_size / 16, config)?;
+
+    Ok(())
+}
+
+/// Gera raster plot dos disparos (índice do neurónio × tempo)
+///
+/// Cada disparo é um ponto, revelando quais neurónios disparam e quando,
+/// ao contrário do gráfico de atividade agregada.
+///
+/// # Argumentos
+/// * `spikes` - Disparos registados (ver `Network::enable_spike_recording`)
+/// * `output_path` - Caminho para salvar o gráfico (.png ou .svg)
+/// * `title` - Título do gráfico
+/// * `config` - Aparência do gráfico
+///
+/// # Retorna
+/// Erro `EmptyNetwork` se o registo não tiver neurónios; um registo sem
+/// disparos produz um raster vazio
+pub fn plot_raster(
+    spikes: &SpikeTrain,
+    output_path: &str,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>> {
+    if spikes.num_neurons() == 0 {
+        return Err(NenvError::EmptyNetwork.into());
+    }
+    render!(output_path, config.pixel_size(), draw_raster(spikes, None, title, config))
+}
+
+/// Gera raster plot com os disparos coloridos pelo tipo de neurónio
+///
+/// Excitatórios na cor primária, inibitórios na cor secundária.
+///
+/// # Argumentos
+/// * `spikes` - Disparos registados
+/// * `neuron_types` - Tipo de cada neurónio, indexado como em `Network::neurons`
+/// * `output_path` - Caminho para salvar o gráfico (.png ou .svg)
+/// * `title` - Título do gráfico
+/// * `config` - Aparência do gráfico
+///
+/// # Retorna
+/// Erro `EmptyNetwork` se o registo não tiver neurónios, ou `LengthMismatch`
+/// se `neuron_types` não tiver um tipo por neurónio do registo
+pub fn plot_raster_by_type(
+    spikes: &SpikeTrain,
+    neuron_types: &[NeuronType],
+    output_path: &str,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>> {
+    if spikes.num_neurons() == 0 {
+        return Err(NenvError::EmptyNetwork.into());
+    }
+    check_len("neuron_types", spikes.num_neurons(), neuron_types.len())?;
+    render!(
+        output_path,
+        config.pixel_size(),
+        draw_raster(spikes, Some(neuron_types), title, config)
+    )
+}
+
+fn draw_raster<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    spikes: &SpikeTrain,
+    neuron_types: Option<&[NeuronType]>,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let labels = config.labels();
+    let palette = config.palette;
+    root.fill(&palette.background)?;
+
+    let (min_time, max_time) = spikes.time_range().unwrap_or((0, 100));
+    let num_neurons = spikes.num_neurons().max(1);
+    let radius = config.px(2);
+
+    let mut chart = ChartBuilder::on(root)
+        .caption(title, config.title_style())
+        .margin(config.px(10))
+        .x_label_area_size(config.x_label_area())
+        .y_label_area_size(config.y_label_area())
+        .build_cartesian_2d(min_time..(max_time + 1), 0..num_neurons)?;
+
+    chart
+        .configure_mesh()
+        .x_desc(labels.time)
+        .y_desc(labels.neuron)
+        .label_style(config.label_style())
+        .axis_desc_style(config.label_style())
+        .draw()?;
+
+    match neuron_types {
+        Some(types) => {
+            let legend_radius = config.px(3);
+            let legend_offset = config.px(10) as i32;
+            for (neuron_type, color, label) in [
+                (NeuronType::Excitatory, palette.primary, labels.excitatory),
+                (NeuronType::Inhibitory, palette.secondary, labels.inhibitory),
+            ] {
+                chart
+                    .draw_series(
+                        spikes
+                            .events()
+                            .iter()
+                            .filter(|&&(n, _)| types.get(n) == Some(&neuron_type))
+                            .map(|&(n, t)| Circle::new((t, n), radius, color.filled())),
+                    )?
+                    .label(label)
+                    .legend(move |(x, y)| {
+                        Circle::new((x + legend_offset, y), legend_radius, color.filled())
+                    });
+            }
+
+            draw_legend(&mut chart, config)?;
+        }
+        None => {
+            chart.draw_series(
+                spikes
+                    .events()
+                    .iter()
+                    .map(|&(n, t)| Circle::new((t, n), radius, palette.foreground.filled())),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Gera um mapa de calor de uma grandeza por neurónio disposta na grade 2D
+///
+/// O neurónio `i` ocupa a linha `i / width` e a coluna `i % width`, com a
+/// linha 0 no topo (a mesma convenção de `Network::index_to_coords`). A
+/// escala de cores é ajustada ao mínimo e máximo dos valores.
+///
+/// # Argumentos
+/// * `values` - Um valor por neurónio
+/// * `width` - Largura da grade (número de colunas)
+/// * `output_path` - Caminho para salvar o gráfico (.png ou .svg)
+/// * `title` - Título do gráfico
+/// * `config` - Aparência do gráfico
+///
+/// # Retorna
+/// Erro `EmptyInput` se `values` estiver vazio
+pub fn plot_grid_heatmap(
+    values: &[f64],
+    width: usize,
+    output_path: &str,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>> {
+    plot_grid_heatmap_with_range(values, width, value_range(values), output_path, title, config)
+}
+
+/// Gera um mapa de calor com escala de cores fixa `(min, max)`
+///
+/// Útil para sequências de mapas comparáveis entre si.
+pub fn plot_grid_heatmap_with_range(
+    values: &[f64],
+    width: usize,
+    range: (f64, f64),
+    output_path: &str,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>> {
+    if values.is_empty() {
+        return Err(NenvError::EmptyInput { what: "values" }.into());
+    }
+    render!(
+        output_path,
+        config.pixel_size(),
+        draw_grid_heatmap(values, width, range, title, config)
+    )
+}
+
+/// Gera o mapa de calor de um campo dos neurónios da rede
+///
+/// # Argumentos
+/// * `network` - Rede a visualizar
+/// * `field` - Campo lido de cada neurónio (energia, disparo, priority, novidade, ...)
+/// * `output_path` - Caminho para salvar o gráfico (.png ou .svg)
+/// * `title` - Título do gráfico
+/// * `config` - Aparência do gráfico
+///
+/// # Retorna
+/// Erro `EmptyInput` se a rede não tiver neurónios
+pub fn plot_network_heatmap(
+    network: &Network,
+    field: NeuronField,
+    output_path: &str,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>> {
+    let values = field_values(network, field);
+    plot_grid_heatmap(&values, heatmap_width(network), output_path, title, config)
+}
+
+/// Valores de um campo para todos os neurónios da rede
+fn field_values(network: &Network, field: NeuronField) -> Vec<f64> {
+    network.neurons.iter().map(|n| field.read(n)).collect()
+}
+
+/// Largura da grade; redes sem grade são dispostas num quadrado
+fn heatmap_width(network: &Network) -> usize {
+    if network.grid_width > 0 {
+        network.grid_width
+    } else {
+        (network.num_neurons() as f64).sqrt().ceil().max(1.0) as usize
+    }
+}
+
+/// Mínimo e máximo dos valores (intervalo degenerado alargado)
+fn value_range(values: &[f64]) -> (f64, f64) {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if !min.is_finite() || !max.is_finite() {
+        (0.0, 1.0)
+    } else if max > min {
+        (min, max)
+    } else {
+        (min - 0.5, max + 0.5)
+    }
+}
+
+/// Desenha o mapa de calor e a barra de cores numa área qualquer
+fn draw_grid_heatmap<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    values: &[f64],
+    width: usize,
+    range: (f64, f64),
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let labels = config.labels();
+    root.fill(&config.palette.background)?;
+
+    let rows: Vec<Vec<f64>> = values.chunks(width.max(1)).map(|row| row.to_vec()).collect();
+
+    let (x_size, _) = root.dim_in_pixel();
+    let (grid_area, bar_area) = root.split_horizontally(x_size - x_size / 8);
+    draw_cells(&grid_area, &rows, range, title, (labels.column, labels.row), config)?;
+    draw_color_bar(&bar_area, range, x_size / 16, config)?;
+
+    Ok(())
+}
+
+/// Cor de um valor na escala `(min, max)`
+fn heat_color(value: f64, (low, high): (f64, f64)) -> RGBColor {
+    ViridisRGB::get_color_normalized(value.clamp(low, high), low, high)
+}
+
+/// Desenha uma matriz de células coloridas com a linha 0 no topo
+fn draw_cells<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    rows: &[Vec<f64>],
+    range: (f64, f64),
+    title: &str,
+    (x_desc, y_desc): (&str, &str),
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0).max(1);
+    let height = rows.len().max(1);
+
+    let mut chart = ChartBuilder::on(area)
+        .caption(title, config.title_style())
+        .margin(config.px(10))
+        .x_label_area_size(config.x_label_area())
+        .y_label_area_size(config.y_label_area())
+        .build_cartesian_2d(0..width, 0..height)?;
+
+    chart
+        .configure_mesh()
+        .disable_mesh()
+        .x_desc(x_desc)
+        .y_desc(y_desc)
+        .label_style(config.label_style())
+        .axis_desc_style(config.label_style())
+        .y_label_formatter(&|y| format!("{}", height - y))
+        .draw()?;
+
+    // A linha r ocupa o intervalo y ∈ [height - r - 1, height - r)
+    chart.draw_series(rows.iter().enumerate().flat_map(|(r, row)| {
+        row.iter().enumerate().map(move |(c, &v)| {
+            Rectangle::new(
+                [(c, height - r - 1), (c + 1, height - r)],
+                heat_color(v, range).filled(),
+            )
+        })
+    }))?;
+
+    Ok(())
+}
+
+/// Desenha a barra de cores vertical da escala `(min, max)`
+fn draw_color_bar<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    range: (f64, f64),
+    label_area: u32,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    const BAR_STEPS: usize = 100;
+    let (low, high) = range;
+    let step = (high - low) / BAR_STEPS as f64;
+
+    let mut bar = ChartBuilder::on(area)
+        .margin_top(config.px(50))
+        .margin_bottom(config.px(50))
+        .margin_right(config.px(10))
+        .y_label_area_size(label_area)
+        .build_cartesian_2d(0.0..1.0, low..high)?;
+
+    bar.configure_mesh()
+        .disable_mesh()
+        .disable_x_axis()
+        .y_labels(6)
+        .y_label_style(config.label_style())
+        .draw()?;
+
+    bar.draw_series((0..BAR_STEPS).map(|k| {
+        let y0 = low + k as f64 * step;
+        Rectangle::new([(0.0, y0), (1.0, y0 + step)], heat_color(y0 + step / 2.0, range).filled())
+    }))?;
+
+    Ok(())
+}
+
+/// Gera o mapa de calor de uma matriz de pesos (linha = pós-sináptico, coluna = entrada)
+///
+/// Indicado para redes pequenas; ver `Network::weight_matrix`.
+///
+/// # Argumentos
+/// * `weights` - Matriz de pesos, uma linha por neurónio
+/// * `output_path` - Caminho para salvar o gráfico (.png ou .svg)
+/// * `title` - Título do gráfico
+/// * `config` - Aparência do gráfico
+pub fn plot_weight_matrix(
+    weights: &[Vec<f64>],
+    output_path: &str,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>> {
+    render!(output_path, config.pixel_size(), draw_weight_matrix(weights, title, config))
+}
+
+fn draw_weight_matrix<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    weights: &[Vec<f64>],
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let labels = config.labels();
+    let all: Vec<f64> = weights.iter().flatten().copied().collect();
+    let range = value_range(&all);
+
+    root.fill(&config.palette.background)?;
+    let (x_size, _) = root.dim_in_pixel();
+    let (matrix_area, bar_area) = root.split_horizontally(x_size - x_size / 8);
+    draw_cells(&matrix_area, weights, range, title, (labels.input, labels.neuron), config)?;
+    draw_color_bar(&bar_area, range, x_size / 16, config)?;
+
     Ok(())
 }
 
+/// Compara duas matrizes de pesos (antes × depois do aprendizado) lado a lado
+///
+/// As duas matrizes partilham a mesma escala de cores.
+///
+/// # Argumentos
+/// * `before` - Pesos antes do aprendizado
+/// * `after` - Pesos depois do aprendizado
+/// * `output_path` - Caminho para salvar o gráfico (.png ou .svg)
+/// * `title` - Título do gráfico
+/// * `config` - Aparência do gráfico (a largura é alargada para os dois painéis)
+pub fn plot_weight_matrix_comparison(
+    before: &[Vec<f64>],
+    after: &[Vec<f64>],
+    output_path: &str,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>> {
+    let labels = config.labels();
+    render!(
+        output_path,
+        config.wide_pixel_size(),
+        draw_comparison(before, after, (labels.input, labels.neuron), title, config)
+    )
+}
+
+/// Compara os pesos de entrada de um neurónio antes e depois do aprendizado
+///
+/// Os pesos são dispostos na grade 2D segundo o índice do neurónio de origem,
+/// mostrando de onde vieram os reforços Hebbianos.
+///
+/// # Argumentos
+/// * `before` - Pesos de entrada antes do aprendizado
+/// * `after` - Pesos de entrada depois do aprendizado
+/// * `width` - Largura da grade (número de colunas)
+/// * `output_path` - Caminho para salvar o gráfico (.png ou .svg)
+/// * `title` - Título do gráfico
+/// * `config` - Aparência do gráfico (a largura é alargada para os dois painéis)
+pub fn plot_incoming_weights_comparison(
+    before: &[f64],
+    after: &[f64],
+    width: usize,
+    output_path: &str,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>> {
+    let labels = config.labels();
+    let to_rows = |w: &[f64]| -> Vec<Vec<f64>> {
+        w.chunks(width.max(1)).map(|row| row.to_vec()).collect()
+    };
+    let (before, after) = (to_rows(before), to_rows(after));
+    render!(
+        output_path,
+        config.wide_pixel_size(),
+        draw_comparison(&before, &after, (labels.column, labels.row), title, config)
+    )
+}
+
+/// Desenha dois painéis "Antes" e "Depois" com barra de cores comum
+fn draw_comparison<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    before: &[Vec<f64>],
+    after: &[Vec<f64>],
+    axes: (&str, &str),
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let labels = config.labels();
+    let all: Vec<f64> = before.iter().chain(after.iter()).flatten().copied().collect();
+    let range = value_range(&all);
+
+    root.fill(&config.palette.background)?;
+    let root = root.titled(title, config.title_style())?;
+
+    let (x_size, _) = root.dim_in_pixel();
+    let (panels, bar_area) = root.split_horizontally(x_size - x_size / 12);
+    let (left, right) = panels.split_horizontally(panels.dim_in_pixel().0 / 2);
+
+    draw_cells(&left, before, range, labels.before, axes, config)?;
+    draw_cells(&right, after, range, labels.after, axes, config)?;
+    draw_color_bar(&bar_area, range, x_size / 24, config)?;
+
+    Ok(())
+}
+
+/// Grava o mapa de calor de um campo da rede a cada K passos
+///
+/// Chamado no loop do experimento após cada `update`, escreve
+/// `{prefix}_{campo}_t{tempo}.png` (ou `.svg`) nos passos múltiplos do intervalo.
+#[derive(Debug, Clone)]
+pub struct HeatmapDumper {
+    field: NeuronField,
+    interval: i64,
+    prefix: String,
+    range: Option<(f64, f64)>,
+    config: PlotConfig,
+}
+
+impl HeatmapDumper {
+    /// Cria um gravador de mapas de calor
+    ///
+    /// # Argumentos
+    /// * `field` - Campo a visualizar
+    /// * `interval` - Intervalo entre mapas em passos (mínimo 1)
+    /// * `prefix` - Prefixo dos arquivos gerados
+    pub fn new(field: NeuronField, interval: i64, prefix: &str) -> Self {
+        Self {
+            field,
+            interval: interval.max(1),
+            prefix: prefix.to_string(),
+            range: None,
+            config: PlotConfig::default(),
+        }
+    }
+
+    /// Fixa a escala de cores para que os mapas sejam comparáveis entre si
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Escolhe o formato dos arquivos gerados
+    pub fn with_format(mut self, format: OutputFormat) -> Self {
+        self.config.format = format;
+        self
+    }
+
+    /// Usa outra aparência (o formato dos arquivos é o da configuração)
+    pub fn with_config(mut self, config: PlotConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Grava o mapa do passo atual se este for múltiplo do intervalo
+    ///
+    /// # Retorna
+    /// O caminho do arquivo gravado, ou `None` se o passo foi ignorado
+    pub fn dump(&self, network: &Network) -> Result<Option<String>, Box<dyn Error>> {
+        let time = network.current_time_step;
+        if time % self.interval != 0 {
+            return Ok(None);
+        }
+
+        let path = self.path(time);
+        let values = field_values(network, self.field);
+        let range = self.range.unwrap_or_else(|| value_range(&values));
+        plot_grid_heatmap_with_range(
+            &values,
+            heatmap_width(network),
+            range,
+            &path,
+            &format!("{} - t = {}", self.field.name(), time),
+            &self.config,
+        )?;
+
+        Ok(Some(path))
+    }
+
+    /// Como `dump`, mas o mapa é desenhado em segundo plano pelo `worker`
+    ///
+    /// Os valores do campo são copiados já; erros de desenho ficam no
+    /// relatório de `PlotWorker::finish`.
+    ///
+    /// # Retorna
+    /// O caminho do arquivo agendado, ou `None` se o passo foi ignorado
+    pub fn dump_in(&self, network: &Network, worker: &mut PlotWorker) -> Option<String> {
+        let time = network.current_time_step;
+        if time % self.interval != 0 {
+            return None;
+        }
+
+        let path = self.path(time);
+        let values = field_values(network, self.field);
+        let range = self.range.unwrap_or_else(|| value_range(&values));
+        let width = heatmap_width(network);
+        let title = format!("{} - t = {}", self.field.name(), time);
+        let (target, config) = (path.clone(), self.config.clone());
+        worker.submit(path.clone(), move || {
+            plot_grid_heatmap_with_range(&values, width, range, &target, &title, &config)
+        });
+
+        Some(path)
+    }
+
+    /// Caminho do mapa do passo `time`
+    fn path(&self, time: i64) -> String {
+        format!(
+            "{}_{}_t{:05}.{}",
+            self.prefix,
+            self.field.name(),
+            time,
+            self.config.format.extension()
+        )
+    }
+}
+
+/// Monta um GIF animado a partir de uma sequência de mapas de calor da grade
+///
+/// Todos os quadros partilham a mesma escala de cores, para que a propagação
+/// da atividade seja visível de um quadro para o outro.
+///
+/// # Argumentos
+/// * `frames` - Pares `(tempo, valores por neurónio)` em ordem cronológica
+/// * `width` - Largura da grade (número de colunas)
+/// * `range` - Escala de cores fixa; `None` usa o mínimo e máximo de todos os quadros
+/// * `output_path` - Caminho para salvar o GIF
+/// * `frame_delay_ms` - Duração de cada quadro em milissegundos
+/// * `title` - Título (o tempo de cada quadro é acrescentado)
+/// * `config` - Aparência dos quadros
+///
+/// # Retorna
+/// Erro `EmptyInput` sem quadros ou com quadros vazios, ou `LengthMismatch`
+/// se os quadros não tiverem todos o mesmo número de valores
+pub fn save_heatmap_gif(
+    frames: &[(i64, Vec<f64>)],
+    width: usize,
+    range: Option<(f64, f64)>,
+    output_path: &str,
+    frame_delay_ms: u32,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<(), Box<dyn Error>> {
+    let Some((_, first)) = frames.first() else {
+        return Err(NenvError::EmptyInput { what: "frames" }.into());
+    };
+    if first.is_empty() {
+        return Err(NenvError::EmptyInput { what: "values" }.into());
+    }
+    for (_, values) in frames {
+        check_len("frames", first.len(), values.len())?;
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = (frames, width, range, output_path, frame_delay_ms, title, config);
+        Err(bitmap_unavailable())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let range = range.unwrap_or_else(|| {
+            let all: Vec<f64> = frames.iter().flat_map(|(_, v)| v.iter().copied()).collect();
+            value_range(&all)
+        });
+
+        let root = BitMapBackend::gif(output_path, config.pixel_size(), frame_delay_ms)?
+            .into_drawing_area();
+        for (time, values) in frames {
+            let frame_title = format!("{} - t = {}", title, time);
+            draw_grid_heatmap(&root, values, width, range, &frame_title, config)?;
+            root.present()?;
+        }
+        Ok(())
+    }
+}
+
+/// Acumula quadros de um campo da rede para exportar como GIF animado
+///
+/// Chamado no loop do experimento após cada `update`, captura um quadro nos
+/// passos múltiplos do intervalo; `save_gif` monta a animação no fim.
+#[derive(Debug, Clone)]
+pub struct GridAnimation {
+    field: NeuronField,
+    interval: i64,
+    width: usize,
+    range: Option<(f64, f64)>,
+    config: PlotConfig,
+    frames: Vec<(i64, Vec<f64>)>,
+}
+
+impl GridAnimation {
+    /// Cria uma animação vazia
+    ///
+    /// Os quadros têm por omissão 480×360, para manter o GIF leve.
+    ///
+    /// # Argumentos
+    /// * `field` - Campo a visualizar
+    /// * `interval` - Intervalo entre quadros em passos (mínimo 1)
+    pub fn new(field: NeuronField, interval: i64) -> Self {
+        Self {
+            field,
+            interval: interval.max(1),
+            width: 0,
+            range: None,
+            config: PlotConfig {
+                width: 480,
+                height: 360,
+                ..PlotConfig::default()
+            },
+            frames: Vec::new(),
+        }
+    }
+
+    /// Fixa a escala de cores (por omissão, mínimo e máximo de todos os quadros)
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Usa outra aparência para os quadros
+    pub fn with_config(mut self, config: PlotConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Captura um quadro se o passo atual for múltiplo do intervalo
+    pub fn capture(&mut self, network: &Network) {
+        let time = network.current_time_step;
+        if time % self.interval == 0 {
+            self.width = heatmap_width(network);
+            self.frames.push((time, field_values(network, self.field)));
+        }
+    }
+
+    /// Quadros capturados
+    pub fn frames(&self) -> &[(i64, Vec<f64>)] {
+        &self.frames
+    }
+
+    /// Número de quadros capturados
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Verifica se nenhum quadro foi capturado
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Exporta os quadros capturados como GIF animado
+    ///
+    /// # Argumentos
+    /// * `output_path` - Caminho para salvar o GIF
+    /// * `frame_delay_ms` - Duração de cada quadro em milissegundos
+    pub fn save_gif(&self, output_path: &str, frame_delay_ms: u32) -> Result<(), Box<dyn Error>> {
+        save_heatmap_gif(
+            &self.frames,
+            self.width,
+            self.range,
+            output_path,
+            frame_delay_ms,
+            self.field.name(),
+            &self.config,
+        )
+    }
+
+    /// Como `save_gif`, mas a animação é montada em segundo plano pelo `worker`
+    pub fn save_gif_in(self, worker: &mut PlotWorker, output_path: &str, frame_delay_ms: u32) {
+        let path = output_path.to_string();
+        worker.submit(output_path, move || self.save_gif(&path, frame_delay_ms));
+    }
+}
+
 /// Gera todos os gráficos padrão de um experimento
 ///
-/// Cria três gráficos:
-/// 1. priority_and_alert.png - Priority e Alert Level
-/// 2. energy.png - Energia do alvo e média
-/// 3. firing_activity.png - Atividade da rede
+/// Cria quatro gráficos (cinco com disparos registados), no formato e
+/// idioma de `config`:
+/// 1. priority_alert - Priority e Alert Level
+/// 2. energy - Energia do alvo e média
+/// 3. activity - Atividade da rede
+/// 4. novelty - Novidade média e Alert Level
+/// 5. raster - Raster plot dos disparos
 ///
 /// # Argumentos
 /// * `snapshots` - Vetor de snapshots da simulação
+/// * `spikes` - Disparos registados, se a gravação estava ativa
 /// * `prefix` - Prefixo para os nomes dos arquivos (ex: "experiment1")
+/// * `config` - Aparência dos gráficos
 pub fn generate_all_plots(
     snapshots: &[SimulationSnapshot],
+    spikes: Option<&SpikeTrain>,
     prefix: &str,
+    config: &PlotConfig,
 ) -> Result<(), Box<dyn Error>> {
+    let ext = config.format.extension();
+    let labels = config.labels();
+
     plot_priority_and_alert(
         snapshots,
-        &format!("{}_priority_alert.png", prefix),
-        &format!("{} - Priority e Alert Level", prefix),
+        &format!("{}_priority_alert.{}", prefix, ext),
+        &format!("{} - {}", prefix, labels.priority_alert_title),
+        config,
     )?;
 
     plot_energy(
         snapshots,
-        &format!("{}_energy.png", prefix),
-        &format!("{} - Energia", prefix),
+        &format!("{}_energy.{}", prefix, ext),
+        &format!("{} - {}", prefix, labels.energy_title),
+        config,
     )?;
 
     plot_firing_activity(
         snapshots,
-        &format!("{}_activity.png", prefix),
-        &format!("{} - Atividade da Rede", prefix),
+        &format!("{}_activity.{}", prefix, ext),
+        &format!("{} - {}", prefix, labels.activity_title),
+        config,
     )?;
 
+    plot_novelty(
+        snapshots,
+        &format!("{}_novelty.{}", prefix, ext),
+        &format!("{} - {}", prefix, labels.novelty_title),
+        config,
+    )?;
+
+    if let Some(spikes) = spikes {
+        plot_raster(
+            spikes,
+            &format!("{}_raster.{}", prefix, ext),
+            &format!("{} - {}", prefix, labels.raster_title),
+            config,
+        )?;
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ConnectivityType;
+
+    /// Caminho temporário único para um gráfico de teste
+    fn temp_plot(name: &str) -> String {
+        let file = format!("nenv_plot_{}_{}", std::process::id(), name);
+        std::env::temp_dir().join(file).to_string_lossy().into_owned()
+    }
+
+    /// Lê o ficheiro gerado, verifica que não está vazio e remove-o
+    fn take_written(path: &str) -> Vec<u8> {
+        let bytes = std::fs::read(path).unwrap_or_default();
+        std::fs::remove_file(path).ok();
+        assert!(!bytes.is_empty(), "{} não foi escrito", path);
+        bytes
+    }
+
+    fn nenv_error(result: Result<(), Box<dyn Error>>) -> NenvError {
+        *result.unwrap_err().downcast::<NenvError>().unwrap()
+    }
+
+    fn small_config() -> PlotConfig {
+        PlotConfig {
+            width: 320,
+            height: 240,
+            ..PlotConfig::default()
+        }
+    }
+
+    /// Rede 3×3 estimulada no centro, com disparos registados
+    fn simulate(seed: u64) -> (Network, Vec<SimulationSnapshot>) {
+        let mut network = Network::new_seeded(9, ConnectivityType::Grid2D, 0.2, 0.2, seed);
+        network.enable_spike_recording();
+        let snapshots = (0..20)
+            .map(|t| {
+                let mut inputs = vec![0.0; 9];
+                inputs[4] = 2.0;
+                network.update(&inputs);
+                let target = &network.neurons[4];
+                SimulationSnapshot {
+                    time: t,
+                    target_firing: target.is_firing,
+                    target_energy: target.glia.energy,
+                    target_priority: target.glia.priority,
+                    total_firing: network.num_firing(),
+                    avg_energy: network.average_energy(),
+                    alert_level: network.alert_level,
+                    avg_novelty: network.average_novelty(),
+                }
+            })
+            .collect();
+        (network, snapshots)
+    }
+
+    #[test]
+    fn test_raster_plots_render_and_validate_inputs() {
+        let (network, _) = simulate(1);
+        let config = small_config();
+        let spikes = network.spike_train().unwrap();
+
+        let png = temp_plot("raster.png");
+        plot_raster(spikes, &png, "raster", &config).unwrap();
+        assert!(take_written(&png).starts_with(b"\x89PNG"));
+
+        let types: Vec<NeuronType> = network.neurons.iter().map(|n| n.neuron_type).collect();
+        plot_raster_by_type(spikes, &types, &png, "raster", &config).unwrap();
+        take_written(&png);
+
+        let rejected = temp_plot("raster_rejected.png");
+        assert_eq!(
+            nenv_error(plot_raster_by_type(spikes, &types[..4], &rejected, "x", &config)),
+            NenvError::LengthMismatch {
+                what: "neuron_types",
+                expected: 9,
+                actual: 4
+            }
+        );
+        let empty = SpikeTrain::new(0);
+        assert_eq!(
+            nenv_error(plot_raster(&empty, &rejected, "x", &config)),
+            NenvError::EmptyNetwork
+        );
+        assert!(!std::path::Path::new(&rejected).exists());
+    }
+
+    #[test]
+    fn test_heatmaps_and_gif_render_and_validate_inputs() {
+        let (network, _) = simulate(2);
+        let config = small_config();
+
+        let png = temp_plot("heatmap.png");
+        plot_grid_heatmap(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0], 3, &png, "mapa", &config).unwrap();
+        assert!(take_written(&png).starts_with(b"\x89PNG"));
+        plot_network_heatmap(&network, NeuronField::Energy, &png, "energia", &config).unwrap();
+        take_written(&png);
+
+        let prefix = temp_plot("dump");
+        let dumper = HeatmapDumper::new(NeuronField::Firing, 5, &prefix)
+            .with_range(0.0, 1.0)
+            .with_config(config.clone());
+        let written = dumper.dump(&network).unwrap().unwrap();
+        take_written(&written);
+
+        let mut animation = GridAnimation::new(NeuronField::Energy, 1).with_config(config.clone());
+        animation.capture(&network);
+        let gif = temp_plot("animation.gif");
+        animation.save_gif(&gif, 100).unwrap();
+        assert!(take_written(&gif).starts_with(b"GIF"));
+
+        let rejected = temp_plot("heatmap_rejected.png");
+        assert_eq!(
+            nenv_error(plot_grid_heatmap(&[], 3, &rejected, "x", &config)),
+            NenvError::EmptyInput { what: "values" }
+        );
+        assert_eq!(
+            nenv_error(save_heatmap_gif(&[], 3, None, &rejected, 100, "x", &config)),
+            NenvError::EmptyInput { what: "frames" }
+        );
+        let frames = vec![(0, vec![0.0; 3]), (1, vec![0.0; 2])];
+        assert_eq!(
+            nenv_error(save_heatmap_gif(&frames, 3, None, &rejected, 100, "x", &config)),
+            NenvError::LengthMismatch {
+                what: "frames",
+                expected: 3,
+                actual: 2
+            }
+        );
+        assert!(!std::path::Path::new(&rejected).exists());
+    }
+
+    #[test]
+    fn test_svg_output_follows_plot_config() {
+        let (network, snapshots) = simulate(3);
+        assert_eq!(OutputFormat::from_path("a/b.SVG"), OutputFormat::Svg);
+        assert_eq!(OutputFormat::from_path("a/b.png"), OutputFormat::Png);
+
+        let config = PlotConfig {
+            format: OutputFormat::Svg,
+            ..PlotConfig::paper()
+        };
+        let prefix = temp_plot("all");
+        generate_all_plots(&snapshots, network.spike_train(), &prefix, &config).unwrap();
+        for name in ["priority_alert", "energy", "activity", "novelty", "raster"] {
+            let svg = take_written(&format!("{}_{}.svg", prefix, name));
+            let svg = String::from_utf8(svg).unwrap();
+            assert!(svg.contains("<svg"));
+            // Rótulos no idioma da configuração
+            assert!(svg.contains(config.labels().time), "{} sem rótulo do eixo", name);
+        }
+    }
+
+    #[test]
+    fn test_run_comparison_and_phase_plane_validate_inputs() {
+        let (_, first) = simulate(4);
+        let (_, second) = simulate(5);
+        let config = small_config();
+        let svg = temp_plot("comparison.svg");
+
+        let groups = [
+            RunGroup::new("a", vec![&first, &second]),
+            RunGroup::single("b", &second),
+        ];
+        plot_run_comparison(&groups, SnapshotField::AverageEnergy, true, &svg, "x", &config)
+            .unwrap();
+        take_written(&svg);
+        for coloring in [PhaseColoring::Time, PhaseColoring::AlertLevel] {
+            plot_phase_plane(&first, 9, coloring, &svg, "fase", &config).unwrap();
+            take_written(&svg);
+        }
+
+        assert_eq!(
+            nenv_error(plot_run_comparison(
+                &[],
+                SnapshotField::AverageEnergy,
+                true,
+                &svg,
+                "x",
+                &config
+            )),
+            NenvError::EmptyInput { what: "groups" }
+        );
+        assert_eq!(
+            nenv_error(plot_phase_plane(&[], 9, PhaseColoring::Time, &svg, "x", &config)),
+            NenvError::EmptyInput { what: "snapshots" }
+        );
+        assert_eq!(
+            nenv_error(plot_phase_plane(&first, 0, PhaseColoring::Time, &svg, "x", &config)),
+            NenvError::EmptyNetwork
+        );
+    }
+}
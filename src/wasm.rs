@@ -0,0 +1,195 @@
+//! Módulo de interface WebAssembly para demos no browser
+//!
+//! Exporta, via wasm-bindgen, a classe `WasmNetwork`: as entradas de cada
+//! passo são passadas como `Float64Array` e os estados de disparo e energia
+//! devolvidos como `Float64Array` novos (cópias, válidas mesmo que a memória
+//! linear do módulo cresça).
+//!
+//! Compilação (sem plotters, que não é necessário no browser):
+//!
+//! ```text
+//! cargo rustc --release --target wasm32-unknown-unknown --lib --crate-type cdylib \
+//!     --no-default-features --features wasm
+//! wasm-bindgen --target web --out-dir pkg \
+//!     target/wasm32-unknown-unknown/release/nen_v_prototipo.wasm
+//! ```
+//!
+//! Uso a partir do JavaScript:
+//!
+//! ```text
+//! import init, { WasmNetwork } from "./pkg/nen_v_prototipo.js";
+//! await init();
+//! const net = new WasmNetwork(100, true, 0.2, 0.2, 42n);  // grade 2D
+//! const stimulus = new Float64Array(net.numNeurons);
+//!
+//! net.step(stimulus);                // lança erro se o comprimento não bater
+//! const firing = net.firing();       // 0.0 ou 1.0 por neurónio
+//! const energy = net.energy();
+//! net.free();
+//! ```
+//!
+//! A rede é sempre criada com semente explícita (`Network::new_seeded`), pelo
+//! que o núcleo não precisa de entropia do sistema; ainda assim, em
+//! `wasm32-unknown-unknown` o `getrandom` é servido por um gerador
+//! determinístico semeado com `seedEntropy`.
+
+use crate::network::{ConnectivityType, Network};
+use wasm_bindgen::prelude::*;
+
+/// Rede exposta ao JavaScript
+#[wasm_bindgen]
+pub struct WasmNetwork {
+    network: Network,
+}
+
+#[wasm_bindgen]
+impl WasmNetwork {
+    /// Cria uma rede
+    ///
+    /// # Argumentos
+    /// * `num_neurons` - Número de neurónios
+    /// * `grid` - Grade 2D (`true`) ou totalmente conectada (`false`)
+    /// * `inhibitory_ratio` - Fração de neurónios inibitórios
+    /// * `initial_threshold` - Limiar inicial de disparo
+    /// * `seed` - Semente do gerador (a mesma semente gera a mesma rede)
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        num_neurons: u32,
+        grid: bool,
+        inhibitory_ratio: f64,
+        initial_threshold: f64,
+        seed: u64,
+    ) -> WasmNetwork {
+        let connectivity = if grid {
+            ConnectivityType::Grid2D
+        } else {
+            ConnectivityType::FullyConnected
+        };
+        WasmNetwork {
+            network: Network::new_seeded(
+                num_neurons as usize,
+                connectivity,
+                inhibitory_ratio,
+                initial_threshold,
+                seed,
+            ),
+        }
+    }
+
+    /// Executa um passo com as entradas externas dadas (`Float64Array`)
+    ///
+    /// # Retorna
+    /// Erro (mensagem) se `inputs` não tiver um valor por neurónio
+    pub fn step(&mut self, inputs: &[f64]) -> Result<(), String> {
+        let expected = self.network.num_neurons();
+        if inputs.len() != expected {
+            return Err(format!(
+                "inputs: esperado comprimento {}, recebido {}",
+                expected,
+                inputs.len()
+            ));
+        }
+        self.network.update(inputs);
+        Ok(())
+    }
+
+    /// Disparos do último passo (`Float64Array`, 1.0 = disparou)
+    pub fn firing(&self) -> Vec<f64> {
+        self.network
+            .neurons
+            .iter()
+            .map(|neuron| if neuron.is_firing { 1.0 } else { 0.0 })
+            .collect()
+    }
+
+    /// Energia de cada neurónio após o último passo (`Float64Array`)
+    pub fn energy(&self) -> Vec<f64> {
+        self.network
+            .neurons
+            .iter()
+            .map(|neuron| neuron.glia.energy)
+            .collect()
+    }
+
+    /// Número de neurónios (comprimento das entradas e dos estados)
+    #[wasm_bindgen(getter, js_name = numNeurons)]
+    pub fn num_neurons(&self) -> u32 {
+        self.network.num_neurons() as u32
+    }
+
+    /// Largura da grade (0 em redes sem grade)
+    #[wasm_bindgen(getter, js_name = gridWidth)]
+    pub fn grid_width(&self) -> u32 {
+        self.network.grid_width as u32
+    }
+
+    /// Alert level global da rede
+    #[wasm_bindgen(getter, js_name = alertLevel)]
+    pub fn alert_level(&self) -> f64 {
+        self.network.alert_level
+    }
+
+    /// Passo de tempo atual da simulação
+    #[wasm_bindgen(getter, js_name = timeStep)]
+    pub fn time_step(&self) -> i64 {
+        self.network.current_time_step
+    }
+}
+
+/// Estado do gerador determinístico que serve o `getrandom` no browser
+#[cfg(target_arch = "wasm32")]
+static ENTROPY_STATE: std::sync::atomic::AtomicU64 =
+    std::sync::atomic::AtomicU64::new(0x9E37_79B9_7F4A_7C15);
+
+/// Semeia a entropia usada por `rand::thread_rng` dentro do módulo
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen(js_name = seedEntropy)]
+pub fn seed_entropy(seed: u64) {
+    ENTROPY_STATE.store(seed, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// Preenche `dest` com bytes de um SplitMix64 (não criptográfico)
+#[cfg(target_arch = "wasm32")]
+fn splitmix_getrandom(dest: &mut [u8]) -> Result<(), getrandom::Error> {
+    use std::sync::atomic::Ordering;
+    for chunk in dest.chunks_mut(8) {
+        let mut z = ENTROPY_STATE
+            .fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed)
+            .wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        chunk.copy_from_slice(&z.to_le_bytes()[..chunk.len()]);
+    }
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+getrandom::register_custom_getrandom!(splitmix_getrandom);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_and_read_state() {
+        let mut net = WasmNetwork::new(9, true, 0.0, 0.2, 7);
+        assert_eq!(net.num_neurons(), 9);
+        assert_eq!(net.grid_width(), 3);
+
+        let mut inputs = vec![0.0; 9];
+        inputs[4] = 5.0;
+        net.step(&inputs).unwrap();
+        assert_eq!(net.time_step(), 1);
+
+        let firing = net.firing();
+        let energy = net.energy();
+        assert_eq!(firing.len(), 9);
+        assert_eq!(firing[4], 1.0);
+        // O disparo consome energia
+        assert!(energy[4] < 100.0);
+
+        assert!(net.step(&[1.0; 4]).is_err());
+        assert_eq!(net.time_step(), 1);
+    }
+}
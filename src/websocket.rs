@@ -0,0 +1,604 @@
+//! Módulo de transmissão do estado da simulação por WebSocket
+//!
+//! O `WebSocketServer` aceita clientes (p.ex. um visualizador no browser)
+//! num thread dedicado e, a cada chamada de `broadcast`, envia-lhes uma
+//! mensagem JSON com o estado do passo. Implementa apenas o necessário do
+//! RFC 6455: handshake HTTP, frames de texto do servidor, resposta a ping e
+//! fecho.
+//!
+//! O handshake de cada cliente corre no seu próprio thread, e cada cliente
+//! tem uma fila limitada esvaziada por um thread de escrita com timeout:
+//! `broadcast` nunca bloqueia a simulação, e um cliente que não lê (fila
+//! cheia ou escrita expirada) é desligado.
+//!
+//! Formato de cada mensagem (um frame de texto por passo):
+//!
+//! ```text
+//! {
+//!   "type": "step",
+//!   "time": 42,
+//!   "width": 10,
+//!   "height": 10,
+//!   "firing": "0010000000...",   // um caractere '0'/'1' por neurónio, em ordem de índice
+//!   "num_firing": 7,
+//!   "avg_energy": 81.3,
+//!   "avg_novelty": 0.021,
+//!   "alert_level": 0.12
+//! }
+//! ```
+//!
+//! Disponível com a feature `websocket`.
+
+use crate::network::Network;
+use serde::Serialize;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// GUID fixo do RFC 6455 usado no cálculo de `Sec-WebSocket-Accept`
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Frames em espera por cliente antes de ser considerado lento e desligado
+const CLIENT_QUEUE_FRAMES: usize = 64;
+
+/// Tempo máximo de uma escrita num cliente antes de o desligar
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Tamanho máximo aceite para um frame enviado pelo cliente
+const MAX_CLIENT_FRAME: usize = 1 << 16;
+
+/// Cliente ligado: fila do thread de escrita e estado da ligação
+struct Client {
+    queue: SyncSender<Arc<[u8]>>,
+    closed: Arc<AtomicBool>,
+    stream: TcpStream,
+}
+
+impl Client {
+    /// Inicia os threads de escrita e de leitura de um cliente já aceite
+    fn start(stream: TcpStream, reader: BufReader<TcpStream>) -> io::Result<Self> {
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        let (queue, frames) = mpsc::sync_channel::<Arc<[u8]>>(CLIENT_QUEUE_FRAMES);
+        let closed = Arc::new(AtomicBool::new(false));
+
+        {
+            let mut stream = stream.try_clone()?;
+            let closed = Arc::clone(&closed);
+            std::thread::spawn(move || {
+                // Timeout (WouldBlock/TimedOut) ou erro de escrita terminam o cliente
+                for frame in frames {
+                    if stream.write_all(&frame).is_err() || frame[0] & 0x0F == OPCODE_CLOSE {
+                        break;
+                    }
+                }
+                closed.store(true, Ordering::SeqCst);
+                let _ = stream.shutdown(Shutdown::Both);
+            });
+        }
+
+        {
+            let queue = queue.clone();
+            let closed = Arc::clone(&closed);
+            std::thread::spawn(move || {
+                let mut reader = reader;
+                while let Ok((opcode, payload)) = read_client_frame(&mut reader) {
+                    match opcode {
+                        OPCODE_PING => {
+                            let _ = queue.try_send(encode_frame(OPCODE_PONG, &payload).into());
+                        }
+                        OPCODE_CLOSE => {
+                            // Ecoa o código de estado e deixa o escritor fechar a ligação
+                            let code = payload.get(..2).unwrap_or(&[]);
+                            let _ = queue.try_send(encode_frame(OPCODE_CLOSE, code).into());
+                            return;
+                        }
+                        _ => {}
+                    }
+                }
+                closed.store(true, Ordering::SeqCst);
+            });
+        }
+
+        Ok(Self { queue, closed, stream })
+    }
+
+    /// Coloca um frame na fila; `false` se o cliente deve ser removido
+    fn send(&self, frame: &Arc<[u8]>) -> bool {
+        if self.closed.load(Ordering::SeqCst) {
+            return false;
+        }
+        match self.queue.try_send(Arc::clone(frame)) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_) | TrySendError::Disconnected(_)) => false,
+        }
+    }
+
+    /// Corta a ligação imediatamente (acorda os threads de leitura e escrita)
+    fn disconnect(&self) {
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+/// Estado de um passo enviado aos clientes
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StepMessage {
+    /// Sempre "step"
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub time: i64,
+    pub width: usize,
+    pub height: usize,
+    /// Bitmap de disparos: '1' se o neurónio disparou, '0' caso contrário
+    pub firing: String,
+    pub num_firing: usize,
+    pub avg_energy: f64,
+    pub avg_novelty: f64,
+    pub alert_level: f64,
+}
+
+impl StepMessage {
+    /// Lê a mensagem do estado atual da rede
+    pub fn from_network(network: &Network) -> Self {
+        let firing = network
+            .neurons
+            .iter()
+            .map(|n| if n.is_firing { '1' } else { '0' })
+            .collect();
+
+        Self {
+            kind: "step",
+            time: network.current_time_step,
+            width: network.grid_width,
+            height: network.grid_height,
+            firing,
+            num_firing: network.num_firing(),
+            avg_energy: network.average_energy(),
+            avg_novelty: network.average_novelty(),
+            alert_level: network.alert_level,
+        }
+    }
+}
+
+/// Servidor WebSocket que difunde o estado da simulação
+pub struct WebSocketServer {
+    clients: Arc<Mutex<Vec<Client>>>,
+    local_addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl WebSocketServer {
+    /// Abre o socket e inicia o thread que aceita clientes
+    ///
+    /// O thread de aceitação só cria ligações: o handshake de cada uma corre
+    /// num thread próprio, pelo que um cliente lento não atrasa os outros.
+    ///
+    /// # Argumentos
+    /// * `addr` - Endereço de escuta (p.ex. "127.0.0.1:9001"; porta 0 escolhe uma livre)
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let clients = Arc::clone(&clients);
+            let shutdown = Arc::clone(&shutdown);
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let Ok(stream) = stream else { continue };
+                    let clients = Arc::clone(&clients);
+                    let shutdown = Arc::clone(&shutdown);
+                    std::thread::spawn(move || {
+                        // Handshakes inválidos são simplesmente descartados
+                        if let Ok(reader) = accept_handshake(&stream)
+                            && let Ok(client) = Client::start(stream, reader)
+                            && let Ok(mut list) = clients.lock()
+                        {
+                            if shutdown.load(Ordering::SeqCst) {
+                                client.disconnect();
+                            } else {
+                                list.push(client);
+                            }
+                        }
+                    });
+                }
+            })
+        };
+
+        Ok(Self {
+            clients,
+            local_addr,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// Endereço efetivo de escuta
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Número de clientes ligados
+    pub fn num_clients(&self) -> usize {
+        self.clients.lock().map(|c| c.len()).unwrap_or(0)
+    }
+
+    /// Envia o estado atual da rede a todos os clientes
+    pub fn broadcast(&self, network: &Network) -> io::Result<()> {
+        let json = serde_json::to_string(&StepMessage::from_network(network))?;
+        self.broadcast_text(&json);
+        Ok(())
+    }
+
+    /// Envia um frame de texto a todos os clientes, removendo os desligados
+    ///
+    /// Não bloqueia: o frame entra na fila de cada cliente, e os clientes
+    /// com a fila cheia ou cuja escrita expirou são desligados.
+    pub fn broadcast_text(&self, text: &str) {
+        let frame: Arc<[u8]> = encode_frame(OPCODE_TEXT, text.as_bytes()).into();
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.retain(|client| {
+                let keep = client.send(&frame);
+                if !keep {
+                    client.disconnect();
+                }
+                keep
+            });
+        }
+    }
+}
+
+impl Drop for WebSocketServer {
+    fn drop(&mut self) {
+        // Antes de esvaziar a lista, para que handshakes em curso não a repovoem
+        self.shutdown.store(true, Ordering::SeqCst);
+
+        if let Ok(mut clients) = self.clients.lock() {
+            // O thread de escrita envia o fecho e termina a ligação
+            let close: Arc<[u8]> = encode_frame(OPCODE_CLOSE, &[]).into();
+            for client in clients.drain(..) {
+                if !client.send(&close) {
+                    client.disconnect();
+                }
+            }
+        }
+
+        // Acorda o thread bloqueado em accept
+        let _ = TcpStream::connect(self.local_addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+/// Lê o pedido HTTP de upgrade e responde com `101 Switching Protocols`
+///
+/// # Retorna
+/// Leitor da ligação, com os bytes que o cliente já enviou após o pedido
+fn accept_handshake(stream: &TcpStream) -> io::Result<BufReader<TcpStream>> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut key = None;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.trim().eq_ignore_ascii_case("sec-websocket-key")
+        {
+            key = Some(value.trim().to_string());
+        }
+    }
+
+    let Some(key) = key else {
+        let mut stream = stream;
+        stream.write_all(b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n")?;
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "pedido sem Sec-WebSocket-Key"));
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n\
+         Connection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(&key)
+    )?;
+    stream.set_read_timeout(None)?;
+    Ok(reader)
+}
+
+/// Lê um frame enviado pelo cliente (com máscara, como exige o RFC 6455)
+///
+/// # Retorna
+/// Par `(opcode, payload)` já desmascarado
+fn read_client_frame<R: Read>(reader: &mut R) -> io::Result<(u8, Vec<u8>)> {
+    let mut header = [0u8; 2];
+    reader.read_exact(&mut header)?;
+
+    let len = match header[1] & 0x7F {
+        126 => {
+            let mut ext = [0u8; 2];
+            reader.read_exact(&mut ext)?;
+            u16::from_be_bytes(ext) as usize
+        }
+        127 => {
+            let mut ext = [0u8; 8];
+            reader.read_exact(&mut ext)?;
+            u64::from_be_bytes(ext) as usize
+        }
+        n => n as usize,
+    };
+    if len > MAX_CLIENT_FRAME {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "frame demasiado grande"));
+    }
+
+    let mut mask = [0u8; 4];
+    if header[1] & 0x80 != 0 {
+        reader.read_exact(&mut mask)?;
+    }
+    let mut payload = vec![0u8; len];
+    reader.read_exact(&mut payload)?;
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+
+    Ok((header[0] & 0x0F, payload))
+}
+
+/// Valor de `Sec-WebSocket-Accept` para a chave enviada pelo cliente
+fn accept_key(key: &str) -> String {
+    base64_encode(&sha1(format!("{}{}", key, WEBSOCKET_GUID).as_bytes()))
+}
+
+/// Codifica um frame do servidor (final, sem máscara)
+fn encode_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+    frame.push(0x80 | opcode);
+
+    let len = payload.len();
+    if len < 126 {
+        frame.push(len as u8);
+    } else if len <= u16::MAX as usize {
+        frame.push(126);
+        frame.extend_from_slice(&(len as u16).to_be_bytes());
+    } else {
+        frame.push(127);
+        frame.extend_from_slice(&(len as u64).to_be_bytes());
+    }
+
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// SHA-1 (usado apenas no handshake, como exige o protocolo)
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+    let mut message = data.to_vec();
+    let bit_len = (data.len() as u64).wrapping_mul(8);
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&bit_len.to_be_bytes());
+
+    for chunk in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in chunk.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, &wi) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(wi);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (hi, v) in h.iter_mut().zip([a, b, c, d, e]) {
+            *hi = hi.wrapping_add(v);
+        }
+    }
+
+    let mut digest = [0u8; 20];
+    for (out, word) in digest.chunks_mut(4).zip(h.iter()) {
+        out.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+/// Base64 padrão com padding
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[(n >> 6) as usize & 63] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[n as usize & 63] as char } else { '=' });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ConnectivityType;
+    use std::io::Read;
+
+    #[test]
+    fn test_accept_key_rfc_example() {
+        // Exemplo da secção 1.3 do RFC 6455
+        assert_eq!(accept_key("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+        assert_eq!(base64_encode(b"ab"), "YWI=");
+    }
+
+    #[test]
+    fn test_frame_length_encoding() {
+        assert_eq!(encode_frame(OPCODE_TEXT, b"hi"), vec![0x81, 2, b'h', b'i']);
+
+        let long = encode_frame(OPCODE_TEXT, &[0; 300]);
+        assert_eq!(&long[..4], &[0x81, 126, 1, 44]);
+        assert_eq!(long.len(), 304);
+    }
+
+    /// Liga um cliente e conclui o handshake
+    fn connect_client(addr: SocketAddr) -> (TcpStream, BufReader<TcpStream>) {
+        let mut client = TcpStream::connect(addr).unwrap();
+        client.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        client
+            .write_all(
+                b"GET / HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\n\
+                  Connection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                  Sec-WebSocket-Version: 13\r\n\r\n",
+            )
+            .unwrap();
+
+        let mut reader = BufReader::new(client.try_clone().unwrap());
+        let mut status = String::new();
+        reader.read_line(&mut status).unwrap();
+        assert!(status.starts_with("HTTP/1.1 101"));
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 0 && line != "\r\n" {
+            line.clear();
+        }
+        (client, reader)
+    }
+
+    /// O servidor regista os clientes noutro thread; espera até estarem ligados
+    fn wait_for_clients(server: &WebSocketServer, n: usize) {
+        for _ in 0..200 {
+            if server.num_clients() == n {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(server.num_clients(), n);
+    }
+
+    /// Frame do cliente, com máscara
+    fn masked_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [0x12, 0x34, 0x56, 0x78];
+        let mut frame = vec![0x80 | opcode, 0x80 | payload.len() as u8];
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        frame
+    }
+
+    #[test]
+    fn test_client_receives_step_message() {
+        let server = WebSocketServer::bind("127.0.0.1:0").unwrap();
+        let (_client, mut reader) = connect_client(server.local_addr());
+        wait_for_clients(&server, 1);
+
+        let mut network = Network::new(4, ConnectivityType::Grid2D, 0.0, 0.5);
+        network.update(&[0.0; 4]);
+        server.broadcast(&network).unwrap();
+
+        let mut header = [0u8; 2];
+        reader.read_exact(&mut header).unwrap();
+        assert_eq!(header[0], 0x81);
+        let len = match header[1] {
+            126 => {
+                let mut ext = [0u8; 2];
+                reader.read_exact(&mut ext).unwrap();
+                u16::from_be_bytes(ext) as usize
+            }
+            n => n as usize,
+        };
+        let mut payload = vec![0u8; len];
+        reader.read_exact(&mut payload).unwrap();
+
+        let message: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(message["type"], "step");
+        assert_eq!(message["time"], 1);
+        assert_eq!(message["firing"].as_str().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_ping_and_close_are_answered() {
+        let server = WebSocketServer::bind("127.0.0.1:0").unwrap();
+        let (mut client, mut reader) = connect_client(server.local_addr());
+        wait_for_clients(&server, 1);
+
+        client.write_all(&masked_frame(OPCODE_PING, b"ola")).unwrap();
+        assert_eq!(read_client_frame(&mut reader).unwrap(), (OPCODE_PONG, b"ola".to_vec()));
+
+        client.write_all(&masked_frame(OPCODE_CLOSE, &1000u16.to_be_bytes())).unwrap();
+        assert_eq!(
+            read_client_frame(&mut reader).unwrap(),
+            (OPCODE_CLOSE, 1000u16.to_be_bytes().to_vec())
+        );
+        // Depois do fecho o servidor termina a ligação e esquece o cliente
+        assert_eq!(reader.read(&mut [0u8; 1]).unwrap(), 0);
+        server.broadcast_text("{}");
+        assert_eq!(server.num_clients(), 0);
+    }
+
+    #[test]
+    fn test_stalled_handshake_does_not_block_other_clients() {
+        let server = WebSocketServer::bind("127.0.0.1:0").unwrap();
+        // Liga-se mas nunca envia o pedido de upgrade
+        let _stalled = TcpStream::connect(server.local_addr()).unwrap();
+        let (_client, _reader) = connect_client(server.local_addr());
+        wait_for_clients(&server, 1);
+    }
+
+    #[test]
+    fn test_client_that_never_reads_is_dropped() {
+        let server = WebSocketServer::bind("127.0.0.1:0").unwrap();
+        let (_client, _reader) = connect_client(server.local_addr());
+        wait_for_clients(&server, 1);
+
+        // Sem leituras, os buffers do socket e a fila enchem; broadcast não bloqueia
+        let text = "x".repeat(64 * 1024);
+        let start = std::time::Instant::now();
+        for _ in 0..10_000 {
+            server.broadcast_text(&text);
+            if server.num_clients() == 0 {
+                break;
+            }
+        }
+        assert_eq!(server.num_clients(), 0);
+        assert!(start.elapsed() < WRITE_TIMEOUT * 5, "{:?}", start.elapsed());
+    }
+}
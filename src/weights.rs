@@ -0,0 +1,323 @@
+//! Módulo de estatísticas dos pesos sinápticos
+//!
+//! Resume a distribuição dos pesos dos Dendritomas (média, desvio padrão,
+//! extremos, esparsidade e histograma) para acompanhar quantitativamente o
+//! progresso do aprendizado ao longo de uma simulação.
+//!
+//! Também lê matrizes de pesos externas (CSV ou `.npy` do NumPy) para
+//! avaliar na NEN-V redes treinadas noutro lugar; ver `Network::load_weights`
+//! (só com a feature `std`).
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::F64Ext;
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io::{self, BufReader, Read};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+/// Pesos com módulo abaixo deste valor contam como nulos na esparsidade
+pub const SPARSITY_EPSILON: f64 = 1e-6;
+
+/// Histograma de largura de bin uniforme
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Limites dos bins (`counts.len() + 1` valores crescentes)
+    pub edges: Vec<f64>,
+
+    /// Número de pesos em cada bin
+    pub counts: Vec<usize>,
+}
+
+/// Estatísticas de um conjunto de pesos
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightStats {
+    /// Número de pesos considerados
+    pub count: usize,
+    pub mean: f64,
+    /// Desvio padrão populacional
+    pub std: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Fração de pesos com módulo menor que `SPARSITY_EPSILON`
+    pub sparsity: f64,
+    pub histogram: Histogram,
+}
+
+impl WeightStats {
+    /// Calcula as estatísticas de um conjunto de pesos
+    ///
+    /// # Argumentos
+    /// * `weights` - Pesos a resumir
+    /// * `bins` - Número de bins do histograma (mínimo 1)
+    pub fn from_weights<'a>(weights: impl Iterator<Item = &'a f64>, bins: usize) -> Self {
+        let values: Vec<f64> = weights.copied().collect();
+        let bins = bins.max(1);
+
+        if values.is_empty() {
+            return Self {
+                count: 0,
+                mean: 0.0,
+                std: 0.0,
+                min: 0.0,
+                max: 0.0,
+                sparsity: 0.0,
+                histogram: Histogram {
+                    edges: vec![0.0; bins + 1],
+                    counts: vec![0; bins],
+                },
+            };
+        }
+
+        let count = values.len();
+        let mean = values.iter().sum::<f64>() / count as f64;
+        let variance = values.iter().map(|w| (w - mean).powi(2)).sum::<f64>() / count as f64;
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let zeros = values.iter().filter(|w| w.abs() < SPARSITY_EPSILON).count();
+
+        // Histograma entre min e max (intervalo degenerado alargado para um bin útil)
+        let (low, high) = if max > min { (min, max) } else { (min - 0.5, max + 0.5) };
+        let width = (high - low) / bins as f64;
+        let edges = (0..=bins).map(|i| low + i as f64 * width).collect();
+        let mut counts = vec![0; bins];
+        for &w in &values {
+            let idx = (((w - low) / width).floor() as usize).min(bins - 1);
+            counts[idx] += 1;
+        }
+
+        Self {
+            count,
+            mean,
+            std: variance.sqrt(),
+            min,
+            max,
+            sparsity: zeros as f64 / count as f64,
+            histogram: Histogram { edges, counts },
+        }
+    }
+}
+
+/// Estatísticas dos pesos de toda a rede, por tipo de neurónio e por grupo
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightStatistics {
+    /// Todos os pesos da rede
+    pub overall: WeightStats,
+
+    /// Pesos de entrada dos neurónios excitatórios
+    pub excitatory: WeightStats,
+
+    /// Pesos de entrada dos neurónios inibitórios
+    pub inhibitory: WeightStats,
+
+    /// Pesos de entrada dos neurónios de cada grupo nomeado
+    pub groups: BTreeMap<String, WeightStats>,
+}
+
+/// Lê uma matriz de pesos de um ficheiro, escolhendo o formato pela extensão
+///
+/// `.npy` é lido como array NumPy 2D; qualquer outra extensão como CSV.
+///
+/// # Retorna
+/// A matriz por linhas: linha i = pesos de entrada do neurónio i
+#[cfg(feature = "std")]
+pub fn load_weight_matrix<P: AsRef<Path>>(path: P) -> io::Result<Vec<Vec<f64>>> {
+    let path = path.as_ref();
+    let reader = BufReader::new(File::open(path)?);
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("npy") => read_npy_matrix(reader),
+        _ => read_csv_matrix(reader),
+    }
+}
+
+/// Lê uma matriz de pesos em CSV: uma linha por neurónio, sem cabeçalho
+///
+/// Linhas vazias e linhas começadas por `#` são ignoradas.
+#[cfg(feature = "std")]
+pub fn read_csv_matrix<R: Read>(reader: R) -> io::Result<Vec<Vec<f64>>> {
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .comment(Some(b'#'))
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(reader);
+
+    let mut matrix = Vec::new();
+    for (line, record) in csv_reader.records().enumerate() {
+        let record = record.map_err(io::Error::other)?;
+        let row = record
+            .iter()
+            .map(|field| {
+                field.parse::<f64>().map_err(|_| {
+                    invalid_data(format!("linha {}: valor inválido '{}'", line + 1, field))
+                })
+            })
+            .collect::<io::Result<Vec<f64>>>()?;
+        matrix.push(row);
+    }
+    Ok(matrix)
+}
+
+/// Lê um array NumPy 2D (`.npy` v1–v3) de `float64` ou `float32`
+///
+/// Suporta ordem C e Fortran e ambas as ordens de bytes.
+#[cfg(feature = "std")]
+pub fn read_npy_matrix<R: Read>(mut reader: R) -> io::Result<Vec<Vec<f64>>> {
+    let mut preamble = [0u8; 8];
+    reader.read_exact(&mut preamble)?;
+    if &preamble[..6] != b"\x93NUMPY" {
+        return Err(invalid_data("assinatura .npy inválida".to_string()));
+    }
+
+    let header_len = if preamble[6] == 1 {
+        let mut len = [0u8; 2];
+        reader.read_exact(&mut len)?;
+        u16::from_le_bytes(len) as usize
+    } else {
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len)?;
+        u32::from_le_bytes(len) as usize
+    };
+    let mut header = vec![0u8; header_len];
+    reader.read_exact(&mut header)?;
+    let header = String::from_utf8_lossy(&header);
+
+    let descr = npy_header_value(&header, "descr")
+        .map(|v| v.trim_matches(|c| c == '\'' || c == '"'))
+        .ok_or_else(|| invalid_data("cabeçalho .npy sem 'descr'".to_string()))?;
+    let fortran_order = npy_header_value(&header, "fortran_order") == Some("True");
+    let shape: Vec<usize> = npy_header_value(&header, "shape")
+        .map(|v| {
+            v.trim_matches(|c| c == '(' || c == ')')
+                .split(',')
+                .filter(|dim| !dim.trim().is_empty())
+                .filter_map(|dim| dim.trim().parse().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    let &[rows, cols] = shape.as_slice() else {
+        return Err(invalid_data(format!("esperado array 2D, shape {:?}", shape)));
+    };
+
+    let (little_endian, width) = match descr {
+        "<f8" | "f8" => (true, 8),
+        ">f8" => (false, 8),
+        "<f4" | "f4" => (true, 4),
+        ">f4" => (false, 4),
+        other => return Err(invalid_data(format!("dtype .npy não suportado: {}", other))),
+    };
+
+    let mut data = vec![0u8; rows * cols * width];
+    reader.read_exact(&mut data)?;
+    let values: Vec<f64> = data
+        .chunks_exact(width)
+        .map(|bytes| match (width, little_endian) {
+            (8, true) => f64::from_le_bytes(bytes.try_into().unwrap()),
+            (8, false) => f64::from_be_bytes(bytes.try_into().unwrap()),
+            (_, true) => f32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            (_, false) => f32::from_be_bytes(bytes.try_into().unwrap()) as f64,
+        })
+        .collect();
+
+    Ok((0..rows)
+        .map(|i| {
+            (0..cols)
+                .map(|j| if fortran_order { values[j * rows + i] } else { values[i * cols + j] })
+                .collect()
+        })
+        .collect())
+}
+
+/// Valor (em texto) de uma chave do dicionário do cabeçalho `.npy`
+#[cfg(feature = "std")]
+fn npy_header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let start = header.find(&format!("'{}'", key))? + key.len() + 2;
+    let rest = header[start..].trim_start().strip_prefix(':')?.trim_start();
+    let end = if rest.starts_with('(') {
+        rest.find(')')? + 1
+    } else {
+        rest.find([',', '}'])?
+    };
+    Some(rest[..end].trim())
+}
+
+#[cfg(feature = "std")]
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_basic_statistics() {
+        let weights = [0.0, 0.5, 0.5, 1.0];
+        let stats = WeightStats::from_weights(weights.iter(), 2);
+
+        assert_eq!(stats.count, 4);
+        assert_relative_eq!(stats.mean, 0.5);
+        assert_relative_eq!(stats.std, (0.125f64).sqrt());
+        assert_relative_eq!(stats.min, 0.0);
+        assert_relative_eq!(stats.max, 1.0);
+        assert_relative_eq!(stats.sparsity, 0.25);
+        assert_eq!(stats.histogram.counts, vec![1, 3]);
+        assert_eq!(stats.histogram.edges, vec![0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn test_constant_and_empty_weights() {
+        let constant = WeightStats::from_weights([0.2, 0.2].iter(), 4);
+        assert_eq!(constant.histogram.counts.iter().sum::<usize>(), 2);
+        assert_relative_eq!(constant.std, 0.0);
+
+        let empty = WeightStats::from_weights(std::iter::empty(), 3);
+        assert_eq!(empty.count, 0);
+        assert_eq!(empty.histogram.counts, vec![0, 0, 0]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_read_csv_matrix() {
+        let csv = "# pesos 2x2\n0.5, 0.1\n\n-0.2,1e-3\n";
+        let matrix = read_csv_matrix(csv.as_bytes()).unwrap();
+        assert_eq!(matrix, vec![vec![0.5, 0.1], vec![-0.2, 1e-3]]);
+        assert!(read_csv_matrix("0.1,abc\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_read_npy_matrix_in_both_orders() {
+        let npy = |fortran: bool, values: &[f64]| {
+            let dict = format!(
+                "{{'descr': '<f8', 'fortran_order': {}, 'shape': (2, 3), }}",
+                if fortran { "True" } else { "False" }
+            );
+            let mut header = dict.into_bytes();
+            // Cabeçalho alinhado a 64 bytes e terminado em '\n', como o NumPy escreve
+            while (10 + header.len() + 1) % 64 != 0 {
+                header.push(b' ');
+            }
+            header.push(b'\n');
+
+            let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+            bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+            bytes.extend_from_slice(&header);
+            for v in values {
+                bytes.extend_from_slice(&v.to_le_bytes());
+            }
+            bytes
+        };
+
+        let expected = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+        let c_order = npy(false, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let f_order = npy(true, &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+        assert_eq!(read_npy_matrix(c_order.as_slice()).unwrap(), expected);
+        assert_eq!(read_npy_matrix(f_order.as_slice()).unwrap(), expected);
+        assert!(read_npy_matrix(&b"not numpy"[..]).is_err());
+    }
+}
@@ -0,0 +1,533 @@
+//! Módulo de publicação de eventos por ZeroMQ
+//!
+//! `ZmqPublisher` é um socket PUB que fala ZMTP 3.0 (mecanismo NULL)
+//! diretamente sobre TCP, sem depender da libzmq: qualquer socket SUB
+//! (libzmq, pyzmq, czmq, ...) pode ligar-se com `connect("tcp://host:porta")`
+//! e subscrever os tópicos que lhe interessam. Implementa `EventSink`, pelo
+//! que recebe os eventos do `EventDetector` tal como o `JsonlEventSink`.
+//!
+//! Cada evento é uma mensagem de duas partes: o tópico (ASCII) e um payload
+//! binário em little-endian.
+//!
+//! ```text
+//! tópico          payload
+//! nenv.fire       time: i64, neuron: u32
+//! nenv.alert      time: i64, alert_level: f64
+//! nenv.novelty    time: i64, avg_novelty: f64, threshold: f64
+//! nenv.ckpt       time: i64, path: UTF-8 (resto do payload)
+//! ```
+//!
+//! A filtragem por prefixo é feita no publicador, como na libzmq: subscrever
+//! `"nenv.a"` recebe apenas os alertas, `""` recebe tudo. As mensagens são
+//! acumuladas por subscritor e enviadas em `flush` (uma vez por passo com
+//! `emit_all`), com `TCP_NODELAY` para minimizar a latência.
+//!
+//! Cada subscritor tem uma fila limitada esvaziada por um thread de escrita
+//! com timeout, pelo que publicar nunca bloqueia a simulação. Como no
+//! high-water mark da libzmq, um subscritor que não lê é descartado: aqui
+//! é desligado quando a fila enche ou uma escrita expira.
+//!
+//! Disponível com a feature `zeromq`.
+
+use crate::events::{EventSink, SimulationEvent};
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Bit de frame com continuação (mensagem multi-parte)
+const FLAG_MORE: u8 = 0x01;
+/// Bit de frame com tamanho de 8 bytes
+const FLAG_LONG: u8 = 0x02;
+/// Bit de frame de comando
+const FLAG_COMMAND: u8 = 0x04;
+
+/// Lotes em espera por subscritor antes de ser considerado lento e desligado
+const SUBSCRIBER_QUEUE_BATCHES: usize = 64;
+
+/// Bytes acumulados por subscritor a partir dos quais o lote segue sem `flush`
+const BATCH_BYTES: usize = 64 * 1024;
+
+/// Tempo máximo de uma escrita num subscritor antes de o desligar
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Tópico e payload binário de um evento
+///
+/// # Retorna
+/// Par `(tópico, payload)` no formato documentado no módulo
+pub fn encode_event(event: &SimulationEvent) -> (&'static [u8], Vec<u8>) {
+    let mut payload = Vec::with_capacity(24);
+    match event {
+        SimulationEvent::NeuronFired { time, neuron } => {
+            payload.extend_from_slice(&time.to_le_bytes());
+            payload.extend_from_slice(&(*neuron as u32).to_le_bytes());
+            (b"nenv.fire", payload)
+        }
+        SimulationEvent::AlertActivated { time, alert_level } => {
+            payload.extend_from_slice(&time.to_le_bytes());
+            payload.extend_from_slice(&alert_level.to_le_bytes());
+            (b"nenv.alert", payload)
+        }
+        SimulationEvent::NoveltyAboveThreshold {
+            time,
+            avg_novelty,
+            threshold,
+        } => {
+            payload.extend_from_slice(&time.to_le_bytes());
+            payload.extend_from_slice(&avg_novelty.to_le_bytes());
+            payload.extend_from_slice(&threshold.to_le_bytes());
+            (b"nenv.novelty", payload)
+        }
+        SimulationEvent::CheckpointWritten { time, path } => {
+            payload.extend_from_slice(&time.to_le_bytes());
+            payload.extend_from_slice(path.as_bytes());
+            (b"nenv.ckpt", payload)
+        }
+    }
+}
+
+/// Subscritor ligado: fila do thread de escrita e prefixos subscritos
+struct Subscriber {
+    queue: SyncSender<Vec<u8>>,
+    closed: Arc<AtomicBool>,
+    stream: TcpStream,
+    pending: Vec<u8>,
+    subscriptions: Arc<Mutex<Vec<Vec<u8>>>>,
+}
+
+impl Subscriber {
+    /// Inicia o thread de escrita de um subscritor já aceite
+    fn start(stream: TcpStream, subscriptions: Arc<Mutex<Vec<Vec<u8>>>>) -> io::Result<Self> {
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        let (queue, batches) = mpsc::sync_channel::<Vec<u8>>(SUBSCRIBER_QUEUE_BATCHES);
+        let closed = Arc::new(AtomicBool::new(false));
+
+        {
+            let mut stream = stream.try_clone()?;
+            let closed = Arc::clone(&closed);
+            std::thread::spawn(move || {
+                // Timeout (WouldBlock/TimedOut) ou erro de escrita terminam o subscritor
+                for batch in batches {
+                    if stream.write_all(&batch).is_err() {
+                        break;
+                    }
+                }
+                closed.store(true, Ordering::SeqCst);
+                let _ = stream.shutdown(Shutdown::Both);
+            });
+        }
+
+        Ok(Self {
+            queue,
+            closed,
+            stream,
+            pending: Vec::new(),
+            subscriptions,
+        })
+    }
+
+    fn matches(&self, topic: &[u8]) -> bool {
+        self.subscriptions
+            .lock()
+            .map(|subs| subs.iter().any(|prefix| topic.starts_with(prefix)))
+            .unwrap_or(false)
+    }
+
+    /// Acumula uma mensagem; `false` se o subscritor deve ser removido
+    fn push(&mut self, topic: &[u8], payload: &[u8]) -> bool {
+        // Escrever num Vec não falha
+        let _ = write_frame(&mut self.pending, FLAG_MORE, topic);
+        let _ = write_frame(&mut self.pending, 0, payload);
+        self.pending.len() < BATCH_BYTES || self.flush()
+    }
+
+    /// Passa o lote acumulado à fila; `false` se o subscritor deve ser removido
+    fn flush(&mut self) -> bool {
+        if self.closed.load(Ordering::SeqCst) {
+            return false;
+        }
+        if self.pending.is_empty() {
+            return true;
+        }
+        // Fila cheia: o subscritor não acompanha o ritmo da simulação
+        self.queue.try_send(std::mem::take(&mut self.pending)).is_ok()
+    }
+
+    /// Corta a ligação imediatamente (acorda os threads de leitura e escrita)
+    fn disconnect(&self) {
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+/// Mantém só os subscritores para os quais `keep` é verdadeiro, desligando os outros
+fn retain_subscribers(
+    subscribers: &Mutex<Vec<Subscriber>>,
+    mut keep: impl FnMut(&mut Subscriber) -> bool,
+) {
+    if let Ok(mut subscribers) = subscribers.lock() {
+        subscribers.retain_mut(|sub| {
+            let kept = keep(sub);
+            if !kept {
+                sub.disconnect();
+            }
+            kept
+        });
+    }
+}
+
+/// Socket PUB ZeroMQ (ZMTP 3.0) que publica eventos da simulação
+pub struct ZmqPublisher {
+    subscribers: Arc<Mutex<Vec<Subscriber>>>,
+    local_addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ZmqPublisher {
+    /// Abre o socket e aceita subscritores num thread dedicado
+    ///
+    /// O handshake de cada subscritor corre num thread próprio, pelo que um
+    /// cliente lento não atrasa os outros.
+    ///
+    /// # Argumentos
+    /// * `addr` - Endereço de escuta (p.ex. "0.0.0.0:5556"; porta 0 escolhe uma livre)
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let subscribers = Arc::new(Mutex::new(Vec::new()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let subscribers = Arc::clone(&subscribers);
+            let shutdown = Arc::clone(&shutdown);
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let Ok(stream) = stream else { continue };
+                    let subscribers = Arc::clone(&subscribers);
+                    let shutdown = Arc::clone(&shutdown);
+                    std::thread::spawn(move || {
+                        // Um handshake falhado descarta apenas esse cliente
+                        if let Ok(subscriber) = accept_subscriber(stream)
+                            && let Ok(mut subscribers) = subscribers.lock()
+                        {
+                            if shutdown.load(Ordering::SeqCst) {
+                                subscriber.disconnect();
+                            } else {
+                                subscribers.push(subscriber);
+                            }
+                        }
+                    });
+                }
+            })
+        };
+
+        Ok(Self {
+            subscribers,
+            local_addr,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// Endereço efetivo de escuta
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Número de subscritores ligados
+    pub fn num_subscribers(&self) -> usize {
+        self.subscribers.lock().map(|s| s.len()).unwrap_or(0)
+    }
+
+    /// Publica uma mensagem de duas partes `(tópico, payload)`
+    ///
+    /// Não bloqueia. Subscritores cuja ligação falhou ou cuja fila está
+    /// cheia são desligados e removidos.
+    pub fn publish(&self, topic: &[u8], payload: &[u8]) {
+        retain_subscribers(&self.subscribers, |sub| {
+            !sub.matches(topic) || sub.push(topic, payload)
+        });
+    }
+}
+
+impl EventSink for ZmqPublisher {
+    fn emit(&mut self, event: &SimulationEvent) -> io::Result<()> {
+        let (topic, payload) = encode_event(event);
+        self.publish(topic, &payload);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        retain_subscribers(&self.subscribers, Subscriber::flush);
+        Ok(())
+    }
+}
+
+impl Drop for ZmqPublisher {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Acorda o thread bloqueado em accept
+        let _ = TcpStream::connect(self.local_addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+        // Largar a fila deixa o thread de escrita enviar o que falta e fechar
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            for mut sub in subscribers.drain(..) {
+                if !sub.flush() {
+                    sub.disconnect();
+                }
+            }
+        }
+    }
+}
+
+/// Faz o handshake ZMTP com um novo cliente e inicia a leitura das subscrições
+fn accept_subscriber(mut stream: TcpStream) -> io::Result<Subscriber> {
+    stream.set_nodelay(true)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+
+    stream.write_all(&greeting())?;
+    let mut peer = [0u8; 64];
+    stream.read_exact(&mut peer)?;
+    if peer[0] != 0xFF || peer[9] != 0x7F || peer[10] < 3 || &peer[12..16] != b"NULL" {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "greeting ZMTP inválido"));
+    }
+
+    write_frame(&mut stream, FLAG_COMMAND, &ready_command(b"PUB"))?;
+    let (flags, body) = read_frame(&mut stream)?;
+    let socket_type = parse_ready(&body).unwrap_or_default();
+    if flags & FLAG_COMMAND == 0 || !(socket_type == b"SUB" || socket_type == b"XSUB") {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "o cliente não é um SUB"));
+    }
+
+    // As subscrições chegam de forma assíncrona; sem timeout no leitor
+    stream.set_read_timeout(None)?;
+    let subscriptions = Arc::new(Mutex::new(Vec::new()));
+    {
+        let mut reader = stream.try_clone()?;
+        let subscriptions = Arc::clone(&subscriptions);
+        std::thread::spawn(move || {
+            while let Ok((flags, body)) = read_frame(&mut reader) {
+                apply_subscription(flags, &body, &subscriptions);
+            }
+        });
+    }
+
+    Subscriber::start(stream, subscriptions)
+}
+
+/// Greeting ZMTP 3.0 com mecanismo NULL
+fn greeting() -> [u8; 64] {
+    let mut greeting = [0u8; 64];
+    greeting[0] = 0xFF;
+    greeting[9] = 0x7F;
+    greeting[10] = 3;
+    greeting[11] = 0;
+    greeting[12..16].copy_from_slice(b"NULL");
+    greeting
+}
+
+/// Corpo do comando READY com a propriedade Socket-Type
+fn ready_command(socket_type: &[u8]) -> Vec<u8> {
+    let mut body = vec![5];
+    body.extend_from_slice(b"READY");
+    body.push(11);
+    body.extend_from_slice(b"Socket-Type");
+    body.extend_from_slice(&(socket_type.len() as u32).to_be_bytes());
+    body.extend_from_slice(socket_type);
+    body
+}
+
+/// Extrai o Socket-Type de um comando READY
+fn parse_ready(body: &[u8]) -> Option<Vec<u8>> {
+    let rest = body.strip_prefix(b"\x05READY")?;
+    let mut i = 0;
+    while i < rest.len() {
+        let name_len = *rest.get(i)? as usize;
+        let name = rest.get(i + 1..i + 1 + name_len)?;
+        i += 1 + name_len;
+        let value_len = u32::from_be_bytes(rest.get(i..i + 4)?.try_into().ok()?) as usize;
+        let value = rest.get(i + 4..i + 4 + value_len)?;
+        i += 4 + value_len;
+        if name.eq_ignore_ascii_case(b"Socket-Type") {
+            return Some(value.to_vec());
+        }
+    }
+    None
+}
+
+/// Atualiza as subscrições a partir de uma mensagem (ZMTP 3.0) ou comando (3.1)
+fn apply_subscription(flags: u8, body: &[u8], subscriptions: &Mutex<Vec<Vec<u8>>>) {
+    let (subscribe, prefix) = if flags & FLAG_COMMAND != 0 {
+        if let Some(prefix) = body.strip_prefix(b"\x09SUBSCRIBE") {
+            (true, prefix)
+        } else if let Some(prefix) = body.strip_prefix(b"\x06CANCEL") {
+            (false, prefix)
+        } else {
+            return;
+        }
+    } else {
+        match body.split_first() {
+            Some((1, prefix)) => (true, prefix),
+            Some((0, prefix)) => (false, prefix),
+            _ => return,
+        }
+    };
+
+    if let Ok(mut subs) = subscriptions.lock() {
+        if subscribe {
+            subs.push(prefix.to_vec());
+        } else if let Some(pos) = subs.iter().position(|s| s == prefix) {
+            subs.remove(pos);
+        }
+    }
+}
+
+/// Escreve um frame ZMTP (curto até 255 bytes, longo acima)
+fn write_frame<W: Write>(writer: &mut W, flags: u8, body: &[u8]) -> io::Result<()> {
+    if body.len() <= 255 {
+        writer.write_all(&[flags, body.len() as u8])?;
+    } else {
+        writer.write_all(&[flags | FLAG_LONG])?;
+        writer.write_all(&(body.len() as u64).to_be_bytes())?;
+    }
+    writer.write_all(body)
+}
+
+/// Lê um frame ZMTP
+///
+/// # Retorna
+/// Par `(flags, corpo)`
+fn read_frame<R: Read>(reader: &mut R) -> io::Result<(u8, Vec<u8>)> {
+    let mut flags = [0u8; 1];
+    reader.read_exact(&mut flags)?;
+    let len = if flags[0] & FLAG_LONG != 0 {
+        let mut len = [0u8; 8];
+        reader.read_exact(&mut len)?;
+        u64::from_be_bytes(len) as usize
+    } else {
+        let mut len = [0u8; 1];
+        reader.read_exact(&mut len)?;
+        len[0] as usize
+    };
+    if len > 1 << 20 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "frame demasiado grande"));
+    }
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body)?;
+    Ok((flags[0], body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cliente SUB mínimo: handshake e subscrição de um prefixo
+    fn connect_sub(addr: SocketAddr, prefix: &[u8]) -> TcpStream {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        stream.write_all(&greeting()).unwrap();
+        let mut peer = [0u8; 64];
+        stream.read_exact(&mut peer).unwrap();
+        assert_eq!(&peer[12..16], b"NULL");
+
+        write_frame(&mut stream, FLAG_COMMAND, &ready_command(b"SUB")).unwrap();
+        let (flags, body) = read_frame(&mut stream).unwrap();
+        assert_eq!(flags, FLAG_COMMAND);
+        assert_eq!(parse_ready(&body).unwrap(), b"PUB");
+
+        let mut subscribe = vec![1];
+        subscribe.extend_from_slice(prefix);
+        write_frame(&mut stream, 0, &subscribe).unwrap();
+        stream
+    }
+
+    #[test]
+    fn test_encode_alert_payload() {
+        let (topic, payload) = encode_event(&SimulationEvent::AlertActivated {
+            time: 5,
+            alert_level: 0.75,
+        });
+        assert_eq!(topic, b"nenv.alert");
+        assert_eq!(&payload[..8], &5i64.to_le_bytes());
+        assert_eq!(&payload[8..], &0.75f64.to_le_bytes());
+    }
+
+    #[test]
+    fn test_prefix_filtered_publish() {
+        let mut publisher = ZmqPublisher::bind("127.0.0.1:0").unwrap();
+        let mut sub = connect_sub(publisher.local_addr(), b"nenv.alert");
+
+        // Espera que o handshake e a subscrição sejam registados
+        for _ in 0..200 {
+            let ready = publisher
+                .subscribers
+                .lock()
+                .unwrap()
+                .first()
+                .is_some_and(|s| !s.subscriptions.lock().unwrap().is_empty());
+            if ready {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        publisher.emit(&SimulationEvent::NeuronFired { time: 1, neuron: 3 }).unwrap();
+        publisher
+            .emit(&SimulationEvent::AlertActivated {
+                time: 2,
+                alert_level: 0.9,
+            })
+            .unwrap();
+        publisher.flush().unwrap();
+
+        // O disparo é filtrado; a primeira mensagem recebida é o alerta
+        let (flags, topic) = read_frame(&mut sub).unwrap();
+        assert_eq!(flags, FLAG_MORE);
+        assert_eq!(topic, b"nenv.alert");
+        let (flags, payload) = read_frame(&mut sub).unwrap();
+        assert_eq!(flags, 0);
+        assert_eq!(&payload[..8], &2i64.to_le_bytes());
+        assert_eq!(publisher.num_subscribers(), 1);
+    }
+
+    #[test]
+    fn test_subscriber_that_never_reads_is_dropped() {
+        let mut publisher = ZmqPublisher::bind("127.0.0.1:0").unwrap();
+        let _stalled = connect_sub(publisher.local_addr(), b"");
+        for _ in 0..200 {
+            let ready = publisher
+                .subscribers
+                .lock()
+                .unwrap()
+                .first()
+                .is_some_and(|s| !s.subscriptions.lock().unwrap().is_empty());
+            if ready {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(publisher.num_subscribers(), 1);
+
+        // Sem leituras, os buffers do socket e a fila enchem; publicar não bloqueia
+        let payload = vec![0u8; 64 * 1024];
+        let start = std::time::Instant::now();
+        for _ in 0..10_000 {
+            publisher.publish(b"nenv.fire", &payload);
+            publisher.flush().unwrap();
+            if publisher.num_subscribers() == 0 {
+                break;
+            }
+        }
+        assert_eq!(publisher.num_subscribers(), 0);
+        assert!(start.elapsed() < WRITE_TIMEOUT * 5, "{:?}", start.elapsed());
+    }
+}
\ No newline at end of file